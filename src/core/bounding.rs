@@ -1,7 +1,7 @@
 use crate::core::{Interval, Point3, Ray, Vector3};
 
 /// Axis-aligned bounding box
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct AxisAlignedBoundingBox {
     /// x coordinate interval
     pub x: Interval,
@@ -81,6 +81,8 @@ impl Hittable for BVHNode {
             Some(node) => node.hit(ray, ray_interval, record),
             None => false,
         };
+        // Hit the right child into an empty record, so no field of a farther hit is left behind.
+        let mut right_record = HitRecord::new();
         let hit_right = match &self.right {
             Some(node) => node.hit(
                 ray,
@@ -88,10 +90,13 @@ impl Hittable for BVHNode {
                     ray_interval.min,
                     if hit_left { record.t } else { ray_interval.max },
                 ),
-                record,
+                &mut right_record,
             ),
             None => false,
         };
+        if hit_right {
+            record.clone_from(&right_record);
+        }
 
         hit_left || hit_right
     }
@@ -1,9 +1,9 @@
 use super::{
-    deg_to_rad, linear_to_gramma, Color3, HitRecord, HittablePDF, Interval, MixturePDF, Point3,
-    Ray, ScatterRecord, Vector3,
+    deg_to_rad, Color3, ColorSpace, HitRecord, HittablePDF, Interval, LightPDF, MediumInterface,
+    MixturePDF, Point3, Ray, RayDifferential, ScatterRecord, Vector2, Vector3,
 };
 use crate::{
-    traits::{Hittable, ProbabilityDensityFunction},
+    traits::{Hittable, Light, Material, Medium, ProbabilityDensityFunction},
     utils::PPMImage,
 };
 use derive_builder::Builder;
@@ -105,10 +105,27 @@ pub struct Camera {
     #[builder(default = "10")]
     pub max_ray_depth: u8,
 
-    /// Scene background color
+    /// Scene background color, replaced by the light sources surrounding the scene if any
     #[builder(default = "Color3::one()")]
     pub background: Color3,
 
+    /// Light sources that are not part of the scene geometry, delta lights are sampled by shadow
+    /// rays and the others surround the scene
+    #[builder(default = "Vec::new()")]
+    pub light_sources: Vec<Rc<dyn Light>>,
+
+    /// Medium the camera is in, `None` for vacuum
+    #[builder(default = "None")]
+    pub medium: Option<Rc<dyn Medium>>,
+
+    /// Color space the colors of the scene are given and rendered in
+    #[builder(default = "ColorSpace::SRGB")]
+    pub working_space: ColorSpace,
+
+    /// Color space the rendered image is encoded for
+    #[builder(default = "ColorSpace::SRGB")]
+    pub display_space: ColorSpace,
+
     /// Rand generator
     #[builder(setter(skip))]
     rng: rand::rngs::ThreadRng,
@@ -207,7 +224,16 @@ impl Camera {
         let ray_direction = &pixel_sample - &ray_origin;
         let ray_time = self.rng.gen::<f32>();
 
-        Ray::new_with_time(ray_origin, ray_direction, ray_time)
+        // The offset rays pass through the neighbouring pixels on the focus plane. They are
+        // brought closer with more samples per pixel, each sample covering less of the pixel.
+        let scale = f32::max(0.125, self.reciprocal_sqrt_spp);
+        let differential = RayDifferential {
+            rx_origin: ray_origin,
+            rx_direction: &ray_direction + &self.pixel_delta_u.mul(scale),
+            ry_origin: ray_origin,
+            ry_direction: &ray_direction + &self.pixel_delta_v.mul(scale),
+        };
+        Ray::new_with_differential(ray_origin, ray_direction, ray_time, differential)
     }
 
     fn ray_color(
@@ -215,6 +241,7 @@ impl Camera {
         ray: &Ray,
         world: Rc<dyn Hittable>,
         lights: Option<Rc<dyn Hittable>>,
+        medium: Option<Rc<dyn Medium>>,
         ray_depth: u8,
     ) -> Color3 {
         // If we've exceeded the ray bounce limit, no more light is gathered.
@@ -224,25 +251,77 @@ impl Camera {
 
         let mut hit_record = HitRecord::new();
         // Fixing shadow acne by setting the nearest surface to 0.001.
-        if !world.hit(ray, &Interval::new(0.001, f32::INFINITY), &mut hit_record) {
-            return self.background;
+        let is_hit = world.hit(ray, &Interval::new(0.001, f32::INFINITY), &mut hit_record);
+        if is_hit {
+            hit_record.compute_differentials(ray);
         }
-        let HitRecord {
-            uv: Some(uv),
-            point: Some(point),
-            material: Some(ref material),
-            ..
-        } = hit_record
-        else {
-            // return background color if there is no hit.
-            return self.background;
+
+        let Some(medium) = medium else {
+            if !is_hit {
+                return self.background_color(ray);
+            }
+            return self.surface_color(ray, &hit_record, world, lights, None, ray_depth);
+        };
+
+        // The ray may scatter in the medium before reaching the surface.
+        let t_max = if is_hit { hit_record.t } else { f32::INFINITY };
+        let medium_record = medium.sample(ray, &Interval::new(0.001, t_max));
+        let color = match medium_record.t {
+            Some(t) => {
+                let mut medium_hit_record = HitRecord::new();
+                medium_hit_record.t = t;
+                medium_hit_record.point = Some(ray.at(t));
+                medium_hit_record.normal = Some(Vector3::new(1., 0., 0.)); // arbitrary
+                medium_hit_record.front_face = true;
+                medium_hit_record.material = Some(medium.phase_function());
+                medium_hit_record.uv = Some(Vector2::zero());
+                self.surface_color(
+                    ray,
+                    &medium_hit_record,
+                    world,
+                    lights,
+                    Some(medium),
+                    ray_depth,
+                )
+            }
+            None if is_hit => {
+                self.surface_color(ray, &hit_record, world, lights, Some(medium), ray_depth)
+            }
+            None => self.background_color(ray),
+        };
+        medium_record.weight.mul(&color)
+    }
+
+    /// Returns the light leaving a hit point along the incoming ray, `medium` is the medium the
+    /// ray travelled through.
+    fn surface_color(
+        &mut self,
+        ray: &Ray,
+        hit_record: &HitRecord,
+        world: Rc<dyn Hittable>,
+        lights: Option<Rc<dyn Hittable>>,
+        medium: Option<Rc<dyn Medium>>,
+        ray_depth: u8,
+    ) -> Color3 {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return self.background_color(ray);
+        };
+        let Some(ref material) = hit_record.material else {
+            if hit_record.medium_interface.is_none() {
+                return self.background_color(ray);
+            }
+            // The surface only bounds media, the ray passes through into the next one.
+            let mut ray_continued = Ray::new_with_time(point, ray.direction, ray.time);
+            ray_continued.differential = ray.differential;
+            let next_medium = Self::medium_along(hit_record, &medium, &ray.direction);
+            return self.ray_color(&ray_continued, world, lights, next_medium, ray_depth - 1);
         };
 
         // compute the emission color of the material at the hit point.
-        let emission_color = material.emitted(ray, &hit_record, &uv, &point);
+        let emission_color = material.emitted(ray, hit_record, &uv, &point);
 
         let mut scatter_record = ScatterRecord::new();
-        if !material.scatter(ray, &hit_record, &mut scatter_record) {
+        if !material.scatter(ray, hit_record, &mut scatter_record) {
             // return the emission color only if the ray is not scattered.
             return emission_color;
         }
@@ -250,35 +329,181 @@ impl Camera {
         if scatter_record.skip_pdf {
             // it means that the material is a perfect reflection or refraction.
             let ray_scattered = scatter_record.ray_scattered.unwrap();
+            let next_medium = Self::medium_along(hit_record, &medium, &ray_scattered.direction);
             return scatter_record.attenuation.mul(&self.ray_color(
                 &ray_scattered,
                 world,
                 lights,
+                next_medium,
                 ray_depth - 1,
             ));
         }
 
         let surface_pdf = scatter_record.pdf.unwrap();
-        let pdf: Rc<dyn ProbabilityDensityFunction> = if let Some(ref lights_some) = lights {
-            let light_pdf = Rc::new(HittablePDF::new(lights_some.clone(), point));
-            Rc::new(MixturePDF::new(light_pdf, surface_pdf))
-        } else {
-            surface_pdf
+        let pdf: Rc<dyn ProbabilityDensityFunction> = match self.light_pdf(&lights, point) {
+            Some(light_pdf) => Rc::new(MixturePDF::new(light_pdf, surface_pdf)),
+            None => surface_pdf,
         };
 
         let ray_scattered = Ray::new_with_time(point, pdf.generate(), ray.time);
         let pdf_value = pdf.value(&ray_scattered.direction);
 
-        let scattering_pdf = material.scattering_pdf(ray, &hit_record, &ray_scattered);
+        let scattering_color = material.scattering_color(ray, hit_record, &ray_scattered);
+
+        // Delta lights can't be hit by scattered rays, so they are sampled separately.
+        let light_color =
+            self.sample_light_sources(ray, hit_record, material.as_ref(), &world, &medium);
+
+        let next_medium = Self::medium_along(hit_record, &medium, &ray_scattered.direction);
+        let sample_color =
+            self.ray_color(&ray_scattered, world, lights, next_medium, ray_depth - 1);
+        let scatter_color = scattering_color.mul(&sample_color).div(pdf_value);
+
+        &(&emission_color + &scatter_color) + &light_color
+    }
+
+    /// Returns the medium a ray leaving the hit point along `direction` travels through, the
+    /// current one unless the surface is a medium interface or its material has an interior.
+    fn medium_along(
+        hit_record: &HitRecord,
+        medium: &Option<Rc<dyn Medium>>,
+        direction: &Vector3,
+    ) -> Option<Rc<dyn Medium>> {
+        let interior = hit_record
+            .material
+            .as_ref()
+            .and_then(|material| material.interior_medium(hit_record));
+        match (&hit_record.medium_interface, interior) {
+            (Some(interface), None) => interface.medium_along(hit_record, direction),
+            (interface, Some(interior)) => {
+                // The material fills the inside, the outside stays vacuum unless given.
+                let outside = interface
+                    .as_ref()
+                    .and_then(|interface| interface.outside.clone());
+                MediumInterface::new(Some(interior), outside).medium_along(hit_record, direction)
+            }
+            (None, None) => medium.clone(),
+        }
+    }
+
+    /// Returns the color of a ray escaping the scene.
+    fn background_color(&self, ray: &Ray) -> Color3 {
+        let mut surrounding_lights = self
+            .light_sources
+            .iter()
+            .filter(|light| !light.is_delta())
+            .peekable();
+        if surrounding_lights.peek().is_none() {
+            return self.background;
+        }
+
+        let mut color = Color3::zero();
+        for light in surrounding_lights {
+            color += &light.emitted(&ray.direction);
+        }
+        color
+    }
+
+    /// Returns the pdf sampling the emissive objects and the light sources surrounding the scene
+    /// uniformly, or `None` if there are none.
+    fn light_pdf(
+        &self,
+        lights: &Option<Rc<dyn Hittable>>,
+        origin: Point3,
+    ) -> Option<Rc<dyn ProbabilityDensityFunction>> {
+        let mut light_pdfs: Vec<Rc<dyn ProbabilityDensityFunction>> = Vec::new();
+        if let Some(lights) = lights {
+            light_pdfs.push(Rc::new(HittablePDF::new(lights.clone(), origin)));
+        }
+        for light in self.light_sources.iter().filter(|light| !light.is_delta()) {
+            light_pdfs.push(Rc::new(LightPDF::new(light.clone(), origin)));
+        }
+
+        // Fold the pdfs into nested mixtures, giving every pdf the same weight.
+        let mut light_pdfs = light_pdfs.into_iter().enumerate();
+        let (_, first_pdf) = light_pdfs.next()?;
+        Some(light_pdfs.fold(first_pdf, |mixture, (i, light_pdf)| {
+            Rc::new(MixturePDF::new_with_weight(
+                mixture,
+                light_pdf,
+                1. / (i + 1) as f32,
+            ))
+        }))
+    }
 
-        let sample_color = self.ray_color(&ray_scattered, world, lights, ray_depth - 1);
-        let scatter_color = scatter_record
-            .attenuation
-            .mul(scattering_pdf)
-            .mul(&sample_color)
-            .div(pdf_value);
+    /// Returns the light arriving directly from the delta light sources and scattered along the
+    /// incoming ray, testing the visibility of each light with a shadow ray.
+    fn sample_light_sources(
+        &self,
+        ray: &Ray,
+        hit_record: &HitRecord,
+        material: &dyn Material,
+        world: &Rc<dyn Hittable>,
+        medium: &Option<Rc<dyn Medium>>,
+    ) -> Color3 {
+        let mut color = Color3::zero();
+        let Some(point) = hit_record.point else {
+            return color;
+        };
 
-        &emission_color + &scatter_color
+        for light in self.light_sources.iter().filter(|light| light.is_delta()) {
+            let Some(light_record) = light.sample(&point) else {
+                continue;
+            };
+
+            let shadow_ray = Ray::new_with_time(point, light_record.direction, ray.time);
+            let shadow_medium = Self::medium_along(hit_record, medium, &shadow_ray.direction);
+            let transmittance = Self::shadow_transmittance(
+                &shadow_ray,
+                light_record.distance - 0.001,
+                world,
+                shadow_medium,
+            );
+            if transmittance.equals_zero() {
+                continue;
+            }
+
+            let scattering_color = material.scattering_color(ray, hit_record, &shadow_ray);
+            color += &scattering_color
+                .mul(&light_record.radiance)
+                .mul(&transmittance)
+                .div(light_record.pdf);
+        }
+
+        color
+    }
+
+    /// Returns the fraction of light passing along the shadow ray up to `t_max`, crossing the
+    /// surfaces that only bound media and blocked by any other surface.
+    fn shadow_transmittance(
+        shadow_ray: &Ray,
+        t_max: f32,
+        world: &Rc<dyn Hittable>,
+        mut medium: Option<Rc<dyn Medium>>,
+    ) -> Color3 {
+        let mut transmittance = Color3::one();
+        let mut t_min = 0.001;
+        loop {
+            let mut shadow_record = HitRecord::new();
+            let is_hit = world.hit(shadow_ray, &Interval::new(t_min, t_max), &mut shadow_record);
+
+            let segment_end = if is_hit { shadow_record.t } else { t_max };
+            if let Some(ref medium) = medium {
+                transmittance = transmittance
+                    .mul(&medium.transmittance(shadow_ray, &Interval::new(t_min, segment_end)));
+            }
+            if !is_hit {
+                return transmittance;
+            }
+
+            match shadow_record.medium_interface {
+                Some(ref interface) if shadow_record.material.is_none() => {
+                    medium = interface.medium_along(&shadow_record, &shadow_ray.direction);
+                    t_min = shadow_record.t + 0.001;
+                }
+                _ => return Color3::zero(),
+            }
+        }
     }
 
     pub fn render(
@@ -294,6 +519,7 @@ impl Camera {
         println!("Rendering:");
 
         let mut image = PPMImage::new(self.width.into(), self.height.into());
+        let to_display = self.working_space.conversion(self.display_space);
         for y in 0..self.height {
             for x in 0..self.width {
                 let mut color = Color3::zero();
@@ -304,6 +530,7 @@ impl Camera {
                             &ray,
                             world.clone(),
                             lights.clone(),
+                            self.medium.clone(),
                             self.max_ray_depth,
                         );
                     }
@@ -320,11 +547,14 @@ impl Camera {
                     color.z = 0.;
                 }
 
-                // Divide the color by the number of samples and gamma-correct for gamma=2.0.
-                let scale = 1. / self.square_sqrt_spp;
-                color.x = linear_to_gramma(color.x * scale);
-                color.y = linear_to_gramma(color.y * scale);
-                color.z = linear_to_gramma(color.z * scale);
+                // Divide the color by the number of samples, convert it to the display space and
+                // encode it with the transfer function of the display.
+                let color = to_display.transform_vector(&color.div(self.square_sqrt_spp));
+                let color = Color3::new(
+                    self.display_space.encode(color.x.max(0.)),
+                    self.display_space.encode(color.y.max(0.)),
+                    self.display_space.encode(color.z.max(0.)),
+                );
 
                 // Write the translated [0,255] value of each color component.
                 let intensity = Interval::new(0.000, 0.999);
@@ -0,0 +1,227 @@
+use crate::core::{Color3, Matrix4, Vector2};
+use image::DynamicImage;
+
+/// Cone response matrix of the Bradford chromatic adaptation, from XYZ.
+const BRADFORD: [[f32; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// Returns a `Matrix4` acting on colors by its upper left 3x3 block.
+fn matrix3(m: [[f32; 3]; 3]) -> Matrix4 {
+    Matrix4::new([
+        [m[0][0], m[0][1], m[0][2], 0.],
+        [m[1][0], m[1][1], m[1][2], 0.],
+        [m[2][0], m[2][1], m[2][2], 0.],
+        [0., 0., 0., 1.],
+    ])
+}
+
+/// Returns the XYZ color of a chromaticity, with a luminance of one.
+fn xy_to_xyz(xy: &Vector2) -> Color3 {
+    Color3::new(xy.x / xy.y, 1., (1. - xy.x - xy.y) / xy.y)
+}
+
+/**
+sRGB Transfer Function
+
+Returns the linear value of a channel encoded by the sRGB transfer function, the EOTF of sRGB
+displays.
+
+# Examples
+
+```
+use raytracing::core::{linear_to_srgb, srgb_to_linear};
+
+let linear = srgb_to_linear(0.5);
+# assert!((linear - 0.21404).abs() < 1e-5);
+# assert!((linear_to_srgb(linear) - 0.5).abs() < 1e-6);
+```
+*/
+#[inline]
+pub fn srgb_to_linear(encoded: f32) -> f32 {
+    if encoded <= 0.04045 {
+        encoded / 12.92
+    } else {
+        f32::powf((encoded + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Returns the sRGB encoding of a linear channel, the inverse of `srgb_to_linear`.
+#[inline]
+pub fn linear_to_srgb(linear: f32) -> f32 {
+    if linear <= 0.0031308 {
+        linear * 12.92
+    } else {
+        1.055 * f32::powf(linear, 1. / 2.4) - 0.055
+    }
+}
+
+/**
+Color Space
+
+The RGB color spaces colors are rendered and displayed in, given by their primaries and white
+point. Colors of the crate are linear RGB in the working space of the camera, sRGB by default,
+and images written are encoded for its display space.
+
+# Examples
+
+```
+use raytracing::core::{Color3, ColorSpace};
+
+// The most saturated red of sRGB is inside the wider gamut of Rec.2020.
+let red = ColorSpace::SRGB.convert(&Color3::new(1., 0., 0.), ColorSpace::Rec2020);
+# assert!(red.x > 0. && red.x < 1. && red.y > 0. && red.z > 0.);
+let back = ColorSpace::Rec2020.convert(&red, ColorSpace::SRGB);
+# assert!((&back - &Color3::new(1., 0., 0.)).length() < 1e-4);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// sRGB, with the primaries of Rec.709 and a D65 white.
+    #[default]
+    SRGB,
+
+    /// Rec.2020, the wide gamut of UHD video, with a D65 white.
+    Rec2020,
+
+    /// ACEScg, the AP1 primaries of ACES used for rendering, with the white of ACES near D60.
+    ACEScg,
+
+    /// Display P3, the primaries of DCI-P3 with a D65 white, the gamut of most wide gamut
+    /// displays.
+    DisplayP3,
+}
+
+impl ColorSpace {
+    /// Returns the chromaticities of the red, green and blue primaries.
+    pub fn primaries(&self) -> [Vector2; 3] {
+        let [r, g, b] = match self {
+            ColorSpace::SRGB => [(0.64, 0.33), (0.30, 0.60), (0.15, 0.06)],
+            ColorSpace::Rec2020 => [(0.708, 0.292), (0.170, 0.797), (0.131, 0.046)],
+            ColorSpace::ACEScg => [(0.713, 0.293), (0.165, 0.830), (0.128, 0.044)],
+            ColorSpace::DisplayP3 => [(0.680, 0.320), (0.265, 0.690), (0.150, 0.060)],
+        };
+        [r, g, b].map(|(x, y)| Vector2::new(x, y))
+    }
+
+    /// Returns the chromaticity of the white point.
+    pub fn white_point(&self) -> Vector2 {
+        match self {
+            ColorSpace::ACEScg => Vector2::new(0.32168, 0.33767),
+            _ => Vector2::new(0.3127, 0.3290),
+        }
+    }
+
+    /// Returns the matrix from linear RGB in the color space to CIE XYZ.
+    pub fn to_xyz(&self) -> Matrix4 {
+        // The primaries are scaled so that they add up to the white point.
+        let [r, g, b] = self.primaries().map(|primary| xy_to_xyz(&primary));
+        let primaries = matrix3([[r.x, g.x, b.x], [r.y, g.y, b.y], [r.z, g.z, b.z]]);
+        let white = xy_to_xyz(&self.white_point());
+        let scale = primaries
+            .inverse()
+            .expect("primaries are independent")
+            .transform_vector(&white);
+        &primaries * &Matrix4::scaling(&scale)
+    }
+
+    /// Returns the matrix from linear RGB in the color space to linear RGB in another, adapting
+    /// the white point with the Bradford transform.
+    pub fn conversion(&self, to: ColorSpace) -> Matrix4 {
+        if *self == to {
+            return Matrix4::identity();
+        }
+        let from_xyz = to.to_xyz().inverse().expect("primaries are independent");
+        let mut adaptation = Matrix4::identity();
+        if self.white_point() != to.white_point() {
+            let bradford = matrix3(BRADFORD);
+            let source = bradford.transform_vector(&xy_to_xyz(&self.white_point()));
+            let target = bradford.transform_vector(&xy_to_xyz(&to.white_point()));
+            let scale = Color3::new(
+                target.x / source.x,
+                target.y / source.y,
+                target.z / source.z,
+            );
+            adaptation =
+                &(&bradford.inverse().expect("invertible") * &Matrix4::scaling(&scale)) * &bradford;
+        }
+        &(&from_xyz * &adaptation) * &self.to_xyz()
+    }
+
+    /// Returns a linear color of the color space in another.
+    pub fn convert(&self, color: &Color3, to: ColorSpace) -> Color3 {
+        self.conversion(to).transform_vector(color)
+    }
+
+    /// Returns the encoding of a linear channel for a display of the color space: the sRGB
+    /// transfer function for sRGB and Display P3, the Rec.2020 one for Rec.2020, and none for
+    /// ACEScg, which images only store linearly.
+    pub fn encode(&self, linear: f32) -> f32 {
+        match self {
+            ColorSpace::SRGB | ColorSpace::DisplayP3 => linear_to_srgb(linear),
+            ColorSpace::Rec2020 => {
+                if linear < 0.018053968 {
+                    4.5 * linear
+                } else {
+                    1.0993 * f32::powf(linear, 0.45) - 0.0993
+                }
+            }
+            ColorSpace::ACEScg => linear,
+        }
+    }
+
+    /// Returns the linear value of a channel encoded for a display of the color space, the
+    /// inverse of `encode`.
+    pub fn decode(&self, encoded: f32) -> f32 {
+        match self {
+            ColorSpace::SRGB | ColorSpace::DisplayP3 => srgb_to_linear(encoded),
+            ColorSpace::Rec2020 => {
+                if encoded < 0.08124286 {
+                    encoded / 4.5
+                } else {
+                    f32::powf((encoded + 0.0993) / 1.0993, 1. / 0.45)
+                }
+            }
+            ColorSpace::ACEScg => encoded,
+        }
+    }
+}
+
+/// How the values of an image stand for colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageEncoding {
+    /// Colors encoded by the sRGB transfer function, the encoding of most 8 and 16-bit images.
+    SRGB,
+
+    /// Linear colors, the encoding of float images.
+    Linear,
+
+    /// Data other than colors, such as normals or roughness, read as they are.
+    Raw,
+}
+
+impl ImageEncoding {
+    /// Returns the encoding images without metadata are assumed to have: sRGB for integer
+    /// images and linear for float images.
+    pub fn detect(image: &DynamicImage) -> Self {
+        match image {
+            DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => ImageEncoding::Linear,
+            _ => ImageEncoding::SRGB,
+        }
+    }
+
+    /// Returns the linear value of a channel in [0, 1].
+    pub fn decode(&self, value: f32) -> f32 {
+        match self {
+            ImageEncoding::SRGB => srgb_to_linear(value),
+            ImageEncoding::Linear | ImageEncoding::Raw => value,
+        }
+    }
+
+    /// Returns true if the values of the image are colors.
+    pub fn is_color(&self) -> bool {
+        *self != ImageEncoding::Raw
+    }
+}
@@ -0,0 +1,48 @@
+use crate::core::Color3;
+
+/**
+RGB to HSV
+
+Returns the hue, saturation and value of a color as the x, y and z of a `Color3`, the hue in turns
+in [0, 1).
+
+# Examples
+
+```
+use raytracing::core::{hsv_to_rgb, rgb_to_hsv, Color3};
+
+let hsv = rgb_to_hsv(&Color3::new(0., 0.5, 0.5));
+# assert!((&hsv - &Color3::new(0.5, 1., 0.5)).length() < 1e-6);
+# assert!((&hsv_to_rgb(&hsv) - &Color3::new(0., 0.5, 0.5)).length() < 1e-6);
+```
+*/
+pub fn rgb_to_hsv(rgb: &Color3) -> Color3 {
+    let max = f32::max(rgb.x, f32::max(rgb.y, rgb.z));
+    let min = f32::min(rgb.x, f32::min(rgb.y, rgb.z));
+    let delta = max - min;
+    if delta <= 0. {
+        return Color3::new(0., 0., max);
+    }
+
+    let sector = if max == rgb.x {
+        (rgb.y - rgb.z) / delta
+    } else if max == rgb.y {
+        (rgb.z - rgb.x) / delta + 2.
+    } else {
+        (rgb.x - rgb.y) / delta + 4.
+    };
+    let hue = (sector / 6.).rem_euclid(1.);
+    let saturation = if max > 0. { delta / max } else { 0. };
+    Color3::new(hue, saturation, max)
+}
+
+/// Returns the color of a hue, saturation and value, the inverse of `rgb_to_hsv`. Hues wrap
+/// around.
+pub fn hsv_to_rgb(hsv: &Color3) -> Color3 {
+    let (hue, saturation, value) = (hsv.x.rem_euclid(1.) * 6., hsv.y, hsv.z);
+    let channel = |n: f32| {
+        let k = (n + hue) % 6.;
+        value - value * saturation * f32::clamp(f32::min(k, 4. - k), 0., 1.)
+    };
+    Color3::new(channel(5.), channel(3.), channel(1.))
+}
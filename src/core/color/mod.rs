@@ -0,0 +1,5 @@
+mod color_space;
+mod hsv;
+
+pub use color_space::*;
+pub use hsv::*;
@@ -1,14 +1,20 @@
 use crate::{
-    core::{AxisAlignedBoundingBox, HitRecord, Interval, Point3, Ray, Vector3},
+    core::{AxisAlignedBoundingBox, Distribution1D, HitRecord, Interval, Point3, Ray, Vector3},
     traits::Hittable,
 };
 use rand::Rng;
-use std::rc::Rc;
+use std::{
+    cell::{Ref, RefCell},
+    rc::Rc,
+};
 
 #[derive(Debug)]
 pub struct HittableList {
     pub objects: Vec<Rc<dyn Hittable>>,
     bbox: AxisAlignedBoundingBox,
+    /// Distribution picking the objects as lights, built the first time a light is picked and
+    /// rebuilt when objects are pushed to `objects` directly.
+    power_distribution: RefCell<Option<Distribution1D>>,
 }
 
 impl HittableList {
@@ -20,16 +26,42 @@ impl HittableList {
                 y: Interval::empty(),
                 z: Interval::empty(),
             },
+            power_distribution: RefCell::new(None),
         }
     }
 
     pub fn add(&mut self, object: Rc<dyn Hittable>) {
         self.bbox = self.bbox.merge(&object.bounding_box());
         self.objects.push(object);
+        self.power_distribution.take();
     }
 
     pub fn clear(&mut self) {
         self.objects.clear();
+        self.power_distribution.take();
+    }
+
+    /// Returns the distribution picking objects proportionally to their emitted power, or
+    /// uniformly if any of them has no power, such as a glass sphere sampled as a light.
+    fn power_distribution(&self) -> Ref<'_, Distribution1D> {
+        let stale = self
+            .power_distribution
+            .borrow()
+            .as_ref()
+            .is_none_or(|distribution| distribution.count() != self.objects.len());
+        if stale {
+            let powers: Vec<f32> = self.objects.iter().map(|object| object.power()).collect();
+            let weights = if powers.iter().all(|&power| power > 0.) {
+                powers
+            } else {
+                vec![1.; powers.len()]
+            };
+            self.power_distribution
+                .replace(Some(Distribution1D::new(weights)));
+        }
+        Ref::map(self.power_distribution.borrow(), |distribution| {
+            distribution.as_ref().unwrap()
+        })
     }
 }
 
@@ -38,9 +70,10 @@ impl Hittable for HittableList {
         record.t = ray_interval.max;
 
         let mut is_hitted = false;
-        let mut hit_record = HitRecord::new();
 
         for object in self.objects.iter() {
+            // Start from an empty record, so no field of a farther hit is left behind.
+            let mut hit_record = HitRecord::new();
             if object.hit(
                 ray,
                 &mut Interval::new(ray_interval.min, record.t),
@@ -59,11 +92,11 @@ impl Hittable for HittableList {
     }
 
     fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
-        let weight = 1.0 / self.objects.len() as f32;
+        let distribution = self.power_distribution();
 
         let mut sum = 0.0;
-        for object in &self.objects {
-            sum += weight * object.pdf_value(origin, direction);
+        for (i, object) in self.objects.iter().enumerate() {
+            sum += distribution.discrete_pdf(i) * object.pdf_value(origin, direction);
         }
 
         return sum;
@@ -72,9 +105,16 @@ impl Hittable for HittableList {
     fn random(&self, origin: &Point3) -> Vector3 {
         let mut rng = rand::thread_rng();
 
-        let size = self.objects.len();
-        let random_index = rng.gen_range(0..size);
+        let (index, _) = self.power_distribution().sample_discrete(rng.gen::<f32>());
+
+        self.objects[index].random(origin)
+    }
+
+    fn area(&self) -> f32 {
+        self.objects.iter().map(|object| object.area()).sum()
+    }
 
-        self.objects[random_index].random(origin)
+    fn power(&self) -> f32 {
+        self.objects.iter().map(|object| object.power()).sum()
     }
 }
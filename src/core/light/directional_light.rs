@@ -0,0 +1,40 @@
+use crate::{
+    core::{Color3, LightRecord, Point3, Vector3},
+    traits::Light,
+};
+use std::ops::Neg;
+
+/**
+Directional Light
+
+A light infinitely far away that lights the whole scene from a single direction, like the sun.
+*/
+#[derive(Debug)]
+pub struct DirectionalLight {
+    /// Unit direction the light travels along.
+    direction: Vector3,
+
+    /// Irradiance arriving on a surface perpendicular to the light.
+    pub irradiance: Color3,
+}
+
+impl DirectionalLight {
+    /// Create a new `DirectionalLight` travelling along `direction`.
+    pub fn new(direction: Vector3, irradiance: Color3) -> Self {
+        Self {
+            direction: direction.normolize(),
+            irradiance,
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn sample(&self, _point: &Point3) -> Option<LightRecord> {
+        Some(LightRecord {
+            direction: self.direction.neg(),
+            distance: f32::INFINITY,
+            radiance: self.irradiance,
+            pdf: 1.,
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{
+    core::{
+        deg_to_rad, Color3, Distribution2D, ImageEncoding, LightRecord, Point3, Vector2, Vector3,
+    },
+    traits::Light,
+};
+use image::ImageResult;
+use rand::Rng;
+use std::{f32::consts::PI, ops::Mul};
+
+/**
+Environment Light
+
+A light infinitely far away that surrounds the scene with an equirectangular image, usually a
+high dynamic range `.hdr` or `.exr` capture. Directions are sampled proportionally to the
+luminance of the image, so small and bright features like the sun converge quickly.
+
+The top row of the image is straight up (+y), and the left edge of the image faces +x, rotating
+towards +z. `rotation` turns the image around the y axis in degrees.
+*/
+#[derive(Debug)]
+pub struct EnvironmentLight {
+    /// Linear pixel colors, row by row from the top.
+    pixels: Vec<Color3>,
+
+    /// Width of the image in pixel count.
+    width: usize,
+
+    /// Height of the image in pixel count.
+    height: usize,
+
+    /// Sine of the rotation around the y axis.
+    sin_rotation: f32,
+
+    /// Cosine of the rotation around the y axis.
+    cos_rotation: f32,
+
+    /// Factor applied to the image colors.
+    pub intensity: f32,
+
+    /// Distribution of the image luminance, weighted by the solid angle of each pixel.
+    distribution: Distribution2D,
+}
+
+impl EnvironmentLight {
+    /// Create a new `EnvironmentLight` from an equirectangular image at the given path. Float
+    /// images such as HDR files are linear, integer images are decoded as sRGB.
+    pub fn new(path: String, rotation: f32, intensity: f32) -> ImageResult<Self> {
+        let image = image::io::Reader::open(path)?.decode()?;
+        let encoding = ImageEncoding::detect(&image);
+        let image = image.to_rgb32f();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels = image
+            .pixels()
+            .map(|pixel| pixel.0.map(|channel| encoding.decode(channel)))
+            .map(|[r, g, b]| Color3::new(r, g, b))
+            .collect();
+        ImageResult::Ok(Self::new_with_pixels(
+            pixels, width, height, rotation, intensity,
+        ))
+    }
+
+    /// Create a new `EnvironmentLight` from linear pixel colors, row by row from the top.
+    pub fn new_with_pixels(
+        pixels: Vec<Color3>,
+        width: usize,
+        height: usize,
+        rotation: f32,
+        intensity: f32,
+    ) -> Self {
+        assert_eq!(pixels.len(), width * height, "pixel count mismatch");
+
+        // Pixels near the poles cover a smaller solid angle, weight them by sin(theta).
+        let mut function = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let sin_theta = f32::sin(PI * (y as f32 + 0.5) / height as f32);
+            for x in 0..width {
+                function.push(pixels[y * width + x].luminance() * sin_theta);
+            }
+        }
+
+        let radians = deg_to_rad(rotation);
+        Self {
+            distribution: Distribution2D::new(&function, width, height),
+            pixels,
+            width,
+            height,
+            sin_rotation: f32::sin(radians),
+            cos_rotation: f32::cos(radians),
+            intensity,
+        }
+    }
+
+    /// Returns the equirectangular uv coordinates of a world space direction.
+    fn direction_to_uv(&self, direction: &Vector3) -> Vector2 {
+        let unit_direction = direction.normolize();
+        // Undo the rotation of the image.
+        let x = self.cos_rotation * unit_direction.x - self.sin_rotation * unit_direction.z;
+        let z = self.sin_rotation * unit_direction.x + self.cos_rotation * unit_direction.z;
+
+        let theta = f32::acos(f32::clamp(unit_direction.y, -1., 1.));
+        let phi = f32::atan2(z, x).rem_euclid(2. * PI);
+        Vector2::new(phi / (2. * PI), theta / PI)
+    }
+
+    /// Returns the world space direction of equirectangular uv coordinates.
+    fn uv_to_direction(&self, uv: &Vector2) -> Vector3 {
+        let theta = uv.v() * PI;
+        let phi = uv.u() * 2. * PI;
+        let x = f32::sin(theta) * f32::cos(phi);
+        let z = f32::sin(theta) * f32::sin(phi);
+
+        Vector3::new(
+            self.cos_rotation * x + self.sin_rotation * z,
+            f32::cos(theta),
+            -self.sin_rotation * x + self.cos_rotation * z,
+        )
+    }
+
+    /// Returns the radiance of the pixel at the uv coordinates.
+    fn lookup(&self, uv: &Vector2) -> Color3 {
+        let x = ((uv.u() * self.width as f32) as usize).min(self.width - 1);
+        let y = ((uv.v() * self.height as f32) as usize).min(self.height - 1);
+        self.pixels[y * self.width + x].mul(self.intensity)
+    }
+}
+
+impl Light for EnvironmentLight {
+    fn sample(&self, _point: &Point3) -> Option<LightRecord> {
+        let mut rng = rand::thread_rng();
+        let (uv, uv_pdf) = self
+            .distribution
+            .sample(&Vector2::new(rng.gen::<f32>(), rng.gen::<f32>()));
+
+        // Convert the density from the image plane to solid angle.
+        let sin_theta = f32::sin(uv.v() * PI);
+        if uv_pdf <= 0. || sin_theta <= 0. {
+            return None;
+        }
+
+        let direction = self.uv_to_direction(&uv);
+        Some(LightRecord {
+            direction,
+            distance: f32::INFINITY,
+            radiance: self.emitted(&direction),
+            pdf: uv_pdf / (2. * PI * PI * sin_theta),
+        })
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn pdf_value(&self, _origin: &Point3, direction: &Vector3) -> f32 {
+        let uv = self.direction_to_uv(direction);
+        let sin_theta = f32::sin(uv.v() * PI);
+        if sin_theta <= 0. {
+            return 0.;
+        }
+        self.distribution.pdf(&uv) / (2. * PI * PI * sin_theta)
+    }
+
+    fn emitted(&self, direction: &Vector3) -> Color3 {
+        self.lookup(&self.direction_to_uv(direction))
+    }
+}
@@ -0,0 +1,198 @@
+use std::{fs, io, path::Path, vec};
+
+/**
+IES Profile
+
+The candela distribution of a luminaire read from an IESNA LM-63 photometric file, using the
+common type C photometry: vertical angles are measured from the nadir of the luminaire, horizontal
+angles around it.
+
+# Examples
+
+```
+use raytracing::core::IesProfile;
+
+let profile = IesProfile::parse(
+    "IESNA:LM-63-2002
+TILT=NONE
+1 1000 1 3 1 1 2 0 0 0
+1 1 100
+0 45 90
+0
+100 50 0",
+)
+.unwrap();
+# assert_eq!(profile.candela(0., 0.), 100.);
+# assert_eq!(profile.candela(22.5, 0.), 75.);
+# assert_eq!(profile.candela(120., 0.), 0.);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct IesProfile {
+    /// Vertical angles in degrees, in increasing order.
+    vertical_angles: Vec<f32>,
+
+    /// Horizontal angles in degrees, in increasing order.
+    horizontal_angles: Vec<f32>,
+
+    /// Candela values, one row of vertical samples per horizontal angle.
+    candela: Vec<Vec<f32>>,
+}
+
+impl IesProfile {
+    /// Load an `IesProfile` from a `.ies` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse an `IesProfile` from the content of a `.ies` file.
+    pub fn parse(content: &str) -> io::Result<Self> {
+        let invalid =
+            |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_owned());
+
+        // Skip the keyword header, the photometric data starts after the TILT line.
+        let mut lines = content.lines();
+        let tilt = lines
+            .by_ref()
+            .find(|line| line.trim_start().starts_with("TILT="))
+            .ok_or_else(|| invalid("missing TILT line"))?;
+
+        let mut numbers = Vec::new();
+        for token in lines.flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ',')) {
+            if token.is_empty() {
+                continue;
+            }
+            numbers.push(
+                token
+                    .parse::<f32>()
+                    .map_err(|_| invalid(&format!("invalid number `{}`", token)))?,
+            );
+        }
+        let mut numbers = numbers.into_iter();
+        let next = |numbers: &mut vec::IntoIter<f32>| {
+            numbers
+                .next()
+                .ok_or_else(|| invalid("unexpected end of file"))
+        };
+        // Counts are checked against the numbers left, so malformed files can't allocate huge
+        // lists.
+        let count = |numbers: &mut vec::IntoIter<f32>| {
+            let value = next(numbers)?;
+            if value >= 0. && value.fract() == 0. && value <= numbers.len() as f32 {
+                Ok(value as usize)
+            } else {
+                Err(invalid(&format!("invalid count `{}`", value)))
+            }
+        };
+
+        if tilt.trim() == "TILT=INCLUDE" {
+            // Lamp to luminaire geometry, then pairs of tilt angles and multipliers.
+            next(&mut numbers)?;
+            let pairs = count(&mut numbers)?;
+            for _ in 0..2 * pairs {
+                next(&mut numbers)?;
+            }
+        }
+
+        let _lamp_count = next(&mut numbers)?;
+        let _lumens_per_lamp = next(&mut numbers)?;
+        let multiplier = next(&mut numbers)?;
+        let vertical_count = count(&mut numbers)?;
+        let horizontal_count = count(&mut numbers)?;
+        let photometric_type = next(&mut numbers)?;
+        if photometric_type != 1. {
+            return Err(invalid("only type C photometry is supported"));
+        }
+        // Units type, luminous opening sizes, ballast factor, future use and input watts.
+        for _ in 0..7 {
+            next(&mut numbers)?;
+        }
+
+        let value_count = vertical_count
+            .checked_mul(horizontal_count)
+            .and_then(|count| count.checked_add(vertical_count + horizontal_count));
+        if value_count.is_none_or(|count| count > numbers.len()) {
+            return Err(invalid("unexpected end of file"));
+        }
+        let vertical_angles = (0..vertical_count)
+            .map(|_| next(&mut numbers))
+            .collect::<io::Result<Vec<f32>>>()?;
+        let horizontal_angles = (0..horizontal_count)
+            .map(|_| next(&mut numbers))
+            .collect::<io::Result<Vec<f32>>>()?;
+        let mut candela = Vec::with_capacity(horizontal_count);
+        for _ in 0..horizontal_count {
+            candela.push(
+                (0..vertical_count)
+                    .map(|_| next(&mut numbers).map(|value| value * multiplier))
+                    .collect::<io::Result<Vec<f32>>>()?,
+            );
+        }
+
+        if vertical_angles.is_empty() || horizontal_angles.is_empty() {
+            return Err(invalid("empty angle list"));
+        }
+
+        Ok(Self {
+            vertical_angles,
+            horizontal_angles,
+            candela,
+        })
+    }
+
+    /// Returns the maximum candela value of the profile.
+    pub fn max_candela(&self) -> f32 {
+        self.candela
+            .iter()
+            .flatten()
+            .fold(0., |max, &value| f32::max(max, value))
+    }
+
+    /// Returns the luminous intensity in candela at the vertical and horizontal angles in degrees.
+    pub fn candela(&self, vertical_angle: f32, horizontal_angle: f32) -> f32 {
+        let first_vertical = self.vertical_angles[0];
+        let last_vertical = self.vertical_angles[self.vertical_angles.len() - 1];
+        if vertical_angle < first_vertical || vertical_angle > last_vertical {
+            return 0.;
+        }
+
+        // Fold the horizontal angle into the range covered by the symmetry of the profile.
+        let mut horizontal_angle = horizontal_angle.rem_euclid(360.);
+        let last_horizontal = self.horizontal_angles[self.horizontal_angles.len() - 1];
+        if last_horizontal <= 90. {
+            if horizontal_angle > 180. {
+                horizontal_angle = 360. - horizontal_angle;
+            }
+            if horizontal_angle > 90. {
+                horizontal_angle = 180. - horizontal_angle;
+            }
+        } else if last_horizontal <= 180. && horizontal_angle > 180. {
+            horizontal_angle = 360. - horizontal_angle;
+        }
+
+        let (h0, h1, h_weight) = Self::lerp_position(&self.horizontal_angles, horizontal_angle);
+        let (v0, v1, v_weight) = Self::lerp_position(&self.vertical_angles, vertical_angle);
+
+        let row = |h: usize| self.candela[h][v0] * (1. - v_weight) + self.candela[h][v1] * v_weight;
+        row(h0) * (1. - h_weight) + row(h1) * h_weight
+    }
+
+    /// Returns the two indices around the angle and the interpolation weight between them.
+    fn lerp_position(angles: &[f32], angle: f32) -> (usize, usize, f32) {
+        if angles.len() == 1 || angle <= angles[0] {
+            return (0, 0, 0.);
+        }
+        match angles.iter().position(|&a| a >= angle) {
+            Some(i) => {
+                let span = angles[i] - angles[i - 1];
+                let weight = if span > 0. {
+                    (angle - angles[i - 1]) / span
+                } else {
+                    0.
+                };
+                (i - 1, i, weight)
+            }
+            None => (angles.len() - 1, angles.len() - 1, 0.),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::core::{AxisAlignedBoundingBox, Point3, Vector3};
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul},
+};
+
+/**
+Light Bounds
+
+Bounds of the emission of one or more lights: where they are, how much power they emit, and in
+which directions. The emitting surfaces face directions within `theta_o` of `w`, and each surface
+emits within `theta_e` around its own normal. It gives a conservative estimate of how much light
+reaches a point, as described by Conty Estevez and Kulla, "Importance Sampling of Many Lights with
+Adaptive Tree Splitting".
+
+# Examples
+
+```
+use raytracing::core::{AxisAlignedBoundingBox, LightBounds, Point3, Vector3};
+
+let bbox = AxisAlignedBoundingBox::new_with_two_points(&Point3::zero(), &Point3::new(1., 0., 1.));
+let bounds = LightBounds::new(bbox, 10., Vector3::new(0., -1., 0.), 1., 0., false);
+
+// The light faces down, so points above it receive nothing.
+# assert!(bounds.importance(&Point3::new(0.5, -2., 0.5)) > 0.);
+# assert_eq!(bounds.importance(&Point3::new(0.5, 2., 0.5)), 0.);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct LightBounds {
+    /// Bounding box of the emitting surfaces.
+    pub bbox: AxisAlignedBoundingBox,
+
+    /// Total power emitted.
+    pub power: f32,
+
+    /// Unit axis of the cone of surface normals.
+    pub w: Vector3,
+
+    /// Cosine of the spread of the surface normals around `w`.
+    pub cos_theta_o: f32,
+
+    /// Cosine of the angle around a surface normal that light is emitted into.
+    pub cos_theta_e: f32,
+
+    /// Whether the surfaces emit from both faces.
+    pub two_sided: bool,
+}
+
+impl LightBounds {
+    /// Create a new `LightBounds`.
+    pub fn new(
+        bbox: AxisAlignedBoundingBox,
+        power: f32,
+        w: Vector3,
+        cos_theta_o: f32,
+        cos_theta_e: f32,
+        two_sided: bool,
+    ) -> Self {
+        Self {
+            bbox,
+            power,
+            w: w.normolize(),
+            cos_theta_o,
+            cos_theta_e,
+            two_sided,
+        }
+    }
+
+    /// Create a new `LightBounds` of lights emitting in every direction.
+    pub fn new_omnidirectional(bbox: AxisAlignedBoundingBox, power: f32) -> Self {
+        Self::new(bbox, power, Vector3::new(0., 0., 1.), -1., 0., false)
+    }
+
+    /// Returns the center of the bounding box.
+    pub fn centroid(&self) -> Point3 {
+        Point3::new(
+            (self.bbox.x.min + self.bbox.x.max) / 2.,
+            (self.bbox.y.min + self.bbox.y.max) / 2.,
+            (self.bbox.z.min + self.bbox.z.max) / 2.,
+        )
+    }
+
+    /// Returns the bounds of the lights of both bounds together.
+    pub fn merge(&self, rhs: &Self) -> Self {
+        if self.power <= 0. {
+            return rhs.clone();
+        }
+        if rhs.power <= 0. {
+            return self.clone();
+        }
+
+        let (w, cos_theta_o) =
+            Self::merge_cones(&self.w, self.cos_theta_o, &rhs.w, rhs.cos_theta_o);
+        Self {
+            bbox: self.bbox.merge(&rhs.bbox),
+            power: self.power + rhs.power,
+            w,
+            cos_theta_o,
+            cos_theta_e: f32::min(self.cos_theta_e, rhs.cos_theta_e),
+            two_sided: self.two_sided || rhs.two_sided,
+        }
+    }
+
+    /// Returns the smallest cone containing both cones of directions.
+    fn merge_cones(w1: &Vector3, cos_theta1: f32, w2: &Vector3, cos_theta2: f32) -> (Vector3, f32) {
+        let theta1 = f32::acos(f32::clamp(cos_theta1, -1., 1.));
+        let theta2 = f32::acos(f32::clamp(cos_theta2, -1., 1.));
+        let theta_d = f32::acos(f32::clamp(w1.dot(w2), -1., 1.));
+
+        // One of the cones contains the other one.
+        if f32::min(theta_d + theta2, PI) <= theta1 {
+            return (*w1, cos_theta1);
+        }
+        if f32::min(theta_d + theta1, PI) <= theta2 {
+            return (*w2, cos_theta2);
+        }
+
+        let theta_o = (theta1 + theta_d + theta2) / 2.;
+        let axis = w1.cross(w2);
+        if theta_o >= PI || axis.length_squared() <= 0. {
+            return (*w1, -1.);
+        }
+
+        // Rotate w1 towards w2 so the new cone touches the far sides of both.
+        let theta_r = theta_o - theta1;
+        let axis = axis.normolize();
+        let w = w1
+            .mul(f32::cos(theta_r))
+            .add(&axis.cross(w1).mul(f32::sin(theta_r)))
+            .add(&axis.mul(axis.dot(w1) * (1. - f32::cos(theta_r))));
+        (w.normolize(), f32::cos(theta_o))
+    }
+
+    /// Returns a conservative estimate of the light arriving at the point from these bounds.
+    pub fn importance(&self, point: &Point3) -> f32 {
+        if self.power <= 0. {
+            return 0.;
+        }
+
+        // Keep the distance from collapsing when the point is close to or inside the box.
+        let centroid = self.centroid();
+        let diagonal = Vector3::new(self.bbox.x.size(), self.bbox.y.size(), self.bbox.z.size());
+        let distance_squared =
+            f32::max((point - &centroid).length_squared(), diagonal.length() / 2.);
+
+        // Angle between the cone axis and the direction towards the point.
+        let to_point = (point - &centroid).normolize();
+        let mut cos_theta_w = self.w.dot(&to_point);
+        if self.two_sided {
+            cos_theta_w = cos_theta_w.abs();
+        }
+        let theta_w = f32::acos(f32::clamp(cos_theta_w, -1., 1.));
+
+        // Angle subtended by the bounding sphere of the box seen from the point.
+        let radius_squared = diagonal.length_squared() / 4.;
+        let center_distance_squared = (point - &centroid).length_squared();
+        let theta_b = if center_distance_squared <= radius_squared {
+            PI
+        } else {
+            f32::asin(f32::sqrt(radius_squared / center_distance_squared))
+        };
+
+        let theta_o = f32::acos(f32::clamp(self.cos_theta_o, -1., 1.));
+        let cos_theta = f32::cos(f32::max(theta_w - theta_o - theta_b, 0.));
+        if cos_theta <= self.cos_theta_e {
+            return 0.;
+        }
+
+        self.power * cos_theta / distance_squared
+    }
+}
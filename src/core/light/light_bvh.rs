@@ -0,0 +1,249 @@
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, HitRecord, HittableList, Interval, LightBounds, Point3, Ray,
+        Vector3,
+    },
+    traits::Hittable,
+};
+use rand::Rng;
+use std::rc::Rc;
+
+/// A node of the light bounding volume hierarchy.
+#[derive(Debug)]
+enum LightNode {
+    /// A single light and its bounds.
+    Leaf(Rc<dyn Hittable>, LightBounds),
+
+    /// Two child nodes and the bounds of all the lights below them.
+    Interior(Box<LightNode>, Box<LightNode>, LightBounds),
+}
+
+impl LightNode {
+    /// Returns the bounds of the lights below this node.
+    fn bounds(&self) -> &LightBounds {
+        match self {
+            Self::Leaf(_, bounds) => bounds,
+            Self::Interior(_, _, bounds) => bounds,
+        }
+    }
+}
+
+/**
+Light Bounding Volume Hierarchy
+
+A tree of emissive objects that picks a light for a shading point by the importance of each
+subtree, a bound of the light it can send to that point from its power, distance and orientation.
+Compared to picking lights uniformly or by power alone, nearby lights facing the point get most of
+the samples, which keeps scenes with hundreds of lights converging. It is used in place of a
+`HittableList` as the lights of a render.
+
+Objects whose `light_bounds` is `None`, e.g. those without an emissive material, are left out and
+never picked.
+*/
+#[derive(Debug)]
+pub struct LightBVH {
+    /// Root of the tree, `None` when there are no lights.
+    root: Option<LightNode>,
+
+    /// Bounding box of all the lights.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl LightBVH {
+    /// Create a new `LightBVH` from the emissive objects of a `HittableList`.
+    pub fn new(list: &HittableList) -> Self {
+        let mut lights: Vec<(Rc<dyn Hittable>, LightBounds)> = list
+            .objects
+            .iter()
+            .filter_map(|object| Some((object.clone(), object.light_bounds()?)))
+            .collect();
+
+        let root = if lights.is_empty() {
+            None
+        } else {
+            Some(Self::split(&mut lights))
+        };
+        let bbox = match &root {
+            Some(node) => node.bounds().bbox.clone(),
+            None => {
+                AxisAlignedBoundingBox::new(Interval::empty(), Interval::empty(), Interval::empty())
+            }
+        };
+
+        Self { root, bbox }
+    }
+
+    /// Internal method, split the lights in half along the longest axis of their centroids and
+    /// recursively create nodes for each half.
+    fn split(lights: &mut [(Rc<dyn Hittable>, LightBounds)]) -> LightNode {
+        if lights.len() == 1 {
+            let (light, bounds) = &lights[0];
+            return LightNode::Leaf(light.clone(), bounds.clone());
+        }
+
+        let mut min = Vector3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = Vector3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for (_, bounds) in lights.iter() {
+            let centroid = bounds.centroid();
+            for i in 0..3 {
+                min[i] = f32::min(min[i], centroid[i]);
+                max[i] = f32::max(max[i], centroid[i]);
+            }
+        }
+        let extent = &max - &min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+
+        lights.sort_by(|a, b| a.1.centroid()[axis].total_cmp(&b.1.centroid()[axis]));
+        let (left, right) = lights.split_at_mut(lights.len() / 2);
+        let left = Self::split(left);
+        let right = Self::split(right);
+        let bounds = left.bounds().merge(right.bounds());
+
+        LightNode::Interior(Box::new(left), Box::new(right), bounds)
+    }
+
+    /// Returns the probability of descending into the left child for a shading point.
+    fn left_probability(origin: &Point3, left: &LightBounds, right: &LightBounds) -> f32 {
+        let left_importance = left.importance(origin);
+        let right_importance = right.importance(origin);
+        if left_importance + right_importance > 0. {
+            left_importance / (left_importance + right_importance)
+        } else if left.power + right.power > 0. {
+            left.power / (left.power + right.power)
+        } else {
+            0.5
+        }
+    }
+
+    /// Returns the light picked for a shading point by a uniform random number `u`, and the
+    /// probability of picking it.
+    pub fn sample(&self, origin: &Point3, mut u: f32) -> Option<(Rc<dyn Hittable>, f32)> {
+        let mut node = self.root.as_ref()?;
+        let mut probability = 1.;
+
+        loop {
+            match node {
+                LightNode::Leaf(light, _) => return Some((light.clone(), probability)),
+                LightNode::Interior(left, right, _) => {
+                    let left_probability =
+                        Self::left_probability(origin, left.bounds(), right.bounds());
+                    // Reuse the random number by remapping it into the chosen side.
+                    if u < left_probability {
+                        u /= left_probability;
+                        probability *= left_probability;
+                        node = left;
+                    } else {
+                        u = (u - left_probability) / (1. - left_probability);
+                        probability *= 1. - left_probability;
+                        node = right;
+                    }
+                    u = f32::min(u, 1. - f32::EPSILON);
+                }
+            }
+        }
+    }
+
+    /// Internal method, returns the density of sampling a direction through the lights below a
+    /// node, which is picked with the given probability.
+    fn node_pdf_value(
+        node: &LightNode,
+        origin: &Point3,
+        direction: &Vector3,
+        probability: f32,
+    ) -> f32 {
+        match node {
+            LightNode::Leaf(light, _) => probability * light.pdf_value(origin, direction),
+            LightNode::Interior(left, right, bounds) => {
+                // Lights the direction can't reach add nothing.
+                let ray = Ray::new(*origin, *direction);
+                if !bounds.bbox.hit(&ray, &Interval::new(0.001, f32::INFINITY)) {
+                    return 0.;
+                }
+
+                let left_probability =
+                    Self::left_probability(origin, left.bounds(), right.bounds());
+                Self::node_pdf_value(left, origin, direction, probability * left_probability)
+                    + Self::node_pdf_value(
+                        right,
+                        origin,
+                        direction,
+                        probability * (1. - left_probability),
+                    )
+            }
+        }
+    }
+
+    /// Internal method, hit the lights below a node.
+    fn node_hit(
+        node: &LightNode,
+        ray: &Ray,
+        ray_interval: &Interval,
+        record: &mut HitRecord,
+    ) -> bool {
+        match node {
+            LightNode::Leaf(light, _) => light.hit(ray, ray_interval, record),
+            LightNode::Interior(left, right, bounds) => {
+                if !bounds.bbox.hit(ray, ray_interval) {
+                    return false;
+                }
+
+                let hit_left = Self::node_hit(left, ray, ray_interval, record);
+                let hit_right = Self::node_hit(
+                    right,
+                    ray,
+                    &Interval::new(
+                        ray_interval.min,
+                        if hit_left { record.t } else { ray_interval.max },
+                    ),
+                    record,
+                );
+                hit_left || hit_right
+            }
+        }
+    }
+}
+
+impl Hittable for LightBVH {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        match &self.root {
+            Some(node) => Self::node_hit(node, ray, ray_interval, record),
+            None => false,
+        }
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        match &self.root {
+            Some(node) => Self::node_pdf_value(node, origin, direction, 1.),
+            None => 0.,
+        }
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        let mut rng = rand::thread_rng();
+        match self.sample(origin, rng.gen::<f32>()) {
+            Some((light, _)) => light.random(origin),
+            None => Vector3::new(1., 0., 0.),
+        }
+    }
+
+    fn power(&self) -> f32 {
+        match &self.root {
+            Some(node) => node.bounds().power,
+            None => 0.,
+        }
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        self.root.as_ref().map(|node| node.bounds().clone())
+    }
+}
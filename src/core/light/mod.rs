@@ -0,0 +1,21 @@
+mod directional_light;
+mod environment_light;
+mod ies_profile;
+mod light_bounds;
+mod light_bvh;
+mod photometric_light;
+mod point_light;
+mod sky_light;
+mod spot_light;
+mod sun_light;
+
+pub use directional_light::*;
+pub use environment_light::*;
+pub use ies_profile::*;
+pub use light_bounds::*;
+pub use light_bvh::*;
+pub use photometric_light::*;
+pub use point_light::*;
+pub use sky_light::*;
+pub use spot_light::*;
+pub use sun_light::*;
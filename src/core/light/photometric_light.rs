@@ -0,0 +1,80 @@
+use crate::{
+    core::{rad_to_deg, Color3, IesProfile, LightRecord, OrthonormalBasis, Point3, Vector3},
+    traits::Light,
+};
+use std::ops::{Mul, Neg};
+
+/**
+Photometric Light
+
+An infinitesimal light whose intensity follows the candela distribution of an IES profile. The
+nadir of the profile, its vertical angle 0, points along `direction`.
+*/
+#[derive(Debug)]
+pub struct PhotometricLight {
+    /// Position of the light.
+    pub position: Point3,
+
+    /// Frame of the luminaire, `w` is the nadir direction.
+    basis: OrthonormalBasis,
+
+    /// Color of the light, scaled by the candela values of the profile.
+    pub color: Color3,
+
+    /// Candela distribution of the luminaire.
+    profile: IesProfile,
+
+    /// Factor converting candela values into the radiant intensity of the scene.
+    pub scale: f32,
+}
+
+impl PhotometricLight {
+    /// Create a new `PhotometricLight` pointing along `direction`.
+    pub fn new(
+        position: Point3,
+        direction: Vector3,
+        color: Color3,
+        profile: IesProfile,
+        scale: f32,
+    ) -> Self {
+        Self {
+            position,
+            basis: OrthonormalBasis::new_with_w(&direction),
+            color,
+            profile,
+            scale,
+        }
+    }
+}
+
+impl Light for PhotometricLight {
+    fn sample(&self, point: &Point3) -> Option<LightRecord> {
+        let to_light = &self.position - point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= 0. {
+            return None;
+        }
+
+        // Find the photometric angles of the direction the light leaves the luminaire.
+        let direction = to_light.normolize();
+        let emitted = direction.neg();
+        let vertical_angle =
+            rad_to_deg(f32::acos(f32::clamp(emitted.dot(self.basis.w()), -1., 1.)));
+        let horizontal_angle = rad_to_deg(f32::atan2(
+            emitted.dot(self.basis.v()),
+            emitted.dot(self.basis.u()),
+        ));
+
+        let candela = self.profile.candela(vertical_angle, horizontal_angle);
+        if candela <= 0. {
+            return None;
+        }
+
+        Some(LightRecord {
+            direction,
+            distance: distance_squared.sqrt(),
+            radiance: self.color.mul(candela * self.scale / distance_squared),
+            pdf: 1.,
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    core::{Color3, LightRecord, Point3},
+    traits::Light,
+};
+
+/**
+Point Light
+
+An infinitesimal light that emits the same intensity in every direction.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, Point3, PointLight},
+    traits::Light,
+};
+
+let light = PointLight::new(Point3::new(0., 2., 0.), Color3::new(4., 4., 4.));
+let record = light.sample(&Point3::zero()).unwrap();
+# assert_eq!(record.distance, 2.);
+# assert_eq!(record.radiance, Color3::one());
+```
+*/
+#[derive(Debug)]
+pub struct PointLight {
+    /// Position of the light.
+    pub position: Point3,
+
+    /// Radiant intensity of the light.
+    pub intensity: Color3,
+}
+
+impl PointLight {
+    /// Create a new `PointLight` with a position and a radiant intensity.
+    pub fn new(position: Point3, intensity: Color3) -> Self {
+        Self {
+            position,
+            intensity,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn sample(&self, point: &Point3) -> Option<LightRecord> {
+        let to_light = &self.position - point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= 0. {
+            return None;
+        }
+
+        Some(LightRecord {
+            direction: to_light.normolize(),
+            distance: distance_squared.sqrt(),
+            radiance: &self.intensity / distance_squared,
+            pdf: 1.,
+        })
+    }
+}
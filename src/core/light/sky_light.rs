@@ -0,0 +1,257 @@
+use crate::{
+    core::{deg_to_rad, Color3, EnvironmentLight, LightRecord, Point3, SunLight, Vector3},
+    traits::Light,
+};
+use std::{f32::consts::PI, ops::Mul};
+
+/// Width of the environment map the sky is baked into, in pixel count.
+const SKY_MAP_WIDTH: usize = 512;
+
+/// Height of the environment map the sky is baked into, in pixel count.
+const SKY_MAP_HEIGHT: usize = 256;
+
+/// Illuminance of the sun outside the atmosphere in klx.
+const SOLAR_ILLUMINANCE: f32 = 128.;
+
+/**
+Sky Light
+
+The clear sky model of Preetham, Shirley and Smits, "A Practical Analytic Model for Daylight",
+parameterised by the sun position and the turbidity of the atmosphere, over a diffuse ground. The
+sky is baked into an environment map, so it is sampled like an `EnvironmentLight`, and `sun` gives
+the matching sun disk.
+
+Radiance is in kcd/m² times `intensity`, a clear sky is around 5 to 10 kcd/m², so an intensity
+around 0.1 keeps it in a displayable range.
+
+# Examples
+
+```
+use raytracing::{
+    core::{SkyLight, Vector3},
+    traits::Light,
+};
+
+let sky = SkyLight::new(45., 90., 3., 0.3, 0.1);
+let sun = sky.sun();
+# assert!(sun.direction().y > 0.7 && sun.direction().y < 0.71);
+# assert!(sky.emitted(&Vector3::new(0., 1., 0.)).z > sky.emitted(&Vector3::new(0., 1., 0.)).x);
+```
+*/
+#[derive(Debug)]
+pub struct SkyLight {
+    /// Unit direction towards the sun.
+    sun_direction: Vector3,
+
+    /// Turbidity of the atmosphere, 2 is very clear and 10 is hazy.
+    pub turbidity: f32,
+
+    /// Factor applied to the sky and sun radiance.
+    pub intensity: f32,
+
+    /// The sky baked into an equirectangular environment map.
+    environment: EnvironmentLight,
+}
+
+/// Coefficients of the Perez sky distribution function.
+struct PerezCoefficients([f32; 5]);
+
+impl PerezCoefficients {
+    /// Evaluate the Perez function at the zenith angle of the view and the angle to the sun.
+    fn evaluate(&self, cos_theta: f32, gamma: f32) -> f32 {
+        let [a, b, c, d, e] = self.0;
+        let cos_gamma = f32::cos(gamma);
+        (1. + a * f32::exp(b / f32::max(cos_theta, 0.01)))
+            * (1. + c * f32::exp(d * gamma) + e * cos_gamma * cos_gamma)
+    }
+}
+
+impl SkyLight {
+    /// Create a new `SkyLight` with the sun elevation and azimuth in degrees, the azimuth turns
+    /// from +x towards +z.
+    pub fn new(
+        sun_elevation: f32,
+        sun_azimuth: f32,
+        turbidity: f32,
+        ground_albedo: f32,
+        intensity: f32,
+    ) -> Self {
+        let elevation = deg_to_rad(sun_elevation);
+        let azimuth = deg_to_rad(sun_azimuth);
+        let sun_direction = Vector3::new(
+            f32::cos(elevation) * f32::cos(azimuth),
+            f32::sin(elevation),
+            f32::cos(elevation) * f32::sin(azimuth),
+        );
+
+        let mut sky = Self {
+            sun_direction,
+            turbidity,
+            intensity,
+            environment: EnvironmentLight::new_with_pixels(vec![Color3::zero()], 1, 1, 0., 0.),
+        };
+        sky.environment = sky.bake(ground_albedo);
+        sky
+    }
+
+    /// Returns the sun disk matching the sky, with the radiance left after passing the atmosphere.
+    pub fn sun(&self) -> SunLight {
+        let mut sun = SunLight::new(self.sun_direction, SunLight::ANGULAR_RADIUS, Color3::zero());
+        sun.radiance = self
+            .sun_transmittance()
+            .mul(SOLAR_ILLUMINANCE / sun.solid_angle() * self.intensity);
+        sun
+    }
+
+    /// Returns the fraction of sunlight passing the atmosphere in each rgb channel, from the
+    /// Rayleigh and aerosol optical depths along the sun direction.
+    fn sun_transmittance(&self) -> Color3 {
+        if self.sun_direction.y <= 0. {
+            return Color3::zero();
+        }
+
+        // Relative optical mass of the air, Kasten's formula.
+        let zenith = f32::acos(self.sun_direction.y).to_degrees();
+        let air_mass = 1. / (self.sun_direction.y + 0.15 * f32::powf(93.885 - zenith, -1.253));
+
+        // Representative wavelengths of the rgb channels in micrometers.
+        let beta = 0.04608 * self.turbidity - 0.04586;
+        let transmittance = |wavelength: f32| {
+            let rayleigh = 0.008735 * f32::powf(wavelength, -4.08);
+            let aerosol = beta * f32::powf(wavelength, -1.3);
+            f32::exp(-air_mass * (rayleigh + aerosol))
+        };
+        Color3::new(
+            transmittance(0.68),
+            transmittance(0.55),
+            transmittance(0.44),
+        )
+    }
+
+    /// Returns the unscaled radiance of the sky in a direction above the horizon.
+    fn sky_radiance(&self, direction: &Vector3) -> Color3 {
+        let t = self.turbidity;
+        let theta_sun = f32::acos(f32::clamp(self.sun_direction.y, -1., 1.));
+        let cos_theta = f32::max(direction.y, 0.);
+        let gamma = f32::acos(f32::clamp(direction.dot(&self.sun_direction), -1., 1.));
+
+        let luminance = PerezCoefficients([
+            0.1787 * t - 1.4630,
+            -0.3554 * t + 0.4275,
+            -0.0227 * t + 5.3251,
+            0.1206 * t - 2.5771,
+            -0.0670 * t + 0.3703,
+        ]);
+        let chromaticity_x = PerezCoefficients([
+            -0.0193 * t - 0.2592,
+            -0.0665 * t + 0.0008,
+            -0.0004 * t + 0.2125,
+            -0.0641 * t - 0.8989,
+            -0.0033 * t + 0.0452,
+        ]);
+        let chromaticity_y = PerezCoefficients([
+            -0.0167 * t - 0.2608,
+            -0.0950 * t + 0.0092,
+            -0.0079 * t + 0.2102,
+            -0.0441 * t - 1.6537,
+            -0.0109 * t + 0.0529,
+        ]);
+
+        // Zenith luminance and chromaticity.
+        let chi = (4. / 9. - t / 120.) * (PI - 2. * theta_sun);
+        let zenith_luminance = f32::max(
+            (4.0453 * t - 4.9710) * f32::tan(chi) - 0.2155 * t + 2.4192,
+            0.,
+        );
+        let theta = [theta_sun.powi(3), theta_sun.powi(2), theta_sun, 1.];
+        let polynomial = |coefficients: [[f32; 4]; 3]| {
+            let row = |i: usize| (0..4).map(|j| coefficients[i][j] * theta[j]).sum::<f32>();
+            t * t * row(0) + t * row(1) + row(2)
+        };
+        let zenith_x = polynomial([
+            [0.00166, -0.00375, 0.00209, 0.],
+            [-0.02903, 0.06377, -0.03202, 0.00394],
+            [0.11693, -0.21196, 0.06052, 0.25886],
+        ]);
+        let zenith_y = polynomial([
+            [0.00275, -0.00610, 0.00317, 0.],
+            [-0.04214, 0.08970, -0.04153, 0.00516],
+            [0.15346, -0.26756, 0.06670, 0.26688],
+        ]);
+
+        let relative = |perez: &PerezCoefficients| {
+            perez.evaluate(cos_theta, gamma) / perez.evaluate(1., theta_sun)
+        };
+        let y_luminance = zenith_luminance * relative(&luminance);
+        let x = zenith_x * relative(&chromaticity_x);
+        let y = zenith_y * relative(&chromaticity_y);
+
+        Self::xyy_to_rgb(x, y, y_luminance)
+    }
+
+    /// Convert a color from CIE xyY to linear Rec.709 rgb.
+    fn xyy_to_rgb(x: f32, y: f32, luminance: f32) -> Color3 {
+        if y <= 0. {
+            return Color3::zero();
+        }
+        let big_x = x * luminance / y;
+        let big_z = (1. - x - y) * luminance / y;
+        Color3::new(
+            f32::max(3.2406 * big_x - 1.5372 * luminance - 0.4986 * big_z, 0.),
+            f32::max(-0.9689 * big_x + 1.8758 * luminance + 0.0415 * big_z, 0.),
+            f32::max(0.0557 * big_x - 0.2040 * luminance + 1.0570 * big_z, 0.),
+        )
+    }
+
+    /// Bake the sky into an environment map, the ground reflects the sky and sun diffusely.
+    fn bake(&self, ground_albedo: f32) -> EnvironmentLight {
+        let (width, height) = (SKY_MAP_WIDTH, SKY_MAP_HEIGHT);
+        let mut pixels = vec![Color3::zero(); width * height];
+        let mut irradiance = Color3::zero();
+
+        for y in 0..height / 2 {
+            let theta = PI * (y as f32 + 0.5) / height as f32;
+            let solid_angle = (2. * PI / width as f32) * (PI / height as f32) * f32::sin(theta);
+            for x in 0..width {
+                let phi = 2. * PI * (x as f32 + 0.5) / width as f32;
+                let direction = Vector3::new(
+                    f32::sin(theta) * f32::cos(phi),
+                    f32::cos(theta),
+                    f32::sin(theta) * f32::sin(phi),
+                );
+                let radiance = self.sky_radiance(&direction);
+                irradiance += &radiance.mul(f32::cos(theta) * solid_angle);
+                pixels[y * width + x] = radiance;
+            }
+        }
+
+        // Light the ground by the sky and the sun, `intensity` is applied by the environment map.
+        let sun_irradiance = self
+            .sun_transmittance()
+            .mul(SOLAR_ILLUMINANCE * f32::max(self.sun_direction.y, 0.));
+        let ground = (&irradiance + &sun_irradiance).mul(ground_albedo / PI);
+        for pixel in pixels[(height / 2) * width..].iter_mut() {
+            *pixel = ground;
+        }
+
+        EnvironmentLight::new_with_pixels(pixels, width, height, 0., self.intensity)
+    }
+}
+
+impl Light for SkyLight {
+    fn sample(&self, point: &Point3) -> Option<LightRecord> {
+        self.environment.sample(point)
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        self.environment.pdf_value(origin, direction)
+    }
+
+    fn emitted(&self, direction: &Vector3) -> Color3 {
+        self.environment.emitted(direction)
+    }
+}
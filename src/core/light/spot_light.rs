@@ -0,0 +1,88 @@
+use crate::{
+    core::{deg_to_rad, Color3, LightRecord, Point3, Vector3},
+    traits::Light,
+};
+use std::ops::{Mul, Neg};
+
+/**
+Spot Light
+
+An infinitesimal light that emits a cone of light, fading out smoothly between the falloff angle
+and the cone angle.
+*/
+#[derive(Debug)]
+pub struct SpotLight {
+    /// Position of the light.
+    pub position: Point3,
+
+    /// Unit direction the light points at.
+    direction: Vector3,
+
+    /// Radiant intensity along the cone axis.
+    pub intensity: Color3,
+
+    /// Cosine of the angle between the axis and the edge of the cone.
+    cos_cone_angle: f32,
+
+    /// Cosine of the angle where the intensity starts to fall off.
+    cos_falloff_angle: f32,
+}
+
+impl SpotLight {
+    /// Create a new `SpotLight` at `position` pointing at `target`, both angles are half angles
+    /// from the axis in degrees.
+    pub fn new(
+        position: Point3,
+        target: Point3,
+        intensity: Color3,
+        cone_angle: f32,
+        falloff_angle: f32,
+    ) -> Self {
+        let falloff_angle = f32::min(falloff_angle, cone_angle);
+        Self {
+            position,
+            direction: (&target - &position).normolize(),
+            intensity,
+            cos_cone_angle: f32::cos(deg_to_rad(cone_angle)),
+            cos_falloff_angle: f32::cos(deg_to_rad(falloff_angle)),
+        }
+    }
+
+    /// Returns the fraction of the intensity emitted along the unit direction.
+    fn falloff(&self, direction: &Vector3) -> f32 {
+        let cos_theta = direction.dot(&self.direction);
+        if cos_theta >= self.cos_falloff_angle {
+            return 1.;
+        }
+        if cos_theta <= self.cos_cone_angle {
+            return 0.;
+        }
+
+        let delta =
+            (cos_theta - self.cos_cone_angle) / (self.cos_falloff_angle - self.cos_cone_angle);
+        delta * delta * (3. - 2. * delta)
+    }
+}
+
+impl Light for SpotLight {
+    fn sample(&self, point: &Point3) -> Option<LightRecord> {
+        let to_light = &self.position - point;
+        let distance_squared = to_light.length_squared();
+        if distance_squared <= 0. {
+            return None;
+        }
+
+        let direction = to_light.normolize();
+        let falloff = self.falloff(&direction.neg());
+        if falloff <= 0. {
+            return None;
+        }
+
+        Some(LightRecord {
+            direction,
+            distance: distance_squared.sqrt(),
+            radiance: self.intensity.mul(falloff / distance_squared),
+            pdf: 1.,
+        })
+    }
+}
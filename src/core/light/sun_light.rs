@@ -0,0 +1,90 @@
+use crate::{
+    core::{deg_to_rad, Color3, LightRecord, OrthonormalBasis, Point3, Vector3},
+    traits::Light,
+};
+use rand::Rng;
+use std::f32::consts::PI;
+
+/**
+Sun Light
+
+A light infinitely far away covering a small disk of the sky. Unlike a `DirectionalLight` the sun
+has a real solid angle, so it is seen by rays that escape the scene and casts soft shadow edges.
+*/
+#[derive(Debug)]
+pub struct SunLight {
+    /// Frame around the unit direction towards the sun.
+    basis: OrthonormalBasis,
+
+    /// Cosine of the angular radius of the sun disk.
+    cos_theta_max: f32,
+
+    /// Radiance of the sun disk.
+    pub radiance: Color3,
+}
+
+impl SunLight {
+    /// Angular radius of the sun seen from the earth, in degrees.
+    pub const ANGULAR_RADIUS: f32 = 0.2666;
+
+    /// Create a new `SunLight` towards `direction` with the angular radius of the disk in degrees.
+    pub fn new(direction: Vector3, angular_radius: f32, radiance: Color3) -> Self {
+        Self {
+            basis: OrthonormalBasis::new_with_w(&direction),
+            cos_theta_max: f32::cos(deg_to_rad(angular_radius)),
+            radiance,
+        }
+    }
+
+    /// Returns the unit direction towards the center of the sun.
+    pub fn direction(&self) -> &Vector3 {
+        self.basis.w()
+    }
+
+    /// Returns the solid angle covered by the sun disk.
+    pub fn solid_angle(&self) -> f32 {
+        2. * PI * (1. - self.cos_theta_max)
+    }
+}
+
+impl Light for SunLight {
+    fn sample(&self, _point: &Point3) -> Option<LightRecord> {
+        // Sample the cone of directions covered by the disk uniformly.
+        let mut rng = rand::thread_rng();
+        let cos_theta = 1. + rng.gen::<f32>() * (self.cos_theta_max - 1.);
+        let sin_theta = f32::sqrt(f32::max(0., 1. - cos_theta * cos_theta));
+        let phi = 2. * PI * rng.gen::<f32>();
+        let direction = self.basis.local_with_floats(
+            sin_theta * f32::cos(phi),
+            sin_theta * f32::sin(phi),
+            cos_theta,
+        );
+
+        Some(LightRecord {
+            direction,
+            distance: f32::INFINITY,
+            radiance: self.radiance,
+            pdf: 1. / self.solid_angle(),
+        })
+    }
+
+    fn is_delta(&self) -> bool {
+        false
+    }
+
+    fn pdf_value(&self, _origin: &Point3, direction: &Vector3) -> f32 {
+        if direction.normolize().dot(self.direction()) >= self.cos_theta_max {
+            1. / self.solid_angle()
+        } else {
+            0.
+        }
+    }
+
+    fn emitted(&self, direction: &Vector3) -> Color3 {
+        if direction.normolize().dot(self.direction()) >= self.cos_theta_max {
+            self.radiance
+        } else {
+            Color3::zero()
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    core::{
+        Color3, DielectricMaterial, HitRecord, Point3, Ray, ScatterRecord, SolidColorTexture,
+        Vector2, Vector3,
+    },
+    traits::{Material, Texture},
+};
+use std::{ops::Neg, rc::Rc};
+
+/**
+Coated Material
+
+A Material that puts a smooth dielectric clearcoat over a base material, like car paint or varnished
+wood. Light is either reflected by the coat with the Fresnel reflectance, or transmitted through it
+to the base material and back out again, losing the Fresnel reflectance of the way out and being
+tinted by the coat on both passes.
+*/
+#[derive(Debug)]
+pub struct CoatedMaterial {
+    /// The material under the coat.
+    base: Rc<dyn Material>,
+
+    /// Index of refraction of the coat.
+    pub ior: f32,
+
+    /// Color transmitted by the coat for a single pass at normal incidence.
+    tint: Rc<dyn Texture>,
+}
+
+impl CoatedMaterial {
+    /// Create a new `CoatedMaterial` with a clear coat.
+    pub fn new(base: Rc<dyn Material>, ior: f32) -> Self {
+        Self {
+            base,
+            ior,
+            tint: Rc::new(SolidColorTexture::new_with_color(Color3::one())),
+        }
+    }
+
+    /// Create a new `CoatedMaterial` with a tinted coat.
+    pub fn new_with_tint(base: Rc<dyn Material>, ior: f32, tint: Rc<dyn Texture>) -> Self {
+        Self { base, ior, tint }
+    }
+
+    /// Returns the cosine between the direction and the normal facing the incoming ray.
+    fn cosine(direction: &Vector3, normal: &Vector3) -> f32 {
+        f32::clamp(direction.normolize().dot(normal).abs(), 0., 1.)
+    }
+
+    /// Returns the cosine of the direction refracted into the coat.
+    fn refracted_cosine(&self, cosine: f32) -> f32 {
+        let sin_theta_squared = (1. - cosine * cosine) / (self.ior * self.ior);
+        f32::sqrt(f32::max(0., 1. - sin_theta_squared))
+    }
+
+    /// Returns the fraction of light passing the coat from `direction_in` to the base and back out
+    /// along `direction_out`, excluding the Fresnel reflectance of the way in.
+    fn transmittance(
+        &self,
+        hit_record: &HitRecord,
+        normal: &Vector3,
+        direction_in: &Vector3,
+        direction_out: &Vector3,
+    ) -> Color3 {
+        let cos_in = Self::cosine(direction_in, normal);
+        let cos_out = Self::cosine(direction_out, normal);
+        let fresnel_out = DielectricMaterial::reflectance(cos_out, 1. / self.ior);
+
+        let tint = self.tint.value_at_hit(hit_record);
+        let path_length = 1. / self.refracted_cosine(cos_in) + 1. / self.refracted_cosine(cos_out);
+
+        &Color3::new(
+            tint.x.powf(path_length),
+            tint.y.powf(path_length),
+            tint.z.powf(path_length),
+        ) * (1. - fresnel_out)
+    }
+}
+
+impl Material for CoatedMaterial {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        let (Some(normal), Some(point)) = (hit_record.normal, hit_record.point) else {
+            return false;
+        };
+
+        let unit_direction = ray_in.direction.normolize();
+        let cos_in = Self::cosine(&unit_direction, &normal);
+        if rand::random::<f32>() < DielectricMaterial::reflectance(cos_in, 1. / self.ior) {
+            // The probability of a coat reflection cancels the Fresnel reflectance.
+            scatter_record.attenuation = Color3::one();
+            let mut ray_scattered =
+                Ray::new_with_time(point, unit_direction.reflect(&normal), ray_in.time);
+            ray_scattered.differential =
+                ray_in.scattered_differential(hit_record, &ray_scattered.direction, None);
+            scatter_record.ray_scattered = Some(ray_scattered);
+            scatter_record.pdf = None;
+            scatter_record.skip_pdf = true;
+            return true;
+        }
+
+        if !self.base.scatter(ray_in, hit_record, scatter_record) {
+            return false;
+        }
+
+        if scatter_record.skip_pdf {
+            if let Some(ray_scattered) = &scatter_record.ray_scattered {
+                let transmittance = self.transmittance(
+                    hit_record,
+                    &normal,
+                    &unit_direction.neg(),
+                    &ray_scattered.direction,
+                );
+                scatter_record.attenuation *= &transmittance;
+            }
+        }
+
+        true
+    }
+
+    fn emitted(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        uv: &Vector2,
+        point: &Point3,
+    ) -> Color3 {
+        self.base.emitted(ray_in, hit_record, uv, point)
+    }
+
+    fn diffuse_probability(&self, ray_in: &Ray, hit_record: &HitRecord) -> f32 {
+        let Some(normal) = hit_record.normal else {
+            return 0.;
+        };
+        let cos_in = Self::cosine(&ray_in.direction, &normal);
+        let fresnel = DielectricMaterial::reflectance(cos_in, 1. / self.ior);
+        (1. - fresnel) * self.base.diffuse_probability(ray_in, hit_record)
+    }
+
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, ray_scattered: &Ray) -> f32 {
+        self.base.scattering_pdf(ray_in, hit_record, ray_scattered)
+    }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        let Some(normal) = hit_record.normal else {
+            return Color3::zero();
+        };
+
+        // The chance of reaching the base cancels the Fresnel transmittance of the way in.
+        let transmittance = self.transmittance(
+            hit_record,
+            &normal,
+            &ray_in.direction.neg(),
+            &ray_scattered.direction,
+        );
+        &self
+            .base
+            .scattering_color(ray_in, hit_record, ray_scattered)
+            * &transmittance
+    }
+}
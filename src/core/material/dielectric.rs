@@ -14,7 +14,8 @@ impl DielectricMaterial {
         Self { ior }
     }
 
-    fn reflectance(cosine: f32, ref_idx: f32) -> f32 {
+    /// Returns the fraction of light reflected at the given incident cosine and refraction ratio.
+    pub fn reflectance(cosine: f32, ref_idx: f32) -> f32 {
         // Use Schlick's approximation for reflectance.
         let mut r0 = (1. - ref_idx) / (1. + ref_idx);
         r0 = r0 * r0;
@@ -50,21 +51,29 @@ impl Material for DielectricMaterial {
             let sin_theta = f32::sqrt(1. - cos_theta * cos_theta);
 
             let cannot_refract = refraction_ratio * sin_theta > 1.;
-            let direction = if cannot_refract
+            let (direction, eta) = if cannot_refract
                 || DielectricMaterial::reflectance(cos_theta, refraction_ratio)
                     > rand::random::<f32>()
             {
-                unit_direction.reflect(&normal)
+                (unit_direction.reflect(&normal), None)
             } else {
-                unit_direction.refract(&normal, refraction_ratio)
+                (
+                    unit_direction.refract(&normal, refraction_ratio),
+                    Some(refraction_ratio),
+                )
             };
 
-            scatter_record.ray_scattered =
-                Some(Ray::new_with_time(point.clone(), direction, ray_in.time));
+            let mut ray_scattered = Ray::new_with_time(point.clone(), direction, ray_in.time);
+            ray_scattered.differential = ray_in.scattered_differential(hit_record, &direction, eta);
+            scatter_record.ray_scattered = Some(ray_scattered);
 
             true
         } else {
             false
         }
     }
+
+    fn diffuse_probability(&self, _ray_in: &Ray, _hit_record: &HitRecord) -> f32 {
+        0.
+    }
 }
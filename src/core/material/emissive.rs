@@ -7,16 +7,30 @@ use std::rc::Rc;
 #[derive(Debug)]
 pub struct EmissiveMaterial {
     emit: Rc<dyn Texture>,
+    /// Whether the back face emits light as well, otherwise it is black.
+    pub two_sided: bool,
 }
 
 impl EmissiveMaterial {
     pub fn new(texture: Rc<dyn Texture>) -> Self {
-        Self { emit: texture }
+        Self {
+            emit: texture,
+            two_sided: false,
+        }
     }
 
     pub fn new_with_color(color: Color3) -> Self {
         Self {
             emit: Rc::new(SolidColorTexture::new_with_color(color)),
+            two_sided: false,
+        }
+    }
+
+    /// Create a new `EmissiveMaterial` that emits from both faces.
+    pub fn new_two_sided(texture: Rc<dyn Texture>) -> Self {
+        Self {
+            emit: texture,
+            two_sided: true,
         }
     }
 }
@@ -35,13 +49,17 @@ impl Material for EmissiveMaterial {
         &self,
         _ray_in: &Ray,
         hit_record: &HitRecord,
-        uv: &Vector2,
-        point: &Point3,
+        _uv: &Vector2,
+        _point: &Point3,
     ) -> Color3 {
-        if !hit_record.front_face {
+        if !hit_record.front_face && !self.two_sided {
             Color3::zero()
         } else {
-            self.emit.value(uv, point)
+            self.emit.value_at_hit(hit_record)
         }
     }
+
+    fn diffuse_probability(&self, _ray_in: &Ray, _hit_record: &HitRecord) -> f32 {
+        0.
+    }
 }
@@ -0,0 +1,493 @@
+use crate::{
+    core::{
+        deg_to_rad, Color3, DielectricMaterial, HitRecord, OrthonormalBasis, Ray, ScatterRecord,
+        Vector3,
+    },
+    traits::{Material, ProbabilityDensityFunction},
+};
+use std::{f32::consts::PI, ops::Neg, rc::Rc};
+
+/// Scattering orders modeled by their own lobes, the rest are folded into one lobe.
+const MAX_ORDER: usize = 3;
+
+/**
+Hair Material
+
+A Material for hair and fur fibers after Chiang et al. 2016, meant for `Curve` primitives. Light is
+reflected off the surface of the fiber (R), transmitted through it (TT) or reflected once inside of
+it (TRT), and higher orders are folded into one lobe. Each lobe spreads along the fiber with the
+longitudinal roughness `beta_m` and around it with the azimuthal roughness `beta_n`, and the
+cuticle scales tilt the lobes by `alpha` degrees. The color comes from the absorption inside of the
+fiber, given directly or by melanin concentrations.
+
+The offset of the ray across the fiber is read from the v coordinate, and the fiber direction from
+the u tangent of the hit record.
+
+# Examples
+
+```
+use raytracing::core::{Color3, HairMaterial};
+
+// Brown hair, and blond hair with the same roughness.
+let brown = HairMaterial::new_with_melanin(1.3, 0.);
+let blond = HairMaterial::new_with_color(Color3::new(0.85, 0.7, 0.45), brown.beta_n);
+# assert!(brown.sigma_a.x > blond.sigma_a.x);
+```
+*/
+#[derive(Debug)]
+pub struct HairMaterial {
+    /// Absorption coefficient inside of the fiber, relative to its diameter.
+    pub sigma_a: Color3,
+
+    /// Index of refraction of the fiber.
+    pub eta: f32,
+
+    /// Longitudinal roughness, in `[0, 1]`.
+    pub beta_m: f32,
+
+    /// Azimuthal roughness, in `[0, 1]`.
+    pub beta_n: f32,
+
+    /// Tilt of the cuticle scales in degrees.
+    pub alpha: f32,
+}
+
+impl HairMaterial {
+    /// Create a new `HairMaterial` from the absorption coefficient inside of the fiber.
+    pub fn new(sigma_a: Color3) -> Self {
+        Self {
+            sigma_a,
+            eta: 1.55,
+            beta_m: 0.3,
+            beta_n: 0.3,
+            alpha: 2.,
+        }
+    }
+
+    /// Create a new `HairMaterial` from the concentrations of eumelanin, which makes hair black or
+    /// brown, and pheomelanin, which makes it red. Around 8 is black and 0.3 blond.
+    pub fn new_with_melanin(eumelanin: f32, pheomelanin: f32) -> Self {
+        let eumelanin_sigma_a = Color3::new(0.419, 0.697, 1.37);
+        let pheomelanin_sigma_a = Color3::new(0.187, 0.4, 1.05);
+        Self::new(&(&eumelanin_sigma_a * eumelanin) + &(&pheomelanin_sigma_a * pheomelanin))
+    }
+
+    /// Create a new `HairMaterial` whose multiple scattering has about the given color, for the
+    /// given azimuthal roughness.
+    pub fn new_with_color(color: Color3, beta_n: f32) -> Self {
+        let denominator = 5.969 - 0.215 * beta_n + 2.532 * beta_n.powi(2) - 10.73 * beta_n.powi(3)
+            + 5.574 * beta_n.powi(4)
+            + 0.245 * beta_n.powi(5);
+        let sigma_a = |channel: f32| (f32::max(channel, 1e-4).ln() / denominator).powi(2);
+        Self {
+            beta_n,
+            ..Self::new(Color3::new(
+                sigma_a(color.x),
+                sigma_a(color.y),
+                sigma_a(color.z),
+            ))
+        }
+    }
+
+    /// Internal method, returns the scattering function at a hit for light leaving against the
+    /// incoming ray.
+    fn bsdf(&self, ray_in: &Ray, hit_record: &HitRecord) -> Option<HairBSDF> {
+        let (Some(uv), Some(dpdu), Some(dpdv)) = (hit_record.uv, hit_record.dpdu, hit_record.dpdv)
+        else {
+            return None;
+        };
+
+        // The x axis runs along the fiber and y across it, the offset h is measured along y.
+        let x = dpdu.normolize();
+        let y = (&dpdv - &(&x * x.dot(&dpdv))).normolize();
+        let frame = OrthonormalBasis::new(x, y, x.cross(&y));
+        let h = f32::clamp(2. * uv.v() - 1., -1., 1.);
+        Some(HairBSDF::new(
+            self,
+            frame,
+            ray_in.direction.normolize().neg(),
+            h,
+        ))
+    }
+}
+
+impl Material for HairMaterial {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        let Some(bsdf) = self.bsdf(ray_in, hit_record) else {
+            return false;
+        };
+
+        // The color depends on the scattered direction, see `scattering_color`.
+        scatter_record.attenuation = Color3::one();
+        scatter_record.pdf = Some(Rc::new(bsdf));
+        scatter_record.skip_pdf = false;
+        true
+    }
+
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, ray_scattered: &Ray) -> f32 {
+        self.bsdf(ray_in, hit_record)
+            .map_or(0., |bsdf| bsdf.value(&ray_scattered.direction))
+    }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        self.bsdf(ray_in, hit_record)
+            .map_or(Color3::zero(), |bsdf| bsdf.color(&ray_scattered.direction))
+    }
+}
+
+/// The hair scattering function at a hit, in a frame whose x axis runs along the fiber.
+struct HairBSDF {
+    /// Frame of the fiber.
+    frame: OrthonormalBasis,
+
+    /// Direction of the light leaving the fiber, in the frame of the fiber.
+    wo: Vector3,
+
+    /// Offset of the ray across the fiber, in `[-1, 1]`.
+    h: f32,
+
+    /// Angle between the ray and the normal around the fiber, `asin(h)`.
+    gamma_o: f32,
+
+    /// Index of refraction of the fiber.
+    eta: f32,
+
+    /// Absorption coefficient inside of the fiber.
+    sigma_a: Color3,
+
+    /// Longitudinal variance of each lobe.
+    variances: [f32; MAX_ORDER + 1],
+
+    /// Azimuthal logistic scale.
+    scale: f32,
+
+    /// Sines and cosines of the scale tilt, twice and four times of it.
+    sin_2k_alpha: [f32; 3],
+    cos_2k_alpha: [f32; 3],
+}
+
+impl HairBSDF {
+    fn new(material: &HairMaterial, frame: OrthonormalBasis, wo: Vector3, h: f32) -> Self {
+        let variance = (0.726 * material.beta_m
+            + 0.812 * material.beta_m.powi(2)
+            + 3.7 * material.beta_m.powi(20))
+        .powi(2);
+        let scale = f32::sqrt(PI / 8.)
+            * (0.265 * material.beta_n
+                + 1.194 * material.beta_n.powi(2)
+                + 5.372 * material.beta_n.powi(22));
+
+        let mut sin_2k_alpha = [f32::sin(deg_to_rad(material.alpha)); 3];
+        let mut cos_2k_alpha = [safe_sqrt(1. - sin_2k_alpha[0] * sin_2k_alpha[0]); 3];
+        for i in 1..3 {
+            sin_2k_alpha[i] = 2. * cos_2k_alpha[i - 1] * sin_2k_alpha[i - 1];
+            cos_2k_alpha[i] = cos_2k_alpha[i - 1].powi(2) - sin_2k_alpha[i - 1].powi(2);
+        }
+
+        Self {
+            wo: frame.project(&wo),
+            frame,
+            h,
+            gamma_o: h.asin(),
+            eta: material.eta,
+            sigma_a: material.sigma_a,
+            variances: [variance, 0.25 * variance, 4. * variance, 4. * variance],
+            scale,
+            sin_2k_alpha,
+            cos_2k_alpha,
+        }
+    }
+
+    /// Returns the angle of the ray refracted into the fiber around it, and the fraction of light
+    /// passing through the fiber once.
+    fn refraction(&self, sin_theta_o: f32, cos_theta_o: f32) -> (f32, Color3) {
+        let sin_theta_t = sin_theta_o / self.eta;
+        let cos_theta_t = safe_sqrt(1. - sin_theta_t * sin_theta_t);
+        let eta_p = safe_sqrt(self.eta * self.eta - sin_theta_o * sin_theta_o) / cos_theta_o;
+        let sin_gamma_t = f32::clamp(self.h / eta_p, -1., 1.);
+        let cos_gamma_t = safe_sqrt(1. - sin_gamma_t * sin_gamma_t);
+        let length = 2. * cos_gamma_t / cos_theta_t;
+        let transmittance = Color3::new(
+            f32::exp(-self.sigma_a.x * length),
+            f32::exp(-self.sigma_a.y * length),
+            f32::exp(-self.sigma_a.z * length),
+        );
+        (sin_gamma_t.asin(), transmittance)
+    }
+
+    /// Returns the fraction of light in each lobe.
+    fn attenuations(&self, cos_theta_o: f32, transmittance: &Color3) -> [Color3; MAX_ORDER + 1] {
+        let cos_gamma_o = safe_sqrt(1. - self.h * self.h);
+        let fresnel = DielectricMaterial::reflectance(cos_theta_o * cos_gamma_o, self.eta);
+
+        let mut attenuations = [Color3::zero(); MAX_ORDER + 1];
+        attenuations[0] = Color3::new(fresnel, fresnel, fresnel);
+        attenuations[1] = transmittance * (1. - fresnel).powi(2);
+        for p in 2..MAX_ORDER {
+            attenuations[p] = &(&attenuations[p - 1] * transmittance) * fresnel;
+        }
+        // The geometric series of all higher orders. It diverges only where nothing enters the
+        // fiber, at grazing edges without absorption, where the higher orders are zero.
+        let last = attenuations[MAX_ORDER - 1];
+        let remainder = |a: f32, t: f32| {
+            let denominator = 1. - t * fresnel;
+            if denominator <= 0. {
+                0.
+            } else {
+                a * fresnel * t / denominator
+            }
+        };
+        attenuations[MAX_ORDER] = Color3::new(
+            remainder(last.x, transmittance.x),
+            remainder(last.y, transmittance.y),
+            remainder(last.z, transmittance.z),
+        );
+        attenuations
+    }
+
+    /// Returns the probability of sampling each lobe.
+    fn lobe_probabilities(&self, sin_theta_o: f32, cos_theta_o: f32) -> [f32; MAX_ORDER + 1] {
+        let (_, transmittance) = self.refraction(sin_theta_o, cos_theta_o);
+        let attenuations = self.attenuations(cos_theta_o, &transmittance);
+        let total: f32 = attenuations.iter().map(|a| a.luminance()).sum();
+        attenuations.map(|a| {
+            if total > 0. {
+                a.luminance() / total
+            } else {
+                0.
+            }
+        })
+    }
+
+    /// Returns the sine and cosine of the outgoing angle along the fiber, tilted by the scales for
+    /// a lobe.
+    fn tilted(&self, p: usize, sin_theta_o: f32, cos_theta_o: f32) -> (f32, f32) {
+        let (sin_theta, cos_theta) = match p {
+            0 => (
+                sin_theta_o * self.cos_2k_alpha[1] - cos_theta_o * self.sin_2k_alpha[1],
+                cos_theta_o * self.cos_2k_alpha[1] + sin_theta_o * self.sin_2k_alpha[1],
+            ),
+            1 => (
+                sin_theta_o * self.cos_2k_alpha[0] + cos_theta_o * self.sin_2k_alpha[0],
+                cos_theta_o * self.cos_2k_alpha[0] - sin_theta_o * self.sin_2k_alpha[0],
+            ),
+            2 => (
+                sin_theta_o * self.cos_2k_alpha[2] + cos_theta_o * self.sin_2k_alpha[2],
+                cos_theta_o * self.cos_2k_alpha[2] - sin_theta_o * self.sin_2k_alpha[2],
+            ),
+            _ => (sin_theta_o, cos_theta_o),
+        };
+        (sin_theta, cos_theta.abs())
+    }
+
+    /// Returns the sine and cosine of the angles along the fiber, and the azimuth around it, of a
+    /// direction in the frame of the fiber.
+    fn angles(direction: &Vector3) -> (f32, f32, f32) {
+        let sin_theta = f32::clamp(direction.x, -1., 1.);
+        (
+            sin_theta,
+            safe_sqrt(1. - sin_theta * sin_theta),
+            f32::atan2(direction.z, direction.y),
+        )
+    }
+
+    /// Returns the scattered light, the scattering function times the cosine to the normal, for
+    /// light arriving from the world space direction `wi`.
+    fn color(&self, wi: &Vector3) -> Color3 {
+        let wi = self.frame.project(&wi.normolize());
+        let (sin_theta_o, cos_theta_o, phi_o) = Self::angles(&self.wo);
+        let (sin_theta_i, cos_theta_i, phi_i) = Self::angles(&wi);
+        let (gamma_t, transmittance) = self.refraction(sin_theta_o, cos_theta_o);
+        let attenuations = self.attenuations(cos_theta_o, &transmittance);
+
+        let phi = phi_i - phi_o;
+        let mut color = Color3::zero();
+        for (p, attenuation) in attenuations.iter().enumerate().take(MAX_ORDER) {
+            let (sin_theta_op, cos_theta_op) = self.tilted(p, sin_theta_o, cos_theta_o);
+            let lobe = longitudinal(
+                cos_theta_i,
+                cos_theta_op,
+                sin_theta_i,
+                sin_theta_op,
+                self.variances[p],
+            ) * azimuthal(phi, p, self.scale, self.gamma_o, gamma_t);
+            color += &(attenuation * lobe);
+        }
+        let remainder = longitudinal(
+            cos_theta_i,
+            cos_theta_o,
+            sin_theta_i,
+            sin_theta_o,
+            self.variances[MAX_ORDER],
+        ) / (2. * PI);
+        color += &(&attenuations[MAX_ORDER] * remainder);
+        color
+    }
+}
+
+impl ProbabilityDensityFunction for HairBSDF {
+    fn value(&self, direction: &Vector3) -> f32 {
+        let wi = self.frame.project(&direction.normolize());
+        let (sin_theta_o, cos_theta_o, phi_o) = Self::angles(&self.wo);
+        let (sin_theta_i, cos_theta_i, phi_i) = Self::angles(&wi);
+        let (gamma_t, _) = self.refraction(sin_theta_o, cos_theta_o);
+        let probabilities = self.lobe_probabilities(sin_theta_o, cos_theta_o);
+
+        let phi = phi_i - phi_o;
+        let mut pdf = 0.;
+        for (p, probability) in probabilities.iter().enumerate().take(MAX_ORDER) {
+            let (sin_theta_op, cos_theta_op) = self.tilted(p, sin_theta_o, cos_theta_o);
+            pdf += longitudinal(
+                cos_theta_i,
+                cos_theta_op,
+                sin_theta_i,
+                sin_theta_op,
+                self.variances[p],
+            ) * probability
+                * azimuthal(phi, p, self.scale, self.gamma_o, gamma_t);
+        }
+        pdf += longitudinal(
+            cos_theta_i,
+            cos_theta_o,
+            sin_theta_i,
+            sin_theta_o,
+            self.variances[MAX_ORDER],
+        ) * probabilities[MAX_ORDER]
+            / (2. * PI);
+        pdf
+    }
+
+    fn generate(&self) -> Vector3 {
+        let (sin_theta_o, cos_theta_o, phi_o) = Self::angles(&self.wo);
+
+        // Pick a lobe by its share of the scattered light.
+        let probabilities = self.lobe_probabilities(sin_theta_o, cos_theta_o);
+        let mut u = rand::random::<f32>();
+        let mut p = 0;
+        while p < MAX_ORDER && u >= probabilities[p] {
+            u -= probabilities[p];
+            p += 1;
+        }
+
+        // Sample the angle along the fiber around the tilted outgoing direction.
+        let (sin_theta_op, cos_theta_op) = self.tilted(p, sin_theta_o, cos_theta_o);
+        let variance = self.variances[p];
+        let u = f32::max(rand::random::<f32>(), 1e-5);
+        let cos_theta = 1. + variance * f32::ln(u + (1. - u) * f32::exp(-2. / variance));
+        let sin_theta = safe_sqrt(1. - cos_theta * cos_theta);
+        let cos_phi = f32::cos(2. * PI * rand::random::<f32>());
+        let sin_theta_i = f32::clamp(
+            -cos_theta * sin_theta_op + sin_theta * cos_phi * cos_theta_op,
+            -1.,
+            1.,
+        );
+        let cos_theta_i = safe_sqrt(1. - sin_theta_i * sin_theta_i);
+
+        // Sample the azimuth around the fiber.
+        let (gamma_t, _) = self.refraction(sin_theta_o, cos_theta_o);
+        let u = rand::random::<f32>();
+        let delta_phi = if p < MAX_ORDER {
+            deflection(p, self.gamma_o, gamma_t) + sample_trimmed_logistic(u, self.scale)
+        } else {
+            2. * PI * u
+        };
+        let phi_i = phi_o + delta_phi;
+
+        self.frame.local_with_floats(
+            sin_theta_i,
+            cos_theta_i * phi_i.cos(),
+            cos_theta_i * phi_i.sin(),
+        )
+    }
+}
+
+/// Returns the square root, zero for slightly negative values from rounding.
+fn safe_sqrt(x: f32) -> f32 {
+    f32::sqrt(f32::max(0., x))
+}
+
+/// Returns the modified Bessel function of the first kind and order zero.
+fn bessel_i0(x: f32) -> f32 {
+    let mut value = 0.;
+    let mut x_2i = 1.;
+    let mut factorial = 1.;
+    let mut four_i = 1.;
+    for i in 0..10 {
+        if i > 1 {
+            factorial *= i as f32;
+        }
+        value += x_2i / (four_i * factorial * factorial);
+        x_2i *= x * x;
+        four_i *= 4.;
+    }
+    value
+}
+
+/// Returns the logarithm of `bessel_i0`, without overflowing for large values.
+fn log_bessel_i0(x: f32) -> f32 {
+    if x > 12. {
+        x + 0.5 * (-f32::ln(2. * PI) + f32::ln(1. / x) + 1. / (8. * x))
+    } else {
+        bessel_i0(x).ln()
+    }
+}
+
+/// Returns the longitudinal scattering function of a lobe with the given variance.
+fn longitudinal(
+    cos_theta_i: f32,
+    cos_theta_o: f32,
+    sin_theta_i: f32,
+    sin_theta_o: f32,
+    variance: f32,
+) -> f32 {
+    let a = cos_theta_i * cos_theta_o / variance;
+    let b = sin_theta_i * sin_theta_o / variance;
+    if variance <= 0.1 {
+        f32::exp(
+            log_bessel_i0(a) - b - 1. / variance
+                + std::f32::consts::LN_2
+                + f32::ln(1. / (2. * variance)),
+        )
+    } else {
+        f32::exp(-b) * bessel_i0(a) / (f32::sinh(1. / variance) * 2. * variance)
+    }
+}
+
+/// Returns the azimuthal deflection of the light leaving the fiber after `p` internal paths.
+fn deflection(p: usize, gamma_o: f32, gamma_t: f32) -> f32 {
+    2. * p as f32 * gamma_t - 2. * gamma_o + p as f32 * PI
+}
+
+/// Returns the logistic distribution with scale `s`.
+fn logistic(x: f32, s: f32) -> f32 {
+    let e = f32::exp(-x.abs() / s);
+    e / (s * (1. + e) * (1. + e))
+}
+
+/// Returns the cumulative logistic distribution with scale `s`.
+fn logistic_cdf(x: f32, s: f32) -> f32 {
+    1. / (1. + f32::exp(-x / s))
+}
+
+/// Returns the azimuthal scattering function of the lobe after `p` internal paths.
+fn azimuthal(phi: f32, p: usize, s: f32, gamma_o: f32, gamma_t: f32) -> f32 {
+    let delta_phi = (phi - deflection(p, gamma_o, gamma_t) + PI).rem_euclid(2. * PI) - PI;
+    logistic(delta_phi, s) / (logistic_cdf(PI, s) - logistic_cdf(-PI, s))
+}
+
+/// Returns a sample of the logistic distribution with scale `s` trimmed to `[-π, π]`.
+fn sample_trimmed_logistic(u: f32, s: f32) -> f32 {
+    let k = logistic_cdf(PI, s) - logistic_cdf(-PI, s);
+    let x = -s * f32::ln(1. / (u * k + logistic_cdf(-PI, s)) - 1.);
+    f32::clamp(x, -PI, PI)
+}
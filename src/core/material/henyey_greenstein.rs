@@ -0,0 +1,100 @@
+use crate::{
+    core::{
+        Color3, HenyeyGreensteinPDF, HitRecord, Point3, Ray, ScatterRecord, SolidColorTexture,
+        Vector2,
+    },
+    traits::{Material, Texture},
+};
+use std::rc::Rc;
+
+/**
+Henyey-Greenstein Material
+
+The phase function of a participating medium with anisotropy `g` in `(-1, 1)`: positive values
+scatter light forward like clouds and fog, negative values scatter it back, and zero is isotropic.
+Emissive media such as fire also emit light at every scattering point.
+*/
+#[derive(Debug)]
+pub struct HenyeyGreensteinMaterial {
+    /// Fraction of light scattered at each collision.
+    pub albedo: Rc<dyn Texture>,
+
+    /// Anisotropy of the scattering.
+    pub g: f32,
+
+    /// Light emitted at each collision.
+    pub emission: Option<Rc<dyn Texture>>,
+}
+
+impl HenyeyGreensteinMaterial {
+    pub fn new(albedo: Rc<dyn Texture>, g: f32) -> Self {
+        Self {
+            albedo,
+            g: f32::clamp(g, -0.999, 0.999),
+            emission: None,
+        }
+    }
+
+    pub fn new_with_color(color: Color3, g: f32) -> Self {
+        Self::new(Rc::new(SolidColorTexture::new_with_color(color)), g)
+    }
+
+    /// Create a new `HenyeyGreensteinMaterial` of a medium that emits light.
+    pub fn new_with_emission(albedo: Rc<dyn Texture>, g: f32, emission: Rc<dyn Texture>) -> Self {
+        Self {
+            emission: Some(emission),
+            ..Self::new(albedo, g)
+        }
+    }
+}
+
+impl Material for HenyeyGreensteinMaterial {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return false;
+        };
+
+        scatter_record.attenuation = self.albedo.value(&uv, &point);
+        scatter_record.pdf = Some(Rc::new(HenyeyGreensteinPDF::new(ray_in.direction, self.g)));
+        scatter_record.skip_pdf = false;
+        true
+    }
+
+    fn emitted(
+        &self,
+        _ray_in: &Ray,
+        _hit_record: &HitRecord,
+        uv: &Vector2,
+        point: &Point3,
+    ) -> Color3 {
+        match &self.emission {
+            Some(emission) => emission.value(uv, point),
+            None => Color3::zero(),
+        }
+    }
+
+    fn scattering_pdf(&self, ray_in: &Ray, _hit_record: &HitRecord, ray_scattered: &Ray) -> f32 {
+        let cos_theta = ray_in
+            .direction
+            .normolize()
+            .dot(&ray_scattered.direction.normolize());
+        HenyeyGreensteinPDF::phase(cos_theta, self.g)
+    }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return Color3::zero();
+        };
+        &self.albedo.value(&uv, &point) * self.scattering_pdf(ray_in, hit_record, ray_scattered)
+    }
+}
@@ -47,4 +47,16 @@ impl Material for IsotropicMaterial {
     fn scattering_pdf(&self, _ray_in: &Ray, _hit_record: &HitRecord, _ray_scattered: &Ray) -> f32 {
         1. / (4. * std::f32::consts::PI)
     }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return Color3::zero();
+        };
+        &self.albedo.value(&uv, &point) * self.scattering_pdf(ray_in, hit_record, ray_scattered)
+    }
 }
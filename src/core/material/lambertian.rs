@@ -30,12 +30,12 @@ impl Material for LambertianMaterial {
     ) -> bool {
         if let HitRecord {
             normal: Some(normal),
-            point: Some(point),
-            uv: Some(uv),
+            point: Some(_),
+            uv: Some(_),
             ..
         } = hit_record
         {
-            scatter_record.attenuation = self.albedo.value(uv, point);
+            scatter_record.attenuation = self.albedo.value_at_hit(hit_record);
             scatter_record.pdf = Some(Rc::new(CosinePDF::new(*normal)));
             scatter_record.skip_pdf = false;
 
@@ -45,7 +45,21 @@ impl Material for LambertianMaterial {
         }
     }
 
-    fn scattering_pdf(&self, _ray_in: &Ray, _hit_record: &HitRecord, _ray_scattered: &Ray) -> f32 {
-        1. / (2. * std::f32::consts::PI)
+    fn scattering_pdf(&self, _ray_in: &Ray, hit_record: &HitRecord, ray_scattered: &Ray) -> f32 {
+        let Some(normal) = hit_record.normal else {
+            return 0.;
+        };
+        let cosine_theta = normal.dot(&ray_scattered.direction.normolize());
+        f32::max(0., cosine_theta / std::f32::consts::PI)
+    }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        &self.albedo.value_at_hit(hit_record)
+            * self.scattering_pdf(ray_in, hit_record, ray_scattered)
     }
 }
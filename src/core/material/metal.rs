@@ -11,6 +11,9 @@ use std::{
 pub struct MetalMaterial {
     albedo: Rc<dyn Texture>,
     fuzz: f32,
+
+    /// Scalar texture of the fuzz, replacing the constant fuzz when set.
+    roughness: Option<Rc<dyn Texture>>,
 }
 
 impl MetalMaterial {
@@ -18,6 +21,24 @@ impl MetalMaterial {
         Self {
             albedo,
             fuzz: f32::max(0., f32::min(fuzz, 1.)),
+            roughness: None,
+        }
+    }
+
+    /// Create a new `MetalMaterial` fuzzed by the average of the rgb channels of a roughness
+    /// texture, clamped to `[0, 1]`.
+    pub fn new_with_roughness_texture(albedo: Rc<dyn Texture>, roughness: Rc<dyn Texture>) -> Self {
+        Self {
+            roughness: Some(roughness),
+            ..Self::new(albedo, 0.)
+        }
+    }
+
+    /// Returns the fuzz at a hit.
+    fn fuzz(&self, hit_record: &HitRecord) -> f32 {
+        match &self.roughness {
+            Some(roughness) => f32::clamp(roughness.value_at_hit(hit_record).average(), 0., 1.),
+            None => self.fuzz,
         }
     }
 }
@@ -36,11 +57,13 @@ impl Material for MetalMaterial {
         } = hit_record
         {
             let reflected = ray_in.direction.normolize().reflect(&normal);
-            let ray_scattered = Ray::new_with_time(
+            let mut ray_scattered = Ray::new_with_time(
                 point.clone(),
-                reflected.add(&(Vector3::random_unit_vector().mul(self.fuzz))),
+                reflected.add(&(Vector3::random_unit_vector().mul(self.fuzz(hit_record)))),
                 ray_in.time,
             );
+            ray_scattered.differential =
+                ray_in.scattered_differential(hit_record, &ray_scattered.direction, None);
 
             let is_hitted = ray_scattered.direction.dot(&normal) > 0.;
 
@@ -54,4 +77,8 @@ impl Material for MetalMaterial {
             false
         }
     }
+
+    fn diffuse_probability(&self, _ray_in: &Ray, _hit_record: &HitRecord) -> f32 {
+        0.
+    }
 }
@@ -0,0 +1,189 @@
+use crate::{
+    core::{Color3, HitRecord, MixturePDF, Point3, Ray, ScatterRecord, SolidColorTexture, Vector2},
+    traits::{Material, Texture},
+};
+use std::{
+    ops::{Add, Mul},
+    rc::Rc,
+};
+
+/**
+Mix Material
+
+A Material that blends two materials by a weight texture, `0` gives the first material and `1`
+gives the second one. Specular lobes are picked stochastically, while the pdfs of the other lobes
+are combined into a single mixture pdf, so light sampling stays unbiased.
+*/
+#[derive(Debug)]
+pub struct MixMaterial {
+    /// The materials to blend.
+    materials: [Rc<dyn Material>; 2],
+
+    /// The blend weight, the average of the rgb channels is the weight of the second material.
+    weight: Rc<dyn Texture>,
+}
+
+impl MixMaterial {
+    /// Create a new `MixMaterial` blending two materials by a weight texture.
+    pub fn new(
+        material1: Rc<dyn Material>,
+        material2: Rc<dyn Material>,
+        weight: Rc<dyn Texture>,
+    ) -> Self {
+        Self {
+            materials: [material1, material2],
+            weight,
+        }
+    }
+
+    /// Create a new `MixMaterial` blending two materials by a constant weight.
+    pub fn new_with_weight(
+        material1: Rc<dyn Material>,
+        material2: Rc<dyn Material>,
+        weight: f32,
+    ) -> Self {
+        Self {
+            materials: [material1, material2],
+            weight: Rc::new(SolidColorTexture::new(weight, weight, weight)),
+        }
+    }
+
+    /// Returns the weight of the second material at the hit point.
+    fn weight(&self, hit_record: &HitRecord) -> f32 {
+        if hit_record.uv.is_none() || hit_record.point.is_none() {
+            return 0.5;
+        }
+        let color = self.weight.value_at_hit(hit_record);
+        f32::clamp((color.x + color.y + color.z) / 3., 0., 1.)
+    }
+
+    /// Returns the probability of picking each material and sampling a lobe of it that is not
+    /// specular. These weigh the pdfs and colors of the materials, since only those lobes reach
+    /// `scattering_pdf` and `scattering_color`.
+    fn diffuse_weights(&self, ray_in: &Ray, hit_record: &HitRecord) -> [f32; 2] {
+        let weight = self.weight(hit_record);
+        [
+            (1. - weight) * self.materials[0].diffuse_probability(ray_in, hit_record),
+            weight * self.materials[1].diffuse_probability(ray_in, hit_record),
+        ]
+    }
+}
+
+impl Material for MixMaterial {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        let chosen = if rand::random::<f32>() < self.weight(hit_record) {
+            1
+        } else {
+            0
+        };
+        if !self.materials[chosen].scatter(ray_in, hit_record, scatter_record) {
+            return false;
+        }
+        if scatter_record.skip_pdf {
+            // The probability of picking a specular lobe cancels its weight.
+            return true;
+        }
+
+        // Sample the mixture of the pdfs of both materials when the other one has a pdf too.
+        let diffuse_weights = self.diffuse_weights(ray_in, hit_record);
+        let diffuse_weight = diffuse_weights[0] + diffuse_weights[1];
+        let other = 1 - chosen;
+        if diffuse_weight <= 0. || diffuse_weights[other] <= 0. {
+            return scatter_record.pdf.is_some();
+        }
+        let mut other_record = ScatterRecord::new();
+        let other_pdf = if self.materials[other].scatter(ray_in, hit_record, &mut other_record)
+            && !other_record.skip_pdf
+        {
+            other_record.pdf
+        } else {
+            None
+        };
+        scatter_record.pdf = match (scatter_record.pdf.take(), other_pdf) {
+            (Some(chosen_pdf), Some(other_pdf)) => {
+                let (pdf1, pdf2) = if chosen == 1 {
+                    (other_pdf, chosen_pdf)
+                } else {
+                    (chosen_pdf, other_pdf)
+                };
+                let weight = diffuse_weights[1] / diffuse_weight;
+                Some(Rc::new(MixturePDF::new_with_weight(pdf1, pdf2, weight)))
+            }
+            (chosen_pdf, _) => chosen_pdf,
+        };
+
+        scatter_record.pdf.is_some()
+    }
+
+    fn diffuse_probability(&self, ray_in: &Ray, hit_record: &HitRecord) -> f32 {
+        let diffuse_weights = self.diffuse_weights(ray_in, hit_record);
+        diffuse_weights[0] + diffuse_weights[1]
+    }
+
+    fn emitted(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        uv: &Vector2,
+        point: &Point3,
+    ) -> Color3 {
+        let weight = self.weight(hit_record);
+        self.materials[0]
+            .emitted(ray_in, hit_record, uv, point)
+            .mul(1. - weight)
+            .add(
+                &self.materials[1]
+                    .emitted(ray_in, hit_record, uv, point)
+                    .mul(weight),
+            )
+    }
+
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, ray_scattered: &Ray) -> f32 {
+        let diffuse_weights = self.diffuse_weights(ray_in, hit_record);
+        let diffuse_weight = diffuse_weights[0] + diffuse_weights[1];
+        if diffuse_weight <= 0. {
+            return 0.;
+        }
+
+        let pdf: f32 = self
+            .materials
+            .iter()
+            .zip(diffuse_weights)
+            .filter(|(_, weight)| *weight > 0.)
+            .map(|(material, weight)| {
+                weight * material.scattering_pdf(ray_in, hit_record, ray_scattered)
+            })
+            .sum();
+        pdf / diffuse_weight
+    }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        let diffuse_weights = self.diffuse_weights(ray_in, hit_record);
+        let diffuse_weight = diffuse_weights[0] + diffuse_weights[1];
+        if diffuse_weight <= 0. {
+            return Color3::zero();
+        }
+
+        // Only the lobes that are not specular reach here, renormalize by the chance of picking
+        // them.
+        let mut color = Color3::zero();
+        for (material, weight) in self.materials.iter().zip(diffuse_weights) {
+            if weight > 0. {
+                color += &material
+                    .scattering_color(ray_in, hit_record, ray_scattered)
+                    .mul(weight);
+            }
+        }
+        &color / diffuse_weight
+    }
+}
@@ -1,11 +1,23 @@
+mod coated;
 mod dielectric;
 mod emissive;
+mod hair;
+mod henyey_greenstein;
 mod isotropic;
 mod lambertian;
 mod metal;
+mod mix;
+mod normal_mapped;
+mod subsurface;
 
+pub use coated::*;
 pub use dielectric::*;
 pub use emissive::*;
+pub use hair::*;
+pub use henyey_greenstein::*;
 pub use isotropic::*;
 pub use lambertian::*;
 pub use metal::*;
+pub use mix::*;
+pub use normal_mapped::*;
+pub use subsurface::*;
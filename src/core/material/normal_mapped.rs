@@ -0,0 +1,206 @@
+use crate::{
+    core::{Color3, HitRecord, Point3, Ray, ScatterRecord, Vector2, Vector3},
+    traits::{Material, Medium, Texture},
+};
+use std::{
+    ops::{Add, Mul, Neg},
+    rc::Rc,
+};
+
+/// The way a `NormalMappedMaterial` perturbs the shading normal.
+#[derive(Debug)]
+pub enum NormalPerturbation {
+    /// Tangent-space normal map, the rgb channels encode the normal in `[0, 1]`.
+    NormalMap(Rc<dyn Texture>),
+
+    /// Scalar bump map, the average of the rgb channels is used as the displacement height.
+    BumpMap {
+        texture: Rc<dyn Texture>,
+        scale: f32,
+    },
+}
+
+/**
+Normal Mapped Material
+
+A Material that wraps another material and perturbs the shading normal by a normal map or a bump
+map before scattering. It relies on the `dpdu` and `dpdv` tangents of the hit record, hits without
+tangents are passed to the wrapped material untouched.
+*/
+#[derive(Debug)]
+pub struct NormalMappedMaterial {
+    /// The material to scatter with the perturbed normal.
+    pub material: Rc<dyn Material>,
+
+    /// How the shading normal is perturbed.
+    pub perturbation: NormalPerturbation,
+}
+
+impl NormalMappedMaterial {
+    /// Create a new `NormalMappedMaterial` with a tangent-space normal map.
+    pub fn new_with_normal_map(material: Rc<dyn Material>, normal_map: Rc<dyn Texture>) -> Self {
+        Self {
+            material,
+            perturbation: NormalPerturbation::NormalMap(normal_map),
+        }
+    }
+
+    /// Create a new `NormalMappedMaterial` with a scalar bump map, `scale` is the displacement in
+    /// world units when the texture value is 1.
+    pub fn new_with_bump_map(
+        material: Rc<dyn Material>,
+        bump_map: Rc<dyn Texture>,
+        scale: f32,
+    ) -> Self {
+        Self {
+            material,
+            perturbation: NormalPerturbation::BumpMap {
+                texture: bump_map,
+                scale,
+            },
+        }
+    }
+
+    /// Returns a copy of the hit record whose normal is replaced by the perturbed shading normal.
+    fn shading_record(&self, hit_record: &HitRecord) -> HitRecord {
+        let mut shading_record = hit_record.clone();
+        if let HitRecord {
+            normal: Some(normal),
+            point: Some(point),
+            uv: Some(uv),
+            dpdu: Some(dpdu),
+            dpdv: Some(dpdv),
+            ..
+        } = hit_record
+        {
+            let shading_normal = match &self.perturbation {
+                NormalPerturbation::NormalMap(texture) => {
+                    Self::normal_map(texture.as_ref(), hit_record, normal, uv, point, dpdu)
+                }
+                NormalPerturbation::BumpMap { texture, scale } => {
+                    Self::bump_map(texture.as_ref(), *scale, normal, uv, point, dpdu, dpdv)
+                }
+            };
+            if !shading_normal.equals_zero() && shading_normal.x.is_finite() {
+                shading_record.normal = Some(shading_normal.normolize());
+            }
+        }
+        shading_record
+    }
+
+    fn normal_map(
+        texture: &dyn Texture,
+        hit_record: &HitRecord,
+        normal: &Vector3,
+        uv: &Vector2,
+        point: &Point3,
+        dpdu: &Vector3,
+    ) -> Vector3 {
+        // Tangent space is built around the outward normal, not the one facing the ray.
+        let outward_normal = if hit_record.front_face {
+            *normal
+        } else {
+            normal.neg()
+        };
+        let bitangent = outward_normal.cross(dpdu).normolize();
+        let tangent = bitangent.cross(&outward_normal);
+
+        let color = texture.value(uv, point);
+        let local = Vector3::new(2. * color.x - 1., 2. * color.y - 1., 2. * color.z - 1.);
+        let shading_normal = tangent
+            .mul(local.x)
+            .add(&bitangent.mul(local.y))
+            .add(&outward_normal.mul(local.z));
+
+        if hit_record.front_face {
+            shading_normal
+        } else {
+            shading_normal.neg()
+        }
+    }
+
+    fn bump_map(
+        texture: &dyn Texture,
+        scale: f32,
+        normal: &Vector3,
+        uv: &Vector2,
+        point: &Point3,
+        dpdu: &Vector3,
+        dpdv: &Vector3,
+    ) -> Vector3 {
+        let height = |uv: &Vector2, point: &Point3| {
+            let color = texture.value(uv, point);
+            scale * (color.x + color.y + color.z) / 3.
+        };
+
+        // Estimate the height derivatives by forward differences.
+        let delta = 0.0005;
+        let displacement = height(uv, point);
+        let u_displacement = height(
+            &Vector2::new(uv.u() + delta, uv.v()),
+            &point.add(&dpdu.mul(delta)),
+        );
+        let v_displacement = height(
+            &Vector2::new(uv.u(), uv.v() + delta),
+            &point.add(&dpdv.mul(delta)),
+        );
+
+        let unit_normal = normal.normolize();
+        let bumped_dpdu = dpdu.add(&unit_normal.mul((u_displacement - displacement) / delta));
+        let bumped_dpdv = dpdv.add(&unit_normal.mul((v_displacement - displacement) / delta));
+
+        // Keep the shading normal on the same side as the geometric normal.
+        let shading_normal = bumped_dpdu.cross(&bumped_dpdv);
+        if shading_normal.dot(normal) < 0. {
+            shading_normal.neg()
+        } else {
+            shading_normal
+        }
+    }
+}
+
+impl Material for NormalMappedMaterial {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        self.material
+            .scatter(ray_in, &self.shading_record(hit_record), scatter_record)
+    }
+
+    fn emitted(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        uv: &Vector2,
+        point: &Vector3,
+    ) -> Color3 {
+        self.material.emitted(ray_in, hit_record, uv, point)
+    }
+
+    fn interior_medium(&self, hit_record: &HitRecord) -> Option<Rc<dyn Medium>> {
+        self.material.interior_medium(hit_record)
+    }
+
+    fn diffuse_probability(&self, ray_in: &Ray, hit_record: &HitRecord) -> f32 {
+        self.material
+            .diffuse_probability(ray_in, &self.shading_record(hit_record))
+    }
+
+    fn scattering_pdf(&self, ray_in: &Ray, hit_record: &HitRecord, ray_scattered: &Ray) -> f32 {
+        self.material
+            .scattering_pdf(ray_in, &self.shading_record(hit_record), ray_scattered)
+    }
+
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        self.material
+            .scattering_color(ray_in, &self.shading_record(hit_record), ray_scattered)
+    }
+}
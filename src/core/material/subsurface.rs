@@ -0,0 +1,122 @@
+use crate::{
+    core::{
+        Color3, DielectricMaterial, HitRecord, HomogeneousMedium, Ray, ScatterRecord,
+        SolidColorTexture,
+    },
+    traits::{Material, Medium, Texture},
+};
+use std::rc::Rc;
+
+/**
+Subsurface Material
+
+A translucent material such as skin, wax, marble or milk. Light refracts through a dielectric
+boundary and takes a volumetric random walk through a homogeneous medium filling the shape, so the
+shape must be closed. The medium is set per color channel by the mean free path, the average
+distance light travels between collisions, and by the albedo, the color of the surface once light
+has scattered many times below it. Both may be textures, read where light enters the surface.
+
+Every collision inside counts as a bounce, so thick or bright materials need a larger
+`max_ray_depth` on the camera.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, HitRecord, Point3, SubsurfaceMaterial, Vector2, Vector3},
+    traits::Material,
+};
+
+let wax = SubsurfaceMaterial::new_with_color(Color3::new(0.9, 0.8, 0.6), Color3::one(), 1.45);
+let mut hit_record = HitRecord::new();
+hit_record.point = Some(Point3::zero());
+hit_record.normal = Some(Vector3::up());
+hit_record.uv = Some(Vector2::zero());
+# assert!(wax.interior_medium(&hit_record).is_some());
+# assert!(SubsurfaceMaterial::single_scattering_albedo(0.) < 1e-4);
+# assert!((SubsurfaceMaterial::single_scattering_albedo(1.) - 1.).abs() < 1e-4);
+```
+*/
+#[derive(Debug)]
+pub struct SubsurfaceMaterial {
+    /// Color of the surface after many scattering events below it.
+    pub albedo: Rc<dyn Texture>,
+
+    /// Average distance between collisions inside, per color channel.
+    pub mean_free_path: Rc<dyn Texture>,
+
+    /// Anisotropy of the scattering inside.
+    pub g: f32,
+
+    /// The refracting boundary.
+    boundary: DielectricMaterial,
+}
+
+impl SubsurfaceMaterial {
+    /// Create a new `SubsurfaceMaterial` from albedo and mean free path textures and the index of
+    /// refraction of the boundary.
+    pub fn new(albedo: Rc<dyn Texture>, mean_free_path: Rc<dyn Texture>, ior: f32) -> Self {
+        Self {
+            albedo,
+            mean_free_path,
+            g: 0.,
+            boundary: DielectricMaterial::new(ior),
+        }
+    }
+
+    pub fn new_with_color(albedo: Color3, mean_free_path: Color3, ior: f32) -> Self {
+        Self::new(
+            Rc::new(SolidColorTexture::new_with_color(albedo)),
+            Rc::new(SolidColorTexture::new_with_color(mean_free_path)),
+            ior,
+        )
+    }
+
+    /// Returns the index of refraction of the boundary.
+    pub fn ior(&self) -> f32 {
+        self.boundary.ior
+    }
+
+    /// Returns the single scattering albedo of a medium whose multiple scattering albedo is
+    /// `albedo`, by the fit of Chiang et al. 2016.
+    pub fn single_scattering_albedo(albedo: f32) -> f32 {
+        let albedo = albedo.clamp(0., 1.);
+        let root = 4.09712 + 4.20863 * albedo
+            - f32::sqrt(9.59217 + 41.6808 * albedo + 17.7126 * albedo * albedo);
+        (1. - root * root).clamp(0., 1.)
+    }
+}
+
+impl Material for SubsurfaceMaterial {
+    fn scatter(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        self.boundary.scatter(ray_in, hit_record, scatter_record)
+    }
+
+    fn diffuse_probability(&self, ray_in: &Ray, hit_record: &HitRecord) -> f32 {
+        self.boundary.diffuse_probability(ray_in, hit_record)
+    }
+
+    fn interior_medium(&self, hit_record: &HitRecord) -> Option<Rc<dyn Medium>> {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return None;
+        };
+
+        let albedo = self.albedo.value_at_hit(hit_record);
+        let mean_free_path = self.mean_free_path.value(&uv, &point);
+        let single_scattering_albedo = Color3::new(
+            Self::single_scattering_albedo(albedo.x),
+            Self::single_scattering_albedo(albedo.y),
+            Self::single_scattering_albedo(albedo.z),
+        );
+        Some(Rc::new(HomogeneousMedium::new_with_albedo(
+            single_scattering_albedo,
+            mean_free_path,
+            self.g,
+        )))
+    }
+}
@@ -2,6 +2,7 @@ use std::ops::{Add, Mul};
 
 use super::vector3::Vector3;
 
+#[derive(Debug, Clone)]
 pub struct OrthonormalBasis {
     axis: [Vector3; 3],
 }
@@ -44,6 +45,15 @@ impl OrthonormalBasis {
             .add(&self.axis[2].mul(vec.z))
     }
 
+    /// Returns the coordinates of a vector in this basis, the inverse of `local`.
+    pub fn project(&self, vec: &Vector3) -> Vector3 {
+        Vector3::new(
+            vec.dot(&self.axis[0]),
+            vec.dot(&self.axis[1]),
+            vec.dot(&self.axis[2]),
+        )
+    }
+
     pub fn local_with_floats(&self, u: f32, v: f32, w: f32) -> Vector3 {
         self.axis[0]
             .mul(u)
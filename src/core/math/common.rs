@@ -1,3 +1,5 @@
+use crate::core::Color3;
+
 /**
 Convert degree to radian.
 
@@ -65,3 +67,36 @@ let linear_component = gramma_to_linear(3.1622776601683795);
 pub fn gramma_to_linear(gramma_component: f32) -> f32 {
     gramma_component.powi(2)
 }
+
+/**
+Returns the spectral radiance of a black body at a temperature in kelvin, by Planck's law at
+representative wavelengths of the red, green and blue channels, in W/(sr·m²·nm).
+
+# Examples
+
+```
+use raytracing::core::blackbody;
+
+let flame = blackbody(1500.);
+# assert!(flame.x > flame.y && flame.y > flame.z);
+# assert!((blackbody(6500.).x / blackbody(6500.).z - 1.).abs() < 0.2);
+# assert_eq!(blackbody(0.).x, 0.);
+```
+*/
+pub fn blackbody(temperature: f32) -> Color3 {
+    if temperature <= 0. {
+        return Color3::zero();
+    }
+
+    // First and second radiation constants, 2hc² and hc/k, in SI units.
+    let c1 = 1.191_043e-16_f64;
+    let c2 = 1.438_777e-2_f64;
+    let planck = |wavelength_nm: f64| {
+        let wavelength = wavelength_nm * 1e-9;
+        let radiance =
+            c1 / (wavelength.powi(5) * ((c2 / (wavelength * temperature as f64)).exp() - 1.));
+        // Per meter of wavelength to per nanometer.
+        (radiance * 1e-9) as f32
+    };
+    Color3::new(planck(610.), planck(550.), planck(465.))
+}
@@ -0,0 +1,218 @@
+use crate::core::{AxisAlignedBoundingBox, Interval, Point3};
+use std::{fs, io, path::Path};
+
+/**
+Density Grid
+
+A voxel grid of values over a box in space, such as the density, temperature or emission of a
+smoke simulation. Values are stored at the voxel centers and interpolated trilinearly, points
+outside of the box read zero.
+
+Grids are read from the binary `.vol` format of Mitsuba, which most simulation tools can export:
+the bytes `VOL` and the version `3`, then little-endian 32-bit integers for the encoding (`1` for
+32-bit floats), the x, y and z resolution and the channel count, six floats for the minimum and
+maximum corners of the box, and the values with x varying fastest and the channels interleaved.
+
+# Examples
+
+```
+use raytracing::core::{AxisAlignedBoundingBox, DensityGrid, Point3};
+
+let bbox = AxisAlignedBoundingBox::new_with_two_points(&Point3::zero(), &Point3::new(2., 1., 1.));
+let grid = DensityGrid::new(2, 1, 1, 1, vec![0., 1.], bbox);
+# assert_eq!(grid.value(&Point3::new(0.5, 0.5, 0.5), 0), 0.);
+# assert_eq!(grid.value(&Point3::new(1., 0.5, 0.5), 0), 0.5);
+# assert_eq!(grid.value(&Point3::new(3., 0.5, 0.5), 0), 0.);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct DensityGrid {
+    /// Resolution of the grid along x, y and z.
+    resolution: [usize; 3],
+
+    /// Number of values per voxel.
+    channels: usize,
+
+    /// Values with x varying fastest and the channels interleaved.
+    data: Vec<f32>,
+
+    /// The box the grid covers in space.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl DensityGrid {
+    /// Create a new `DensityGrid` from its resolution, channel count, values and box.
+    pub fn new(
+        nx: usize,
+        ny: usize,
+        nz: usize,
+        channels: usize,
+        data: Vec<f32>,
+        bbox: AxisAlignedBoundingBox,
+    ) -> Self {
+        assert_eq!(data.len(), nx * ny * nz * channels, "voxel count mismatch");
+        Self {
+            resolution: [nx, ny, nz],
+            channels,
+            data,
+            bbox,
+        }
+    }
+
+    /// Load a `DensityGrid` from a `.vol` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    /// Parse a `DensityGrid` from the content of a `.vol` file.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let invalid =
+            |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_owned());
+
+        if bytes.len() < 48 || &bytes[0..3] != b"VOL" {
+            return Err(invalid("not a VOL file"));
+        }
+        if bytes[3] != 3 {
+            return Err(invalid("unsupported VOL version"));
+        }
+
+        let word = |offset: usize| {
+            let mut word = [0u8; 4];
+            word.copy_from_slice(&bytes[offset..offset + 4]);
+            word
+        };
+        let integer = |offset: usize| i32::from_le_bytes(word(offset));
+        let float = |offset: usize| f32::from_le_bytes(word(offset));
+
+        if integer(4) != 1 {
+            return Err(invalid("only 32-bit float VOL files are supported"));
+        }
+        let dimensions = [integer(8), integer(12), integer(16), integer(20)];
+        if dimensions.iter().any(|&dimension| dimension <= 0) {
+            return Err(invalid("invalid VOL dimensions"));
+        }
+        let [nx, ny, nz, channels] = dimensions.map(|dimension| dimension as usize);
+
+        let count = nx
+            .checked_mul(ny)
+            .and_then(|count| count.checked_mul(nz))
+            .and_then(|count| count.checked_mul(channels))
+            .ok_or_else(|| invalid("invalid VOL dimensions"))?;
+        let length = count
+            .checked_mul(4)
+            .and_then(|length| length.checked_add(48))
+            .ok_or_else(|| invalid("invalid VOL dimensions"))?;
+        if bytes.len() < length {
+            return Err(invalid("unexpected end of file"));
+        }
+        let data = (0..count).map(|i| float(48 + 4 * i)).collect();
+        let bbox = AxisAlignedBoundingBox::new_with_two_points(
+            &Point3::new(float(24), float(28), float(32)),
+            &Point3::new(float(36), float(40), float(44)),
+        );
+
+        Ok(Self::new(nx, ny, nz, channels, data, bbox))
+    }
+
+    /// Returns the resolution of the grid along x, y and z.
+    pub fn resolution(&self) -> [usize; 3] {
+        self.resolution
+    }
+
+    /// Returns the number of values per voxel.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Returns the box the grid covers in space.
+    pub fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    /// Returns the value of a channel at a voxel, clamping the indices to the grid.
+    pub fn voxel(&self, x: isize, y: isize, z: isize, channel: usize) -> f32 {
+        let [nx, ny, nz] = self.resolution;
+        let x = x.clamp(0, nx as isize - 1) as usize;
+        let y = y.clamp(0, ny as isize - 1) as usize;
+        let z = z.clamp(0, nz as isize - 1) as usize;
+        self.data[((z * ny + y) * nx + x) * self.channels + channel]
+    }
+
+    /// Returns the position of a point relative to the box, in `[0, 1]` inside of it.
+    pub fn to_grid(&self, point: &Point3) -> Point3 {
+        let relative = |interval: &Interval, value: f32| {
+            if interval.size() > 0. {
+                (value - interval.min) / interval.size()
+            } else {
+                0.5
+            }
+        };
+        Point3::new(
+            relative(&self.bbox.x, point.x),
+            relative(&self.bbox.y, point.y),
+            relative(&self.bbox.z, point.z),
+        )
+    }
+
+    /// Returns the trilinearly interpolated value of a channel at a point in space.
+    pub fn value(&self, point: &Point3, channel: usize) -> f32 {
+        let grid_point = self.to_grid(point);
+        if (0..3).any(|i| !(0. ..=1.).contains(&grid_point[i])) {
+            return 0.;
+        }
+
+        // Voxel centers sit at half integer positions.
+        let mut base = [0isize; 3];
+        let mut weight = [0f32; 3];
+        for i in 0..3 {
+            let position = grid_point[i] * self.resolution[i] as f32 - 0.5;
+            base[i] = position.floor() as isize;
+            weight[i] = position - position.floor();
+        }
+
+        let mut value = 0.;
+        for corner in 0..8 {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let mut corner_weight = 1.;
+            for i in 0..3 {
+                corner_weight *= if offset[i] == 1 {
+                    weight[i]
+                } else {
+                    1. - weight[i]
+                };
+            }
+            if corner_weight > 0. {
+                value += corner_weight
+                    * self.voxel(
+                        base[0] + offset[0] as isize,
+                        base[1] + offset[1] as isize,
+                        base[2] + offset[2] as isize,
+                        channel,
+                    );
+            }
+        }
+        value
+    }
+
+    /// Returns the largest value of a channel that interpolation can produce within a region of
+    /// the grid, given in `[0, 1]` coordinates relative to the box.
+    pub fn max_value(&self, min: &Point3, max: &Point3, channel: usize) -> f32 {
+        let mut low = [0isize; 3];
+        let mut high = [0isize; 3];
+        for i in 0..3 {
+            let resolution = self.resolution[i] as f32;
+            low[i] = (min[i] * resolution - 0.5).floor() as isize;
+            high[i] = (max[i] * resolution - 0.5).floor() as isize + 1;
+        }
+
+        let mut value = f32::NEG_INFINITY;
+        for z in low[2]..=high[2] {
+            for y in low[1]..=high[1] {
+                for x in low[0]..=high[0] {
+                    value = f32::max(value, self.voxel(x, y, z, channel));
+                }
+            }
+        }
+        value
+    }
+}
@@ -0,0 +1,155 @@
+use crate::core::Vector2;
+
+/**
+Piecewise-constant 1D distribution
+
+A distribution over `[0, 1)` whose density is proportional to a list of non-negative function
+values, sampled by inverting its cumulative distribution function.
+
+# Examples
+
+```
+use raytracing::core::Distribution1D;
+
+let distribution = Distribution1D::new(vec![1., 3.]);
+let (x, pdf, offset) = distribution.sample_continuous(0.5);
+# assert_eq!(offset, 1);
+# assert_eq!(pdf, 1.5);
+# assert!((x - 0.5 - 1. / 6.).abs() < 1e-6);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct Distribution1D {
+    /// The function values of each piece.
+    function: Vec<f32>,
+
+    /// The cumulative distribution function, one more entry than `function`.
+    cdf: Vec<f32>,
+
+    /// The integral of the function over `[0, 1)`.
+    integral: f32,
+}
+
+impl Distribution1D {
+    /// Create a new `Distribution1D` from the function values of each piece.
+    pub fn new(function: Vec<f32>) -> Self {
+        let count = function.len();
+        let mut cdf = vec![0.; count + 1];
+        for i in 0..count {
+            cdf[i + 1] = cdf[i] + f32::max(function[i], 0.) / count as f32;
+        }
+
+        let integral = cdf[count];
+        if integral > 0. {
+            for value in cdf.iter_mut() {
+                *value /= integral;
+            }
+        } else {
+            // Fall back to a uniform distribution if the function is zero everywhere.
+            for (i, value) in cdf.iter_mut().enumerate() {
+                *value = i as f32 / count as f32;
+            }
+        }
+
+        Self {
+            function,
+            cdf,
+            integral,
+        }
+    }
+
+    /// Returns the number of pieces.
+    pub fn count(&self) -> usize {
+        self.function.len()
+    }
+
+    /// Returns the integral of the function over `[0, 1)`.
+    pub fn integral(&self) -> f32 {
+        self.integral
+    }
+
+    /// Sample a value in `[0, 1)` by a uniform random number `u`, returning the value, its
+    /// density and the index of the piece it falls into.
+    pub fn sample_continuous(&self, u: f32) -> (f32, f32, usize) {
+        // Find the last cdf entry that is less than or equal to u.
+        let offset = self
+            .cdf
+            .partition_point(|&value| value <= u)
+            .clamp(1, self.count())
+            - 1;
+
+        let mut delta = u - self.cdf[offset];
+        let span = self.cdf[offset + 1] - self.cdf[offset];
+        if span > 0. {
+            delta /= span;
+        }
+
+        let x = (offset as f32 + delta) / self.count() as f32;
+        (f32::min(x, 1. - f32::EPSILON), self.pdf(offset), offset)
+    }
+
+    /// Sample the index of a piece by a uniform random number `u`, returning the index and its
+    /// probability.
+    pub fn sample_discrete(&self, u: f32) -> (usize, f32) {
+        let (_, _, offset) = self.sample_continuous(u);
+        (offset, self.discrete_pdf(offset))
+    }
+
+    /// Returns the density of the values in the piece at `offset`.
+    pub fn pdf(&self, offset: usize) -> f32 {
+        self.discrete_pdf(offset) * self.count() as f32
+    }
+
+    /// Returns the probability of sampling the piece at `offset`.
+    pub fn discrete_pdf(&self, offset: usize) -> f32 {
+        self.cdf[offset + 1] - self.cdf[offset]
+    }
+}
+
+/// Piecewise-constant 2D distribution over `[0, 1)²`, sampling a row by the marginal distribution
+/// and then a column by the conditional distribution of that row.
+#[derive(Debug, Clone)]
+pub struct Distribution2D {
+    /// The distribution of u in each row.
+    conditional: Vec<Distribution1D>,
+
+    /// The distribution of the rows.
+    marginal: Distribution1D,
+}
+
+impl Distribution2D {
+    /// Create a new `Distribution2D` from row-major function values.
+    pub fn new(function: &[f32], width: usize, height: usize) -> Self {
+        let conditional: Vec<Distribution1D> = (0..height)
+            .map(|v| Distribution1D::new(function[v * width..(v + 1) * width].to_vec()))
+            .collect();
+        let marginal = Distribution1D::new(conditional.iter().map(|row| row.integral()).collect());
+
+        Self {
+            conditional,
+            marginal,
+        }
+    }
+
+    /// Sample a point in `[0, 1)²` by two uniform random numbers, returning the point and its
+    /// density.
+    pub fn sample(&self, u: &Vector2) -> (Vector2, f32) {
+        let (v, v_pdf, row) = self.marginal.sample_continuous(u.y);
+        let (u, u_pdf, _) = self.conditional[row].sample_continuous(u.x);
+        (Vector2::new(u, v), u_pdf * v_pdf)
+    }
+
+    /// Returns the density of sampling the point in `[0, 1)²`.
+    pub fn pdf(&self, point: &Vector2) -> f32 {
+        let row =
+            ((point.y * self.marginal.count() as f32) as usize).min(self.marginal.count() - 1);
+        let row_distribution = &self.conditional[row];
+        let column = ((point.x * row_distribution.count() as f32) as usize)
+            .min(row_distribution.count() - 1);
+
+        if self.marginal.integral() <= 0. {
+            return 1.;
+        }
+        f32::max(row_distribution.function[column], 0.) / self.marginal.integral()
+    }
+}
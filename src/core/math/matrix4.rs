@@ -0,0 +1,201 @@
+use super::vector3::{Point3, Vector3};
+use std::ops::Mul;
+
+/**
+Matrix4
+
+A 4x4 matrix of an affine transform, applied to points, vectors and normals. Matrices compose
+like the transforms they stand for, `&a * &b` applies `b` first and then `a`.
+
+# Examples
+
+```
+use raytracing::core::{Matrix4, Point3, Vector3};
+
+let transform = &Matrix4::translation(&Vector3::new(1., 2., 3.))
+    * &Matrix4::scaling(&Vector3::new(2., 2., 2.));
+let point = transform.transform_point(&Point3::new(1., 0., 0.));
+# assert!(point.equals(&Point3::new(3., 2., 3.)));
+let inverse = transform.inverse().unwrap();
+# assert!((&inverse.transform_point(&point) - &Point3::new(1., 0., 0.)).length() < 1e-6);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix4 {
+    /// Entries of the matrix, row by row.
+    pub m: [[f32; 4]; 4],
+}
+
+impl Matrix4 {
+    /// Create a new `Matrix4` from its rows.
+    pub fn new(m: [[f32; 4]; 4]) -> Self {
+        Self { m }
+    }
+
+    /// Create a new `Matrix4` from its 16 entries column by column, the layout of glTF and OpenGL.
+    pub fn new_with_columns(columns: &[f32; 16]) -> Self {
+        let mut m = [[0.; 4]; 4];
+        for (column, values) in columns.chunks_exact(4).enumerate() {
+            for (row, &value) in values.iter().enumerate() {
+                m[row][column] = value;
+            }
+        }
+        Self { m }
+    }
+
+    /// Returns the identity matrix.
+    pub fn identity() -> Self {
+        Self::new([
+            [1., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [0., 0., 1., 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Returns a translation by an offset.
+    pub fn translation(offset: &Vector3) -> Self {
+        Self::new([
+            [1., 0., 0., offset.x],
+            [0., 1., 0., offset.y],
+            [0., 0., 1., offset.z],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Returns a scaling by a factor along each axis.
+    pub fn scaling(factor: &Vector3) -> Self {
+        Self::new([
+            [factor.x, 0., 0., 0.],
+            [0., factor.y, 0., 0.],
+            [0., 0., factor.z, 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Returns a rotation by a unit quaternion `[x, y, z, w]`.
+    pub fn rotation(quaternion: &[f32; 4]) -> Self {
+        let [x, y, z, w] = *quaternion;
+        Self::new([
+            [
+                1. - 2. * (y * y + z * z),
+                2. * (x * y - z * w),
+                2. * (x * z + y * w),
+                0.,
+            ],
+            [
+                2. * (x * y + z * w),
+                1. - 2. * (x * x + z * z),
+                2. * (y * z - x * w),
+                0.,
+            ],
+            [
+                2. * (x * z - y * w),
+                2. * (y * z + x * w),
+                1. - 2. * (x * x + y * y),
+                0.,
+            ],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    /// Returns the transform that scales, then rotates by a unit quaternion, then translates.
+    pub fn new_with_trs(translation: &Vector3, rotation: &[f32; 4], scale: &Vector3) -> Self {
+        &(&Self::translation(translation) * &Self::rotation(rotation)) * &Self::scaling(scale)
+    }
+
+    /// Returns the transposed matrix.
+    pub fn transpose(&self) -> Self {
+        let mut m = [[0.; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = self.m[column][row];
+            }
+        }
+        Self { m }
+    }
+
+    /// Returns the inverse matrix, or `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        // Gauss-Jordan elimination with partial pivoting.
+        let mut m = self.m;
+        let mut inverse = Self::identity().m;
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|&a, &b| f32::total_cmp(&m[a][column].abs(), &m[b][column].abs()))
+                .unwrap();
+            if m[pivot][column].abs() < 1e-12 {
+                return None;
+            }
+            m.swap(column, pivot);
+            inverse.swap(column, pivot);
+
+            let scale = 1. / m[column][column];
+            for k in 0..4 {
+                m[column][k] *= scale;
+                inverse[column][k] *= scale;
+            }
+            for row in 0..4 {
+                if row == column {
+                    continue;
+                }
+                let factor = m[row][column];
+                for k in 0..4 {
+                    m[row][k] -= factor * m[column][k];
+                    inverse[row][k] -= factor * inverse[column][k];
+                }
+            }
+        }
+        Some(Self { m: inverse })
+    }
+
+    /// Returns a point transformed by the matrix, translations included.
+    pub fn transform_point(&self, point: &Point3) -> Point3 {
+        let m = &self.m;
+        let x = m[0][0] * point.x + m[0][1] * point.y + m[0][2] * point.z + m[0][3];
+        let y = m[1][0] * point.x + m[1][1] * point.y + m[1][2] * point.z + m[1][3];
+        let z = m[2][0] * point.x + m[2][1] * point.y + m[2][2] * point.z + m[2][3];
+        let w = m[3][0] * point.x + m[3][1] * point.y + m[3][2] * point.z + m[3][3];
+        if w == 1. || w == 0. {
+            Point3::new(x, y, z)
+        } else {
+            Point3::new(x / w, y / w, z / w)
+        }
+    }
+
+    /// Returns a vector transformed by the matrix, translations left out.
+    pub fn transform_vector(&self, vector: &Vector3) -> Vector3 {
+        let m = &self.m;
+        Vector3::new(
+            m[0][0] * vector.x + m[0][1] * vector.y + m[0][2] * vector.z,
+            m[1][0] * vector.x + m[1][1] * vector.y + m[1][2] * vector.z,
+            m[2][0] * vector.x + m[2][1] * vector.y + m[2][2] * vector.z,
+        )
+    }
+
+    /// Returns a normal transformed by the matrix whose inverse this is, which keeps it
+    /// perpendicular to the transformed surface.
+    pub fn transform_normal(&self, normal: &Vector3) -> Vector3 {
+        self.transpose().transform_vector(normal)
+    }
+}
+
+impl Default for Matrix4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul<&Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+
+    fn mul(self, rhs: &Matrix4) -> Self::Output {
+        let mut m = [[0.; 4]; 4];
+        for (row, values) in m.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[row][k] * rhs.m[k][column]).sum();
+            }
+        }
+        Matrix4 { m }
+    }
+}
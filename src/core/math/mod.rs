@@ -1,15 +1,25 @@
 mod basis;
 mod common;
+mod density_grid;
+mod distribution;
 mod interval;
+mod matrix4;
 mod pdf;
 mod perlin;
+mod polynomial;
 mod vector2;
 mod vector3;
+mod worley;
 
 pub use basis::*;
 pub use common::*;
+pub use density_grid::*;
+pub use distribution::*;
 pub use interval::*;
+pub use matrix4::*;
 pub use pdf::*;
 pub use perlin::*;
+pub use polynomial::*;
 pub use vector2::*;
 pub use vector3::*;
+pub use worley::*;
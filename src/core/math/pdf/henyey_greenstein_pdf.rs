@@ -0,0 +1,56 @@
+use crate::{
+    core::{OrthonormalBasis, Vector3},
+    traits::ProbabilityDensityFunction,
+};
+use std::f32::consts::PI;
+
+/// Probability density function of the Henyey-Greenstein phase function around the direction of
+/// the incoming ray. Positive anisotropy `g` scatters forward, negative scatters back.
+pub struct HenyeyGreensteinPDF {
+    onb: OrthonormalBasis,
+    g: f32,
+}
+
+impl HenyeyGreensteinPDF {
+    pub fn new(direction: Vector3, g: f32) -> Self {
+        Self {
+            onb: OrthonormalBasis::new_with_w(&direction),
+            g,
+        }
+    }
+
+    /// Returns the Henyey-Greenstein phase function for the cosine between the incoming and the
+    /// scattered directions.
+    pub fn phase(cos_theta: f32, g: f32) -> f32 {
+        let denominator = 1. + g * g - 2. * g * cos_theta;
+        (1. - g * g) / (4. * PI * denominator * f32::sqrt(f32::max(denominator, 0.)))
+    }
+}
+
+impl ProbabilityDensityFunction for HenyeyGreensteinPDF {
+    fn value(&self, direction: &Vector3) -> f32 {
+        let cos_theta = direction.normolize().dot(self.onb.w());
+        Self::phase(cos_theta, self.g)
+    }
+
+    fn generate(&self) -> Vector3 {
+        let (u1, u2) = (rand::random::<f32>(), rand::random::<f32>());
+        let g = self.g;
+
+        // Invert the cumulative distribution of the cosine.
+        let cos_theta = if g.abs() < 1e-3 {
+            1. - 2. * u1
+        } else {
+            let term = (1. - g * g) / (1. - g + 2. * g * u1);
+            f32::clamp((1. + g * g - term * term) / (2. * g), -1., 1.)
+        };
+        let sin_theta = f32::sqrt(f32::max(0., 1. - cos_theta * cos_theta));
+        let phi = 2. * PI * u2;
+
+        self.onb.local_with_floats(
+            sin_theta * f32::cos(phi),
+            sin_theta * f32::sin(phi),
+            cos_theta,
+        )
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{
+    core::{Point3, Vector3},
+    traits::{Light, ProbabilityDensityFunction},
+};
+use std::rc::Rc;
+
+/// Probability density function of the directions sampled towards a light source.
+pub struct LightPDF {
+    light: Rc<dyn Light>,
+    origin: Point3,
+}
+
+impl LightPDF {
+    pub fn new(light: Rc<dyn Light>, origin: Point3) -> Self {
+        Self { light, origin }
+    }
+}
+
+impl ProbabilityDensityFunction for LightPDF {
+    fn value(&self, direction: &Vector3) -> f32 {
+        self.light.pdf_value(&self.origin, direction)
+    }
+
+    fn generate(&self) -> Vector3 {
+        match self.light.sample(&self.origin) {
+            Some(light_record) => light_record.direction,
+            None => Vector3::random_unit_vector(),
+        }
+    }
+}
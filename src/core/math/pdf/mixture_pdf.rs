@@ -3,6 +3,8 @@ use std::rc::Rc;
 
 pub struct MixturePDF {
     pdfs: [Rc<dyn ProbabilityDensityFunction>; 2],
+    /// Probability of sampling the second pdf.
+    weight: f32,
 }
 
 impl MixturePDF {
@@ -10,20 +12,36 @@ impl MixturePDF {
         pdf1: Rc<dyn ProbabilityDensityFunction>,
         pdf2: Rc<dyn ProbabilityDensityFunction>,
     ) -> Self {
-        Self { pdfs: [pdf1, pdf2] }
+        Self {
+            pdfs: [pdf1, pdf2],
+            weight: 0.5,
+        }
+    }
+
+    /// Create a new `MixturePDF` that samples `pdf2` with probability `weight`, and `pdf1` otherwise.
+    pub fn new_with_weight(
+        pdf1: Rc<dyn ProbabilityDensityFunction>,
+        pdf2: Rc<dyn ProbabilityDensityFunction>,
+        weight: f32,
+    ) -> Self {
+        Self {
+            pdfs: [pdf1, pdf2],
+            weight,
+        }
     }
 }
 
 impl ProbabilityDensityFunction for MixturePDF {
     fn value(&self, direction: &Vector3) -> f32 {
-        self.pdfs[0].value(direction) * 0.5 + self.pdfs[1].value(direction) * 0.5
+        self.pdfs[0].value(direction) * (1. - self.weight)
+            + self.pdfs[1].value(direction) * self.weight
     }
 
     fn generate(&self) -> Vector3 {
-        if rand::random::<f32>() < 0.5 {
-            self.pdfs[0].generate()
-        } else {
+        if rand::random::<f32>() < self.weight {
             self.pdfs[1].generate()
+        } else {
+            self.pdfs[0].generate()
         }
     }
 }
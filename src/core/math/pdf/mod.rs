@@ -1,9 +1,13 @@
 mod cosine_pdf;
+mod henyey_greenstein_pdf;
 mod hittable_pdf;
+mod light_pdf;
 mod mixture_pdf;
 mod sphere_pdf;
 
 pub use cosine_pdf::*;
+pub use henyey_greenstein_pdf::*;
 pub use hittable_pdf::*;
+pub use light_pdf::*;
 pub use mixture_pdf::*;
 pub use sphere_pdf::*;
@@ -119,4 +119,53 @@ impl PerlinNoise {
 
         f32::abs(accumulate)
     }
+
+    /// Returns fractional Brownian motion: the sum of `octaves` layers of noise, each
+    /// `lacunarity` times finer and `gain` times weaker than the previous one, divided by the sum
+    /// of their weights so it stays in about [-1, 1].
+    pub fn fbm(&self, point: &Point3, octaves: usize, lacunarity: f32, gain: f32) -> f32 {
+        let mut accumulate = 0.;
+        let mut total_weight = 0.;
+        let mut weight = 1.;
+
+        let mut temp_point = *point;
+        for _ in 0..octaves {
+            accumulate += weight * self.noise(&temp_point);
+            total_weight += weight;
+            weight *= gain;
+            temp_point *= lacunarity;
+        }
+
+        if total_weight > 0. {
+            accumulate / total_weight
+        } else {
+            0.
+        }
+    }
+
+    /// Returns ridged multifractal noise in [0, 1]: like `fbm`, but layers of `(1 - |noise|)²`,
+    /// sharp ridges along the zero crossings of the noise, each weighted by the previous one so
+    /// that valleys stay smooth.
+    pub fn ridged(&self, point: &Point3, octaves: usize, lacunarity: f32, gain: f32) -> f32 {
+        let mut accumulate = 0.;
+        let mut total_weight = 0.;
+        let mut weight = 1.;
+        let mut previous = 1.;
+
+        let mut temp_point = *point;
+        for _ in 0..octaves {
+            let signal = (1. - f32::abs(self.noise(&temp_point))).powi(2) * previous;
+            accumulate += weight * signal;
+            total_weight += weight;
+            previous = signal.clamp(0., 1.);
+            weight *= gain;
+            temp_point *= lacunarity;
+        }
+
+        if total_weight > 0. {
+            accumulate / total_weight
+        } else {
+            0.
+        }
+    }
 }
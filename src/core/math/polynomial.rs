@@ -0,0 +1,151 @@
+/**
+Returns the real roots of `a x² + b x + c = 0` in ascending order, falling back to the linear
+equation when `a` is zero.
+
+# Examples
+
+```
+use raytracing::core::solve_quadratic;
+
+let roots = solve_quadratic(1., -3., 2.);
+# assert_eq!(roots, vec![1., 2.]);
+# assert_eq!(solve_quadratic(0., 2., -1.), vec![0.5]);
+# assert!(solve_quadratic(1., 0., 1.).is_empty());
+```
+*/
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a == 0. {
+        return if b == 0. { vec![] } else { vec![-c / b] };
+    }
+
+    let discriminant = b * b - 4. * a * c;
+    if discriminant < 0. {
+        return vec![];
+    }
+
+    // Avoid the cancellation of `-b + sqrt(discriminant)` when `b` is large.
+    let q = -0.5 * (b + f64::copysign(f64::sqrt(discriminant), b));
+    let (x0, x1) = if q == 0. { (0., 0.) } else { (q / a, c / q) };
+    if x0 <= x1 {
+        vec![x0, x1]
+    } else {
+        vec![x1, x0]
+    }
+}
+
+/**
+Returns the real roots of `x³ + a x² + b x + c = 0` in ascending order.
+
+# Examples
+
+```
+use raytracing::core::solve_cubic;
+
+// (x - 1)(x - 2)(x - 3)
+let roots = solve_cubic(-6., 11., -6.);
+# assert_eq!(roots.len(), 3);
+# assert!(roots.iter().zip([1., 2., 3.]).all(|(x, y)| (x - y).abs() < 1e-9));
+# assert_eq!(solve_cubic(0., 0., -8.).len(), 1);
+```
+*/
+pub fn solve_cubic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    // Depress the cubic with x = y - a / 3, giving y³ + p y + q = 0.
+    let shift = a / 3.;
+    let p = b - a * shift;
+    let q = c - b * shift + 2. * shift * shift * shift;
+
+    let discriminant = q * q / 4. + p * p * p / 27.;
+    let mut roots = if discriminant > 0. {
+        // One real root, by Cardano's formula.
+        let root = f64::sqrt(discriminant);
+        vec![f64::cbrt(-q / 2. + root) + f64::cbrt(-q / 2. - root)]
+    } else if p == 0. {
+        vec![0.]
+    } else {
+        // Three real roots, by the trigonometric method.
+        let radius = 2. * f64::sqrt(-p / 3.);
+        let angle = f64::acos((3. * q / (p * radius)).clamp(-1., 1.)) / 3.;
+        (0..3)
+            .map(|k| radius * f64::cos(angle - 2. * std::f64::consts::PI * k as f64 / 3.))
+            .collect()
+    };
+
+    for root in roots.iter_mut() {
+        *root -= shift;
+    }
+    roots.sort_by(|x, y| x.total_cmp(y));
+    roots
+}
+
+/**
+Returns the real roots of `a x⁴ + b x³ + c x² + d x + e = 0` in ascending order, by Ferrari's
+method with the roots polished by Newton's method.
+
+# Examples
+
+```
+use raytracing::core::solve_quartic;
+
+// (x - 1)(x - 2)(x - 3)(x - 4)
+let roots = solve_quartic(1., -10., 35., -50., 24.);
+# assert_eq!(roots.len(), 4);
+# assert!(roots.iter().zip([1., 2., 3., 4.]).all(|(x, y)| (x - y).abs() < 1e-9));
+# assert!(solve_quartic(1., 0., 0., 0., 1.).is_empty());
+```
+*/
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a == 0. {
+        return solve_cubic_general(b, c, d, e);
+    }
+    let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+
+    // Depress the quartic with x = y - b / 4, giving y⁴ + p y² + q y + r = 0.
+    let shift = b / 4.;
+    let p = c - 6. * shift * shift;
+    let q = d - 2. * c * shift + 8. * shift * shift * shift;
+    let r = e - d * shift + c * shift * shift - 3. * shift * shift * shift * shift;
+
+    let mut roots = Vec::with_capacity(4);
+    if q.abs() < 1e-12 {
+        // Biquadratic, a quadratic in y².
+        for z in solve_quadratic(1., p, r) {
+            if z >= 0. {
+                roots.push(f64::sqrt(z));
+                roots.push(-f64::sqrt(z));
+            }
+        }
+    } else {
+        // Complete the square with the largest root of the resolvent cubic, which is positive.
+        let m = solve_cubic(p, p * p / 4. - r, -q * q / 8.)
+            .into_iter()
+            .fold(f64::NEG_INFINITY, f64::max);
+        if m > 0. {
+            let s = f64::sqrt(2. * m);
+            roots.extend(solve_quadratic(1., -s, p / 2. + m + q / (2. * s)));
+            roots.extend(solve_quadratic(1., s, p / 2. + m - q / (2. * s)));
+        }
+    }
+
+    let polynomial = |x: f64| (((x + b) * x + c) * x + d) * x + e;
+    let derivative = |x: f64| ((4. * x + 3. * b) * x + 2. * c) * x + d;
+    for root in roots.iter_mut() {
+        *root -= shift;
+        for _ in 0..2 {
+            let slope = derivative(*root);
+            if slope != 0. {
+                *root -= polynomial(*root) / slope;
+            }
+        }
+    }
+    roots.sort_by(|x, y| x.total_cmp(y));
+    roots
+}
+
+/// Internal method, returns the real roots of a cubic whose leading coefficient may be zero.
+fn solve_cubic_general(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a == 0. {
+        solve_quadratic(b, c, d)
+    } else {
+        solve_cubic(b / a, c / a, d / a)
+    }
+}
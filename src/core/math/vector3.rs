@@ -99,6 +99,16 @@ impl Vector3 {
         self.x
     }
 
+    /// Returns the luminance of a linear Rec.709 color.
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.x + 0.7152 * self.y + 0.0722 * self.z
+    }
+
+    /// Returns the average of the three components.
+    pub fn average(&self) -> f32 {
+        (self.x + self.y + self.z) / 3.
+    }
+
     pub fn length_squared(&self) -> f32 {
         self.dot(self)
     }
@@ -0,0 +1,93 @@
+use crate::core::Point3;
+
+/// Returns a well mixed hash of an integer, the `lowbias32` hash of Chris Wellons.
+#[inline]
+fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846ca68b);
+    x ^= x >> 16;
+    x
+}
+
+/**
+Worley Noise
+
+Cellular noise: a feature point is scattered in each unit cube of space, and the noise at a point
+is given by its distances to the nearest feature points, F1, and the second nearest, F2. F1 shades
+the cells of a Voronoi diagram around the feature points, and F2 - F1 their borders.
+
+The feature points only depend on the seed, so the same seed gives the same noise.
+
+# Examples
+
+```
+use raytracing::core::{Point3, WorleyNoise};
+
+let noise = WorleyNoise::new_with_seed(7);
+let (f1, f2) = noise.distances(&Point3::new(0.3, 1.2, -4.5));
+# assert!(0. <= f1 && f1 <= f2);
+# assert_eq!(noise.distances(&Point3::new(0.3, 1.2, -4.5)), (f1, f2));
+```
+*/
+#[derive(Debug, Clone, Copy)]
+pub struct WorleyNoise {
+    /// Seed of the feature points.
+    seed: u32,
+}
+
+impl WorleyNoise {
+    /// Create a new `WorleyNoise` with a random seed.
+    pub fn new() -> Self {
+        Self::new_with_seed(rand::random())
+    }
+
+    /// Create a new `WorleyNoise` with the given seed.
+    pub fn new_with_seed(seed: u32) -> Self {
+        Self { seed }
+    }
+
+    /// Returns the distances F1 and F2 to the nearest and second nearest feature points.
+    pub fn distances(&self, point: &Point3) -> (f32, f32) {
+        let cell = [
+            f32::floor(point.x) as i32,
+            f32::floor(point.y) as i32,
+            f32::floor(point.z) as i32,
+        ];
+
+        // The nearest feature points are almost always in the neighbouring cells.
+        let (mut f1, mut f2) = (f32::INFINITY, f32::INFINITY);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let feature = self.feature_point(cell[0] + dx, cell[1] + dy, cell[2] + dz);
+                    let distance = (&feature - point).length();
+                    if distance < f1 {
+                        f2 = f1;
+                        f1 = distance;
+                    } else if distance < f2 {
+                        f2 = distance;
+                    }
+                }
+            }
+        }
+        (f1, f2)
+    }
+
+    /// Returns the feature point of a cell.
+    fn feature_point(&self, x: i32, y: i32, z: i32) -> Point3 {
+        let mut state = hash(self.seed ^ hash(x as u32 ^ hash(y as u32 ^ hash(z as u32))));
+        let mut next = || {
+            state = hash(state);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        Point3::new(x as f32 + next(), y as f32 + next(), z as f32 + next())
+    }
+}
+
+impl Default for WorleyNoise {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    core::{Color3, HenyeyGreensteinMaterial, Interval, MediumRecord, Ray},
+    traits::{Material, Medium},
+};
+use rand::Rng;
+use std::rc::Rc;
+
+/**
+Homogeneous Medium
+
+A participating medium with the same absorption and scattering coefficients everywhere, per unit
+length and per color channel, such as fog, murky water or the inside of a liquid. Distances are
+sampled in a randomly picked channel and weighted by the average density of all channels, so
+chromatic media stay unbiased.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, HomogeneousMedium, Interval, Point3, Ray, Vector3},
+    traits::Medium,
+};
+
+let medium = HomogeneousMedium::new(Color3::new(1., 0.5, 0.), Color3::zero(), 0.);
+let ray = Ray::new(Point3::zero(), Vector3::new(2., 0., 0.));
+let transmittance = medium.transmittance(&ray, &Interval::new(0., 1.));
+# assert!((transmittance.x - f32::exp(-2.)).abs() < 1e-6);
+# assert!((transmittance.y - f32::exp(-1.)).abs() < 1e-6);
+# assert_eq!(transmittance.z, 1.);
+```
+*/
+#[derive(Debug)]
+pub struct HomogeneousMedium {
+    /// Absorption coefficient.
+    pub sigma_a: Color3,
+
+    /// Scattering coefficient.
+    pub sigma_s: Color3,
+
+    /// The phase function, its albedo is carried by the sample weights.
+    phase_function: Rc<dyn Material>,
+}
+
+impl HomogeneousMedium {
+    /// Create a new `HomogeneousMedium` with a Henyey-Greenstein phase function of anisotropy `g`.
+    pub fn new(sigma_a: Color3, sigma_s: Color3, g: f32) -> Self {
+        Self {
+            sigma_a,
+            sigma_s,
+            phase_function: Rc::new(HenyeyGreensteinMaterial::new_with_color(Color3::one(), g)),
+        }
+    }
+
+    /// Create a new `HomogeneousMedium` from the albedo and the mean free path, the average
+    /// distance between collisions, in each color channel.
+    pub fn new_with_albedo(albedo: Color3, mean_free_path: Color3, g: f32) -> Self {
+        let sigma_t = Color3::new(
+            1. / mean_free_path.x,
+            1. / mean_free_path.y,
+            1. / mean_free_path.z,
+        );
+        let sigma_s = &sigma_t * &albedo;
+        Self::new(&sigma_t - &sigma_s, sigma_s, g)
+    }
+
+    /// Returns the extinction coefficient.
+    pub fn sigma_t(&self) -> Color3 {
+        &self.sigma_a + &self.sigma_s
+    }
+
+    /// Returns the transmittance over a distance.
+    fn transmittance_over(&self, distance: f32) -> Color3 {
+        let sigma_t = self.sigma_t();
+        let channel = |sigma: f32| {
+            if sigma > 0. {
+                f32::exp(-sigma * distance)
+            } else {
+                1.
+            }
+        };
+        Color3::new(channel(sigma_t.x), channel(sigma_t.y), channel(sigma_t.z))
+    }
+}
+
+impl Medium for HomogeneousMedium {
+    fn sample(&self, ray: &Ray, ray_interval: &Interval) -> MediumRecord {
+        let sigma_t = self.sigma_t();
+        let ray_length = ray.direction.length();
+        let distance = (ray_interval.max - ray_interval.min) * ray_length;
+
+        // Sample a distance by the extinction of a random channel.
+        let channel = rand::thread_rng().gen_range(0..3);
+        let sampled_distance = if sigma_t[channel] > 0. {
+            -f32::ln(1. - rand::random::<f32>()) / sigma_t[channel]
+        } else {
+            f32::INFINITY
+        };
+
+        if sampled_distance < distance {
+            // The density of the distance averaged over the channels it could be sampled in.
+            let transmittance = self.transmittance_over(sampled_distance);
+            let pdf = (&sigma_t * &transmittance).average();
+            if pdf <= 0. {
+                return MediumRecord {
+                    t: None,
+                    weight: Color3::zero(),
+                };
+            }
+            return MediumRecord {
+                t: Some(ray_interval.min + sampled_distance / ray_length),
+                weight: &(&self.sigma_s * &transmittance) / pdf,
+            };
+        }
+
+        let transmittance = self.transmittance_over(distance);
+        let probability = transmittance.average();
+        MediumRecord {
+            t: None,
+            weight: if probability > 0. {
+                &transmittance / probability
+            } else {
+                Color3::zero()
+            },
+        }
+    }
+
+    fn transmittance(&self, ray: &Ray, ray_interval: &Interval) -> Color3 {
+        self.transmittance_over((ray_interval.max - ray_interval.min) * ray.direction.length())
+    }
+
+    fn phase_function(&self) -> Rc<dyn Material> {
+        self.phase_function.clone()
+    }
+}
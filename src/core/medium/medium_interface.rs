@@ -0,0 +1,43 @@
+use crate::{
+    core::{HitRecord, Vector3},
+    traits::Medium,
+};
+use std::{ops::Neg, rc::Rc};
+
+/// The media on both sides of a surface, `None` stands for vacuum.
+#[derive(Debug, Clone, Default)]
+pub struct MediumInterface {
+    /// Medium on the side the outward normal points away from.
+    pub inside: Option<Rc<dyn Medium>>,
+
+    /// Medium on the side the outward normal points to.
+    pub outside: Option<Rc<dyn Medium>>,
+}
+
+impl MediumInterface {
+    /// Create a new `MediumInterface` with the media inside and outside of a surface.
+    pub fn new(inside: Option<Rc<dyn Medium>>, outside: Option<Rc<dyn Medium>>) -> Self {
+        Self { inside, outside }
+    }
+
+    /// Returns the medium a ray leaving the hit point along `direction` travels through.
+    pub fn medium_along(
+        &self,
+        hit_record: &HitRecord,
+        direction: &Vector3,
+    ) -> Option<Rc<dyn Medium>> {
+        let normal = hit_record.normal?;
+        // The normal of the hit record faces the incoming ray, recover the outward one.
+        let outward_normal = if hit_record.front_face {
+            normal
+        } else {
+            normal.neg()
+        };
+
+        if direction.dot(&outward_normal) < 0. {
+            self.inside.clone()
+        } else {
+            self.outside.clone()
+        }
+    }
+}
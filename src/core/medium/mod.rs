@@ -0,0 +1,5 @@
+mod homogeneous_medium;
+mod medium_interface;
+
+pub use homogeneous_medium::*;
+pub use medium_interface::*;
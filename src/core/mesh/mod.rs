@@ -0,0 +1,5 @@
+mod ply;
+mod stl;
+mod triangle_mesh;
+
+pub use triangle_mesh::*;
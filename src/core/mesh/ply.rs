@@ -0,0 +1,361 @@
+use super::TriangleMesh;
+use crate::core::{Color3, Vector2, Vector3};
+use std::{fs, io, path::Path, str::SplitAsciiWhitespace};
+
+/// Encoding of the data after the header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// Type of a property value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    /// Returns the type of a type name, in either the old or the sized spelling.
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::I8),
+            "uchar" | "uint8" => Some(Self::U8),
+            "short" | "int16" => Some(Self::I16),
+            "ushort" | "uint16" => Some(Self::U16),
+            "int" | "int32" => Some(Self::I32),
+            "uint" | "uint32" => Some(Self::U32),
+            "float" | "float32" => Some(Self::F32),
+            "double" | "float64" => Some(Self::F64),
+            _ => None,
+        }
+    }
+
+    /// Returns the size of a value in bytes.
+    fn size(&self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    /// Returns the factor bringing an integer color channel to `[0, 1]`.
+    fn color_scale(&self) -> f64 {
+        match self {
+            Self::U8 => 1. / 255.,
+            Self::U16 => 1. / 65535.,
+            _ => 1.,
+        }
+    }
+}
+
+/// A property of an element, a single value or a list of values preceded by their count.
+#[derive(Debug)]
+struct Property {
+    name: String,
+    value_type: ScalarType,
+    count_type: Option<ScalarType>,
+}
+
+/// An element declared by the header, with the properties of each of its instances.
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+impl Element {
+    /// Returns the position of a property among the properties of the element.
+    fn find(&self, names: &[&str]) -> Option<usize> {
+        self.properties
+            .iter()
+            .position(|property| names.contains(&property.name.as_str()))
+    }
+}
+
+/// Reads the values after the header.
+struct Reader<'a> {
+    format: Format,
+    bytes: &'a [u8],
+    offset: usize,
+    tokens: Option<SplitAsciiWhitespace<'a>>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(format: Format, bytes: &'a [u8]) -> io::Result<Self> {
+        let tokens = if format == Format::Ascii {
+            let text = std::str::from_utf8(bytes).map_err(|_| invalid("invalid ASCII PLY data"))?;
+            Some(text.split_ascii_whitespace())
+        } else {
+            None
+        };
+        Ok(Self {
+            format,
+            bytes,
+            offset: 0,
+            tokens,
+        })
+    }
+
+    /// Reads the next value of a type.
+    fn read(&mut self, value_type: ScalarType) -> io::Result<f64> {
+        if let Some(tokens) = &mut self.tokens {
+            let token = tokens
+                .next()
+                .ok_or_else(|| invalid("unexpected end of file"))?;
+            return token
+                .parse::<f64>()
+                .map_err(|_| invalid("invalid number in PLY data"));
+        }
+
+        let size = value_type.size();
+        if self.offset + size > self.bytes.len() {
+            return Err(invalid("unexpected end of file"));
+        }
+        let mut word = [0u8; 8];
+        word[..size].copy_from_slice(&self.bytes[self.offset..self.offset + size]);
+        if self.format == Format::BinaryBigEndian {
+            word[..size].reverse();
+        }
+        self.offset += size;
+
+        let value = match value_type {
+            ScalarType::I8 => word[0] as i8 as f64,
+            ScalarType::U8 => word[0] as f64,
+            ScalarType::I16 => i16::from_le_bytes([word[0], word[1]]) as f64,
+            ScalarType::U16 => u16::from_le_bytes([word[0], word[1]]) as f64,
+            ScalarType::I32 => i32::from_le_bytes([word[0], word[1], word[2], word[3]]) as f64,
+            ScalarType::U32 => u32::from_le_bytes([word[0], word[1], word[2], word[3]]) as f64,
+            ScalarType::F32 => f32::from_le_bytes([word[0], word[1], word[2], word[3]]) as f64,
+            ScalarType::F64 => f64::from_le_bytes(word),
+        };
+        Ok(value)
+    }
+
+    /// Reads the values of a property, one value unless it is a list.
+    fn read_property(&mut self, property: &Property) -> io::Result<Vec<f64>> {
+        match property.count_type {
+            None => Ok(vec![self.read(property.value_type)?]),
+            Some(count_type) => {
+                let count = self.read(count_type)?;
+                if count < 0. || count.fract() != 0. {
+                    return Err(invalid("invalid list length in PLY data"));
+                }
+                (0..count as usize)
+                    .map(|_| self.read(property.value_type))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Returns an error for malformed data.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// Returns the header and the data after it.
+fn split_header(bytes: &[u8]) -> io::Result<(&str, &[u8])> {
+    let marker = b"end_header";
+    let start = bytes
+        .windows(marker.len())
+        .position(|window| window == marker)
+        .ok_or_else(|| invalid("missing PLY end_header"))?;
+    let mut end = start + marker.len();
+    if bytes.get(end) == Some(&b'\r') {
+        end += 1;
+    }
+    if bytes.get(end) == Some(&b'\n') {
+        end += 1;
+    }
+    let header = std::str::from_utf8(&bytes[..start]).map_err(|_| invalid("invalid PLY header"))?;
+    Ok((header, &bytes[end..]))
+}
+
+/// Returns the format and the elements declared by a header.
+fn parse_header(header: &str) -> io::Result<(Format, Vec<Element>)> {
+    let mut lines = header.lines().map(str::trim);
+    if lines.next() != Some("ply") {
+        return Err(invalid("not a PLY file"));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    for line in lines {
+        let words: Vec<&str> = line.split_ascii_whitespace().collect();
+        match words.as_slice() {
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            ["format", name, _version] => {
+                format = Some(match *name {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::BinaryLittleEndian,
+                    "binary_big_endian" => Format::BinaryBigEndian,
+                    _ => return Err(invalid("unknown PLY format")),
+                });
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| invalid("invalid PLY element count"))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, value_type, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| invalid("PLY property outside of an element"))?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    value_type: ScalarType::parse(value_type)
+                        .ok_or_else(|| invalid("unknown PLY property type"))?,
+                    count_type: Some(
+                        ScalarType::parse(count_type)
+                            .ok_or_else(|| invalid("unknown PLY property type"))?,
+                    ),
+                });
+            }
+            ["property", value_type, name] => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| invalid("PLY property outside of an element"))?;
+                element.properties.push(Property {
+                    name: name.to_string(),
+                    value_type: ScalarType::parse(value_type)
+                        .ok_or_else(|| invalid("unknown PLY property type"))?,
+                    count_type: None,
+                });
+            }
+            _ => return Err(invalid("invalid PLY header line")),
+        }
+    }
+
+    let format = format.ok_or_else(|| invalid("missing PLY format"))?;
+    Ok((format, elements))
+}
+
+impl TriangleMesh {
+    /// Load a `TriangleMesh` from a `.ply` file.
+    pub fn load_ply<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse_ply(&fs::read(path)?)
+    }
+
+    /// Parse a `TriangleMesh` from the content of a `.ply` file, in the ASCII or either binary
+    /// format. Vertices are read with their positions and, if present, their normals (`nx`, `ny`,
+    /// `nz`), uv coordinates (`u`, `v` or `s`, `t`) and colors (`red`, `green`, `blue`). Faces with
+    /// more than three vertices are split into triangles around their first vertex.
+    pub fn parse_ply(bytes: &[u8]) -> io::Result<Self> {
+        let (header, data) = split_header(bytes)?;
+        let (format, elements) = parse_header(header)?;
+        let mut reader = Reader::new(format, data)?;
+
+        let mut positions = Vec::new();
+        let mut normals = Vec::new();
+        let mut uvs = Vec::new();
+        let mut colors = Vec::new();
+        let mut indices = Vec::new();
+        let (mut has_normals, mut has_uvs, mut has_colors) = (false, false, false);
+
+        for element in &elements {
+            match element.name.as_str() {
+                "vertex" => {
+                    let find_all = |names: [&[&str]; 3]| -> Option<[usize; 3]> {
+                        Some([
+                            element.find(names[0])?,
+                            element.find(names[1])?,
+                            element.find(names[2])?,
+                        ])
+                    };
+                    let position = find_all([&["x"], &["y"], &["z"]])
+                        .ok_or_else(|| invalid("PLY vertices without positions"))?;
+                    let normal = find_all([&["nx"], &["ny"], &["nz"]]);
+                    let uv = element
+                        .find(&["u", "s", "texture_u", "texture_s"])
+                        .zip(element.find(&["v", "t", "texture_v", "texture_t"]));
+                    let color = find_all([&["red"], &["green"], &["blue"]]);
+                    (has_normals, has_uvs, has_colors) =
+                        (normal.is_some(), uv.is_some(), color.is_some());
+
+                    for _ in 0..element.count {
+                        let mut values = Vec::with_capacity(element.properties.len());
+                        for property in &element.properties {
+                            let list = reader.read_property(property)?;
+                            values.push(list.first().copied().unwrap_or(0.));
+                        }
+                        let vector = |[x, y, z]: [usize; 3]| {
+                            Vector3::new(values[x] as f32, values[y] as f32, values[z] as f32)
+                        };
+
+                        positions.push(vector(position));
+                        if let Some(normal) = normal {
+                            normals.push(vector(normal));
+                        }
+                        if let Some((u, v)) = uv {
+                            uvs.push(Vector2::new(values[u] as f32, values[v] as f32));
+                        }
+                        if let Some(color) = color {
+                            let scale = element.properties[color[0]].value_type.color_scale();
+                            colors.push(Color3::new(
+                                (values[color[0]] * scale) as f32,
+                                (values[color[1]] * scale) as f32,
+                                (values[color[2]] * scale) as f32,
+                            ));
+                        }
+                    }
+                }
+                "face" => {
+                    let list = element
+                        .find(&["vertex_indices", "vertex_index"])
+                        .ok_or_else(|| invalid("PLY faces without vertex indices"))?;
+                    for _ in 0..element.count {
+                        for (i, property) in element.properties.iter().enumerate() {
+                            let values = reader.read_property(property)?;
+                            if i != list {
+                                continue;
+                            }
+                            if values.iter().any(|&i| i < 0. || i.fract() != 0.) {
+                                return Err(invalid("invalid PLY face vertex index"));
+                            }
+                            let face: Vec<usize> = values.iter().map(|&i| i as usize).collect();
+                            for k in 1..face.len().saturating_sub(1) {
+                                indices.push([face[0], face[k], face[k + 1]]);
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    // Other elements, such as edges, are read past.
+                    for _ in 0..element.count {
+                        for property in &element.properties {
+                            reader.read_property(property)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        if indices
+            .iter()
+            .flatten()
+            .any(|&index| index >= positions.len())
+        {
+            return Err(invalid("PLY face vertex index out of range"));
+        }
+
+        let mut mesh = Self::new(positions, indices);
+        mesh.normals = has_normals.then_some(normals);
+        mesh.uvs = has_uvs.then_some(uvs);
+        mesh.colors = has_colors.then_some(colors);
+        Ok(mesh)
+    }
+}
@@ -0,0 +1,92 @@
+use super::TriangleMesh;
+use crate::core::Point3;
+use std::{fs, io, path::Path};
+
+/// Returns an error for malformed data.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// Returns the vertices of the triangles of a binary STL file.
+fn parse_binary(bytes: &[u8]) -> io::Result<Vec<Point3>> {
+    let float = |offset: usize| {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&bytes[offset..offset + 4]);
+        f32::from_le_bytes(word)
+    };
+
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    if bytes.len() < 84 + 50 * count {
+        return Err(invalid("unexpected end of file"));
+    }
+
+    // Each triangle is a normal, three vertices and two bytes of attributes.
+    let mut vertices = Vec::with_capacity(3 * count);
+    for triangle in 0..count {
+        let offset = 84 + 50 * triangle + 12;
+        for vertex in 0..3 {
+            let offset = offset + 12 * vertex;
+            vertices.push(Point3::new(
+                float(offset),
+                float(offset + 4),
+                float(offset + 8),
+            ));
+        }
+    }
+    Ok(vertices)
+}
+
+/// Returns the vertices of the triangles of an ASCII STL file.
+fn parse_ascii(bytes: &[u8]) -> io::Result<Vec<Point3>> {
+    let text = std::str::from_utf8(bytes).map_err(|_| invalid("invalid ASCII STL data"))?;
+    let mut tokens = text.split_ascii_whitespace();
+    let mut vertices = Vec::new();
+    while let Some(token) = tokens.next() {
+        if token != "vertex" {
+            continue;
+        }
+        let mut coordinate = || -> io::Result<f32> {
+            tokens
+                .next()
+                .ok_or_else(|| invalid("unexpected end of file"))?
+                .parse()
+                .map_err(|_| invalid("invalid number in STL data"))
+        };
+        vertices.push(Point3::new(coordinate()?, coordinate()?, coordinate()?));
+    }
+    if vertices.len() % 3 != 0 {
+        return Err(invalid("STL facet without three vertices"));
+    }
+    Ok(vertices)
+}
+
+impl TriangleMesh {
+    /// Load a `TriangleMesh` from a `.stl` file.
+    pub fn load_stl<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::parse_stl(&fs::read(path)?)
+    }
+
+    /// Parse a `TriangleMesh` from the content of a `.stl` file, in the binary or the ASCII format.
+    /// STL files store each triangle with its own vertices and a face normal, so the mesh is flat
+    /// shaded and its triangles share no vertices.
+    pub fn parse_stl(bytes: &[u8]) -> io::Result<Self> {
+        // Binary files may start with `solid` too, their size tells them apart.
+        let binary_size = (bytes.len() >= 84).then(|| {
+            let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]);
+            84 + 50 * count as usize
+        });
+        let vertices = if binary_size == Some(bytes.len()) || !bytes.starts_with(b"solid") {
+            if binary_size.is_none() {
+                return Err(invalid("not an STL file"));
+            }
+            parse_binary(bytes)?
+        } else {
+            parse_ascii(bytes)?
+        };
+
+        let indices = (0..vertices.len() / 3)
+            .map(|triangle| [3 * triangle, 3 * triangle + 1, 3 * triangle + 2])
+            .collect();
+        Ok(Self::new(vertices, indices))
+    }
+}
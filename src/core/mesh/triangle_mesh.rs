@@ -0,0 +1,88 @@
+use crate::{
+    core::{Color3, HittableList, MeshTriangle, Point3, Vector2, Vector3},
+    traits::Material,
+};
+use std::rc::Rc;
+
+/**
+Triangle Mesh
+
+Vertices and the triangles between them, as loaded from PLY or STL files. Vertices may carry
+normals, which are interpolated across the triangles for smooth shading, uv coordinates and colors,
+read by a `VertexColorTexture`. The triangles of a mesh are hit as `MeshTriangle`s sharing the
+mesh, and are best put into a `BVHNode`.
+
+# Examples
+
+```
+use raytracing::{
+    core::{BVHNode, Color3, LambertianMaterial, Point3, TriangleMesh},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let mesh = TriangleMesh::new(
+    vec![Point3::zero(), Point3::new(1., 0., 0.), Point3::new(0., 1., 0.)],
+    vec![[0, 1, 2]],
+);
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let object = BVHNode::new(&mut mesh.triangles(material));
+# assert_eq!(object.bounding_box().axis(0).max, 1.);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct TriangleMesh {
+    /// Positions of the vertices.
+    pub positions: Vec<Point3>,
+
+    /// Normals of the vertices, if the mesh is smooth.
+    pub normals: Option<Vec<Vector3>>,
+
+    /// uv coordinates of the vertices.
+    pub uvs: Option<Vec<Vector2>>,
+
+    /// Colors of the vertices.
+    pub colors: Option<Vec<Color3>>,
+
+    /// Vertex indices of the triangles, counterclockwise seen from the front.
+    pub indices: Vec<[usize; 3]>,
+}
+
+impl TriangleMesh {
+    /// Create a new `TriangleMesh` from its vertex positions and triangles.
+    pub fn new(positions: Vec<Point3>, indices: Vec<[usize; 3]>) -> Self {
+        assert!(
+            indices
+                .iter()
+                .flatten()
+                .all(|&index| index < positions.len()),
+            "vertex index out of range"
+        );
+        Self {
+            positions,
+            normals: None,
+            uvs: None,
+            colors: None,
+            indices,
+        }
+    }
+
+    /// Returns the number of triangles.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns the triangles of the mesh made of a material.
+    pub fn triangles(self, material: Rc<dyn Material>) -> HittableList {
+        let mesh = Rc::new(self);
+        let mut list = HittableList::new();
+        for index in 0..mesh.triangle_count() {
+            list.add(Rc::new(MeshTriangle::new(
+                Rc::clone(&mesh),
+                index,
+                Rc::clone(&material),
+            )));
+        }
+        list
+    }
+}
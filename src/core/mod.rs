@@ -1,21 +1,31 @@
 mod bounding;
 mod bvh;
 mod camera;
+mod color;
 mod hittable_list;
+mod light;
 mod material;
 mod math;
+mod medium;
+mod mesh;
 mod primitive;
 mod ray;
 mod record;
+mod scene;
 mod texture;
 
 pub use bounding::*;
 pub use bvh::*;
 pub use camera::*;
+pub use color::*;
 pub use hittable_list::*;
+pub use light::*;
 pub use material::*;
 pub use math::*;
+pub use medium::*;
+pub use mesh::*;
 pub use primitive::*;
 pub use ray::*;
 pub use record::*;
+pub use scene::*;
 pub use texture::*;
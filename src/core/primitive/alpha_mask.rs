@@ -0,0 +1,171 @@
+use crate::{
+    core::{AxisAlignedBoundingBox, HitRecord, Interval, LightBounds, Point3, Ray, Vector3},
+    traits::{Hittable, Texture},
+};
+use std::rc::Rc;
+
+/// Which part of the mask texture is read as the opacity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlphaSource {
+    /// The alpha channel of the texture, e.g. the transparency of an `ImageTexture`.
+    AlphaChannel,
+
+    /// The average of the rgb channels of the texture.
+    Color,
+}
+
+/**
+Alpha Mask
+
+A hittable wrapper that cuts holes into another object by an opacity texture evaluated at the hit
+uv. Hits whose opacity is below `threshold` are treated as misses, or when there is no threshold
+the hit is kept with a probability equal to the opacity. Since the test happens inside `hit`,
+scattered rays and shadow rays respect the mask as well.
+*/
+#[derive(Debug)]
+pub struct AlphaMask {
+    /// The masked object.
+    object: Rc<dyn Hittable>,
+
+    /// The opacity texture.
+    mask: Rc<dyn Texture>,
+
+    /// Which part of the mask texture is read as the opacity.
+    pub source: AlphaSource,
+
+    /// Opacity below which hits are discarded, `None` means stochastic transparency.
+    pub threshold: Option<f32>,
+}
+
+impl AlphaMask {
+    /// The most transparent layers a single ray can pass through, bounding the cost of foliage.
+    const MAX_LAYERS: usize = 64;
+
+    /// Create a new `AlphaMask` that reads the alpha channel of the texture with a threshold of 0.5.
+    pub fn new(object: Rc<dyn Hittable>, mask: Rc<dyn Texture>) -> Self {
+        Self {
+            object,
+            mask,
+            source: AlphaSource::AlphaChannel,
+            threshold: Some(0.5),
+        }
+    }
+
+    /// Create a new `AlphaMask` that keeps fractional opacities as stochastic transparency.
+    pub fn new_stochastic(
+        object: Rc<dyn Hittable>,
+        mask: Rc<dyn Texture>,
+        source: AlphaSource,
+    ) -> Self {
+        Self {
+            object,
+            mask,
+            source,
+            threshold: None,
+        }
+    }
+
+    /// Create a new `AlphaMask` with an explicit source and threshold.
+    pub fn new_with_threshold(
+        object: Rc<dyn Hittable>,
+        mask: Rc<dyn Texture>,
+        source: AlphaSource,
+        threshold: f32,
+    ) -> Self {
+        Self {
+            object,
+            mask,
+            source,
+            threshold: Some(threshold),
+        }
+    }
+
+    /// Returns true if the hit survives the opacity test.
+    fn is_opaque(&self, ray: &Ray, record: &HitRecord) -> bool {
+        let (Some(uv), Some(point)) = (record.uv, record.point) else {
+            return true;
+        };
+
+        let alpha = match self.source {
+            AlphaSource::AlphaChannel => self.mask.alpha(&uv, &point),
+            AlphaSource::Color => {
+                let color = self.mask.value(&uv, &point);
+                (color.x + color.y + color.z) / 3.
+            }
+        };
+
+        match self.threshold {
+            Some(threshold) => alpha >= threshold,
+            None if alpha >= 1. => true,
+            None if alpha <= 0. => false,
+            // Use a hash of the ray instead of a random number, so that repeated intersection tests
+            // of the same ray, e.g. by a medium boundary, agree on the result.
+            None => Self::hash_ray(ray, record.t) < alpha,
+        }
+    }
+
+    /// Hash a ray and a hit distance into a float in `[0, 1)`.
+    fn hash_ray(ray: &Ray, t: f32) -> f32 {
+        let mut hash: u32 = 0x811c9dc5;
+        for value in [
+            ray.origin.x,
+            ray.origin.y,
+            ray.origin.z,
+            ray.direction.x,
+            ray.direction.y,
+            ray.direction.z,
+            t,
+        ] {
+            hash ^= value.to_bits();
+            hash = hash.wrapping_mul(0x01000193);
+            hash ^= hash >> 15;
+        }
+        (hash >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+impl Hittable for AlphaMask {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let mut interval = *ray_interval;
+        for _ in 0..Self::MAX_LAYERS {
+            let mut hit_record = HitRecord::new();
+            if !self.object.hit(ray, &interval, &mut hit_record) {
+                return false;
+            }
+
+            if self.is_opaque(ray, &hit_record) {
+                record.clone_from(&hit_record);
+                return true;
+            }
+
+            // Continue the search behind the transparent hit.
+            interval.min = hit_record.t + 0.0001;
+        }
+
+        false
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        self.object.bounding_box()
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        self.object.pdf_value(origin, direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        self.object.random(origin)
+    }
+
+    fn area(&self) -> f32 {
+        self.object.area()
+    }
+
+    fn power(&self) -> f32 {
+        self.object.power()
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        self.object.light_bounds()
+    }
+}
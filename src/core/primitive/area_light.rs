@@ -0,0 +1,168 @@
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, Color3, EmissiveMaterial, HitRecord, Interval, LightBounds, Point3,
+        Ray, SolidColorTexture, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::{f32::consts::PI, rc::Rc};
+
+/// Luminous efficacy of the photometric units, in lumens per watt.
+const LUMENS_PER_WATT: f32 = 683.;
+
+/// How much light an `AreaLight` emits, in physical units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LightEmission {
+    /// Total emitted power in watts, spread over the whole surface.
+    Watts(f32),
+
+    /// Total emitted luminous power in lumens, spread over the whole surface.
+    Lumens(f32),
+
+    /// Luminance of the surface in nits, candela per square meter, independent of its size.
+    Nits(f32),
+}
+
+/**
+Area Light
+
+A hittable wrapper that turns a shape into a diffuse light whose emission is given in physical
+units. Power and lumens are spread over the area of the shape, so resizing the shape keeps the
+total light output the same, while nits keep the surface brightness the same. The material of the
+wrapped shape is replaced by the emission.
+
+# Examples
+
+```
+use raytracing::{
+    core::{AreaLight, Color3, EmissiveMaterial, LightEmission, Point3, Quad, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let quad = Rc::new(Quad::new(
+    Point3::zero(),
+    Vector3::new(2., 0., 0.),
+    Vector3::new(0., 0., 2.),
+    Rc::new(EmissiveMaterial::new_with_color(Color3::zero())),
+));
+let light = AreaLight::new(quad, Color3::one(), LightEmission::Watts(100.), false);
+# assert!((light.power() - 100.).abs() < 1e-3);
+# assert!((light.radiance().x - 100. / (std::f32::consts::PI * 4.)).abs() < 1e-4);
+```
+*/
+#[derive(Debug)]
+pub struct AreaLight {
+    /// The emitting shape.
+    shape: Rc<dyn Hittable>,
+
+    /// The emission replacing the material of the shape.
+    material: Rc<dyn Material>,
+
+    /// Radiance of the surface.
+    radiance: Color3,
+
+    /// Whether both faces of the shape emit light.
+    pub two_sided: bool,
+}
+
+impl AreaLight {
+    /// Create a new `AreaLight` emitting `color` from one or both faces of `shape`, scaled to the
+    /// given emission. Only the chromaticity of `color` matters, its luminance is normalized.
+    /// Emissions in watts or lumens must be spread over a shape of known area, otherwise it panics.
+    pub fn new(
+        shape: Rc<dyn Hittable>,
+        color: Color3,
+        emission: LightEmission,
+        two_sided: bool,
+    ) -> Self {
+        if matches!(emission, LightEmission::Watts(_) | LightEmission::Lumens(_)) {
+            assert!(
+                shape.area() > 0.,
+                "an area light given in watts or lumens needs a shape of known area"
+            );
+        }
+        let faces = if two_sided { 2. } else { 1. };
+        let luminance = match emission {
+            // A diffuse surface emits pi times its radiance per unit area from each face.
+            LightEmission::Watts(watts) => watts / (PI * shape.area() * faces),
+            LightEmission::Lumens(lumens) => lumens / LUMENS_PER_WATT / (PI * shape.area() * faces),
+            LightEmission::Nits(nits) => nits / LUMENS_PER_WATT,
+        };
+
+        let color_luminance = color.luminance();
+        let radiance = if color_luminance > 0. && luminance.is_finite() {
+            &color * (luminance / color_luminance)
+        } else {
+            Color3::zero()
+        };
+
+        let texture = Rc::new(SolidColorTexture::new_with_color(radiance));
+        let material = if two_sided {
+            EmissiveMaterial::new_two_sided(texture)
+        } else {
+            EmissiveMaterial::new(texture)
+        };
+
+        Self {
+            shape,
+            material: Rc::new(material),
+            radiance,
+            two_sided,
+        }
+    }
+
+    /// Returns the radiance of the surface.
+    pub fn radiance(&self) -> Color3 {
+        self.radiance
+    }
+}
+
+impl Hittable for AreaLight {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        if !self.shape.hit(ray, ray_interval, record) {
+            return false;
+        }
+
+        record.material = Some(Rc::clone(&self.material));
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        self.shape.bounding_box()
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        self.shape.pdf_value(origin, direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        self.shape.random(origin)
+    }
+
+    fn area(&self) -> f32 {
+        self.shape.area()
+    }
+
+    fn power(&self) -> f32 {
+        let faces = if self.two_sided { 2. } else { 1. };
+        PI * self.area() * faces * self.radiance.luminance()
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let power = self.power();
+        if power <= 0. {
+            return None;
+        }
+
+        // Keep the orientation of the shape when it is known, its material might not emit.
+        let mut bounds = self.shape.light_bounds().unwrap_or_else(|| {
+            LightBounds::new_omnidirectional(self.bounding_box().clone(), power)
+        });
+        bounds.power = power;
+        if self.two_sided {
+            bounds.two_sided = true;
+        }
+        Some(bounds)
+    }
+}
@@ -67,7 +67,7 @@ pub fn get_cube_box(start: Point3, end: Point3, material: Rc<dyn Material>) -> R
     ))); // back
     cube_box_mut_ref.add(Rc::new(Quad::new(
         Point3::new(min.x, min.y, min.z),
-        dx,
+        dz,
         dy,
         material.clone(),
     ))); // left
@@ -0,0 +1,243 @@
+use super::surface::{
+    area_pdf_value, average_emitted_luminance, azimuth, cap_shading, disk_bounding_box,
+    right_handed_basis,
+};
+use crate::{
+    core::{
+        solve_quadratic, AxisAlignedBoundingBox, HitRecord, Interval, OrthonormalBasis, Point3,
+        Ray, Vector2, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use rand::Rng;
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul},
+    rc::Rc,
+};
+
+/**
+Cone
+
+A circular cone from the center of its base to its apex, open or closed by a cap at the base. On
+the side the u coordinate runs around the axis and v from the base to the apex, on the cap u runs
+around the center and v from the rim to the center. Points are sampled uniformly by area, so cones
+work as area lights.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, Cone, HitRecord, Interval, LambertianMaterial, Point3, Ray, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let cone = Cone::new(Point3::zero(), Point3::new(0., 1., 0.), 1., material);
+
+// Halfway up, the side is half as wide as the base.
+let ray = Ray::new(Point3::new(2., 0.5, 0.), Vector3::new(-1., 0., 0.));
+# let mut record = HitRecord::new();
+# assert!(cone.hit(&ray, &Interval::new(0., 10.), &mut record));
+# assert!((record.t - 1.5).abs() < 1e-5);
+```
+*/
+#[derive(Debug)]
+pub struct Cone {
+    /// Center of the base.
+    pub base: Point3,
+
+    /// Radius of the base.
+    pub radius: f32,
+
+    /// Distance from the base to the apex.
+    height: f32,
+
+    /// Whether the base is closed by a cap.
+    pub capped: bool,
+
+    /// Material of the cone.
+    material: Rc<dyn Material>,
+
+    /// Local frame whose z axis runs from the base to the apex.
+    basis: OrthonormalBasis,
+
+    /// Bounding box of the cone.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl Cone {
+    /// Create a new open `Cone` from the center of its base and its apex.
+    pub fn new(base: Point3, apex: Point3, radius: f32, material: Rc<dyn Material>) -> Self {
+        let axis = &apex - &base;
+        Self {
+            base,
+            radius,
+            height: axis.length(),
+            capped: false,
+            material,
+            basis: right_handed_basis(&axis),
+            bbox: disk_bounding_box(&base, &axis, radius)
+                .merge(&AxisAlignedBoundingBox::new_with_two_points(&apex, &apex).pad()),
+        }
+    }
+
+    /// Create a new `Cone` whose base is closed by a cap.
+    pub fn new_capped(base: Point3, apex: Point3, radius: f32, material: Rc<dyn Material>) -> Self {
+        Self {
+            capped: true,
+            ..Self::new(base, apex, radius, material)
+        }
+    }
+
+    /// Returns the distance from the base to the apex.
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Returns the area of the side.
+    fn side_area(&self) -> f32 {
+        PI * self.radius * f32::sqrt(self.radius * self.radius + self.height * self.height)
+    }
+
+    /// Returns the area of the cap, zero if the cone is open.
+    fn cap_area(&self) -> f32 {
+        if self.capped {
+            PI * self.radius * self.radius
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the outward normal, uv coordinates and tangents at a local point, on the side or
+    /// on the cap.
+    fn shading(&self, point: &Point3, on_cap: bool) -> (Vector3, Vector2, Vector3, Vector3) {
+        if on_cap {
+            return cap_shading(point, self.radius, false);
+        }
+
+        let phi = azimuth(point.x, point.y);
+        let slope = self.radius / self.height;
+        (
+            Vector3::new(point.x, point.y, slope * slope * (self.height - point.z)),
+            Vector2::new(phi / (2. * PI), point.z / self.height),
+            Vector3::new(-point.y, point.x, 0.).mul(2. * PI),
+            Vector3::new(
+                -self.radius * f32::cos(phi),
+                -self.radius * f32::sin(phi),
+                self.height,
+            ),
+        )
+    }
+
+    /// Returns the local point at surface parameters in `[0, 1]²`, on the side or on the cap,
+    /// uniformly distributed by area.
+    fn local_point(&self, u: f32, v: f32, on_cap: bool) -> Point3 {
+        let phi = 2. * PI * u;
+        // The side widens linearly from the apex, like the cap from its center.
+        let s = f32::sqrt(v);
+        let z = if on_cap { 0. } else { self.height * (1. - s) };
+        let rho = self.radius * s;
+        Point3::new(rho * f32::cos(phi), rho * f32::sin(phi), z)
+    }
+
+    /// Returns the average luminance emitted by the outside of the side or of the cap.
+    fn emitted_luminance(&self, on_cap: bool) -> f32 {
+        average_emitted_luminance(self.material.as_ref(), true, |u, v| {
+            let point = self.local_point(u, v, on_cap);
+            let (normal, uv, _, _) = self.shading(&point, on_cap);
+            (
+                self.base.add(&self.basis.local(&point)),
+                self.basis.local(&normal.normolize()),
+                uv,
+            )
+        })
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let origin = self.basis.project(&(&ray.origin - &self.base));
+        let direction = self.basis.project(&ray.direction);
+
+        let mut closest: Option<(f32, bool)> = None;
+        let mut consider = |t: f32, on_cap: bool| {
+            if ray_interval.contains(t) && closest.is_none_or(|(closest_t, _)| t < closest_t) {
+                closest = Some((t, on_cap));
+            }
+        };
+
+        // The side, x² + y² = (r (h - z) / h)² between the base and the apex.
+        let slope_squared = (self.radius / self.height).powi(2);
+        let w = self.height - origin.z;
+        let a = direction.x * direction.x + direction.y * direction.y
+            - slope_squared * direction.z * direction.z;
+        let b = 2.
+            * (origin.x * direction.x + origin.y * direction.y + slope_squared * w * direction.z);
+        let c = origin.x * origin.x + origin.y * origin.y - slope_squared * w * w;
+        for t in solve_quadratic(a as f64, b as f64, c as f64) {
+            let t = t as f32;
+            let z = origin.z + t * direction.z;
+            if (0. ..=self.height).contains(&z) {
+                consider(t, false);
+            }
+        }
+
+        // The cap, the plane of the base within the radius.
+        if self.capped && direction.z != 0. {
+            let t = -origin.z / direction.z;
+            let x = origin.x + t * direction.x;
+            let y = origin.y + t * direction.y;
+            if x * x + y * y <= self.radius * self.radius {
+                consider(t, true);
+            }
+        }
+
+        let Some((t, on_cap)) = closest else {
+            return false;
+        };
+
+        let local_point = origin.add(&direction.mul(t));
+        let (normal, uv, dpdu, dpdv) = self.shading(&local_point, on_cap);
+        record.t = t;
+        record.point = Some(ray.at(t));
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &self.basis.local(&normal));
+        record.uv = Some(uv);
+        record.dpdu = Some(self.basis.local(&dpdu));
+        record.dpdv = Some(self.basis.local(&dpdv));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        area_pdf_value(self, self.area(), origin, direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        let mut rng = rand::thread_rng();
+        // Pick the side or the cap by area, then a point uniformly on it.
+        let on_cap = rng.gen::<f32>() * self.area() >= self.side_area();
+        let point = self.local_point(rng.gen::<f32>(), rng.gen::<f32>(), on_cap);
+        &self.base.add(&self.basis.local(&point)) - origin
+    }
+
+    fn area(&self) -> f32 {
+        self.side_area() + self.cap_area()
+    }
+
+    fn power(&self) -> f32 {
+        let side = self.side_area() * self.emitted_luminance(false);
+        let cap = if self.capped {
+            self.cap_area() * self.emitted_luminance(true)
+        } else {
+            0.
+        };
+        PI * (side + cap)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    core::{AxisAlignedBoundingBox, HitRecord, Interval, Ray},
+    traits::Hittable,
+};
+use std::{ops::Neg, rc::Rc};
+
+/// The boolean operations combining two solids.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CSGOperation {
+    /// Inside of either solid.
+    Union,
+
+    /// Inside of both solids.
+    Intersection,
+
+    /// Inside of the first solid but not of the second.
+    Difference,
+}
+
+impl CSGOperation {
+    /// Returns whether a point is inside of the combined solid, given whether it is inside of each
+    /// of the two solids.
+    fn contains(&self, inside_left: bool, inside_right: bool) -> bool {
+        match self {
+            CSGOperation::Union => inside_left || inside_right,
+            CSGOperation::Intersection => inside_left && inside_right,
+            CSGOperation::Difference => inside_left && !inside_right,
+        }
+    }
+}
+
+/**
+Constructive Solid Geometry
+
+A solid combining two closed hittables by a boolean operation. All hits of both solids along the
+ray are merged in order, and the surfaces where the ray enters or leaves the combined solid are
+kept, each with the material of the solid it belongs to. Surfaces of a subtracted solid face the
+other way, into the hole they cut.
+
+Whether the ray starts inside of a solid is found from its hits along the whole line, so the
+solids must be closed, and `CSGNode`s may be nested.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, CSGOperation, HitRecord, Interval, LambertianMaterial, Point3, Ray, Sphere, Vector3, CSGNode},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let ball = Rc::new(Sphere::new(Point3::zero(), 1., material.clone()));
+let bite = Rc::new(Sphere::new(Point3::new(1., 0., 0.), 0.5, material));
+let apple = CSGNode::new(ball, bite, CSGOperation::Difference);
+
+// The ray enters the ball through the bite, on its surface half a unit from the center.
+let ray = Ray::new(Point3::new(2., 0., 0.), Vector3::new(-1., 0., 0.));
+# let mut record = HitRecord::new();
+# assert!(apple.hit(&ray, &Interval::new(0., f32::INFINITY), &mut record));
+# assert!((record.t - 1.5).abs() < 1e-5);
+# assert!(record.front_face);
+# assert_eq!(apple.hit_all(&ray, &Interval::new(0., f32::INFINITY)).len(), 2);
+```
+*/
+#[derive(Debug)]
+pub struct CSGNode {
+    /// The first solid.
+    left: Rc<dyn Hittable>,
+
+    /// The second solid.
+    right: Rc<dyn Hittable>,
+
+    /// How the solids are combined.
+    pub operation: CSGOperation,
+
+    /// Bounding box of the combined solid.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl CSGNode {
+    pub fn new(left: Rc<dyn Hittable>, right: Rc<dyn Hittable>, operation: CSGOperation) -> Self {
+        let left_bbox = left.bounding_box();
+        let right_bbox = right.bounding_box();
+        let bbox = match operation {
+            CSGOperation::Union => left_bbox.merge(right_bbox),
+            CSGOperation::Intersection => {
+                let overlap = |left: &Interval, right: &Interval| {
+                    Interval::new(f32::max(left.min, right.min), f32::min(left.max, right.max))
+                };
+                AxisAlignedBoundingBox::new(
+                    overlap(&left_bbox.x, &right_bbox.x),
+                    overlap(&left_bbox.y, &right_bbox.y),
+                    overlap(&left_bbox.z, &right_bbox.z),
+                )
+            }
+            CSGOperation::Difference => left_bbox.clone(),
+        };
+
+        Self {
+            left,
+            right,
+            operation,
+            bbox,
+        }
+    }
+
+    /// Create the union of two solids.
+    pub fn new_union(left: Rc<dyn Hittable>, right: Rc<dyn Hittable>) -> Self {
+        Self::new(left, right, CSGOperation::Union)
+    }
+
+    /// Create the intersection of two solids.
+    pub fn new_intersection(left: Rc<dyn Hittable>, right: Rc<dyn Hittable>) -> Self {
+        Self::new(left, right, CSGOperation::Intersection)
+    }
+
+    /// Create the second solid subtracted from the first.
+    pub fn new_difference(left: Rc<dyn Hittable>, right: Rc<dyn Hittable>) -> Self {
+        Self::new(left, right, CSGOperation::Difference)
+    }
+
+    /// Internal method, turn a hit on the surface of the subtracted solid inside out.
+    fn flip(record: &mut HitRecord) {
+        // The normal still faces the ray, only the side it faces changes.
+        record.front_face = !record.front_face;
+        // Keep the tangents consistent with the flipped outward normal.
+        record.dpdv = record.dpdv.map(|dpdv| dpdv.neg());
+    }
+}
+
+impl Hittable for CSGNode {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        if !self.bbox.hit(ray, ray_interval) {
+            return false;
+        }
+        match self.hit_all(ray, ray_interval).into_iter().next() {
+            Some(hit_record) => {
+                *record = hit_record;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn hit_all(&self, ray: &Ray, ray_interval: &Interval) -> Vec<HitRecord> {
+        // Walk the whole line, the ray may start inside of either solid.
+        let left = self.left.hit_all(ray, &Interval::universe());
+        let right = self.right.hit_all(ray, &Interval::universe());
+
+        let mut hits = Vec::new();
+        let (mut inside_left, mut inside_right) = (false, false);
+        let (mut left, mut right) = (left.into_iter().peekable(), right.into_iter().peekable());
+        loop {
+            let is_left = match (left.peek(), right.peek()) {
+                (Some(left_record), Some(right_record)) => left_record.t <= right_record.t,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+
+            let was_inside = self.operation.contains(inside_left, inside_right);
+            // Hits on the outside enter a solid, hits on the inside leave it.
+            let mut record = if is_left {
+                let record = left.next().unwrap();
+                inside_left = record.front_face;
+                record
+            } else {
+                let record = right.next().unwrap();
+                inside_right = record.front_face;
+                record
+            };
+            let is_inside = self.operation.contains(inside_left, inside_right);
+
+            if was_inside != is_inside && ray_interval.contains(record.t) {
+                if !is_left && self.operation == CSGOperation::Difference {
+                    CSGNode::flip(&mut record);
+                }
+                hits.push(record);
+            }
+        }
+        hits
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{
+    core::{AxisAlignedBoundingBox, HitRecord, Interval, OrthonormalBasis, Point3, Ray, Vector2},
+    traits::{Hittable, Material},
+};
+use std::{ops::Mul, rc::Rc};
+
+/// Most subdivisions of a curve when intersecting it.
+const MAX_DEPTH: i32 = 10;
+
+/// How the width of a curve is shaded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveType {
+    /// A flat ribbon always facing the ray, for thin strands seen from afar such as fur or grass.
+    Flat,
+
+    /// A ribbon facing the ray with the normals of a cylinder, so it shades like a round fiber.
+    Cylinder,
+}
+
+/**
+Curve
+
+A cubic Bézier curve swept with a width, for hair, fur and grass. The width is given at each
+control point and blends along the curve like the points do. The curve faces the ray and is shaded
+as a flat ribbon or, with `CurveType::Cylinder`, as a round fiber.
+
+Rays are intersected by subdividing the curve until its pieces are nearly straight, then testing
+the distance of the ray to each piece. The u coordinate runs along the curve, over the part of a
+spline the curve covers, and v across its width from 0 to 1.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, Curve, CurveType, HitRecord, Interval, LambertianMaterial, Point3, Ray, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let points = [
+    Point3::new(-1., 0., 0.),
+    Point3::new(-0.3, 0.5, 0.),
+    Point3::new(0.3, 0.5, 0.),
+    Point3::new(1., 0., 0.),
+];
+let strand = Curve::new(points, [0.1, 0.1, 0.1, 0.1], CurveType::Cylinder, material);
+let ray = Ray::new(Point3::new(0., 0.375, 2.), Vector3::new(0., 0., -1.));
+# let mut record = HitRecord::new();
+# assert!(strand.hit(&ray, &Interval::new(0., f32::INFINITY), &mut record));
+# assert!((record.t - 2.).abs() < 1e-4);
+# assert!((record.uv.unwrap().u() - 0.5).abs() < 1e-3);
+```
+*/
+#[derive(Debug)]
+pub struct Curve {
+    /// Control points of the curve.
+    control_points: [Point3; 4],
+
+    /// Width of the curve at each control point.
+    widths: [f32; 4],
+
+    /// How the width of the curve is shaded.
+    pub curve_type: CurveType,
+
+    /// Range of the u coordinate along the curve.
+    u_range: Interval,
+
+    /// Material of the curve.
+    material: Rc<dyn Material>,
+
+    /// Bounding box of the curve.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl Curve {
+    /// Create a new `Curve` from its control points and the width at each of them.
+    pub fn new(
+        control_points: [Point3; 4],
+        widths: [f32; 4],
+        curve_type: CurveType,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        Self::new_with_u_range(
+            control_points,
+            widths,
+            curve_type,
+            Interval::new(0., 1.),
+            material,
+        )
+    }
+
+    /// Create a new `Curve` covering part of the u coordinates of a spline.
+    pub fn new_with_u_range(
+        control_points: [Point3; 4],
+        widths: [f32; 4],
+        curve_type: CurveType,
+        u_range: Interval,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        let half_width = widths.iter().copied().fold(0., f32::max) / 2.;
+        let bbox = control_points
+            .iter()
+            .map(|point| {
+                AxisAlignedBoundingBox::new_with_two_points(
+                    &(point - &Point3::new(half_width, half_width, half_width)),
+                    &(point + &Point3::new(half_width, half_width, half_width)),
+                )
+            })
+            .reduce(|bbox, point_box| bbox.merge(&point_box))
+            .unwrap();
+
+        Self {
+            control_points,
+            widths,
+            curve_type,
+            u_range,
+            material,
+            bbox,
+        }
+    }
+
+    /// Create the curves of a cubic Bézier spline through `3n + 1` control points, each curve
+    /// sharing its last point with the next one and covering an equal part of the u coordinates.
+    pub fn new_spline(
+        control_points: &[Point3],
+        widths: &[f32],
+        curve_type: CurveType,
+        material: Rc<dyn Material>,
+    ) -> Vec<Self> {
+        assert_eq!(control_points.len(), widths.len(), "width count mismatch");
+        assert!(
+            control_points.len() >= 4 && (control_points.len() - 1) % 3 == 0,
+            "a cubic spline needs 3n + 1 control points"
+        );
+
+        let count = (control_points.len() - 1) / 3;
+        (0..count)
+            .map(|i| {
+                let points = [0, 1, 2, 3].map(|j| control_points[3 * i + j]);
+                let curve_widths = [0, 1, 2, 3].map(|j| widths[3 * i + j]);
+                let u_range = Interval::new(i as f32 / count as f32, (i + 1) as f32 / count as f32);
+                Self::new_with_u_range(
+                    points,
+                    curve_widths,
+                    curve_type,
+                    u_range,
+                    Rc::clone(&material),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the point on the curve at the curve parameter `s` in `[0, 1]`.
+    pub fn point(&self, s: f32) -> Point3 {
+        evaluate_bezier(&self.control_points, s).0
+    }
+
+    /// Returns the width of the curve at the curve parameter `s` in `[0, 1]`.
+    pub fn width(&self, s: f32) -> f32 {
+        let [w0, w1, w2, w3] = self.widths;
+        let t = 1. - s;
+        t * t * t * w0 + 3. * t * t * s * w1 + 3. * t * s * s * w2 + s * s * s * w3
+    }
+
+    /// Internal method, intersects the ray with a piece of the curve given in the frame of the
+    /// ray, whose z axis runs along it, and keeps the closest hit in `closest`.
+    fn intersect(
+        &self,
+        points: &[Point3; 4],
+        s_range: (f32, f32),
+        z_range: (f32, f32),
+        depth: i32,
+        closest: &mut Option<(f32, f32)>,
+    ) {
+        let z_max = closest.map_or(z_range.1, |(z, _)| z);
+
+        // Skip pieces whose bounds, widened by the curve, miss the ray.
+        let half_width = self.widths.iter().copied().fold(0., f32::max) / 2.;
+        let (mut min, mut max) = (points[0], points[0]);
+        for point in &points[1..] {
+            for axis in 0..3 {
+                min[axis] = f32::min(min[axis], point[axis]);
+                max[axis] = f32::max(max[axis], point[axis]);
+            }
+        }
+        if min.x - half_width > 0.
+            || max.x + half_width < 0.
+            || min.y - half_width > 0.
+            || max.y + half_width < 0.
+            || min.z - half_width > z_max
+            || max.z + half_width < z_range.0
+        {
+            return;
+        }
+
+        if depth > 0 {
+            let [p0, p1, p2, p3] = points;
+            let p01 = (p0 + p1).mul(0.5);
+            let p12 = (p1 + p2).mul(0.5);
+            let p23 = (p2 + p3).mul(0.5);
+            let p012 = (&p01 + &p12).mul(0.5);
+            let p123 = (&p12 + &p23).mul(0.5);
+            let middle = (&p012 + &p123).mul(0.5);
+            let s_middle = (s_range.0 + s_range.1) / 2.;
+            self.intersect(
+                &[*p0, p01, p012, middle],
+                (s_range.0, s_middle),
+                z_range,
+                depth - 1,
+                closest,
+            );
+            self.intersect(
+                &[middle, p123, p23, *p3],
+                (s_middle, s_range.1),
+                z_range,
+                depth - 1,
+                closest,
+            );
+            return;
+        }
+
+        // The ray passes the ends of the piece on the inner side of their perpendiculars.
+        let [p0, p1, p2, p3] = points;
+        if (p1.y - p0.y) * -p0.y + p0.x * (p0.x - p1.x) < 0.
+            || (p2.y - p3.y) * -p3.y + p3.x * (p3.x - p2.x) < 0.
+        {
+            return;
+        }
+
+        // Closest point to the ray on the segment between the ends of the piece.
+        let segment = Vector2::new(p3.x - p0.x, p3.y - p0.y);
+        let length_squared = segment.u() * segment.u() + segment.v() * segment.v();
+        if length_squared == 0. {
+            return;
+        }
+        let w = -(p0.x * segment.u() + p0.y * segment.v()) / length_squared;
+        let s = f32::clamp(
+            s_range.0 + w * (s_range.1 - s_range.0),
+            s_range.0,
+            s_range.1,
+        );
+        let width = self.width(s);
+        let (center, _) = evaluate_bezier(points, f32::clamp(w, 0., 1.));
+        if center.x * center.x + center.y * center.y > width * width / 4. {
+            return;
+        }
+        if center.z < z_range.0 || center.z > z_max {
+            return;
+        }
+        *closest = Some((center.z, s));
+    }
+}
+
+/// Returns the point and the derivative of a cubic Bézier curve at `s`.
+fn evaluate_bezier(points: &[Point3; 4], s: f32) -> (Point3, Point3) {
+    let lerp = |a: &Point3, b: &Point3| &a.mul(1. - s) + &b.mul(s);
+    let first = [
+        lerp(&points[0], &points[1]),
+        lerp(&points[1], &points[2]),
+        lerp(&points[2], &points[3]),
+    ];
+    let second = [lerp(&first[0], &first[1]), lerp(&first[1], &first[2])];
+    let derivative = (&second[1] - &second[0]).mul(3.);
+    let derivative = if derivative.length_squared() > 0. {
+        derivative
+    } else {
+        &points[3] - &points[0]
+    };
+    (lerp(&second[0], &second[1]), derivative)
+}
+
+impl Hittable for Curve {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let ray_length = ray.direction.length();
+        if ray_length == 0. {
+            return false;
+        }
+
+        // Work in the frame of the ray, where it runs from the origin along z.
+        let frame = OrthonormalBasis::new_with_w(&ray.direction);
+        let points = self
+            .control_points
+            .map(|point| frame.project(&(&point - &ray.origin)));
+
+        // Subdivide until the pieces are straight to a fraction of the width.
+        let flatness = (0..2)
+            .flat_map(|i| {
+                let second_difference = &(&points[i] - &points[i + 1].mul(2.)) + &points[i + 2];
+                [
+                    second_difference.x,
+                    second_difference.y,
+                    second_difference.z,
+                ]
+            })
+            .fold(0., |flatness: f32, value| flatness.max(value.abs()));
+        let epsilon = self.widths.iter().copied().fold(0., f32::max) * 0.05;
+        let depth = if flatness > 0. && epsilon > 0. {
+            ((std::f32::consts::SQRT_2 * 6. * flatness / (8. * epsilon)).log2() / 2.) as i32
+        } else {
+            0
+        };
+
+        let mut closest = None;
+        self.intersect(
+            &points,
+            (0., 1.),
+            (ray_interval.min * ray_length, ray_interval.max * ray_length),
+            depth.clamp(0, MAX_DEPTH),
+            &mut closest,
+        );
+        let Some((z, s)) = closest else {
+            return false;
+        };
+
+        // The normal faces the ray across the width, bending around the axis for cylinders.
+        let t = z / ray_length;
+        let point = ray.at(t);
+        let (center, derivative) = evaluate_bezier(&self.control_points, s);
+        let tangent = derivative.normolize();
+        let across = tangent.cross(&ray.direction).normolize();
+        let facing = tangent.cross(&across);
+        let width = self.width(s);
+        let v = f32::clamp(0.5 + (&point - &center).dot(&across) / width, 0., 1.);
+        let normal = match self.curve_type {
+            CurveType::Flat => facing,
+            CurveType::Cylinder => {
+                let offset = 2. * v - 1.;
+                &across.mul(offset) + &facing.mul(f32::sqrt(f32::max(0., 1. - offset * offset)))
+            }
+        };
+
+        record.t = t;
+        record.point = Some(point);
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &normal);
+        record.uv = Some(Vector2::new(self.u_range.min + s * self.u_range.size(), v));
+        record.dpdu = Some(derivative.mul(1. / self.u_range.size()));
+        record.dpdv = Some(normal.cross(&tangent).mul(width));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+}
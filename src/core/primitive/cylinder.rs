@@ -0,0 +1,256 @@
+use super::surface::{
+    area_pdf_value, average_emitted_luminance, azimuth, cap_shading, disk_bounding_box,
+    right_handed_basis,
+};
+use crate::{
+    core::{
+        solve_quadratic, AxisAlignedBoundingBox, HitRecord, Interval, OrthonormalBasis, Point3,
+        Ray, Vector2, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use rand::Rng;
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul},
+    rc::Rc,
+};
+
+/// The parts of the surface of a cylinder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CylinderPart {
+    Side,
+    Bottom,
+    Top,
+}
+
+/**
+Cylinder
+
+A circular cylinder between the centers of its two ends, open or closed by caps. On the side the u
+coordinate runs around the axis and v from the start to the end, on the caps u runs around the
+center and v from the rim to the center. Points are sampled uniformly by area, so cylinders work
+as area lights.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, Cylinder, HitRecord, Interval, LambertianMaterial, Point3, Ray, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let pipe = Cylinder::new(Point3::zero(), Point3::new(0., 2., 0.), 0.5, material.clone());
+let can = Cylinder::new_capped(Point3::zero(), Point3::new(0., 2., 0.), 0.5, material);
+
+// Looking down the axis, only the can has a lid.
+let ray = Ray::new(Point3::new(0., 3., 0.), Vector3::new(0., -1., 0.));
+# let mut record = HitRecord::new();
+# assert!(!pipe.hit(&ray, &Interval::new(0., 10.), &mut record));
+# assert!(can.hit(&ray, &Interval::new(0., 10.), &mut record));
+# assert!((record.t - 1.).abs() < 1e-6);
+```
+*/
+#[derive(Debug)]
+pub struct Cylinder {
+    /// Center of the start of the cylinder.
+    pub start: Point3,
+
+    /// Radius of the cylinder.
+    pub radius: f32,
+
+    /// Distance between the two ends.
+    height: f32,
+
+    /// Whether both ends are closed by caps.
+    pub capped: bool,
+
+    /// Material of the cylinder.
+    material: Rc<dyn Material>,
+
+    /// Local frame whose z axis runs from the start to the end.
+    basis: OrthonormalBasis,
+
+    /// Bounding box of the cylinder.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl Cylinder {
+    /// Create a new open `Cylinder` from the centers of its two ends.
+    pub fn new(start: Point3, end: Point3, radius: f32, material: Rc<dyn Material>) -> Self {
+        let axis = &end - &start;
+        Self {
+            start,
+            radius,
+            height: axis.length(),
+            capped: false,
+            material,
+            basis: right_handed_basis(&axis),
+            bbox: disk_bounding_box(&start, &axis, radius)
+                .merge(&disk_bounding_box(&end, &axis, radius)),
+        }
+    }
+
+    /// Create a new `Cylinder` closed by caps at both ends.
+    pub fn new_capped(start: Point3, end: Point3, radius: f32, material: Rc<dyn Material>) -> Self {
+        Self {
+            capped: true,
+            ..Self::new(start, end, radius, material)
+        }
+    }
+
+    /// Returns the distance between the two ends.
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Returns the area of the side.
+    fn side_area(&self) -> f32 {
+        2. * PI * self.radius * self.height
+    }
+
+    /// Returns the area of one cap, zero if the cylinder is open.
+    fn cap_area(&self) -> f32 {
+        if self.capped {
+            PI * self.radius * self.radius
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the outward normal, uv coordinates and tangents at a local point on a part.
+    fn shading(&self, point: &Point3, part: CylinderPart) -> (Vector3, Vector2, Vector3, Vector3) {
+        match part {
+            CylinderPart::Side => (
+                Vector3::new(point.x, point.y, 0.).mul(1. / self.radius),
+                Vector2::new(azimuth(point.x, point.y) / (2. * PI), point.z / self.height),
+                Vector3::new(-point.y, point.x, 0.).mul(2. * PI),
+                Vector3::new(0., 0., self.height),
+            ),
+            CylinderPart::Bottom => cap_shading(point, self.radius, false),
+            CylinderPart::Top => cap_shading(point, self.radius, true),
+        }
+    }
+
+    /// Returns the local point at surface parameters in `[0, 1]²` on a part.
+    fn local_point(&self, u: f32, v: f32, part: CylinderPart) -> Point3 {
+        let phi = 2. * PI * u;
+        let (rho, z) = match part {
+            CylinderPart::Side => (self.radius, v * self.height),
+            // Uniform by area over the cap.
+            CylinderPart::Bottom => (self.radius * f32::sqrt(v), 0.),
+            CylinderPart::Top => (self.radius * f32::sqrt(v), self.height),
+        };
+        Point3::new(rho * f32::cos(phi), rho * f32::sin(phi), z)
+    }
+
+    /// Returns the average luminance emitted by the outside of a part.
+    fn emitted_luminance(&self, part: CylinderPart) -> f32 {
+        average_emitted_luminance(self.material.as_ref(), true, |u, v| {
+            let point = self.local_point(u, v, part);
+            let (normal, uv, _, _) = self.shading(&point, part);
+            (
+                self.start.add(&self.basis.local(&point)),
+                self.basis.local(&normal),
+                uv,
+            )
+        })
+    }
+}
+
+impl Hittable for Cylinder {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let origin = self.basis.project(&(&ray.origin - &self.start));
+        let direction = self.basis.project(&ray.direction);
+
+        let mut closest: Option<(f32, CylinderPart)> = None;
+        let mut consider = |t: f32, part: CylinderPart| {
+            if ray_interval.contains(t) && closest.is_none_or(|(closest_t, _)| t < closest_t) {
+                closest = Some((t, part));
+            }
+        };
+
+        // The side, x² + y² = r² between the ends.
+        let a = direction.x * direction.x + direction.y * direction.y;
+        let b = 2. * (origin.x * direction.x + origin.y * direction.y);
+        let c = origin.x * origin.x + origin.y * origin.y - self.radius * self.radius;
+        if a > 0. {
+            for t in solve_quadratic(a as f64, b as f64, c as f64) {
+                let t = t as f32;
+                let z = origin.z + t * direction.z;
+                if (0. ..=self.height).contains(&z) {
+                    consider(t, CylinderPart::Side);
+                }
+            }
+        }
+
+        // The caps, the planes of the ends within the radius.
+        if self.capped && direction.z != 0. {
+            for (z, part) in [(0., CylinderPart::Bottom), (self.height, CylinderPart::Top)] {
+                let t = (z - origin.z) / direction.z;
+                let x = origin.x + t * direction.x;
+                let y = origin.y + t * direction.y;
+                if x * x + y * y <= self.radius * self.radius {
+                    consider(t, part);
+                }
+            }
+        }
+
+        let Some((t, part)) = closest else {
+            return false;
+        };
+
+        let local_point = origin.add(&direction.mul(t));
+        let (normal, uv, dpdu, dpdv) = self.shading(&local_point, part);
+        record.t = t;
+        record.point = Some(ray.at(t));
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &self.basis.local(&normal));
+        record.uv = Some(uv);
+        record.dpdu = Some(self.basis.local(&dpdu));
+        record.dpdv = Some(self.basis.local(&dpdv));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        area_pdf_value(self, self.area(), origin, direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        let mut rng = rand::thread_rng();
+        // Pick a part by its area, then a point uniformly on it.
+        let r = rng.gen::<f32>() * self.area();
+        let part = if r < self.side_area() {
+            CylinderPart::Side
+        } else if r < self.side_area() + self.cap_area() {
+            CylinderPart::Bottom
+        } else {
+            CylinderPart::Top
+        };
+        let point = self.local_point(rng.gen::<f32>(), rng.gen::<f32>(), part);
+        &self.start.add(&self.basis.local(&point)) - origin
+    }
+
+    fn area(&self) -> f32 {
+        self.side_area() + 2. * self.cap_area()
+    }
+
+    fn power(&self) -> f32 {
+        let side = self.side_area() * self.emitted_luminance(CylinderPart::Side);
+        let caps = if self.capped {
+            self.cap_area()
+                * (self.emitted_luminance(CylinderPart::Bottom)
+                    + self.emitted_luminance(CylinderPart::Top))
+        } else {
+            0.
+        };
+        PI * (side + caps)
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, Color3, DensityGrid, HenyeyGreensteinMaterial, HitRecord, Interval,
+        MediumRecord, Point3, Ray, SolidColorTexture, Vector2, Vector3,
+    },
+    traits::{Hittable, Material, Medium, Texture},
+};
+use std::rc::Rc;
+
+/// Cells of the majorant grid along each axis, at most.
+const MAJORANT_RESOLUTION: usize = 16;
+
+/// A coarse grid bounding the extinction coefficient within each of its cells.
+#[derive(Debug)]
+struct MajorantGrid {
+    /// Resolution of the grid along x, y and z.
+    resolution: [usize; 3],
+
+    /// Largest extinction coefficient within each cell, x varying fastest.
+    values: Vec<f32>,
+}
+
+impl MajorantGrid {
+    /// Create a new `MajorantGrid` bounding a density grid scaled by the extinction coefficient.
+    fn new(density: &DensityGrid, sigma_t: f32) -> Self {
+        let resolution = density
+            .resolution()
+            .map(|resolution| resolution.clamp(1, MAJORANT_RESOLUTION));
+
+        let mut values = Vec::with_capacity(resolution.iter().product());
+        for z in 0..resolution[2] {
+            for y in 0..resolution[1] {
+                for x in 0..resolution[0] {
+                    let cell = [x, y, z];
+                    let bound = |offset: usize| {
+                        let corner = |i: usize| (cell[i] + offset) as f32 / resolution[i] as f32;
+                        Point3::new(corner(0), corner(1), corner(2))
+                    };
+                    values.push(sigma_t * density.max_value(&bound(0), &bound(1), 0).max(0.));
+                }
+            }
+        }
+
+        Self { resolution, values }
+    }
+
+    /// Returns the majorant of a cell.
+    fn value(&self, cell: [usize; 3]) -> f32 {
+        self.values[(cell[2] * self.resolution[1] + cell[1]) * self.resolution[0] + cell[0]]
+    }
+}
+
+/// A texture scaling another one, turning the emitted radiance of the medium into the light added
+/// at each collision.
+#[derive(Debug)]
+struct ScaledTexture {
+    texture: Rc<dyn Texture>,
+    scale: f32,
+}
+
+impl Texture for ScaledTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        &self.texture.value(uv, point) * self.scale
+    }
+}
+
+/**
+Grid Medium
+
+A heterogeneous participating medium, such as smoke or clouds, whose density is read from a
+`DensityGrid` and scaled by the absorption and scattering coefficients. Collisions are found by
+delta tracking against a coarse grid of majorants, so thin and empty regions are crossed in few
+steps. Light scatters by a Henyey-Greenstein phase function, and the absorbing part of the medium
+may emit light, e.g. by a `GridTexture` of temperatures.
+
+The medium fills the box of the density grid. As a hittable it can be placed in the scene with
+instances, and as a `Medium` it can fill a `MediumBoundary`, in the space of the scene.
+*/
+#[derive(Debug)]
+pub struct GridMedium {
+    /// Density of the medium.
+    density: Rc<DensityGrid>,
+
+    /// Absorption coefficient per unit density.
+    pub sigma_a: f32,
+
+    /// Scattering coefficient per unit density.
+    pub sigma_s: f32,
+
+    /// Bounds of the extinction coefficient.
+    majorants: MajorantGrid,
+
+    /// The phase function, emitting light at collisions.
+    phase_function: Rc<dyn Material>,
+}
+
+impl GridMedium {
+    /// Create a new `GridMedium` scattering light tinted by `color` with anisotropy `g`.
+    pub fn new(
+        density: Rc<DensityGrid>,
+        sigma_a: f32,
+        sigma_s: f32,
+        color: Color3,
+        g: f32,
+    ) -> Self {
+        Self::new_with_phase_function(density, sigma_a, sigma_s, color, g, None)
+    }
+
+    /// Create a new `GridMedium` whose absorbing part emits the radiance of `emission`.
+    pub fn new_with_emission(
+        density: Rc<DensityGrid>,
+        sigma_a: f32,
+        sigma_s: f32,
+        color: Color3,
+        g: f32,
+        emission: Rc<dyn Texture>,
+    ) -> Self {
+        Self::new_with_phase_function(density, sigma_a, sigma_s, color, g, Some(emission))
+    }
+
+    /// Internal method, create a new `GridMedium` and its phase function.
+    fn new_with_phase_function(
+        density: Rc<DensityGrid>,
+        sigma_a: f32,
+        sigma_s: f32,
+        color: Color3,
+        g: f32,
+        emission: Option<Rc<dyn Texture>>,
+    ) -> Self {
+        let sigma_t = sigma_a + sigma_s;
+        let (scattering, absorption) = if sigma_t > 0. {
+            (sigma_s / sigma_t, sigma_a / sigma_t)
+        } else {
+            (0., 0.)
+        };
+
+        // Collisions happen in proportion to the extinction, weight them by the chance of
+        // scattering or being absorbed and emitting.
+        let albedo = Rc::new(SolidColorTexture::new_with_color(&color * scattering));
+        let phase_function = match emission {
+            Some(texture) => HenyeyGreensteinMaterial::new_with_emission(
+                albedo,
+                g,
+                Rc::new(ScaledTexture {
+                    texture,
+                    scale: absorption,
+                }),
+            ),
+            None => HenyeyGreensteinMaterial::new(albedo, g),
+        };
+
+        Self {
+            majorants: MajorantGrid::new(&density, sigma_t),
+            density,
+            sigma_a,
+            sigma_s,
+            phase_function: Rc::new(phase_function),
+        }
+    }
+
+    /// Returns the extinction coefficient at a point.
+    pub fn sigma_t(&self, point: &Point3) -> f32 {
+        (self.sigma_a + self.sigma_s) * self.density.value(point, 0)
+    }
+
+    /// Returns the fraction of light passing through the medium along the ray within the interval,
+    /// estimated by ratio tracking.
+    pub fn transmittance(&self, ray: &Ray, ray_interval: &Interval) -> f32 {
+        let mut transmittance = 1.;
+        self.track(ray, ray_interval, |t, majorant| {
+            transmittance *= 1. - self.sigma_t(&ray.at(t)) / majorant;
+            false
+        });
+        f32::max(transmittance, 0.)
+    }
+
+    /// Internal method, walk the majorant grid along the ray and sample tentative collisions by
+    /// the majorant of each cell. `collide` decides whether a tentative collision is real, and the
+    /// ray parameter of the first real collision is returned.
+    fn track<F: FnMut(f32, f32) -> bool>(
+        &self,
+        ray: &Ray,
+        ray_interval: &Interval,
+        mut collide: F,
+    ) -> Option<f32> {
+        let bbox = self.density.bounding_box();
+        let size = [bbox.x.size(), bbox.y.size(), bbox.z.size()];
+        if size.iter().any(|&size| size <= 0.) {
+            return None;
+        }
+
+        // Walk in the coordinates of the grid, the box becomes [0, 1]³.
+        let origin = self.density.to_grid(&ray.origin);
+        let direction = [0, 1, 2].map(|i| ray.direction[i] / size[i]);
+        let (mut t_min, mut t_max) = (ray_interval.min, ray_interval.max);
+        for i in 0..3 {
+            if direction[i] == 0. {
+                if origin[i] < 0. || origin[i] > 1. {
+                    return None;
+                }
+                continue;
+            }
+            let t0 = -origin[i] / direction[i];
+            let t1 = (1. - origin[i]) / direction[i];
+            t_min = f32::max(t_min, f32::min(t0, t1));
+            t_max = f32::min(t_max, f32::max(t0, t1));
+        }
+        if t_min >= t_max {
+            return None;
+        }
+
+        let resolution = self.majorants.resolution;
+        let mut cell = [0usize; 3];
+        let mut step = [0isize; 3];
+        let mut next_t = [f32::INFINITY; 3];
+        let mut delta_t = [f32::INFINITY; 3];
+        for i in 0..3 {
+            let position = origin[i] + direction[i] * t_min;
+            cell[i] = ((position * resolution[i] as f32) as isize)
+                .clamp(0, resolution[i] as isize - 1) as usize;
+            if direction[i] != 0. {
+                step[i] = if direction[i] > 0. { 1 } else { -1 };
+                let boundary = (cell[i] as isize + (step[i] + 1) / 2) as f32 / resolution[i] as f32;
+                next_t[i] = t_min + (boundary - position) / direction[i];
+                delta_t[i] = 1. / (resolution[i] as f32 * direction[i].abs());
+            }
+        }
+
+        let ray_length = ray.direction.length();
+        let mut t = t_min;
+        loop {
+            let axis = if next_t[0] <= next_t[1] && next_t[0] <= next_t[2] {
+                0
+            } else if next_t[1] <= next_t[2] {
+                1
+            } else {
+                2
+            };
+            let cell_end = f32::min(next_t[axis], t_max);
+
+            let majorant = self.majorants.value(cell);
+            if majorant > 0. {
+                loop {
+                    t -= f32::ln(1. - rand::random::<f32>()) / (majorant * ray_length);
+                    if t >= cell_end {
+                        break;
+                    }
+                    if collide(t, majorant) {
+                        return Some(t);
+                    }
+                }
+            }
+
+            // Free flight is memoryless, so sampling restarts at the cell boundary.
+            if cell_end >= t_max {
+                return None;
+            }
+            t = cell_end;
+            let next_cell = cell[axis] as isize + step[axis];
+            if next_cell < 0 || next_cell >= resolution[axis] as isize {
+                return None;
+            }
+            cell[axis] = next_cell as usize;
+            next_t[axis] += delta_t[axis];
+        }
+    }
+}
+
+impl Hittable for GridMedium {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        // Delta tracking, a tentative collision is real in proportion to the local extinction.
+        let Some(t) = self.track(ray, ray_interval, |t, majorant| {
+            rand::random::<f32>() * majorant < self.sigma_t(&ray.at(t))
+        }) else {
+            return false;
+        };
+
+        record.t = t;
+        record.point = Some(ray.at(t));
+        record.normal = Some(Vector3::new(1., 0., 0.)); // arbitrary
+        record.front_face = true;
+        record.material = Some(Rc::clone(&self.phase_function));
+        record.uv = Some(Vector2::zero());
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        self.density.bounding_box()
+    }
+}
+
+impl Medium for GridMedium {
+    fn sample(&self, ray: &Ray, ray_interval: &Interval) -> MediumRecord {
+        // The phase function carries the albedo, and delta tracking needs no other weight.
+        let t = self.track(ray, ray_interval, |t, majorant| {
+            rand::random::<f32>() * majorant < self.sigma_t(&ray.at(t))
+        });
+        MediumRecord {
+            t,
+            weight: Color3::one(),
+        }
+    }
+
+    fn transmittance(&self, ray: &Ray, ray_interval: &Interval) -> Color3 {
+        let transmittance = GridMedium::transmittance(self, ray, ray_interval);
+        Color3::new(transmittance, transmittance, transmittance)
+    }
+
+    fn phase_function(&self) -> Rc<dyn Material> {
+        self.phase_function.clone()
+    }
+}
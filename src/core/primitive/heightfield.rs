@@ -0,0 +1,342 @@
+use crate::{
+    core::{AxisAlignedBoundingBox, HitRecord, Interval, Point3, Ray, Vector2, Vector3},
+    traits::{Hittable, Material},
+};
+use image::{
+    error::{ParameterError, ParameterErrorKind},
+    ImageError, ImageResult,
+};
+use std::{ops::Mul, rc::Rc};
+
+/**
+Heightfield
+
+A terrain surface given by a grid of heights over a box, such as a landscape from a grayscale
+image. Heights are fractions of the box height, from zero at its bottom to one at its top, and the
+samples spread evenly from the minimum to the maximum x and z of the box, row by row along z.
+
+Rays walk the grid cell by cell, only cells whose heights reach the part of the ray above them are
+split into two triangles and intersected, so large fields cost no more memory than their heights.
+Normals are interpolated between the samples, the u coordinate runs along x and v against z, so an
+`ImageTexture` of the same image lines up with the heights.
+
+# Examples
+
+```
+use raytracing::{
+    core::{
+        AxisAlignedBoundingBox, Color3, Heightfield, HitRecord, Interval, LambertianMaterial,
+        Point3, Ray, Vector3,
+    },
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let bbox = AxisAlignedBoundingBox::new_with_two_points(
+    &Point3::new(-1., 0., -1.),
+    &Point3::new(1., 1., 1.),
+);
+// A ramp rising along x.
+let heights = vec![0., 0.5, 1., 0., 0.5, 1.];
+let ramp = Heightfield::new(heights, 3, 2, bbox, material);
+let ray = Ray::new(Point3::new(0.5, 5., 0.), Vector3::new(0., -1., 0.));
+# let mut record = HitRecord::new();
+# assert!(ramp.hit(&ray, &Interval::new(0., f32::INFINITY), &mut record));
+# assert!((record.t - 4.25).abs() < 1e-5);
+# assert!((record.uv.unwrap().u() - 0.75).abs() < 1e-5);
+```
+*/
+#[derive(Debug)]
+pub struct Heightfield {
+    /// Height samples as fractions of the box height, x varying fastest.
+    heights: Vec<f32>,
+
+    /// Sample count along x.
+    width: usize,
+
+    /// Sample count along z.
+    depth: usize,
+
+    /// Normals at the samples, x varying fastest.
+    normals: Vec<Vector3>,
+
+    /// The box the samples spread over.
+    extent: AxisAlignedBoundingBox,
+
+    /// Material of the surface.
+    material: Rc<dyn Material>,
+
+    /// Bounding box of the surface, padded when the field is flat.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl Heightfield {
+    /// Create a new `Heightfield` from its samples, row by row along z, and the box they fill.
+    pub fn new(
+        heights: Vec<f32>,
+        width: usize,
+        depth: usize,
+        bbox: AxisAlignedBoundingBox,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        assert_eq!(heights.len(), width * depth, "sample count mismatch");
+        assert!(
+            width >= 2 && depth >= 2,
+            "a heightfield needs two samples per axis"
+        );
+
+        let mut heightfield = Self {
+            heights,
+            width,
+            depth,
+            normals: Vec::new(),
+            bbox: bbox.pad(),
+            extent: bbox,
+            material,
+        };
+        heightfield.normals = (0..depth)
+            .flat_map(|j| (0..width).map(move |i| (i, j)))
+            .map(|(i, j)| heightfield.sample_normal(i, j))
+            .collect();
+        heightfield
+    }
+
+    /// Create a new `Heightfield` from the luminance of a grayscale image at the given path, the
+    /// top row of the image at the minimum z of the box.
+    pub fn new_from_image(
+        path: String,
+        bbox: AxisAlignedBoundingBox,
+        material: Rc<dyn Material>,
+    ) -> ImageResult<Self> {
+        let image = image::io::Reader::open(path)?.decode()?.to_luma32f();
+        let (width, depth) = (image.width() as usize, image.height() as usize);
+        if width < 2 || depth < 2 {
+            return Err(ImageError::Parameter(ParameterError::from_kind(
+                ParameterErrorKind::Generic("a heightfield needs two samples per axis".to_owned()),
+            )));
+        }
+        let heights = image.pixels().map(|pixel| pixel[0]).collect();
+        ImageResult::Ok(Self::new(heights, width, depth, bbox, material))
+    }
+
+    /// Returns the sample count along x and z.
+    pub fn resolution(&self) -> (usize, usize) {
+        (self.width, self.depth)
+    }
+
+    /// Returns the world space height of a sample.
+    pub fn height(&self, i: usize, j: usize) -> f32 {
+        let y = self.extent.axis(1);
+        y.min + self.heights[j * self.width + i] * y.size()
+    }
+
+    /// Returns the size of a cell along x and z.
+    fn cell_size(&self) -> (f32, f32) {
+        (
+            self.extent.axis(0).size() / (self.width - 1) as f32,
+            self.extent.axis(2).size() / (self.depth - 1) as f32,
+        )
+    }
+
+    /// Returns the world space position of a sample.
+    fn sample_point(&self, i: usize, j: usize) -> Point3 {
+        let (cell_x, cell_z) = self.cell_size();
+        Point3::new(
+            self.extent.axis(0).min + i as f32 * cell_x,
+            self.height(i, j),
+            self.extent.axis(2).min + j as f32 * cell_z,
+        )
+    }
+
+    /// Internal method, returns the normal at a sample from the slopes to its neighbors.
+    fn sample_normal(&self, i: usize, j: usize) -> Vector3 {
+        let (cell_x, cell_z) = self.cell_size();
+        let (i0, i1) = (i.saturating_sub(1), usize::min(i + 1, self.width - 1));
+        let (j0, j1) = (j.saturating_sub(1), usize::min(j + 1, self.depth - 1));
+        let slope_x = (self.height(i1, j) - self.height(i0, j)) / ((i1 - i0) as f32 * cell_x);
+        let slope_z = (self.height(i, j1) - self.height(i, j0)) / ((j1 - j0) as f32 * cell_z);
+        Vector3::new(-slope_x, 1., -slope_z).normolize()
+    }
+
+    /// Internal method, returns the lowest and highest world space heights of a cell.
+    fn cell_heights(&self, i: usize, j: usize) -> Interval {
+        let corners = [
+            self.height(i, j),
+            self.height(i + 1, j),
+            self.height(i, j + 1),
+            self.height(i + 1, j + 1),
+        ];
+        Interval::new(
+            corners.iter().copied().fold(f32::INFINITY, f32::min),
+            corners.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+        )
+    }
+
+    /// Internal method, returns the part of the ray interval within the bounding box.
+    fn clip(&self, ray: &Ray, ray_interval: &Interval) -> Option<(f32, f32)> {
+        let (mut t_min, mut t_max) = (ray_interval.min, ray_interval.max);
+        for axis in 0..3 {
+            let inverse = 1. / ray.direction[axis];
+            let mut t0 = (self.bbox.axis(axis).min - ray.origin[axis]) * inverse;
+            let mut t1 = (self.bbox.axis(axis).max - ray.origin[axis]) * inverse;
+            if inverse < 0. {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = f32::max(t_min, t0);
+            t_max = f32::min(t_max, t1);
+            if t_max < t_min {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+
+    /// Internal method, intersects the two triangles of a cell and fills in the closest hit.
+    fn hit_cell(
+        &self,
+        i: usize,
+        j: usize,
+        ray: &Ray,
+        ray_interval: &Interval,
+        record: &mut HitRecord,
+    ) -> bool {
+        let corners = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)];
+        let mut closest = ray_interval.max;
+        let mut found = None;
+        // The cell splits along the diagonal from its first to its third corner.
+        for triangle in [[0, 1, 2], [0, 2, 3]] {
+            let [a, b, c] = triangle.map(|corner| corners[corner]);
+            let (pa, pb, pc) = (
+                self.sample_point(a.0, a.1),
+                self.sample_point(b.0, b.1),
+                self.sample_point(c.0, c.1),
+            );
+            let edge1 = &pb - &pa;
+            let edge2 = &pc - &pa;
+            let p = ray.direction.cross(&edge2);
+            let determinant = edge1.dot(&p);
+            if determinant.abs() < 1e-12 {
+                continue;
+            }
+            let inverse = 1. / determinant;
+            let s = &ray.origin - &pa;
+            let beta = s.dot(&p) * inverse;
+            let q = s.cross(&edge1);
+            let gamma = ray.direction.dot(&q) * inverse;
+            if beta < 0. || gamma < 0. || beta + gamma > 1. {
+                continue;
+            }
+            let t = edge2.dot(&q) * inverse;
+            if t <= ray_interval.min || t >= closest {
+                continue;
+            }
+            closest = t;
+            found = Some((a, b, c, beta, gamma));
+        }
+
+        let Some((a, b, c, beta, gamma)) = found else {
+            return false;
+        };
+        let normal_at = |(i, j): (usize, usize)| &self.normals[j * self.width + i];
+        let normal = &(&normal_at(a).mul(1. - beta - gamma) + &normal_at(b).mul(beta))
+            + &normal_at(c).mul(gamma);
+
+        let point = ray.at(closest);
+        let (size_x, size_z) = (self.extent.axis(0).size(), self.extent.axis(2).size());
+        record.t = closest;
+        record.point = Some(point);
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &normal);
+        record.uv = Some(Vector2::new(
+            (point.x - self.extent.axis(0).min) / size_x,
+            1. - (point.z - self.extent.axis(2).min) / size_z,
+        ));
+        // Tangents of the smooth surface along u (+x) and v (-z).
+        record.dpdu = Some(Vector3::new(size_x, -normal.x / normal.y * size_x, 0.));
+        record.dpdv = Some(Vector3::new(0., normal.z / normal.y * size_z, -size_z));
+        true
+    }
+}
+
+impl Hittable for Heightfield {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let Some((t_min, t_max)) = self.clip(ray, ray_interval) else {
+            return false;
+        };
+
+        // Walk the cells under the ray in order with a 2D DDA over x and z.
+        let (cell_x, cell_z) = self.cell_size();
+        let (cells_x, cells_z) = (self.width - 1, self.depth - 1);
+        let start = ray.at(t_min);
+        let cell_of = |offset: f32, size: f32, count: usize| {
+            ((offset / size).floor().max(0.) as usize).min(count - 1)
+        };
+        let mut i = cell_of(start.x - self.extent.axis(0).min, cell_x, cells_x);
+        let mut j = cell_of(start.z - self.extent.axis(2).min, cell_z, cells_z);
+
+        // Parameters of the next cell boundaries crossed, and of the steps between them.
+        let step = |direction: f32, origin: f32, min: f32, size: f32, cell: usize| {
+            if direction > 0. {
+                let boundary = min + (cell + 1) as f32 * size;
+                ((boundary - origin) / direction, size / direction, 1)
+            } else if direction < 0. {
+                let boundary = min + cell as f32 * size;
+                ((boundary - origin) / direction, -size / direction, -1)
+            } else {
+                (f32::INFINITY, f32::INFINITY, 0)
+            }
+        };
+        let (mut next_x, delta_x, step_x) = step(
+            ray.direction.x,
+            ray.origin.x,
+            self.extent.axis(0).min,
+            cell_x,
+            i,
+        );
+        let (mut next_z, delta_z, step_z) = step(
+            ray.direction.z,
+            ray.origin.z,
+            self.extent.axis(2).min,
+            cell_z,
+            j,
+        );
+
+        let mut t_enter = t_min;
+        loop {
+            let t_exit = f32::min(f32::min(next_x, next_z), t_max);
+
+            // Skip cells the ray passes entirely above or below.
+            let (y_enter, y_exit) = (ray.at(t_enter).y, ray.at(t_exit).y);
+            let heights = self.cell_heights(i, j);
+            if f32::min(y_enter, y_exit) <= heights.max
+                && f32::max(y_enter, y_exit) >= heights.min
+                && self.hit_cell(i, j, ray, ray_interval, record)
+            {
+                return true;
+            }
+
+            if t_exit >= t_max {
+                return false;
+            }
+            if next_x < next_z {
+                if (step_x < 0 && i == 0) || (step_x > 0 && i + 1 == cells_x) {
+                    return false;
+                }
+                i = (i as isize + step_x) as usize;
+                next_x += delta_x;
+            } else {
+                if (step_z < 0 && j == 0) || (step_z > 0 && j + 1 == cells_z) {
+                    return false;
+                }
+                j = (j as isize + step_z) as usize;
+                next_z += delta_z;
+            }
+            t_enter = t_exit;
+        }
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+}
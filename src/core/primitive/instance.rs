@@ -1,9 +1,12 @@
 use crate::{
-    core::{deg_to_rad, AxisAlignedBoundingBox, HitRecord, Interval, Point3, Ray, Vector3},
+    core::{
+        deg_to_rad, AxisAlignedBoundingBox, HitRecord, Interval, LightBounds, Matrix4, Point3, Ray,
+        Vector3,
+    },
     traits::Hittable,
 };
 use std::{
-    ops::{Add, Sub},
+    ops::{Add, Mul, Sub},
     rc::Rc,
 };
 
@@ -32,6 +35,7 @@ impl Hittable for TranslateInstance {
             return false;
         }
 
+        record.object_point = record.object_point.or(record.point);
         record.point = Some(&record.point.unwrap() + &self.offset);
 
         true
@@ -40,6 +44,28 @@ impl Hittable for TranslateInstance {
     fn bounding_box(&self) -> &AxisAlignedBoundingBox {
         &self.bbox
     }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        self.object.pdf_value(&origin.sub(&self.offset), direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        self.object.random(&origin.sub(&self.offset))
+    }
+
+    fn area(&self) -> f32 {
+        self.object.area()
+    }
+
+    fn power(&self) -> f32 {
+        self.object.power()
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let mut bounds = self.object.light_bounds()?;
+        bounds.bbox = bounds.bbox.add(&self.offset);
+        Some(bounds)
+    }
 }
 
 #[derive(Debug)]
@@ -88,6 +114,24 @@ impl RotateYInstance {
             bbox: AxisAlignedBoundingBox::new_with_two_points(&min, &max),
         };
     }
+
+    /// Rotate a vector from world space to object space.
+    fn rotate_to_object(&self, vector: &Vector3) -> Vector3 {
+        Vector3::new(
+            self.cos_theta * vector[0] - self.sin_theta * vector[2],
+            vector[1],
+            self.sin_theta * vector[0] + self.cos_theta * vector[2],
+        )
+    }
+
+    /// Rotate a vector from object space to world space.
+    fn rotate_to_world(&self, vector: &Vector3) -> Vector3 {
+        Vector3::new(
+            self.cos_theta * vector[0] + self.sin_theta * vector[2],
+            vector[1],
+            -self.sin_theta * vector[0] + self.cos_theta * vector[2],
+        )
+    }
 }
 
 impl Hittable for RotateYInstance {
@@ -125,8 +169,11 @@ impl Hittable for RotateYInstance {
             -self.sin_theta * normal[0] + self.cos_theta * normal[2],
         );
 
+        record.object_point = record.object_point.or(record.point);
         record.point = Some(new_point);
         record.normal = Some(new_normal);
+        record.dpdu = record.dpdu.map(|dpdu| self.rotate_to_world(&dpdu));
+        record.dpdv = record.dpdv.map(|dpdv| self.rotate_to_world(&dpdv));
 
         true
     }
@@ -134,4 +181,257 @@ impl Hittable for RotateYInstance {
     fn bounding_box(&self) -> &AxisAlignedBoundingBox {
         &self.bbox
     }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        self.object.pdf_value(
+            &self.rotate_to_object(origin),
+            &self.rotate_to_object(direction),
+        )
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        self.rotate_to_world(&self.object.random(&self.rotate_to_object(origin)))
+    }
+
+    fn area(&self) -> f32 {
+        self.object.area()
+    }
+
+    fn power(&self) -> f32 {
+        self.object.power()
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let mut bounds = self.object.light_bounds()?;
+        bounds.bbox = self.bbox.clone();
+        bounds.w = self.rotate_to_world(&bounds.w);
+        Some(bounds)
+    }
+}
+
+/**
+Transform Instance
+
+An instance of an object under an affine transform from object space to world space, such as the
+node transforms of a glTF scene. Rays are brought into object space without normalizing their
+direction, so hit distances carry over unchanged, and light sampling accounts for the stretching of
+the surface so transformed emissive objects keep working as lights.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, LambertianMaterial, Matrix4, Point3, Sphere, TransformInstance, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let sphere = Rc::new(Sphere::new(Point3::zero(), 1., material));
+let transform = &Matrix4::translation(&Vector3::new(0., 2., 0.))
+    * &Matrix4::scaling(&Vector3::new(2., 1., 1.));
+let ellipsoid = TransformInstance::new(sphere, transform);
+# assert!((ellipsoid.bounding_box().axis(0).max - 2.).abs() < 1e-3);
+# assert!((ellipsoid.bounding_box().axis(1).min - 1.).abs() < 1e-3);
+```
+*/
+#[derive(Debug)]
+pub struct TransformInstance {
+    /// The transformed object.
+    object: Rc<dyn Hittable>,
+
+    /// Transform from object space to world space.
+    transform: Matrix4,
+
+    /// Transform from world space to object space.
+    inverse: Matrix4,
+
+    /// Bounding box of the transformed object.
+    bbox: AxisAlignedBoundingBox,
+
+    /// Ratio of the area of the transformed object to the area of the object.
+    area_scale: f32,
+}
+
+impl TransformInstance {
+    /// Create a new `TransformInstance` of an object, the transform must be invertible.
+    pub fn new(object: Rc<dyn Hittable>, transform: Matrix4) -> Self {
+        let inverse = transform
+            .inverse()
+            .expect("the transform of an instance must be invertible");
+
+        // Bound the transformed corners of the bounding box of the object.
+        let bbox = object.bounding_box();
+        let mut min = Vector3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = Vector3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for i in 0..8 {
+            let corner = Point3::new(
+                if i & 1 == 0 { bbox.x.min } else { bbox.x.max },
+                if i & 2 == 0 { bbox.y.min } else { bbox.y.max },
+                if i & 4 == 0 { bbox.z.min } else { bbox.z.max },
+            );
+            let point = transform.transform_point(&corner);
+            for axis in 0..3 {
+                min[axis] = f32::min(min[axis], point[axis]);
+                max[axis] = f32::max(max[axis], point[axis]);
+            }
+        }
+
+        let area_scale = Self::area_scale(object.as_ref(), &transform, &inverse);
+        Self {
+            object,
+            transform,
+            inverse,
+            bbox: AxisAlignedBoundingBox::new_with_two_points(&min, &max).pad(),
+            area_scale,
+        }
+    }
+
+    /// Returns the ratio of the area of the transformed object to the area of the object. Surfaces
+    /// stretch by the determinant of the transform over the stretch of their normals, which is
+    /// averaged over the normals seen from the corners, edges and faces of the bounding box: exact
+    /// for flat objects and rigid or uniformly scaling transforms, an estimate for curved objects
+    /// under other transforms.
+    fn area_scale(object: &dyn Hittable, transform: &Matrix4, inverse: &Matrix4) -> f32 {
+        let x = transform.transform_vector(&Vector3::new(1., 0., 0.));
+        let y = transform.transform_vector(&Vector3::new(0., 1., 0.));
+        let z = transform.transform_vector(&Vector3::new(0., 0., 1.));
+        let determinant = x.cross(&y).dot(&z).abs();
+
+        let bbox = object.bounding_box();
+        let center = Point3::new(
+            (bbox.x.min + bbox.x.max) / 2.,
+            (bbox.y.min + bbox.y.max) / 2.,
+            (bbox.z.min + bbox.z.max) / 2.,
+        );
+        let size = bbox.x.size() + bbox.y.size() + bbox.z.size();
+        let (mut total, mut count) = (0., 0);
+        for i in (0..27).filter(|&i| i != 13) {
+            let direction = Vector3::new(
+                (i % 3) as f32 - 1.,
+                (i / 3 % 3) as f32 - 1.,
+                (i / 9) as f32 - 1.,
+            );
+            let ray = Ray::new(&center - &direction.mul(size), direction);
+            let mut record = HitRecord::new();
+            if !object.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record) {
+                continue;
+            }
+            if let Some(normal) = record.normal {
+                total += determinant * inverse.transform_normal(&normal.normolize()).length();
+                count += 1;
+            }
+        }
+
+        if count > 0 && f32::is_finite(total) {
+            total / count as f32
+        } else {
+            determinant.powf(2. / 3.)
+        }
+    }
+
+    /// Returns the transform from object space to world space.
+    pub fn transform(&self) -> &Matrix4 {
+        &self.transform
+    }
+
+    /// Internal method, returns the ray in object space.
+    fn to_object(&self, ray: &Ray) -> Ray {
+        Ray::new_with_time(
+            self.inverse.transform_point(&ray.origin),
+            self.inverse.transform_vector(&ray.direction),
+            ray.time,
+        )
+    }
+}
+
+impl Hittable for TransformInstance {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        if !self.object.hit(&self.to_object(ray), ray_interval, record) {
+            return false;
+        }
+
+        // Normals take the inverse transpose, which keeps their side of the surface.
+        record.object_point = record.object_point.or(record.point);
+        record.point = record
+            .point
+            .map(|point| self.transform.transform_point(&point));
+        record.normal = record
+            .normal
+            .map(|normal| self.inverse.transform_normal(&normal).normolize());
+        record.dpdu = record
+            .dpdu
+            .map(|dpdu| self.transform.transform_vector(&dpdu));
+        record.dpdv = record
+            .dpdv
+            .map(|dpdv| self.transform.transform_vector(&dpdv));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        let ray = self.to_object(&Ray::new(*origin, *direction));
+        let pdf = self.object.pdf_value(&ray.origin, &ray.direction);
+        if pdf == 0. {
+            return 0.;
+        }
+        let mut record = HitRecord::new();
+        if !self
+            .object
+            .hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record)
+        {
+            return 0.;
+        }
+
+        // Change the solid angle density to an area density, stretch the area to world space,
+        // then change it back to a solid angle density seen from the world space origin.
+        let normal = record.normal.unwrap();
+        let world_normal = self.inverse.transform_normal(&normal);
+        let x = self.transform.transform_vector(&Vector3::new(1., 0., 0.));
+        let y = self.transform.transform_vector(&Vector3::new(0., 1., 0.));
+        let z = self.transform.transform_vector(&Vector3::new(0., 0., 1.));
+        let area_scale = x.cross(&y).dot(&z).abs() * world_normal.length();
+
+        let local_cosine = normal.dot(&ray.direction).abs() / ray.direction.length();
+        let world_cosine =
+            world_normal.dot(direction).abs() / (world_normal.length() * direction.length());
+        if local_cosine == 0. || world_cosine == 0. {
+            return 0.;
+        }
+        let distance_ratio = direction.length_squared() / ray.direction.length_squared();
+
+        pdf * local_cosine * distance_ratio / (area_scale * world_cosine)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        let local_origin = self.inverse.transform_point(origin);
+        let local_point = &local_origin + &self.object.random(&local_origin);
+        &self.transform.transform_point(&local_point) - origin
+    }
+
+    fn area(&self) -> f32 {
+        self.object.area() * self.area_scale
+    }
+
+    fn power(&self) -> f32 {
+        // Surfaces emit in proportion to their area.
+        self.object.power() * self.area_scale
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let mut bounds = self.object.light_bounds()?;
+        bounds.bbox = self.bbox.clone();
+        bounds.power *= self.area_scale;
+        bounds.w = self.inverse.transform_normal(&bounds.w).normolize();
+        // A single normal stays a single normal, but other transforms than rotations can widen
+        // a cone of normals, so wider cones are replaced by every direction.
+        if bounds.cos_theta_o < 1. {
+            bounds.cos_theta_o = -1.;
+        }
+        Some(bounds)
+    }
 }
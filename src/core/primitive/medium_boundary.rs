@@ -0,0 +1,99 @@
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, HitRecord, Interval, LightBounds, MediumInterface, Point3, Ray,
+        Vector3,
+    },
+    traits::{Hittable, Medium},
+};
+use std::rc::Rc;
+
+/**
+Medium Boundary
+
+A hittable wrapper that marks the surface of a shape as the boundary between two participating
+media, so rays crossing it switch to the medium on the other side. The shape keeps its material,
+e.g. a `DielectricMaterial` sphere filled with fog, or it can be made invisible so the surface only
+bounds the medium. Unlike `ConstantMedium`, the boundary may be non-convex and rays may start
+inside of it.
+
+The camera tracks the medium a ray is in, starting from `Camera::medium`, so boundaries are
+expected to be closed and consistent with each other.
+*/
+#[derive(Debug)]
+pub struct MediumBoundary {
+    /// The bounding shape.
+    shape: Rc<dyn Hittable>,
+
+    /// The media inside and outside of the shape.
+    interface: MediumInterface,
+
+    /// Whether the surface is invisible, passing rays through unchanged.
+    pub invisible: bool,
+}
+
+impl MediumBoundary {
+    /// Create a new `MediumBoundary` keeping the material of the shape.
+    pub fn new(
+        shape: Rc<dyn Hittable>,
+        inside: Option<Rc<dyn Medium>>,
+        outside: Option<Rc<dyn Medium>>,
+    ) -> Self {
+        Self {
+            shape,
+            interface: MediumInterface::new(inside, outside),
+            invisible: false,
+        }
+    }
+
+    /// Create a new `MediumBoundary` whose surface is invisible and only bounds the media.
+    pub fn new_invisible(
+        shape: Rc<dyn Hittable>,
+        inside: Option<Rc<dyn Medium>>,
+        outside: Option<Rc<dyn Medium>>,
+    ) -> Self {
+        Self {
+            shape,
+            interface: MediumInterface::new(inside, outside),
+            invisible: true,
+        }
+    }
+}
+
+impl Hittable for MediumBoundary {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        if !self.shape.hit(ray, ray_interval, record) {
+            return false;
+        }
+
+        record.medium_interface = Some(self.interface.clone());
+        if self.invisible {
+            // A hit without a material only changes the medium of the ray.
+            record.material = None;
+        }
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        self.shape.bounding_box()
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        self.shape.pdf_value(origin, direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        self.shape.random(origin)
+    }
+
+    fn area(&self) -> f32 {
+        self.shape.area()
+    }
+
+    fn power(&self) -> f32 {
+        self.shape.power()
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        self.shape.light_bounds()
+    }
+}
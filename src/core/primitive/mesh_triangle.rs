@@ -0,0 +1,244 @@
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, HitRecord, Interval, LightBounds, Point3, Ray, TriangleMesh,
+        Vector2, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use rand::Rng;
+use std::{
+    f32::consts::PI,
+    ops::{Mul, Neg},
+    rc::Rc,
+};
+
+/**
+Mesh Triangle
+
+A triangle of a `TriangleMesh`. Normals, uv coordinates and colors of the mesh vertices are
+interpolated at the hit, without uv coordinates the barycentric coordinates of the second and third
+vertices stand in for them. Points are sampled uniformly by area, so emissive meshes work as area
+lights.
+*/
+#[derive(Debug)]
+pub struct MeshTriangle {
+    /// The mesh the triangle belongs to.
+    mesh: Rc<TriangleMesh>,
+
+    /// Index of the triangle in the mesh.
+    index: usize,
+
+    /// Material of the triangle.
+    material: Rc<dyn Material>,
+
+    /// Area of the triangle.
+    area: f32,
+
+    /// Bounding box of the triangle.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl MeshTriangle {
+    /// Create a new `MeshTriangle` from the triangle at `index` in a mesh.
+    pub fn new(mesh: Rc<TriangleMesh>, index: usize, material: Rc<dyn Material>) -> Self {
+        let [p0, p1, p2] = mesh.indices[index].map(|vertex| mesh.positions[vertex]);
+        let area = (&p1 - &p0).cross(&(&p2 - &p0)).length() / 2.;
+        let bbox = AxisAlignedBoundingBox::new_with_two_points(&p0, &p1)
+            .merge(&AxisAlignedBoundingBox::new_with_two_points(&p2, &p2))
+            .pad();
+        Self {
+            mesh,
+            index,
+            material,
+            area,
+            bbox,
+        }
+    }
+
+    /// Returns the vertex indices of the triangle.
+    fn vertices(&self) -> [usize; 3] {
+        self.mesh.indices[self.index]
+    }
+
+    /// Returns the unit normal of the plane of the triangle, the front face of its vertex order.
+    fn face_normal(&self) -> Vector3 {
+        let [p0, p1, p2] = self.vertices().map(|vertex| self.mesh.positions[vertex]);
+        (&p1 - &p0).cross(&(&p2 - &p0)).normolize()
+    }
+
+    /// Returns the average luminance emitted by one face of the triangle, estimated on a grid of
+    /// points.
+    fn emitted_luminance(&self, front_face: bool) -> f32 {
+        const GRID_SIZE: usize = 4;
+
+        let normal = self.face_normal();
+        let mut record = HitRecord::new();
+        record.front_face = front_face;
+        record.normal = Some(if front_face { normal } else { normal.neg() });
+
+        let [p0, p1, p2] = self.vertices().map(|vertex| self.mesh.positions[vertex]);
+        let [uv0, uv1, uv2] = self.vertex_uvs();
+        let mut luminance = 0.;
+        for i in 0..GRID_SIZE {
+            for j in 0..GRID_SIZE {
+                // Stratify the points by area, folding the square onto the triangle.
+                let mut b1 = (i as f32 + 0.5) / GRID_SIZE as f32;
+                let mut b2 = (j as f32 + 0.5) / GRID_SIZE as f32;
+                if b1 + b2 > 1. {
+                    (b1, b2) = (1. - b1, 1. - b2);
+                }
+                let b0 = 1. - b1 - b2;
+                record.point = Some(&(&p0.mul(b0) + &p1.mul(b1)) + &p2.mul(b2));
+                record.uv = Some(Vector2::new(
+                    b0 * uv0.u() + b1 * uv1.u() + b2 * uv2.u(),
+                    b0 * uv0.v() + b1 * uv1.v() + b2 * uv2.v(),
+                ));
+                luminance += self.material.emitted_luminance(&record);
+            }
+        }
+        luminance / (GRID_SIZE * GRID_SIZE) as f32
+    }
+
+    /// Returns the uv coordinates of the vertices.
+    fn vertex_uvs(&self) -> [Vector2; 3] {
+        match &self.mesh.uvs {
+            Some(uvs) => self.vertices().map(|vertex| uvs[vertex]),
+            None => [
+                Vector2::new(0., 0.),
+                Vector2::new(1., 0.),
+                Vector2::new(0., 1.),
+            ],
+        }
+    }
+}
+
+impl Hittable for MeshTriangle {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let vertices = self.vertices();
+        let [p0, p1, p2] = vertices.map(|vertex| self.mesh.positions[vertex]);
+
+        // Möller-Trumbore intersection.
+        let edge1 = &p1 - &p0;
+        let edge2 = &p2 - &p0;
+        let p = ray.direction.cross(&edge2);
+        let determinant = edge1.dot(&p);
+        if determinant.abs() < 1e-12 {
+            return false;
+        }
+        let inverse = 1. / determinant;
+        let s = &ray.origin - &p0;
+        let b1 = s.dot(&p) * inverse;
+        let q = s.cross(&edge1);
+        let b2 = ray.direction.dot(&q) * inverse;
+        if b1 < 0. || b2 < 0. || b1 + b2 > 1. {
+            return false;
+        }
+        let t = edge2.dot(&q) * inverse;
+        if !ray_interval.surrounds(t) {
+            return false;
+        }
+        let b0 = 1. - b1 - b2;
+
+        let normal = match &self.mesh.normals {
+            Some(normals) => {
+                let [n0, n1, n2] = vertices.map(|vertex| normals[vertex]);
+                &(&n0.mul(b0) + &n1.mul(b1)) + &n2.mul(b2)
+            }
+            None => edge1.cross(&edge2),
+        };
+        let [uv0, uv1, uv2] = self.vertex_uvs();
+        let uv = Vector2::new(
+            b0 * uv0.u() + b1 * uv1.u() + b2 * uv2.u(),
+            b0 * uv0.v() + b1 * uv1.v() + b2 * uv2.v(),
+        );
+
+        // Tangents from the change of the uv coordinates along the edges.
+        let (du1, dv1) = (uv1.u() - uv0.u(), uv1.v() - uv0.v());
+        let (du2, dv2) = (uv2.u() - uv0.u(), uv2.v() - uv0.v());
+        let uv_determinant = du1 * dv2 - dv1 * du2;
+        let (dpdu, dpdv) = if uv_determinant.abs() < 1e-12 {
+            (edge1, edge2)
+        } else {
+            let inverse = 1. / uv_determinant;
+            (
+                (&edge1.mul(dv2) - &edge2.mul(dv1)).mul(inverse),
+                (&edge2.mul(du1) - &edge1.mul(du2)).mul(inverse),
+            )
+        };
+
+        record.t = t;
+        record.point = Some(ray.at(t));
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &normal);
+        record.uv = Some(uv);
+        record.dpdu = Some(dpdu);
+        record.dpdv = Some(dpdv);
+        record.vertex_color = self.mesh.colors.as_ref().map(|colors| {
+            let [c0, c1, c2] = vertices.map(|vertex| colors[vertex]);
+            &(&c0.mul(b0) + &c1.mul(b1)) + &c2.mul(b2)
+        });
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        let mut record = HitRecord::new();
+        if !self.hit(
+            &Ray::new(*origin, *direction),
+            &Interval::new(0.001, f32::INFINITY),
+            &mut record,
+        ) {
+            return 0.;
+        }
+
+        // The density of the area seen through the plane of the triangle.
+        let normal = self.face_normal();
+        let distance_squared = record.t * record.t * direction.length_squared();
+        let cosine = f32::abs(direction.dot(&normal) / direction.length());
+
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        let mut rng = rand::thread_rng();
+        let (mut b1, mut b2) = (rng.gen::<f32>(), rng.gen::<f32>());
+        if b1 + b2 > 1. {
+            (b1, b2) = (1. - b1, 1. - b2);
+        }
+        let [p0, p1, p2] = self.vertices().map(|vertex| self.mesh.positions[vertex]);
+        let point = &(&p0 + &(&p1 - &p0).mul(b1)) + &(&p2 - &p0).mul(b2);
+        &point - origin
+    }
+
+    fn area(&self) -> f32 {
+        self.area
+    }
+
+    fn power(&self) -> f32 {
+        // Each face emits like a Lambertian surface, radiating pi times its radiance.
+        PI * self.area * (self.emitted_luminance(true) + self.emitted_luminance(false))
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let front = self.emitted_luminance(true);
+        let back = self.emitted_luminance(false);
+        if front + back <= 0. {
+            return None;
+        }
+
+        let normal = self.face_normal();
+        let w = if front > 0. { normal } else { normal.neg() };
+        Some(LightBounds::new(
+            self.bbox.clone(),
+            PI * self.area * (front + back),
+            w,
+            1.,
+            0.,
+            front > 0. && back > 0.,
+        ))
+    }
+}
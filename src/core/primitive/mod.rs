@@ -1,11 +1,40 @@
+mod alpha_mask;
+mod area_light;
 mod r#box;
+mod cone;
+mod csg;
+mod curve;
+mod cylinder;
+mod grid_medium;
+mod heightfield;
 mod instance;
 mod medium;
-mod quad;
+mod medium_boundary;
+mod mesh_triangle;
+mod planar;
+mod planar_shape;
+mod sdf_hittable;
+mod sdf_node;
 mod sphere;
+mod surface;
+mod torus;
 
-pub use instance::{RotateYInstance, TranslateInstance};
+pub use alpha_mask::{AlphaMask, AlphaSource};
+pub use area_light::{AreaLight, LightEmission};
+pub use cone::Cone;
+pub use csg::{CSGNode, CSGOperation};
+pub use curve::{Curve, CurveType};
+pub use cylinder::Cylinder;
+pub use grid_medium::GridMedium;
+pub use heightfield::Heightfield;
+pub use instance::{RotateYInstance, TransformInstance, TranslateInstance};
 pub use medium::ConstantMedium;
-pub use quad::Quad;
+pub use medium_boundary::MediumBoundary;
+pub use mesh_triangle::MeshTriangle;
+pub use planar::{Annulus, Disk, Ellipse, Planar, Quad, Triangle};
+pub use planar_shape::{UnitAnnulus, UnitDisk, UnitSquare, UnitTriangle};
 pub use r#box::get_cube_box;
+pub use sdf_hittable::SDFHittable;
+pub use sdf_node::{SDFFunction, SDFNode};
 pub use sphere::Sphere;
+pub use torus::Torus;
@@ -0,0 +1,361 @@
+use super::{
+    planar_shape::{UnitAnnulus, UnitDisk, UnitSquare, UnitTriangle},
+    surface::average_emitted_luminance,
+};
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, HitRecord, Interval, LightBounds, OrthonormalBasis, Point3, Ray,
+        Vector2, Vector3,
+    },
+    traits::{Hittable, Material, PlanarShape},
+};
+use rand::Rng;
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul, Neg},
+    rc::Rc,
+};
+
+/// A parallelogram spanned by its two edge vectors.
+pub type Quad = Planar<UnitSquare>;
+
+/// A triangle spanned by its two edge vectors.
+pub type Triangle = Planar<UnitTriangle>;
+
+/// An ellipse, or a disk, around its center with the two vectors as semi-axes.
+pub type Ellipse = Planar<UnitDisk>;
+
+/// An elliptical annulus, or a disk with a hole, around its center with the two vectors as
+/// semi-axes.
+pub type Annulus = Planar<UnitAnnulus>;
+
+/// A disk facing along its normal, optionally with a hole in the middle making an annulus.
+pub type Disk = Annulus;
+
+/**
+Planar
+
+A flat primitive on the plane through `origin` spanned by the vectors u and v, bounded by a
+`PlanarShape` in the planar coordinates `(α, β)` of each point `origin + α u + β v`. The shapes
+share the plane math, bounding box and uv coordinates, and are sampled uniformly by area, so all
+of them work as area lights: `Quad`, `Triangle`, `Ellipse`, `Annulus` and `Disk`.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, Ellipse, HitRecord, Interval, LambertianMaterial, Point3, Ray, Triangle, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let triangle = Triangle::new(
+    Point3::zero(),
+    Vector3::new(1., 0., 0.),
+    Vector3::new(0., 0., 1.),
+    material.clone(),
+);
+let disk = Ellipse::new_disk(Point3::zero(), Vector3::up(), 1., material);
+
+// The corner of the unit square beyond the diagonal is outside of the triangle.
+let ray = Ray::new(Point3::new(0.75, 1., 0.5), Vector3::new(0., -1., 0.));
+# let mut record = HitRecord::new();
+# assert!(!triangle.hit(&ray, &Interval::new(0., 2.), &mut record));
+# assert!(disk.hit(&ray, &Interval::new(0., 2.), &mut record));
+# assert!((record.t - 1.).abs() < 1e-6);
+```
+*/
+#[derive(Debug)]
+pub struct Planar<S: PlanarShape> {
+    /// The shape within the plane, in planar coordinates.
+    shape: S,
+
+    /// A point in space, form a parallelogram plane with two boundary vectors u and v
+    origin: Point3,
+
+    /// One of the boundary vector of a parallelogram plane.
+    u: Vector3,
+
+    /// Another one of the boundary vector of a parallelogram plane.
+    v: Vector3,
+
+    /// Material of this plane.
+    material: Rc<dyn Material>,
+
+    /// Bounding box of this plane.
+    bbox: AxisAlignedBoundingBox,
+
+    /// A vector perpendicular to this plane, can be used with `point` to form the implicit
+    /// formula of the borderless plane which this parallelogram plane lies.
+    normal: Vector3,
+
+    /// A precalculated number be defined by the implicit formula of the borderless plane: `ax + by + cz + d = 0``.
+    d: f32,
+
+    /// A precalculated number that accelerates the UV computation of the ray-plane intersection.
+    w: Vector3,
+
+    /// The area of this planar shape.
+    area: f32,
+}
+
+impl<S: PlanarShape> Planar<S> {
+    /// Create a new `Planar` primitive of a shape on the plane spanned by u and v.
+    pub fn new_with_shape(
+        shape: S,
+        origin: Point3,
+        u: Vector3,
+        v: Vector3,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        let n = u.cross(&v);
+        let normal = n.normolize();
+        let d = normal.dot(&origin);
+        let w = &n / n.dot(&n);
+        let area = n.length() * shape.area();
+
+        // Bound the corners of the planar coordinates covered by the shape.
+        let (min, max) = shape.bounds();
+        let corner = |alpha: f32, beta: f32| origin.add(&u.mul(alpha)).add(&v.mul(beta));
+        let bbox = AxisAlignedBoundingBox::new_with_two_points(
+            &corner(min.x, min.y),
+            &corner(max.x, max.y),
+        )
+        .merge(&AxisAlignedBoundingBox::new_with_two_points(
+            &corner(max.x, min.y),
+            &corner(min.x, max.y),
+        ))
+        .pad();
+
+        Self {
+            shape,
+            origin,
+            u,
+            v,
+            material,
+            bbox,
+            normal,
+            d,
+            w,
+            area,
+        }
+    }
+
+    /// Returns the shape within the plane.
+    pub fn shape(&self) -> &S {
+        &self.shape
+    }
+
+    /// Returns the unit normal of the plane.
+    pub fn normal(&self) -> Vector3 {
+        self.normal
+    }
+
+    /// Returns the point and uv coordinates at planar coordinates.
+    fn surface_point(&self, alpha: f32, beta: f32) -> (Point3, Vector2) {
+        let (min, max) = self.shape.bounds();
+        let point = self.origin.add(&self.u.mul(alpha)).add(&self.v.mul(beta));
+        let uv = Vector2::new(
+            (alpha - min.x) / (max.x - min.x),
+            (beta - min.y) / (max.y - min.y),
+        );
+        (point, uv)
+    }
+
+    /// Returns the average luminance emitted by one face of the plane, estimated on a grid of
+    /// points.
+    fn emitted_luminance(&self, front_face: bool) -> f32 {
+        average_emitted_luminance(self.material.as_ref(), front_face, |r1, r2| {
+            // Stratify the points by area over the shape.
+            let planar = self.shape.sample(r1, r2);
+            let (point, uv) = self.surface_point(planar.x, planar.y);
+            (point, self.normal, uv)
+        })
+    }
+}
+
+impl Planar<UnitSquare> {
+    /// Create a new parallelogram from a corner and its two edge vectors.
+    pub fn new(origin: Point3, u: Vector3, v: Vector3, material: Rc<dyn Material>) -> Self {
+        Self::new_with_shape(UnitSquare, origin, u, v, material)
+    }
+}
+
+impl Planar<UnitTriangle> {
+    /// Create a new triangle from a corner and its two edge vectors from that corner.
+    pub fn new(origin: Point3, u: Vector3, v: Vector3, material: Rc<dyn Material>) -> Self {
+        Self::new_with_shape(UnitTriangle, origin, u, v, material)
+    }
+
+    /// Create a new triangle from its three vertices.
+    pub fn new_with_vertices(a: Point3, b: Point3, c: Point3, material: Rc<dyn Material>) -> Self {
+        Self::new(a, &b - &a, &c - &a, material)
+    }
+}
+
+impl Planar<UnitDisk> {
+    /// Create a new ellipse from its center and its two semi-axes.
+    pub fn new(center: Point3, u: Vector3, v: Vector3, material: Rc<dyn Material>) -> Self {
+        Self::new_with_shape(UnitDisk, center, u, v, material)
+    }
+
+    /// Create a new disk from its center, normal and radius.
+    pub fn new_disk(
+        center: Point3,
+        normal: Vector3,
+        radius: f32,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        let (u, v) = disk_axes(&normal, radius);
+        Self::new(center, u, v, material)
+    }
+}
+
+impl Planar<UnitAnnulus> {
+    /// Create a new disk from its center, normal and radius.
+    pub fn new(center: Point3, normal: Vector3, radius: f32, material: Rc<dyn Material>) -> Self {
+        Self::new_annulus(center, normal, 0., radius, material)
+    }
+
+    /// Create a new elliptical annulus from its center, its two semi-axes and the radius of the
+    /// hole relative to them.
+    pub fn new_elliptical(
+        center: Point3,
+        u: Vector3,
+        v: Vector3,
+        inner_radius: f32,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        let shape = UnitAnnulus {
+            inner_radius: f32::clamp(inner_radius, 0., 1.),
+        };
+        Self::new_with_shape(shape, center, u, v, material)
+    }
+
+    /// Create a new annulus from its center, normal, and inner and outer radii.
+    pub fn new_annulus(
+        center: Point3,
+        normal: Vector3,
+        inner_radius: f32,
+        radius: f32,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        let (u, v) = disk_axes(&normal, radius);
+        Self::new_elliptical(center, u, v, inner_radius / radius, material)
+    }
+}
+
+/// Returns two perpendicular semi-axes of `radius` spanning a disk facing along `normal`.
+fn disk_axes(normal: &Vector3, radius: f32) -> (Vector3, Vector3) {
+    // The basis around w has u × v = -w, so v comes first to face along the normal.
+    let basis = OrthonormalBasis::new_with_w(normal);
+    (basis.v().mul(radius), basis.u().mul(radius))
+}
+
+impl<S: PlanarShape> Hittable for Planar<S> {
+    fn hit(
+        &self,
+        ray: &crate::core::Ray,
+        ray_interval: &crate::core::Interval,
+        record: &mut crate::core::HitRecord,
+    ) -> bool {
+        let nd = self.normal.dot(&ray.direction);
+        if f32::abs(nd) < f32::EPSILON {
+            // The ray is parallel to the plane.
+            return false;
+        }
+
+        let t = (self.d - self.normal.dot(&ray.origin)) / nd;
+        if !ray_interval.contains(t) {
+            // The hit point parameter t is outside the ray interval.
+            return false;
+        }
+
+        let intersection = ray.at(t);
+        let planar_intersection_vector = &intersection - &self.origin;
+        let planar_u = self.w.dot(&planar_intersection_vector.cross(&self.v));
+        let planar_v = self.w.dot(&self.u.cross(&planar_intersection_vector));
+        if !self.shape.contains(planar_u, planar_v) {
+            // The hit point lies outside the planar shape using its plane coordinates.
+            return false;
+        }
+
+        let (min, max) = self.shape.bounds();
+        let (_, uv) = self.surface_point(planar_u, planar_v);
+        record.uv = Some(uv);
+        record.t = t;
+        record.point = Some(intersection);
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &self.normal);
+        record.dpdu = Some(self.u.mul(max.x - min.x));
+        record.dpdv = Some(self.v.mul(max.y - min.y));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Vector3, direction: &Vector3) -> f32 {
+        let mut record = HitRecord::new();
+        if !self.hit(
+            &Ray::new(*origin, *direction),
+            &Interval::new(0.001, f32::INFINITY),
+            &mut record,
+        ) {
+            return 0.;
+        }
+        let HitRecord {
+            normal: Some(normal),
+            ..
+        } = record
+        else {
+            return 0.;
+        };
+
+        let distance_squared = record.t * record.t * direction.length_squared();
+        let cosine = f32::abs(direction.dot(&normal) / direction.length());
+
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random(&self, origin: &Point3) -> Point3 {
+        let mut rng = rand::thread_rng();
+        let planar = self.shape.sample(rng.gen::<f32>(), rng.gen::<f32>());
+        let (point, _) = self.surface_point(planar.x, planar.y);
+        &point - origin
+    }
+
+    fn area(&self) -> f32 {
+        self.area
+    }
+
+    fn power(&self) -> f32 {
+        // Each face emits like a Lambertian surface, radiating pi times its radiance.
+        PI * self.area * (self.emitted_luminance(true) + self.emitted_luminance(false))
+    }
+
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let front = self.emitted_luminance(true);
+        let back = self.emitted_luminance(false);
+        if front + back <= 0. {
+            return None;
+        }
+
+        let w = if front > 0. {
+            self.normal
+        } else {
+            self.normal.neg()
+        };
+        Some(LightBounds::new(
+            self.bbox.clone(),
+            PI * self.area * (front + back),
+            w,
+            1.,
+            0.,
+            front > 0. && back > 0.,
+        ))
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{core::Vector2, traits::PlanarShape};
+use std::f32::consts::PI;
+
+/// The unit square `[0, 1]²`, making a parallelogram of the edge vectors.
+#[derive(Debug, Clone, Copy)]
+pub struct UnitSquare;
+
+impl PlanarShape for UnitSquare {
+    fn contains(&self, alpha: f32, beta: f32) -> bool {
+        (0. ..=1.).contains(&alpha) && (0. ..=1.).contains(&beta)
+    }
+
+    fn bounds(&self) -> (Vector2, Vector2) {
+        (Vector2::zero(), Vector2::new(1., 1.))
+    }
+
+    fn area(&self) -> f32 {
+        1.
+    }
+
+    fn sample(&self, r1: f32, r2: f32) -> Vector2 {
+        Vector2::new(r1, r2)
+    }
+}
+
+/// The unit triangle with corners `(0, 0)`, `(1, 0)` and `(0, 1)`, making a triangle of the edge
+/// vectors.
+#[derive(Debug, Clone, Copy)]
+pub struct UnitTriangle;
+
+impl PlanarShape for UnitTriangle {
+    fn contains(&self, alpha: f32, beta: f32) -> bool {
+        alpha >= 0. && beta >= 0. && alpha + beta <= 1.
+    }
+
+    fn bounds(&self) -> (Vector2, Vector2) {
+        (Vector2::zero(), Vector2::new(1., 1.))
+    }
+
+    fn area(&self) -> f32 {
+        0.5
+    }
+
+    fn sample(&self, r1: f32, r2: f32) -> Vector2 {
+        // Fold the unit square onto the triangle along its diagonal.
+        if r1 + r2 > 1. {
+            Vector2::new(1. - r1, 1. - r2)
+        } else {
+            Vector2::new(r1, r2)
+        }
+    }
+}
+
+/// The unit disk centered at the origin, making an ellipse of the edge vectors as semi-axes.
+#[derive(Debug, Clone, Copy)]
+pub struct UnitDisk;
+
+impl PlanarShape for UnitDisk {
+    fn contains(&self, alpha: f32, beta: f32) -> bool {
+        alpha * alpha + beta * beta <= 1.
+    }
+
+    fn bounds(&self) -> (Vector2, Vector2) {
+        (Vector2::new(-1., -1.), Vector2::new(1., 1.))
+    }
+
+    fn area(&self) -> f32 {
+        PI
+    }
+
+    fn sample(&self, r1: f32, r2: f32) -> Vector2 {
+        UnitAnnulus { inner_radius: 0. }.sample(r1, r2)
+    }
+}
+
+/// The unit disk with a hole of `inner_radius` in the middle, making an elliptical annulus of the
+/// edge vectors as semi-axes.
+#[derive(Debug, Clone, Copy)]
+pub struct UnitAnnulus {
+    /// Radius of the hole, in `[0, 1]`.
+    pub inner_radius: f32,
+}
+
+impl PlanarShape for UnitAnnulus {
+    fn contains(&self, alpha: f32, beta: f32) -> bool {
+        let radius_squared = alpha * alpha + beta * beta;
+        radius_squared <= 1. && radius_squared >= self.inner_radius * self.inner_radius
+    }
+
+    fn bounds(&self) -> (Vector2, Vector2) {
+        (Vector2::new(-1., -1.), Vector2::new(1., 1.))
+    }
+
+    fn area(&self) -> f32 {
+        PI * (1. - self.inner_radius * self.inner_radius)
+    }
+
+    fn sample(&self, r1: f32, r2: f32) -> Vector2 {
+        // Uniform by area, the squared radius is uniform between the rims.
+        let inner_squared = self.inner_radius * self.inner_radius;
+        let radius = f32::sqrt(inner_squared + r1 * (1. - inner_squared));
+        let phi = 2. * PI * r2;
+        Vector2::new(radius * f32::cos(phi), radius * f32::sin(phi))
+    }
+}
@@ -0,0 +1,177 @@
+use super::SDFNode;
+use crate::{
+    core::{AxisAlignedBoundingBox, HitRecord, Interval, Point3, Ray, Sphere, Vector3},
+    traits::{Hittable, Material},
+};
+use std::rc::Rc;
+
+/**
+SDF Hittable
+
+A solid given by a signed distance function, intersected by sphere tracing: the ray steps forward
+by the distance to the surface, which can't be crossed within it, until it is close enough to
+count as a hit. Tracing is limited to the bounding box given with the function, and normals come
+from the gradient of the distance.
+
+Distortions such as twists make the function change faster than the distance it reports, raising
+`lipschitz` above one shortens the steps so the surface isn't stepped over. The uv coordinates
+follow the direction of the normal like those of a `Sphere`.
+
+# Examples
+
+```
+use raytracing::{
+    core::{
+        AxisAlignedBoundingBox, Color3, HitRecord, Interval, LambertianMaterial, Point3, Ray,
+        SDFHittable, SDFNode, Vector3,
+    },
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let bbox = AxisAlignedBoundingBox::new_with_two_points(
+    &Point3::new(-1., -1., -1.),
+    &Point3::new(1., 1., 1.),
+);
+let ball = SDFHittable::new(SDFNode::sphere(1.), bbox, material);
+let ray = Ray::new(Point3::new(0., 0., -3.), Vector3::new(0., 0., 1.));
+# let mut record = HitRecord::new();
+# assert!(ball.hit(&ray, &Interval::new(0., f32::INFINITY), &mut record));
+# assert!((record.t - 2.).abs() < 1e-3);
+# assert!((record.normal.unwrap().z + 1.).abs() < 1e-3);
+```
+*/
+#[derive(Debug)]
+pub struct SDFHittable {
+    /// The signed distance function.
+    sdf: SDFNode,
+
+    /// Box the surface lies within.
+    bbox: AxisAlignedBoundingBox,
+
+    /// Material of the surface.
+    material: Rc<dyn Material>,
+
+    /// Most steps along a ray before giving up.
+    pub max_steps: u32,
+
+    /// Distance to the surface that counts as a hit.
+    pub epsilon: f32,
+
+    /// Bound of how fast the function changes per unit of distance.
+    pub lipschitz: f32,
+}
+
+impl SDFHittable {
+    pub fn new(sdf: SDFNode, bbox: AxisAlignedBoundingBox, material: Rc<dyn Material>) -> Self {
+        Self {
+            sdf,
+            bbox,
+            material,
+            max_steps: 256,
+            epsilon: 1e-4,
+            lipschitz: 1.,
+        }
+    }
+
+    /// Returns the signed distance function.
+    pub fn sdf(&self) -> &SDFNode {
+        &self.sdf
+    }
+
+    /// Returns the outward normal at a point, from the gradient of the distance.
+    pub fn normal(&self, point: &Point3) -> Vector3 {
+        let h = f32::max(self.epsilon, 1e-4);
+        let derivative = |axis: usize| {
+            let mut forward = *point;
+            let mut backward = *point;
+            forward[axis] += h;
+            backward[axis] -= h;
+            self.sdf.distance(&forward) - self.sdf.distance(&backward)
+        };
+        Vector3::new(derivative(0), derivative(1), derivative(2)).normolize()
+    }
+
+    /// Internal method, returns the part of the ray interval within the bounding box.
+    fn clip(&self, ray: &Ray, ray_interval: &Interval) -> Option<(f32, f32)> {
+        let (mut t_min, mut t_max) = (ray_interval.min, ray_interval.max);
+        for axis in 0..3 {
+            let inverse = 1. / ray.direction[axis];
+            let mut t0 = (self.bbox.axis(axis).min - ray.origin[axis]) * inverse;
+            let mut t1 = (self.bbox.axis(axis).max - ray.origin[axis]) * inverse;
+            if inverse < 0. {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = f32::max(t_min, t0);
+            t_max = f32::min(t_max, t1);
+            if t_max < t_min {
+                return None;
+            }
+        }
+        Some((t_min, t_max))
+    }
+}
+
+impl Hittable for SDFHittable {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        let Some((t_min, t_max)) = self.clip(ray, ray_interval) else {
+            return false;
+        };
+
+        let ray_length = ray.direction.length();
+        let start = ray.at(t_min);
+        // March on the side the ray starts on. Rays leaving a point on the surface start on the
+        // side they move toward, so they don't hit it again right away, while rays entering the
+        // box right on the surface hit it there.
+        let start_distance = self.sdf.distance(&start);
+        let on_surface = start_distance.abs() < self.epsilon;
+        let clipped = t_min > ray_interval.min;
+        let side = if on_surface {
+            let toward = self.normal(&start).dot(&ray.direction).signum();
+            if clipped {
+                -toward
+            } else {
+                toward
+            }
+        } else {
+            start_distance.signum()
+        };
+
+        let mut t = t_min;
+        let mut left_surface = !on_surface || clipped;
+        for _ in 0..self.max_steps {
+            let distance = side * self.sdf.distance(&ray.at(t));
+            if distance < self.epsilon {
+                if left_surface {
+                    break;
+                }
+            } else {
+                left_surface = true;
+            }
+
+            t += f32::max(distance, self.epsilon) / (self.lipschitz * ray_length);
+            if t > t_max {
+                return false;
+            }
+        }
+        if !left_surface || side * self.sdf.distance(&ray.at(t)) >= self.epsilon {
+            // Out of steps without reaching the surface.
+            return false;
+        }
+
+        let point = ray.at(t);
+        let outward_normal = self.normal(&point);
+        record.t = t;
+        record.point = Some(point);
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &outward_normal);
+        record.uv = Some(Sphere::compute_uv(&outward_normal));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+}
@@ -0,0 +1,230 @@
+use crate::core::{Point3, Vector3};
+use std::{fmt, rc::Rc};
+
+/// A distance function given by a closure.
+#[derive(Clone)]
+pub struct SDFFunction(pub Rc<dyn Fn(&Point3) -> f32>);
+
+impl fmt::Debug for SDFFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SDFFunction")
+    }
+}
+
+/**
+Signed Distance Function Node
+
+A node of a tree of signed distance functions, negative inside of the solid, zero on its surface
+and positive outside. Leaves are primitives centered at the origin, or closures, and inner nodes
+combine or transform their children. The combinators and distortions return bounds of the distance
+rather than exact distances, `twist` and `scale` may need a larger Lipschitz bound in
+`SDFHittable`.
+
+# Examples
+
+```
+use raytracing::core::{Point3, SDFNode, Vector3};
+
+let rounded_cube = SDFNode::round_box(Vector3::new(1., 1., 1.), 0.2);
+let drilled = rounded_cube.smooth_subtraction(
+    SDFNode::capsule(Point3::new(0., -2., 0.), Point3::new(0., 2., 0.), 0.5),
+    0.1,
+);
+# assert!((SDFNode::sphere(1.).distance(&Point3::new(0., 2., 0.)) - 1.).abs() < 1e-6);
+# assert!(drilled.distance(&Point3::zero()) > 0.);
+# assert!(drilled.distance(&Point3::new(0.8, 0., 0.)) < 0.);
+```
+*/
+#[derive(Debug, Clone)]
+pub enum SDFNode {
+    /// A sphere of radius.
+    Sphere(f32),
+
+    /// A box of half extents.
+    Box(Vector3),
+
+    /// A box of half extents with edges rounded by radius.
+    RoundBox(Vector3, f32),
+
+    /// A torus around the y axis, with the major and minor radii.
+    Torus(f32, f32),
+
+    /// A capsule between two points, with radius.
+    Capsule(Point3, Point3, f32),
+
+    /// A distance function given by a closure.
+    Function(SDFFunction),
+
+    /// Inside of either node.
+    Union(Rc<SDFNode>, Rc<SDFNode>),
+
+    /// Inside of both nodes.
+    Intersection(Rc<SDFNode>, Rc<SDFNode>),
+
+    /// Inside of the first node but not of the second.
+    Subtraction(Rc<SDFNode>, Rc<SDFNode>),
+
+    /// Union blending the surfaces over a distance.
+    SmoothUnion(Rc<SDFNode>, Rc<SDFNode>, f32),
+
+    /// Subtraction blending the surfaces over a distance.
+    SmoothSubtraction(Rc<SDFNode>, Rc<SDFNode>, f32),
+
+    /// A node moved by an offset.
+    Translate(Rc<SDFNode>, Vector3),
+
+    /// A node scaled uniformly by a factor.
+    Scale(Rc<SDFNode>, f32),
+
+    /// A node repeated along each axis with a period, zero for no repetition.
+    Repeat(Rc<SDFNode>, Vector3),
+
+    /// A node twisted around the y axis by an angle in radians per unit of height.
+    Twist(Rc<SDFNode>, f32),
+}
+
+impl SDFNode {
+    pub fn sphere(radius: f32) -> Self {
+        SDFNode::Sphere(radius)
+    }
+
+    pub fn cube_box(half_extents: Vector3) -> Self {
+        SDFNode::Box(half_extents)
+    }
+
+    pub fn round_box(half_extents: Vector3, radius: f32) -> Self {
+        SDFNode::RoundBox(half_extents, radius)
+    }
+
+    pub fn torus(major_radius: f32, minor_radius: f32) -> Self {
+        SDFNode::Torus(major_radius, minor_radius)
+    }
+
+    pub fn capsule(start: Point3, end: Point3, radius: f32) -> Self {
+        SDFNode::Capsule(start, end, radius)
+    }
+
+    /// Create a node from a closure returning the signed distance to a point.
+    pub fn function<F: Fn(&Point3) -> f32 + 'static>(function: F) -> Self {
+        SDFNode::Function(SDFFunction(Rc::new(function)))
+    }
+
+    pub fn union(self, other: SDFNode) -> Self {
+        SDFNode::Union(Rc::new(self), Rc::new(other))
+    }
+
+    pub fn intersection(self, other: SDFNode) -> Self {
+        SDFNode::Intersection(Rc::new(self), Rc::new(other))
+    }
+
+    pub fn subtraction(self, other: SDFNode) -> Self {
+        SDFNode::Subtraction(Rc::new(self), Rc::new(other))
+    }
+
+    pub fn smooth_union(self, other: SDFNode, blend: f32) -> Self {
+        SDFNode::SmoothUnion(Rc::new(self), Rc::new(other), blend)
+    }
+
+    pub fn smooth_subtraction(self, other: SDFNode, blend: f32) -> Self {
+        SDFNode::SmoothSubtraction(Rc::new(self), Rc::new(other), blend)
+    }
+
+    pub fn translate(self, offset: Vector3) -> Self {
+        SDFNode::Translate(Rc::new(self), offset)
+    }
+
+    pub fn scale(self, factor: f32) -> Self {
+        SDFNode::Scale(Rc::new(self), factor)
+    }
+
+    pub fn repeat(self, period: Vector3) -> Self {
+        SDFNode::Repeat(Rc::new(self), period)
+    }
+
+    pub fn twist(self, rate: f32) -> Self {
+        SDFNode::Twist(Rc::new(self), rate)
+    }
+
+    /// Returns the signed distance from a point to the surface.
+    pub fn distance(&self, point: &Point3) -> f32 {
+        match self {
+            SDFNode::Sphere(radius) => point.length() - radius,
+            SDFNode::Box(half_extents) => SDFNode::box_distance(point, half_extents),
+            SDFNode::RoundBox(half_extents, radius) => {
+                let inner = Vector3::new(
+                    half_extents.x - radius,
+                    half_extents.y - radius,
+                    half_extents.z - radius,
+                );
+                SDFNode::box_distance(point, &inner) - radius
+            }
+            SDFNode::Torus(major_radius, minor_radius) => {
+                let ring = f32::sqrt(point.x * point.x + point.z * point.z) - major_radius;
+                f32::sqrt(ring * ring + point.y * point.y) - minor_radius
+            }
+            SDFNode::Capsule(start, end, radius) => {
+                let pa = point - start;
+                let ba = end - start;
+                let h = (pa.dot(&ba) / ba.length_squared()).clamp(0., 1.);
+                (&pa - &(&ba * h)).length() - radius
+            }
+            SDFNode::Function(function) => (function.0)(point),
+            SDFNode::Union(a, b) => f32::min(a.distance(point), b.distance(point)),
+            SDFNode::Intersection(a, b) => f32::max(a.distance(point), b.distance(point)),
+            SDFNode::Subtraction(a, b) => f32::max(a.distance(point), -b.distance(point)),
+            SDFNode::SmoothUnion(a, b, blend) => {
+                let (a, b) = (a.distance(point), b.distance(point));
+                if *blend <= 0. {
+                    return f32::min(a, b);
+                }
+                let h = (0.5 + 0.5 * (b - a) / blend).clamp(0., 1.);
+                b + (a - b) * h - blend * h * (1. - h)
+            }
+            SDFNode::SmoothSubtraction(a, b, blend) => {
+                let (a, b) = (a.distance(point), b.distance(point));
+                if *blend <= 0. {
+                    return f32::max(a, -b);
+                }
+                let h = (0.5 - 0.5 * (a + b) / blend).clamp(0., 1.);
+                a + (-b - a) * h + blend * h * (1. - h)
+            }
+            SDFNode::Translate(node, offset) => node.distance(&(point - offset)),
+            SDFNode::Scale(node, factor) => node.distance(&(point / *factor)) * factor,
+            SDFNode::Repeat(node, period) => {
+                let wrap = |x: f32, period: f32| {
+                    if period > 0. {
+                        x - period * (x / period).round()
+                    } else {
+                        x
+                    }
+                };
+                node.distance(&Point3::new(
+                    wrap(point.x, period.x),
+                    wrap(point.y, period.y),
+                    wrap(point.z, period.z),
+                ))
+            }
+            SDFNode::Twist(node, rate) => {
+                let angle = rate * point.y;
+                let (sin, cos) = angle.sin_cos();
+                node.distance(&Point3::new(
+                    cos * point.x - sin * point.z,
+                    point.y,
+                    sin * point.x + cos * point.z,
+                ))
+            }
+        }
+    }
+
+    /// Internal method, returns the signed distance to a box centered at the origin.
+    fn box_distance(point: &Point3, half_extents: &Vector3) -> f32 {
+        let q = Vector3::new(
+            point.x.abs() - half_extents.x,
+            point.y.abs() - half_extents.y,
+            point.z.abs() - half_extents.z,
+        );
+        let outside = Vector3::new(q.x.max(0.), q.y.max(0.), q.z.max(0.)).length();
+        let inside = f32::min(f32::max(q.x, f32::max(q.y, q.z)), 0.);
+        outside + inside
+    }
+}
@@ -76,6 +76,50 @@ impl Sphere {
         )
     }
 
+    /// Returns the partial derivatives of a point on the sphere with respect to u and v, given the
+    /// unit outward normal at that point. They are consistent with `compute_uv`.
+    pub fn compute_tangents(point: &Point3, radius: f32) -> (Vector3, Vector3) {
+        let pi = std::f32::consts::PI;
+        // Clamp sin(theta) away from zero so the poles still get a usable tangent frame.
+        let sin_theta = f32::max(f32::sqrt(1. - point.y * point.y), 1e-4);
+
+        let dpdu = Vector3::new(point.z, 0., -point.x).mul(2. * pi * radius);
+        let dpdv = Vector3::new(
+            -point.x * point.y / sin_theta,
+            sin_theta,
+            -point.y * point.z / sin_theta,
+        )
+        .mul(pi * radius);
+
+        (dpdu, dpdv)
+    }
+
+    /// Returns the average luminance emitted by the outside of the sphere, estimated on a grid of
+    /// points.
+    fn emitted_luminance(&self) -> f32 {
+        const GRID_SIZE: usize = 4;
+
+        let mut record = HitRecord::new();
+        record.front_face = true;
+
+        let mut luminance = 0.;
+        for i in 0..GRID_SIZE {
+            for j in 0..GRID_SIZE {
+                // Stratify the points by area over the sphere.
+                let y = 1. - 2. * (i as f32 + 0.5) / GRID_SIZE as f32;
+                let phi = 2. * std::f32::consts::PI * (j as f32 + 0.5) / GRID_SIZE as f32;
+                let radius = f32::sqrt(1. - y * y);
+                let normal = Vector3::new(radius * f32::cos(phi), y, radius * f32::sin(phi));
+
+                record.uv = Some(Sphere::compute_uv(&normal));
+                record.point = Some(self.center.add(&normal.mul(self.radius)));
+                record.normal = Some(normal);
+                luminance += self.material.emitted_luminance(&record);
+            }
+        }
+        luminance / (GRID_SIZE * GRID_SIZE) as f32
+    }
+
     fn random_to_sphere(radius: f32, distance_squared: f32) -> Vector3 {
         let mut rng = rand::thread_rng();
         let r1 = rng.gen::<f32>();
@@ -118,6 +162,10 @@ impl Hittable for Sphere {
 
         record.t = root;
         record.point = Some(ray.at(record.t));
+        // Moving spheres keep their points at the start of the motion in object space.
+        record.object_point = self
+            .is_moving
+            .then(|| &(&ray.at(record.t) - &center) + &self.center);
         let outward_normal = record
             .point
             .expect("Ray should always have some value at t.")
@@ -125,6 +173,9 @@ impl Hittable for Sphere {
             .div(self.radius);
         record.set_face_normal(&ray, &outward_normal);
         record.uv = Some(Sphere::compute_uv(&outward_normal));
+        let (dpdu, dpdv) = Sphere::compute_tangents(&outward_normal, self.radius);
+        record.dpdu = Some(dpdu);
+        record.dpdv = Some(dpdv);
         record.material = Some(Rc::clone(&self.material));
 
         return true;
@@ -158,4 +209,12 @@ impl Hittable for Sphere {
         let onb = OrthonormalBasis::new_with_w(&direction);
         onb.local(&Sphere::random_to_sphere(self.radius, distance_squared))
     }
+
+    fn area(&self) -> f32 {
+        4. * std::f32::consts::PI * self.radius * self.radius
+    }
+
+    fn power(&self) -> f32 {
+        std::f32::consts::PI * self.area() * self.emitted_luminance()
+    }
 }
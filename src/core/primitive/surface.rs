@@ -0,0 +1,144 @@
+use crate::{
+    core::{
+        AxisAlignedBoundingBox, HitRecord, Interval, OrthonormalBasis, Point3, Ray, Vector2,
+        Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::{f32::consts::PI, ops::Mul};
+
+/// Most surfaces a ray crosses when summing the sampling density, four for a torus.
+const MAX_CROSSINGS: usize = 4;
+
+/// Returns a right-handed basis whose z axis is `w`, the local frame of the rotationally symmetric
+/// surfaces.
+pub(super) fn right_handed_basis(w: &Vector3) -> OrthonormalBasis {
+    // The basis built around w alone has u × v = -w, swapping u and v turns it right-handed.
+    let basis = OrthonormalBasis::new_with_w(w);
+    OrthonormalBasis::new(*basis.v(), *basis.u(), *basis.w())
+}
+
+/// Returns the angle of a local point around the z axis, in `[0, 2π)`.
+pub(super) fn azimuth(x: f32, y: f32) -> f32 {
+    let phi = f32::atan2(y, x);
+    if phi < 0. {
+        phi + 2. * PI
+    } else {
+        phi
+    }
+}
+
+/// Returns the bounding box of a disk.
+pub(super) fn disk_bounding_box(
+    center: &Point3,
+    normal: &Vector3,
+    radius: f32,
+) -> AxisAlignedBoundingBox {
+    let normal = normal.normolize();
+    let extent = Vector3::new(
+        f32::sqrt(f32::max(1. - normal.x * normal.x, 0.)),
+        f32::sqrt(f32::max(1. - normal.y * normal.y, 0.)),
+        f32::sqrt(f32::max(1. - normal.z * normal.z, 0.)),
+    )
+    .mul(radius);
+    AxisAlignedBoundingBox::new_with_two_points(&(center - &extent), &(center + &extent)).pad()
+}
+
+/// Returns the outward normal, uv coordinates and tangents at a local point on the cap of a
+/// cylinder or cone, the disk of `radius` at the top or the bottom of the local z axis. The uv
+/// coordinates run around the cap and from its rim to its center.
+pub(super) fn cap_shading(
+    point: &Point3,
+    radius: f32,
+    top: bool,
+) -> (Vector3, Vector2, Vector3, Vector3) {
+    let rho = f32::sqrt(point.x * point.x + point.y * point.y);
+    let radial = if rho > 0. {
+        Vector3::new(point.x / rho, point.y / rho, 0.)
+    } else {
+        Vector3::new(1., 0., 0.)
+    };
+    let dpdv = radial.mul(-radius);
+    let u = azimuth(point.x, point.y) / (2. * PI);
+    let v = 1. - rho / radius;
+
+    // The bottom cap runs the other way around, keeping dpdu × dpdv along the outward normal.
+    if top {
+        let dpdu = Vector3::new(-point.y, point.x, 0.).mul(2. * PI);
+        (Vector3::new(0., 0., 1.), Vector2::new(u, v), dpdu, dpdv)
+    } else {
+        let dpdu = Vector3::new(point.y, -point.x, 0.).mul(2. * PI);
+        (
+            Vector3::new(0., 0., -1.),
+            Vector2::new(1. - u, v),
+            dpdu,
+            dpdv,
+        )
+    }
+}
+
+/// Returns the solid angle density of sampling points uniformly by area on the surface of a shape,
+/// seen from `origin` along `direction`. Every point of the surface the direction crosses could
+/// have been sampled, so their densities add up.
+pub(super) fn area_pdf_value(
+    shape: &dyn Hittable,
+    area: f32,
+    origin: &Point3,
+    direction: &Vector3,
+) -> f32 {
+    if area <= 0. {
+        return 0.;
+    }
+
+    let ray = Ray::new(*origin, *direction);
+    let mut t_min = 0.001;
+    let mut pdf = 0.;
+    for _ in 0..MAX_CROSSINGS {
+        let mut record = HitRecord::new();
+        if !shape.hit(&ray, &Interval::new(t_min, f32::INFINITY), &mut record) {
+            break;
+        }
+        let Some(normal) = record.normal else {
+            break;
+        };
+
+        let distance_squared = record.t * record.t * direction.length_squared();
+        let cosine = f32::abs(direction.dot(&normal) / direction.length());
+        if cosine > 0. {
+            pdf += distance_squared / (cosine * area);
+        }
+        t_min = record.t + 0.001;
+    }
+    pdf
+}
+
+/// Returns the average luminance emitted by one face of a surface, estimated on a grid of points.
+/// `surface_point` maps surface parameters in `[0, 1]²` to the point, the outward normal and the
+/// uv coordinates there.
+pub(super) fn average_emitted_luminance<F>(
+    material: &dyn Material,
+    front_face: bool,
+    surface_point: F,
+) -> f32
+where
+    F: Fn(f32, f32) -> (Point3, Vector3, Vector2),
+{
+    const GRID_SIZE: usize = 4;
+
+    let mut record = HitRecord::new();
+    record.front_face = front_face;
+
+    let mut luminance = 0.;
+    for i in 0..GRID_SIZE {
+        for j in 0..GRID_SIZE {
+            let u = (i as f32 + 0.5) / GRID_SIZE as f32;
+            let v = (j as f32 + 0.5) / GRID_SIZE as f32;
+            let (point, normal, uv) = surface_point(u, v);
+            record.point = Some(point);
+            record.normal = Some(if front_face { normal } else { normal.mul(-1.) });
+            record.uv = Some(uv);
+            luminance += material.emitted_luminance(&record);
+        }
+    }
+    luminance / (GRID_SIZE * GRID_SIZE) as f32
+}
@@ -0,0 +1,215 @@
+use super::surface::{area_pdf_value, average_emitted_luminance, azimuth, right_handed_basis};
+use crate::{
+    core::{
+        solve_quartic, AxisAlignedBoundingBox, HitRecord, Interval, OrthonormalBasis, Point3, Ray,
+        Vector2, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use rand::Rng;
+use std::{
+    f32::consts::PI,
+    ops::{Add, Mul},
+    rc::Rc,
+};
+
+/**
+Torus
+
+A ring torus around an axis through its center, the tube of `minor_radius` sweeping a circle of
+`major_radius`, which should be the larger of the two. Intersections solve a quartic equation. The
+u coordinate runs around the axis and v around the tube, starting from its outer equator. Points
+are sampled uniformly by area, so tori work as area lights.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, HitRecord, Interval, LambertianMaterial, Point3, Ray, Torus, Vector3},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+let ring = Torus::new(Point3::zero(), Vector3::up(), 2., 0.5, material);
+
+// A ray along the axis passes through the hole.
+let ray = Ray::new(Point3::new(0., 5., 0.), Vector3::new(0., -1., 0.));
+# let mut record = HitRecord::new();
+# assert!(!ring.hit(&ray, &Interval::new(0., 10.), &mut record));
+let ray = Ray::new(Point3::new(5., 0., 0.), Vector3::new(-1., 0., 0.));
+# assert!(ring.hit(&ray, &Interval::new(0., 10.), &mut record));
+# assert!((record.t - 2.5).abs() < 1e-4);
+```
+*/
+#[derive(Debug)]
+pub struct Torus {
+    /// Center of the torus.
+    pub center: Point3,
+
+    /// Radius of the circle through the middle of the tube.
+    pub major_radius: f32,
+
+    /// Radius of the tube.
+    pub minor_radius: f32,
+
+    /// Material of the torus.
+    material: Rc<dyn Material>,
+
+    /// Local frame whose z axis is the axis of the torus.
+    basis: OrthonormalBasis,
+
+    /// Bounding box of the torus.
+    bbox: AxisAlignedBoundingBox,
+}
+
+impl Torus {
+    pub fn new(
+        center: Point3,
+        axis: Vector3,
+        major_radius: f32,
+        minor_radius: f32,
+        material: Rc<dyn Material>,
+    ) -> Self {
+        let axis = axis.normolize();
+        let extent =
+            |axis: f32| major_radius * f32::sqrt(f32::max(1. - axis * axis, 0.)) + minor_radius;
+        let extent = Vector3::new(extent(axis.x), extent(axis.y), extent(axis.z));
+        Self {
+            center,
+            major_radius,
+            minor_radius,
+            material,
+            basis: right_handed_basis(&axis),
+            bbox: AxisAlignedBoundingBox::new_with_two_points(
+                &(&center - &extent),
+                &(&center + &extent),
+            ),
+        }
+    }
+
+    /// Returns the local point at the angles around the axis and around the tube.
+    fn local_point(&self, phi: f32, theta: f32) -> Point3 {
+        let rho = self.major_radius + self.minor_radius * f32::cos(theta);
+        Point3::new(
+            rho * f32::cos(phi),
+            rho * f32::sin(phi),
+            self.minor_radius * f32::sin(theta),
+        )
+    }
+
+    /// Returns the outward normal, uv coordinates and tangents at a local point.
+    fn shading(&self, point: &Point3) -> (Vector3, Vector2, Vector3, Vector3) {
+        let phi = azimuth(point.x, point.y);
+        // Offset of the point from the circle through the middle of the tube.
+        let radial = f32::sqrt(point.x * point.x + point.y * point.y) - self.major_radius;
+        let theta = azimuth(radial, point.z);
+        (
+            Vector3::new(radial * f32::cos(phi), radial * f32::sin(phi), point.z)
+                .mul(1. / self.minor_radius),
+            Vector2::new(phi / (2. * PI), theta / (2. * PI)),
+            Vector3::new(-point.y, point.x, 0.).mul(2. * PI),
+            Vector3::new(-point.z * f32::cos(phi), -point.z * f32::sin(phi), radial).mul(2. * PI),
+        )
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, ray: &Ray, ray_interval: &Interval, record: &mut HitRecord) -> bool {
+        if !self.bbox.hit(ray, ray_interval) {
+            return false;
+        }
+
+        let origin = self.basis.project(&(&ray.origin - &self.center));
+        let direction = self.basis.project(&ray.direction);
+        let length = direction.length() as f64;
+        if length == 0. {
+            return false;
+        }
+
+        // Solve along the unit direction from the point closest to the center, keeping the
+        // coefficients of the quartic small.
+        let d = [direction.x, direction.y, direction.z].map(|x| x as f64 / length);
+        let shift = -(origin.x as f64 * d[0] + origin.y as f64 * d[1] + origin.z as f64 * d[2]);
+        let o = [origin.x, origin.y, origin.z]
+            .iter()
+            .zip(d)
+            .map(|(&o, d)| o as f64 + shift * d)
+            .collect::<Vec<f64>>();
+
+        // (|p|² + R² - r²)² = 4 R² (x² + y²)
+        let major_squared = (self.major_radius as f64).powi(2);
+        let od = o[0] * d[0] + o[1] * d[1] + o[2] * d[2];
+        let k = o[0] * o[0] + o[1] * o[1] + o[2] * o[2] + major_squared
+            - (self.minor_radius as f64).powi(2);
+        let roots = solve_quartic(
+            1.,
+            4. * od,
+            2. * k + 4. * od * od - 4. * major_squared * (d[0] * d[0] + d[1] * d[1]),
+            4. * od * k - 8. * major_squared * (o[0] * d[0] + o[1] * d[1]),
+            k * k - 4. * major_squared * (o[0] * o[0] + o[1] * o[1]),
+        );
+        let Some(t) = roots
+            .into_iter()
+            .map(|root| ((root + shift) / length) as f32)
+            .find(|&t| ray_interval.contains(t))
+        else {
+            return false;
+        };
+
+        let local_point = origin.add(&direction.mul(t));
+        let (normal, uv, dpdu, dpdv) = self.shading(&local_point);
+        record.t = t;
+        record.point = Some(ray.at(t));
+        record.material = Some(Rc::clone(&self.material));
+        record.set_face_normal(ray, &self.basis.local(&normal));
+        record.uv = Some(uv);
+        record.dpdu = Some(self.basis.local(&dpdu));
+        record.dpdv = Some(self.basis.local(&dpdv));
+
+        true
+    }
+
+    fn bounding_box(&self) -> &AxisAlignedBoundingBox {
+        &self.bbox
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vector3) -> f32 {
+        area_pdf_value(self, self.area(), origin, direction)
+    }
+
+    fn random(&self, origin: &Point3) -> Vector3 {
+        let mut rng = rand::thread_rng();
+        let phi = 2. * PI * rng.gen::<f32>();
+        // The area element grows with the distance from the axis, sample the angle around the
+        // tube by rejection.
+        let theta = loop {
+            let theta = 2. * PI * rng.gen::<f32>();
+            let rho = self.major_radius + self.minor_radius * f32::cos(theta);
+            if rng.gen::<f32>() * (self.major_radius + self.minor_radius) <= rho {
+                break theta;
+            }
+        };
+        let point = self
+            .center
+            .add(&self.basis.local(&self.local_point(phi, theta)));
+        &point - origin
+    }
+
+    fn area(&self) -> f32 {
+        4. * PI * PI * self.major_radius * self.minor_radius
+    }
+
+    fn power(&self) -> f32 {
+        let luminance = average_emitted_luminance(self.material.as_ref(), true, |u, v| {
+            let point = self.local_point(2. * PI * u, 2. * PI * v);
+            let (normal, uv, _, _) = self.shading(&point);
+            (
+                self.center.add(&self.basis.local(&point)),
+                self.basis.local(&normal),
+                uv,
+            )
+        });
+        PI * self.area() * luminance
+    }
+}
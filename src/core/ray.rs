@@ -1,4 +1,4 @@
-use super::{Point3, Vector3};
+use super::{HitRecord, Point3, Vector3};
 use std::ops::{Add, Mul};
 
 /**
@@ -28,6 +28,8 @@ pub struct Ray {
     pub direction: Vector3,
     /// Time of the ray emission, relatived to the start of this frame, generally between 0 and 1.
     pub time: f32,
+    /// Offset rays through the neighbouring pixels, if the ray is traced from the camera.
+    pub differential: Option<RayDifferential>,
 }
 
 impl Ray {
@@ -37,6 +39,7 @@ impl Ray {
             origin,
             direction,
             time: 0.,
+            differential: None,
         }
     }
 
@@ -46,6 +49,22 @@ impl Ray {
             origin,
             direction,
             time,
+            differential: None,
+        }
+    }
+
+    /// Create a new `Ray` with origin, direction, time and the offset rays of its differential.
+    pub fn new_with_differential(
+        origin: Point3,
+        direction: Vector3,
+        time: f32,
+        differential: RayDifferential,
+    ) -> Self {
+        Self {
+            origin,
+            direction,
+            time,
+            differential: Some(differential),
         }
     }
 
@@ -53,4 +72,71 @@ impl Ray {
     pub fn at(&self, time: f32) -> Point3 {
         self.direction.mul(time).add(&self.origin)
     }
+
+    /// Returns the differential of the ray scattered specularly at a hit of this ray, reflected
+    /// if `refraction_ratio` is `None` and refracted otherwise, or `None` if this ray has no
+    /// differential. The surface is taken as locally flat.
+    pub fn scattered_differential(
+        &self,
+        hit_record: &HitRecord,
+        direction: &Vector3,
+        refraction_ratio: Option<f32>,
+    ) -> Option<RayDifferential> {
+        let differential = self.differential.as_ref()?;
+        let (Some(point), Some(normal), Some(dpdx), Some(dpdy)) = (
+            hit_record.point,
+            hit_record.normal,
+            hit_record.dpdx,
+            hit_record.dpdy,
+        ) else {
+            return None;
+        };
+
+        // The change of the scattered direction from the change of the incoming direction, with
+        // the normal facing the incoming ray.
+        let incoming = self.direction.normolize();
+        let cos_incoming = -incoming.dot(&normal);
+        let scattered = |offset_direction: &Vector3| {
+            let d_incoming = &offset_direction.normolize() - &incoming;
+            let d_cos_incoming = -d_incoming.dot(&normal);
+            let d_scattered = match refraction_ratio {
+                None => &d_incoming + &normal.mul(2. * d_cos_incoming),
+                Some(eta) => {
+                    // The refracted direction is eta * incoming + mu * normal.
+                    let cos_transmitted = -direction.normolize().dot(&normal);
+                    if cos_transmitted <= 0. {
+                        return None;
+                    }
+                    let d_mu = (eta - eta * eta * cos_incoming / cos_transmitted) * d_cos_incoming;
+                    &d_incoming.mul(eta) + &normal.mul(d_mu)
+                }
+            };
+            Some(&direction.normolize() + &d_scattered)
+        };
+
+        Some(RayDifferential {
+            rx_origin: &point + &dpdx,
+            rx_direction: scattered(&differential.rx_direction)?,
+            ry_origin: &point + &dpdy,
+            ry_direction: scattered(&differential.ry_direction)?,
+        })
+    }
+}
+
+/**
+Ray Differential
+
+The rays through the neighbouring pixels of a camera ray, one pixel along x and one along y. They
+track the footprint of the ray on the surfaces it hits, which image textures filter over.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayDifferential {
+    /// Origin of the ray offset along x.
+    pub rx_origin: Point3,
+    /// Direction of the ray offset along x.
+    pub rx_direction: Vector3,
+    /// Origin of the ray offset along y.
+    pub ry_origin: Point3,
+    /// Direction of the ray offset along y.
+    pub ry_direction: Vector3,
 }
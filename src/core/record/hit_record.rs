@@ -1,15 +1,37 @@
 use crate::{
-    core::{Point3, Ray, Vector2, Vector3},
+    core::{Color3, MediumInterface, Point3, Ray, Vector2, Vector3},
     traits::Material,
 };
-use std::{ops::Neg, rc::Rc};
+use std::{
+    ops::{Mul, Neg},
+    rc::Rc,
+};
 
 #[derive(Debug, Clone)]
 pub struct HitRecord {
     pub material: Option<Rc<dyn Material>>,
     pub point: Option<Point3>,
+    /// The hit point in the space of the object before instancing and motion, if the object is
+    /// instanced or moving, for textures that stick to the object.
+    pub object_point: Option<Point3>,
     pub normal: Option<Vector3>,
     pub uv: Option<Vector2>,
+    /// Partial derivative of the hit point with respect to u, a tangent of the surface.
+    pub dpdu: Option<Vector3>,
+    /// Partial derivative of the hit point with respect to v, a tangent of the surface.
+    pub dpdv: Option<Vector3>,
+    /// Change of the hit point from one pixel to the next along x, if the ray has a differential.
+    pub dpdx: Option<Vector3>,
+    /// Change of the hit point from one pixel to the next along y, if the ray has a differential.
+    pub dpdy: Option<Vector3>,
+    /// Change of the uv coordinates from one pixel to the next along x, the texture footprint.
+    pub duvdx: Option<Vector2>,
+    /// Change of the uv coordinates from one pixel to the next along y, the texture footprint.
+    pub duvdy: Option<Vector2>,
+    /// The media on both sides of the surface, if the surface bounds a participating medium.
+    pub medium_interface: Option<MediumInterface>,
+    /// Color interpolated from the vertices of a mesh, if the mesh has vertex colors.
+    pub vertex_color: Option<Color3>,
     pub t: f32,
     pub front_face: bool,
 }
@@ -19,8 +41,17 @@ impl HitRecord {
         Self {
             material: None,
             point: None,
+            object_point: None,
             normal: None,
             uv: None,
+            dpdu: None,
+            dpdv: None,
+            dpdx: None,
+            dpdy: None,
+            duvdx: None,
+            duvdy: None,
+            medium_interface: None,
+            vertex_color: None,
             t: f32::INFINITY,
             front_face: false,
         }
@@ -37,4 +68,55 @@ impl HitRecord {
             Some(unit_outward_normal.neg())
         };
     }
+
+    /// Computes the footprint of a ray differential at the hit, where the offset rays meet the
+    /// tangent plane, in world space and in uv coordinates.
+    pub fn compute_differentials(&mut self, ray: &Ray) {
+        let (Some(differential), Some(point), Some(normal)) =
+            (ray.differential.as_ref(), self.point, self.normal)
+        else {
+            return;
+        };
+        let offset = |origin: &Point3, direction: &Vector3| {
+            let t = normal.dot(&(&point - origin)) / normal.dot(direction);
+            t.is_finite()
+                .then(|| &(origin + &direction.mul(t)) - &point)
+        };
+        let (Some(dpdx), Some(dpdy)) = (
+            offset(&differential.rx_origin, &differential.rx_direction),
+            offset(&differential.ry_origin, &differential.ry_direction),
+        ) else {
+            return;
+        };
+        self.dpdx = Some(dpdx);
+        self.dpdy = Some(dpdy);
+
+        let (Some(dpdu), Some(dpdv)) = (self.dpdu, self.dpdv) else {
+            return;
+        };
+        // Least squares solution of dpdx = dpdu * dudx + dpdv * dvdx, and the same along y.
+        let (a00, a01, a11) = (dpdu.dot(&dpdu), dpdu.dot(&dpdv), dpdv.dot(&dpdv));
+        let inverse_determinant = 1. / (a00 * a11 - a01 * a01);
+        let inverse_determinant = if inverse_determinant.is_finite() {
+            inverse_determinant
+        } else {
+            0.
+        };
+        let solve = |dp: &Vector3| {
+            let (b0, b1) = (dpdu.dot(dp), dpdv.dot(dp));
+            let clamp = |value: f32| {
+                if value.is_finite() {
+                    value.clamp(-1e8, 1e8)
+                } else {
+                    0.
+                }
+            };
+            Vector2::new(
+                clamp((a11 * b0 - a01 * b1) * inverse_determinant),
+                clamp((a00 * b1 - a01 * b0) * inverse_determinant),
+            )
+        };
+        self.duvdx = Some(solve(&dpdx));
+        self.duvdy = Some(solve(&dpdy));
+    }
 }
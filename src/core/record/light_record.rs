@@ -0,0 +1,17 @@
+use crate::core::{Color3, Vector3};
+
+/// Light arriving at a point from a sampled light source.
+#[derive(Debug, Clone)]
+pub struct LightRecord {
+    /// Unit direction from the lit point towards the light.
+    pub direction: Vector3,
+
+    /// Distance from the lit point to the light, infinite for distant lights.
+    pub distance: f32,
+
+    /// Radiance arriving along `direction`, including the falloff for delta lights.
+    pub radiance: Color3,
+
+    /// Probability density of sampling `direction`, 1 for delta lights.
+    pub pdf: f32,
+}
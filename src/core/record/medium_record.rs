@@ -0,0 +1,12 @@
+use crate::core::Color3;
+
+/// A sampled interaction of a ray with a participating medium.
+#[derive(Debug, Clone)]
+pub struct MediumRecord {
+    /// Ray parameter of the scattering point, `None` if the ray passes through.
+    pub t: Option<f32>,
+
+    /// Factor applied to the light arriving from the scattering point, or from the end of the
+    /// interval when the ray passes through.
+    pub weight: Color3,
+}
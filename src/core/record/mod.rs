@@ -1,5 +1,9 @@
 mod hit_record;
+mod light_record;
+mod medium_record;
 mod scatter_record;
 
 pub use hit_record::*;
+pub use light_record::*;
+pub use medium_record::*;
 pub use scatter_record::*;
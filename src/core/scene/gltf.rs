@@ -0,0 +1,878 @@
+use crate::{
+    core::{
+        rad_to_deg, AlphaMask, AlphaSource, BVHNode, CameraBuilder, Color3, DirectionalLight,
+        EmissiveMaterial, HitRecord, HittableList, ImageEncoding, ImageTexture, LambertianMaterial,
+        Matrix4, MetalMaterial, MixMaterial, NormalMappedMaterial, Point3, PointLight, SpotLight,
+        TransformInstance, TriangleMesh, Vector2, Vector3, WrapMode,
+    },
+    traits::{Hittable, Light, Material, Texture},
+};
+use serde_json::Value;
+use std::{f32::consts::FRAC_PI_4, fs, io, ops::Mul, path::Path, rc::Rc};
+
+/// Magic number at the start of a `.glb` file.
+const GLB_MAGIC: &[u8] = b"glTF";
+
+/// Type of the chunk holding the JSON document of a `.glb` file.
+const CHUNK_JSON: u32 = 0x4E4F534A;
+
+/// Type of the chunk holding the binary buffer of a `.glb` file.
+const CHUNK_BIN: u32 = 0x004E4942;
+
+/// Extensions the importer understands, files requiring any other one are rejected.
+const SUPPORTED_EXTENSIONS: [&str; 2] = ["KHR_lights_punctual", "KHR_materials_emissive_strength"];
+
+/// Most values of an accessor without a buffer view, whose zeros aren't bounded by any buffer.
+const MAX_ZERO_VALUES: usize = 1 << 24;
+
+/// Returns an error for malformed data.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// Returns the elements of an array property, none if it is missing.
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Returns an element of an array property of the document.
+fn element<'a>(json: &'a Value, key: &str, index: usize) -> io::Result<&'a Value> {
+    array(json, key)
+        .get(index)
+        .ok_or_else(|| invalid(&format!("glTF {key} index {index} out of range")))
+}
+
+/// Returns an index property.
+fn index(value: &Value, key: &str) -> Option<usize> {
+    value.get(key).and_then(Value::as_u64).map(|i| i as usize)
+}
+
+/// Returns a number property, or a default if it is missing.
+fn number(value: &Value, key: &str, default: f32) -> f32 {
+    value
+        .get(key)
+        .and_then(Value::as_f64)
+        .map_or(default, |number| number as f32)
+}
+
+/// Returns a property holding `N` numbers.
+fn numbers<const N: usize>(value: &Value, key: &str) -> Option<[f32; N]> {
+    let array = value.get(key)?.as_array()?;
+    if array.len() != N {
+        return None;
+    }
+    let mut numbers = [0.; N];
+    for (number, value) in numbers.iter_mut().zip(array) {
+        *number = value.as_f64()? as f32;
+    }
+    Some(numbers)
+}
+
+/// Returns the bytes of base64 data, in the standard or the URL-safe alphabet.
+fn decode_base64(text: &str) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut bits, mut bit_count) = (0u32, 0);
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            _ => return Err(invalid("invalid base64 data in glTF URI")),
+        };
+        bits = bits << 6 | value as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            bytes.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+    Ok(bytes)
+}
+
+/// Returns a URI with its percent-encoded bytes decoded.
+fn decode_percent(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Returns the data a URI points at, embedded as base64 or in a file next to the glTF file.
+fn load_uri(uri: &str, base_dir: &Path) -> io::Result<Vec<u8>> {
+    match uri.strip_prefix("data:") {
+        Some(data) => {
+            let (_, payload) = data
+                .split_once(";base64,")
+                .ok_or_else(|| invalid("only base64 data URIs are supported in glTF files"))?;
+            decode_base64(payload)
+        }
+        None => fs::read(base_dir.join(decode_percent(uri))),
+    }
+}
+
+/// Returns the JSON chunk and the binary chunk of a `.glb` file.
+fn split_glb(bytes: &[u8]) -> io::Result<(&[u8], Option<&[u8]>)> {
+    let word = |offset: usize| {
+        u32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ])
+    };
+    if bytes.len() < 12 || word(4) != 2 {
+        return Err(invalid("unsupported GLB version"));
+    }
+
+    let (mut json, mut bin) = (None, None);
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let (length, chunk_type) = (word(offset) as usize, word(offset + 4));
+        let data = bytes
+            .get(offset + 8..offset + 8 + length)
+            .ok_or_else(|| invalid("unexpected end of GLB file"))?;
+        match chunk_type {
+            CHUNK_JSON if json.is_none() => json = Some(data),
+            CHUNK_BIN if bin.is_none() => bin = Some(data),
+            _ => {}
+        }
+        offset += 8 + length;
+    }
+    Ok((
+        json.ok_or_else(|| invalid("GLB file without JSON chunk"))?,
+        bin,
+    ))
+}
+
+/// Returns the size of a component type in bytes.
+fn component_size(component_type: u64) -> io::Result<usize> {
+    match component_type {
+        5120 | 5121 => Ok(1),
+        5122 | 5123 => Ok(2),
+        5125 | 5126 => Ok(4),
+        _ => Err(invalid("unknown glTF accessor component type")),
+    }
+}
+
+/// Returns a component read from little endian bytes, brought to `[0, 1]` or `[-1, 1]` if it is
+/// a normalized integer.
+fn read_component(bytes: &[u8], component_type: u64, normalized: bool) -> f64 {
+    let (value, scale) = match component_type {
+        5120 => (bytes[0] as i8 as f64, 127.),
+        5121 => (bytes[0] as f64, 255.),
+        5122 => (i16::from_le_bytes([bytes[0], bytes[1]]) as f64, 32767.),
+        5123 => (u16::from_le_bytes([bytes[0], bytes[1]]) as f64, 65535.),
+        5125 => (
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            1.,
+        ),
+        _ => (
+            f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+            1.,
+        ),
+    };
+    if normalized && component_type != 5126 {
+        f64::max(value / scale, -1.)
+    } else {
+        value
+    }
+}
+
+/**
+Tinted Texture
+
+The textures of a glTF material, a texture multiplied by a constant factor and, for base colors,
+by the vertex colors of the mesh. Without a texture it is the factor alone.
+*/
+#[derive(Debug)]
+struct TintedTexture {
+    /// The texture, `None` for a constant color.
+    texture: Option<Rc<dyn Texture>>,
+
+    /// The color multiplying the texture.
+    factor: Color3,
+
+    /// The opacity multiplying the alpha of the texture.
+    alpha: f32,
+
+    /// Whether the vertex colors multiply the texture.
+    vertex_colors: bool,
+}
+
+impl Texture for TintedTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        match &self.texture {
+            Some(texture) => &texture.value(uv, point) * &self.factor,
+            None => self.factor,
+        }
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let color = match &self.texture {
+            Some(texture) => &texture.value_at_hit(hit_record) * &self.factor,
+            None => self.factor,
+        };
+        match hit_record.vertex_color {
+            Some(vertex_color) if self.vertex_colors => &color * &vertex_color,
+            _ => color,
+        }
+    }
+
+    fn alpha(&self, uv: &Vector2, point: &Point3) -> f32 {
+        match &self.texture {
+            Some(texture) => texture.alpha(uv, point) * self.alpha,
+            None => self.alpha,
+        }
+    }
+}
+
+/// A glTF material mapped onto the crate, with the alpha mask its primitives are cut by.
+#[derive(Debug, Clone)]
+struct MaterialEntry {
+    material: Rc<dyn Material>,
+
+    /// The opacity texture and the alpha cutoff, `None` as cutoff for blended alpha.
+    alpha: Option<(Rc<dyn Texture>, Option<f32>)>,
+}
+
+/// The state of an import, the document with its buffers, textures, materials and meshes.
+struct Importer<'a> {
+    json: &'a Value,
+    buffers: Vec<Vec<u8>>,
+    textures: Vec<Option<Rc<dyn Texture>>>,
+    materials: Vec<MaterialEntry>,
+    default_material: MaterialEntry,
+    meshes: Vec<Option<Rc<dyn Hittable>>>,
+}
+
+impl<'a> Importer<'a> {
+    fn new(json: &'a Value, bin: Option<&[u8]>, base_dir: &Path) -> io::Result<Self> {
+        let mut buffers = Vec::new();
+        for (i, buffer) in array(json, "buffers").iter().enumerate() {
+            let data = match (buffer.get("uri").and_then(Value::as_str), bin) {
+                (Some(uri), _) => load_uri(uri, base_dir)?,
+                (None, Some(bin)) if i == 0 => bin.to_vec(),
+                _ => return Err(invalid("glTF buffer without data")),
+            };
+            if data.len() < index(buffer, "byteLength").unwrap_or(0) {
+                return Err(invalid("glTF buffer shorter than its byteLength"));
+            }
+            buffers.push(data);
+        }
+
+        let mut importer = Self {
+            json,
+            buffers,
+            textures: Vec::new(),
+            materials: Vec::new(),
+            // Replaced below by the default material of glTF, a white rough metal.
+            default_material: MaterialEntry {
+                material: Rc::new(LambertianMaterial::new_with_color(Color3::one())),
+                alpha: None,
+            },
+            meshes: Vec::new(),
+        };
+
+        // Images are decoded once, however many textures use them. Color images are sRGB and
+        // normal maps hold raw data.
+        let source = |info: &Value| {
+            let texture = index(info, "index")?;
+            index(array(json, "textures").get(texture)?, "source")
+        };
+        let normal_maps: Vec<usize> = array(json, "materials")
+            .iter()
+            .filter_map(|material| source(material.get("normalTexture")?))
+            .collect();
+        let mut images: Vec<Option<Rc<dyn Texture>>> = Vec::new();
+        for (i, image) in array(json, "images").iter().enumerate() {
+            let encoding = if normal_maps.contains(&i) {
+                ImageEncoding::Raw
+            } else {
+                ImageEncoding::SRGB
+            };
+            images.push(Some(Rc::new(importer.image(image, base_dir, encoding)?)));
+        }
+        importer.textures = array(json, "textures")
+            .iter()
+            .map(|texture| index(texture, "source").and_then(|i| images.get(i).cloned()?))
+            .collect();
+
+        importer.default_material = importer.material(&Value::Null)?;
+        importer.materials = array(json, "materials")
+            .iter()
+            .map(|material| importer.material(material))
+            .collect::<io::Result<_>>()?;
+        importer.meshes = array(json, "meshes")
+            .iter()
+            .map(|mesh| importer.mesh(mesh))
+            .collect::<io::Result<_>>()?;
+        Ok(importer)
+    }
+
+    /// Returns the bytes of a buffer view and the stride between its elements, if set.
+    fn buffer_view(&self, view: usize) -> io::Result<(&[u8], Option<usize>)> {
+        let view = element(self.json, "bufferViews", view)?;
+        let buffer = index(view, "buffer")
+            .and_then(|buffer| self.buffers.get(buffer))
+            .ok_or_else(|| invalid("glTF buffer view without buffer"))?;
+        let offset = index(view, "byteOffset").unwrap_or(0);
+        let length = index(view, "byteLength").unwrap_or(0);
+        let data = offset
+            .checked_add(length)
+            .and_then(|end| buffer.get(offset..end))
+            .ok_or_else(|| invalid("glTF buffer view out of range"))?;
+        Ok((data, index(view, "byteStride")))
+    }
+
+    /// Returns the values of an accessor, flattened, and the number of components per element.
+    fn accessor(&self, accessor: usize) -> io::Result<(Vec<f64>, usize)> {
+        let accessor = element(self.json, "accessors", accessor)?;
+        if accessor.get("sparse").is_some() {
+            return Err(invalid("sparse glTF accessors are not supported"));
+        }
+        let count =
+            index(accessor, "count").ok_or_else(|| invalid("glTF accessor without count"))?;
+        let components = match accessor.get("type").and_then(Value::as_str) {
+            Some("SCALAR") => 1,
+            Some("VEC2") => 2,
+            Some("VEC3") => 3,
+            Some("VEC4") | Some("MAT2") => 4,
+            Some("MAT3") => 9,
+            Some("MAT4") => 16,
+            _ => return Err(invalid("unknown glTF accessor type")),
+        };
+        let component_type = accessor
+            .get("componentType")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let size = component_size(component_type)?;
+        let normalized = accessor
+            .get("normalized")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        // Accessors without a buffer view are all zeros.
+        let value_count = count
+            .checked_mul(components)
+            .ok_or_else(|| invalid("glTF accessor out of range"))?;
+        let Some(view) = index(accessor, "bufferView") else {
+            if value_count > MAX_ZERO_VALUES {
+                return Err(invalid("glTF accessor too large"));
+            }
+            return Ok((vec![0.; value_count], components));
+        };
+        let (data, stride) = self.buffer_view(view)?;
+        let offset = index(accessor, "byteOffset").unwrap_or(0);
+        let stride = stride.unwrap_or(size * components);
+        // Elements may not overlap, which also bounds the count by the length of the data.
+        if stride < size * components {
+            return Err(invalid("glTF buffer view stride too small"));
+        }
+        if count > 0 {
+            // The end of the last element, which must fit in the buffer view.
+            let end = stride
+                .checked_mul(count - 1)
+                .and_then(|last| last.checked_add(offset))
+                .and_then(|last| last.checked_add(size * components));
+            if end.is_none_or(|end| end > data.len()) {
+                return Err(invalid("glTF accessor out of range"));
+            }
+        }
+
+        let mut values = Vec::with_capacity(count * components);
+        for i in 0..count {
+            for component in 0..components {
+                let start = offset + stride * i + size * component;
+                values.push(read_component(&data[start..], component_type, normalized));
+            }
+        }
+        Ok((values, components))
+    }
+
+    /// Returns a decoded image, from a URI or a buffer view.
+    fn image(
+        &self,
+        image: &Value,
+        base_dir: &Path,
+        encoding: ImageEncoding,
+    ) -> io::Result<ImageTexture> {
+        let bytes = match (
+            image.get("uri").and_then(Value::as_str),
+            index(image, "bufferView"),
+        ) {
+            (Some(uri), _) => load_uri(uri, base_dir)?,
+            (None, Some(view)) => self.buffer_view(view)?.0.to_vec(),
+            _ => return Err(invalid("glTF image without data")),
+        };
+        let image = image::load_from_memory(&bytes)
+            .map_err(|error| invalid(&format!("invalid glTF image: {error}")))?;
+        // Samplers aren't read, so textures repeat as glTF samplers do by default.
+        let mut texture = ImageTexture::new_with_image(&image, encoding);
+        texture.wrap = WrapMode::Repeat;
+        Ok(texture)
+    }
+
+    /// Returns the texture of a texture info, `None` if there is none.
+    fn texture(&self, info: Option<&Value>) -> Option<Rc<dyn Texture>> {
+        let texture = index(info?, "index")?;
+        self.textures.get(texture).cloned()?
+    }
+
+    /// Returns the crate material standing for a glTF material.
+    fn material(&self, material: &Value) -> io::Result<MaterialEntry> {
+        let pbr = material.get("pbrMetallicRoughness").unwrap_or(&Value::Null);
+        let [r, g, b, a] = numbers(pbr, "baseColorFactor").unwrap_or([1.; 4]);
+        let base_color: Rc<dyn Texture> = Rc::new(TintedTexture {
+            texture: self.texture(pbr.get("baseColorTexture")),
+            factor: Color3::new(r, g, b),
+            alpha: a,
+            vertex_colors: true,
+        });
+
+        // Dielectrics scatter diffusely and metals specularly, blurred by the roughness.
+        let metallic = number(pbr, "metallicFactor", 1.).clamp(0., 1.);
+        let roughness = number(pbr, "roughnessFactor", 1.).clamp(0., 1.);
+        let diffuse: Rc<dyn Material> = Rc::new(LambertianMaterial::new(Rc::clone(&base_color)));
+        let metal: Rc<dyn Material> =
+            Rc::new(MetalMaterial::new(Rc::clone(&base_color), roughness));
+        let mut result: Rc<dyn Material> = if metallic >= 1. {
+            metal
+        } else if metallic <= 0. {
+            diffuse
+        } else {
+            Rc::new(MixMaterial::new_with_weight(diffuse, metal, metallic))
+        };
+
+        let strength = material
+            .pointer("/extensions/KHR_materials_emissive_strength")
+            .map_or(1., |extension| number(extension, "emissiveStrength", 1.));
+        let [r, g, b] = numbers(material, "emissiveFactor").unwrap_or([0.; 3]);
+        let emission = Color3::new(r, g, b).mul(strength);
+        if !emission.equals_zero() {
+            let texture = Rc::new(TintedTexture {
+                texture: self.texture(material.get("emissiveTexture")),
+                factor: emission,
+                alpha: 1.,
+                vertex_colors: false,
+            });
+            let double_sided = material
+                .get("doubleSided")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            result = if double_sided {
+                Rc::new(EmissiveMaterial::new_two_sided(texture))
+            } else {
+                Rc::new(EmissiveMaterial::new(texture))
+            };
+        }
+
+        if let Some(normal_map) = self.texture(material.get("normalTexture")) {
+            result = Rc::new(NormalMappedMaterial::new_with_normal_map(
+                result, normal_map,
+            ));
+        }
+
+        let alpha = match material.get("alphaMode").and_then(Value::as_str) {
+            Some("MASK") => Some((base_color, Some(number(material, "alphaCutoff", 0.5)))),
+            Some("BLEND") => Some((base_color, None)),
+            _ => None,
+        };
+        Ok(MaterialEntry {
+            material: result,
+            alpha,
+        })
+    }
+
+    /// Returns the values of a vertex attribute, checking there is one per vertex and each has
+    /// one of the allowed numbers of components.
+    fn attribute(
+        &self,
+        attributes: &Value,
+        name: &str,
+        allowed_components: &[usize],
+        vertex_count: usize,
+    ) -> io::Result<Option<(Vec<f64>, usize)>> {
+        let Some(accessor) = index(attributes, name) else {
+            return Ok(None);
+        };
+        let (values, components) = self.accessor(accessor)?;
+        if !allowed_components.contains(&components) {
+            return Err(invalid(&format!("glTF {name} of invalid type")));
+        }
+        if values.len() != vertex_count * components {
+            return Err(invalid(&format!("glTF {name} count mismatch")));
+        }
+        Ok(Some((values, components)))
+    }
+
+    /// Returns a mesh primitive, `None` for points and lines.
+    fn primitive(&self, primitive: &Value) -> io::Result<Option<Rc<dyn Hittable>>> {
+        let mode = index(primitive, "mode").unwrap_or(4);
+        if !(4..=6).contains(&mode) {
+            return Ok(None);
+        }
+        let attributes = primitive.get("attributes").unwrap_or(&Value::Null);
+        let position = index(attributes, "POSITION")
+            .ok_or_else(|| invalid("glTF primitive without positions"))?;
+        let (values, components) = self.accessor(position)?;
+        if components != 3 {
+            return Err(invalid("glTF positions must be 3D vectors"));
+        }
+        let vector = |v: &[f64]| Vector3::new(v[0] as f32, v[1] as f32, v[2] as f32);
+        let positions: Vec<Point3> = values.chunks_exact(3).map(vector).collect();
+
+        // Triangle strips flip every other triangle to keep the winding.
+        let vertices: Vec<usize> = match index(primitive, "indices") {
+            Some(indices) => self
+                .accessor(indices)?
+                .0
+                .iter()
+                .map(|&i| i as usize)
+                .collect(),
+            None => (0..positions.len()).collect(),
+        };
+        let triangles: Vec<[usize; 3]> = match mode {
+            4 => vertices
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+                .collect(),
+            5 => (2..vertices.len())
+                .map(|i| match i % 2 {
+                    0 => [vertices[i - 2], vertices[i - 1], vertices[i]],
+                    _ => [vertices[i - 1], vertices[i - 2], vertices[i]],
+                })
+                .collect(),
+            _ => (2..vertices.len())
+                .map(|i| [vertices[0], vertices[i - 1], vertices[i]])
+                .collect(),
+        };
+        if triangles.is_empty() {
+            return Ok(None);
+        }
+        if triangles.iter().flatten().any(|&i| i >= positions.len()) {
+            return Err(invalid("glTF vertex index out of range"));
+        }
+
+        let vertex_count = positions.len();
+        let mut mesh = TriangleMesh::new(positions, triangles);
+        mesh.normals = self
+            .attribute(attributes, "NORMAL", &[3], vertex_count)?
+            .map(|(values, _)| values.chunks_exact(3).map(vector).collect());
+        // glTF images start at the top, the crate reads v from the bottom.
+        mesh.uvs = self
+            .attribute(attributes, "TEXCOORD_0", &[2], vertex_count)?
+            .map(|(values, _)| {
+                values
+                    .chunks_exact(2)
+                    .map(|uv| Vector2::new(uv[0] as f32, 1. - uv[1] as f32))
+                    .collect()
+            });
+        mesh.colors = self
+            .attribute(attributes, "COLOR_0", &[3, 4], vertex_count)?
+            .map(|(values, components)| values.chunks_exact(components).map(vector).collect());
+
+        let entry = match index(primitive, "material") {
+            Some(material) => self
+                .materials
+                .get(material)
+                .ok_or_else(|| invalid("glTF material index out of range"))?,
+            None => &self.default_material,
+        };
+        let object: Rc<dyn Hittable> = Rc::new(BVHNode::new(
+            &mut mesh.triangles(Rc::clone(&entry.material)),
+        ));
+        Ok(Some(match &entry.alpha {
+            Some((mask, Some(cutoff))) => Rc::new(AlphaMask::new_with_threshold(
+                object,
+                Rc::clone(mask),
+                AlphaSource::AlphaChannel,
+                *cutoff,
+            )),
+            Some((mask, None)) => Rc::new(AlphaMask::new_stochastic(
+                object,
+                Rc::clone(mask),
+                AlphaSource::AlphaChannel,
+            )),
+            None => object,
+        }))
+    }
+
+    /// Returns a mesh with all of its primitives, `None` if it has no surface.
+    fn mesh(&self, mesh: &Value) -> io::Result<Option<Rc<dyn Hittable>>> {
+        let mut list = HittableList::new();
+        for primitive in array(mesh, "primitives") {
+            if let Some(object) = self.primitive(primitive)? {
+                list.add(object);
+            }
+        }
+        Ok(match list.objects.len() {
+            0 => None,
+            1 => list.objects.pop(),
+            _ => Some(Rc::new(BVHNode::new(&mut list))),
+        })
+    }
+
+    /// Returns the camera of a node, `None` for orthographic cameras.
+    fn camera(&self, camera: usize, transform: &Matrix4) -> io::Result<Option<CameraBuilder>> {
+        let camera = element(self.json, "cameras", camera)?;
+        if camera.get("type").and_then(Value::as_str) != Some("perspective") {
+            return Ok(None);
+        }
+        let perspective = camera
+            .get("perspective")
+            .ok_or_else(|| invalid("glTF perspective camera without perspective"))?;
+        let y_fov = perspective
+            .get("yfov")
+            .and_then(Value::as_f64)
+            .ok_or_else(|| invalid("glTF camera without yfov"))?;
+
+        // Cameras look down their negative z axis with y up.
+        let position = transform.transform_point(&Point3::zero());
+        let forward = transform
+            .transform_vector(&Vector3::new(0., 0., -1.))
+            .normolize();
+        let up = transform.transform_vector(&Vector3::up()).normolize();
+        let mut builder = CameraBuilder::default();
+        builder
+            .position(position)
+            .target(&position + &forward)
+            .up(up)
+            .fov(rad_to_deg(y_fov as f32));
+        if let Some(aspect) = perspective.get("aspectRatio").and_then(Value::as_f64) {
+            builder.aspect(aspect as f32);
+        }
+        Ok(Some(builder))
+    }
+
+    /// Returns a light of the `KHR_lights_punctual` extension placed by a node.
+    fn light(&self, light: usize, transform: &Matrix4) -> io::Result<Rc<dyn Light>> {
+        let light = self
+            .json
+            .pointer("/extensions/KHR_lights_punctual/lights")
+            .and_then(|lights| lights.get(light))
+            .ok_or_else(|| invalid("glTF light index out of range"))?;
+        let [r, g, b] = numbers(light, "color").unwrap_or([1.; 3]);
+        let intensity = Color3::new(r, g, b).mul(number(light, "intensity", 1.));
+
+        // Lights point down their negative z axis.
+        let position = transform.transform_point(&Point3::zero());
+        let direction = transform
+            .transform_vector(&Vector3::new(0., 0., -1.))
+            .normolize();
+        match light.get("type").and_then(Value::as_str) {
+            Some("point") => Ok(Rc::new(PointLight::new(position, intensity))),
+            Some("spot") => {
+                let spot = light.get("spot").unwrap_or(&Value::Null);
+                Ok(Rc::new(SpotLight::new(
+                    position,
+                    &position + &direction,
+                    intensity,
+                    rad_to_deg(number(spot, "outerConeAngle", FRAC_PI_4)),
+                    rad_to_deg(number(spot, "innerConeAngle", 0.)),
+                )))
+            }
+            Some("directional") => Ok(Rc::new(DirectionalLight::new(direction, intensity))),
+            _ => Err(invalid("unknown glTF light type")),
+        }
+    }
+
+    /// Adds a node and its children to the scene, under the transform of its parent.
+    fn visit(
+        &self,
+        node: usize,
+        parent: &Matrix4,
+        depth: usize,
+        scene: &mut GLTFScene,
+    ) -> io::Result<()> {
+        let nodes = array(self.json, "nodes");
+        if depth > nodes.len() {
+            return Err(invalid("glTF node hierarchy with a cycle"));
+        }
+        let node = element(self.json, "nodes", node)?;
+        let local = match numbers::<16>(node, "matrix") {
+            Some(matrix) => Matrix4::new_with_columns(&matrix),
+            None => {
+                let [x, y, z] = numbers(node, "translation").unwrap_or([0.; 3]);
+                let rotation = numbers(node, "rotation").unwrap_or([0., 0., 0., 1.]);
+                let [sx, sy, sz] = numbers(node, "scale").unwrap_or([1.; 3]);
+                Matrix4::new_with_trs(&Vector3::new(x, y, z), &rotation, &Vector3::new(sx, sy, sz))
+            }
+        };
+        let transform = parent * &local;
+
+        // Nodes scaled to nothing are hidden.
+        let mesh = index(node, "mesh").map(|mesh| self.meshes.get(mesh));
+        match mesh {
+            Some(None) => return Err(invalid("glTF mesh index out of range")),
+            Some(Some(Some(object))) if transform == Matrix4::identity() => {
+                scene.world.add(Rc::clone(object));
+            }
+            Some(Some(Some(object))) if transform.inverse().is_some() => {
+                scene.world.add(Rc::new(TransformInstance::new(
+                    Rc::clone(object),
+                    transform,
+                )));
+            }
+            _ => {}
+        }
+        if let Some(camera) = index(node, "camera") {
+            scene.cameras.extend(self.camera(camera, &transform)?);
+        }
+        if let Some(light) = node
+            .pointer("/extensions/KHR_lights_punctual/light")
+            .and_then(Value::as_u64)
+        {
+            scene
+                .light_sources
+                .push(self.light(light as usize, &transform)?);
+        }
+
+        for child in array(node, "children") {
+            let child = child
+                .as_u64()
+                .ok_or_else(|| invalid("invalid glTF child index"))?;
+            self.visit(child as usize, &transform, depth + 1, scene)?;
+        }
+        Ok(())
+    }
+}
+
+/**
+glTF Scene
+
+A scene imported from a glTF 2.0 file, either a `.gltf` file with its buffers and images embedded
+or next to it, or a binary `.glb` file. The node hierarchy of the default scene is walked from its
+roots: meshes become `TransformInstance`s of a `BVHNode` shared by every node using the mesh,
+perspective cameras become `CameraBuilder`s with their position, target, up direction, fov and
+aspect set, and the lights of the `KHR_lights_punctual` extension become point, spot and
+directional lights.
+
+Metallic-roughness materials map onto the materials of the crate: dielectrics become a
+`LambertianMaterial`, metals a `MetalMaterial` fuzzed by the roughness, and partly metallic
+materials a `MixMaterial` of the two weighted by the metallic factor. Emissive materials become an
+`EmissiveMaterial`, normal textures wrap the material in a `NormalMappedMaterial`, and masked or
+blended alpha cuts the primitive with an `AlphaMask`. Color factors and vertex colors multiply the
+textures. Metallic-roughness and occlusion textures, texture samplers, skins, morph targets and
+animations are ignored.
+
+# Examples
+
+```no_run
+use raytracing::{
+    core::{BVHNode, CameraBuilder, GLTFScene},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let mut scene = GLTFScene::load("assets/scene.glb").unwrap();
+let camera = scene
+    .cameras
+    .first()
+    .cloned()
+    .unwrap_or_else(CameraBuilder::default)
+    .light_sources(scene.light_sources.clone())
+    .width(800)
+    .build()
+    .unwrap();
+let world: Rc<dyn Hittable> = Rc::new(BVHNode::new(&mut scene.world));
+```
+*/
+pub struct GLTFScene {
+    /// The objects of the scene, one for each node with a mesh.
+    pub world: HittableList,
+
+    /// The lights of the `KHR_lights_punctual` extension.
+    pub light_sources: Vec<Rc<dyn Light>>,
+
+    /// The perspective cameras of the scene, in the order of the node hierarchy.
+    pub cameras: Vec<CameraBuilder>,
+}
+
+impl GLTFScene {
+    /// Load a `GLTFScene` from a `.gltf` or `.glb` file, external buffers and images are read
+    /// relative to the file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&fs::read(path)?, base_dir)
+    }
+
+    /// Parse a `GLTFScene` from the content of a `.gltf` or `.glb` file, telling them apart by the
+    /// magic number of `.glb` files. External buffers and images are read relative to `base_dir`.
+    pub fn parse(bytes: &[u8], base_dir: &Path) -> io::Result<Self> {
+        let (json, bin) = if bytes.starts_with(GLB_MAGIC) {
+            split_glb(bytes)?
+        } else {
+            (bytes, None)
+        };
+        let json: Value = serde_json::from_slice(json)
+            .map_err(|error| invalid(&format!("invalid glTF JSON: {error}")))?;
+
+        let version = json.pointer("/asset/version").and_then(Value::as_str);
+        if !version.is_some_and(|version| version.starts_with("2.")) {
+            return Err(invalid("unsupported glTF version"));
+        }
+        for extension in array(&json, "extensionsRequired") {
+            let extension = extension.as_str().unwrap_or_default();
+            if !SUPPORTED_EXTENSIONS.contains(&extension) {
+                return Err(invalid(&format!(
+                    "unsupported required glTF extension {extension}"
+                )));
+            }
+        }
+
+        let importer = Importer::new(&json, bin, base_dir)?;
+        let mut scene = Self {
+            world: HittableList::new(),
+            light_sources: Vec::new(),
+            cameras: Vec::new(),
+        };
+
+        // Without scenes, every node that is no child of another is a root.
+        let scene_index = index(&json, "scene").unwrap_or(0);
+        let roots: Vec<usize> = match array(&json, "scenes").get(scene_index) {
+            Some(root_scene) => array(root_scene, "nodes")
+                .iter()
+                .filter_map(|node| node.as_u64().map(|node| node as usize))
+                .collect(),
+            None => {
+                let nodes = array(&json, "nodes");
+                let children: Vec<u64> = nodes
+                    .iter()
+                    .flat_map(|node| array(node, "children"))
+                    .filter_map(Value::as_u64)
+                    .collect();
+                (0..nodes.len())
+                    .filter(|&node| !children.contains(&(node as u64)))
+                    .collect()
+            }
+        };
+        for root in roots {
+            importer.visit(root, &Matrix4::identity(), 0, &mut scene)?;
+        }
+        Ok(scene)
+    }
+}
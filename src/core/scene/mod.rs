@@ -0,0 +1,5 @@
+mod gltf;
+mod pbrt;
+
+pub use gltf::*;
+pub use pbrt::*;
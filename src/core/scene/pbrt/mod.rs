@@ -0,0 +1,1159 @@
+mod parameters;
+mod textures;
+
+use self::{
+    parameters::{invalid, tokenize, ParameterValue, Parameters, Token},
+    textures::{uv_transform, TextureType},
+};
+use crate::{
+    core::{
+        blackbody, deg_to_rad, rad_to_deg, BVHNode, CameraBuilder, ChannelTexture, CheckerTexture,
+        Color3, DielectricMaterial, DirectionalLight, EmissiveMaterial, EnvironmentLight,
+        HittableList, ImageEncoding, ImageFilter, ImageTexture, LambertianMaterial, Matrix4,
+        MetalMaterial, NormalMappedMaterial, Point3, PointLight, SolidColorTexture, Sphere,
+        SpotLight, TextureChannel, TransformInstance, TriangleMesh, UVTransform,
+        UVTransformTexture, Vector2, Vector3, WrapMode,
+    },
+    traits::{Hittable, Light, Material, Texture},
+};
+use std::{
+    collections::HashMap,
+    f32::consts::PI,
+    fs, io,
+    ops::{Mul, Neg},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Most nested `Include` directives, guarding against files including themselves.
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// Normal incidence reflectance of the metals pbrt names its conductor spectra after.
+const METALS: [(&str, [f32; 3]); 5] = [
+    ("Ag", [0.972, 0.960, 0.915]),
+    ("Al", [0.913, 0.922, 0.924]),
+    ("Au", [1.000, 0.766, 0.336]),
+    ("Cu", [0.955, 0.638, 0.538]),
+    ("CuZn", [0.910, 0.778, 0.423]),
+];
+
+/// Returns true if a transform turns right-handed frames into left-handed ones.
+fn swaps_handedness(transform: &Matrix4) -> bool {
+    let x = transform.transform_vector(&Vector3::new(1., 0., 0.));
+    let y = transform.transform_vector(&Vector3::new(0., 1., 0.));
+    let z = transform.transform_vector(&Vector3::new(0., 0., 1.));
+    x.cross(&y).dot(&z) < 0.
+}
+
+/// Returns the transform from world space to the camera space of pbrt's `LookAt`, where the
+/// camera looks down +z with +y up.
+fn look_at(eye: &Point3, target: &Point3, up: &Vector3) -> Option<Matrix4> {
+    let direction = (target - eye).normolize();
+    let right = up.normolize().cross(&direction);
+    if right.length_squared() == 0. {
+        return None;
+    }
+    let right = right.normolize();
+    let new_up = direction.cross(&right);
+    let world_from_camera = Matrix4::new([
+        [right.x, new_up.x, direction.x, eye.x],
+        [right.y, new_up.y, direction.y, eye.y],
+        [right.z, new_up.z, direction.z, eye.z],
+        [0., 0., 0., 1.],
+    ]);
+    world_from_camera.inverse()
+}
+
+/// Returns the uv coordinates of a unit direction in pbrt's equal-area octahedral mapping of the
+/// sphere onto a square, with v running down the image.
+fn equal_area_square(direction: &Vector3) -> Vector2 {
+    let (x, y, z) = (direction.x.abs(), direction.y.abs(), direction.z.abs());
+    let r = f32::sqrt(f32::max(0., 1. - z));
+    let (a, b) = (f32::max(x, y), f32::min(x, y));
+    let ratio = if a == 0. { 0. } else { b / a };
+    let mut phi = f32::atan(ratio) * 2. / PI;
+    if x < y {
+        phi = 1. - phi;
+    }
+    let mut v = phi * r;
+    let mut u = r - v;
+    if direction.z < 0. {
+        (u, v) = (1. - v, 1. - u);
+    }
+    u = u.copysign(direction.x);
+    v = v.copysign(direction.y);
+    Vector2::new((u + 1.) / 2., (v + 1.) / 2.)
+}
+
+/// The attributes shapes and lights are created with, saved by `AttributeBegin`.
+#[derive(Debug, Clone)]
+struct GraphicsState {
+    /// The current transformation matrix, from object space to world space.
+    transform: Matrix4,
+
+    /// Whether shapes face the other way.
+    reverse_orientation: bool,
+
+    /// The material of new shapes.
+    material: Rc<dyn Material>,
+
+    /// The radiance and sidedness of the light new shapes emit, if any.
+    area_light: Option<(Color3, bool)>,
+}
+
+/// The state of an import, the directives read so far and the scene they describe.
+struct Importer {
+    base_dir: PathBuf,
+    state: GraphicsState,
+
+    /// Saved states, and whether only their transform is restored.
+    stack: Vec<(GraphicsState, bool)>,
+    coordinate_systems: HashMap<String, Matrix4>,
+    materials: HashMap<String, Rc<dyn Material>>,
+    textures: HashMap<String, Rc<dyn Texture>>,
+    float_textures: HashMap<String, Rc<dyn Texture>>,
+
+    /// The camera transform from world space and the camera, film, sampler and integrator
+    /// parameters.
+    camera: Option<Matrix4>,
+    camera_parameters: Parameters,
+    film: Parameters,
+    sampler: Parameters,
+    integrator: Parameters,
+
+    /// The reflection bringing pbrt's left-handed scenes into the crate, set at `WorldBegin`.
+    mirror: Option<Matrix4>,
+
+    /// Whether shapes are inside an unsupported object definition.
+    in_object: bool,
+    include_depth: usize,
+    light_sources: Vec<Rc<dyn Light>>,
+    scene: PBRTScene,
+}
+
+impl Importer {
+    fn new(base_dir: &Path) -> Self {
+        Self {
+            base_dir: base_dir.to_path_buf(),
+            state: GraphicsState {
+                transform: Matrix4::identity(),
+                reverse_orientation: false,
+                material: Rc::new(LambertianMaterial::new_with_color(Color3::new(
+                    0.5, 0.5, 0.5,
+                ))),
+                area_light: None,
+            },
+            stack: Vec::new(),
+            coordinate_systems: HashMap::new(),
+            materials: HashMap::new(),
+            textures: HashMap::new(),
+            float_textures: HashMap::new(),
+            camera: None,
+            camera_parameters: Parameters::default(),
+            film: Parameters::default(),
+            sampler: Parameters::default(),
+            integrator: Parameters::default(),
+            mirror: None,
+            in_object: false,
+            include_depth: 0,
+            light_sources: Vec::new(),
+            scene: PBRTScene {
+                world: HittableList::new(),
+                lights: HittableList::new(),
+                camera: CameraBuilder::default(),
+                filename: None,
+                warnings: Vec::new(),
+            },
+        }
+    }
+
+    /// Records a warning once.
+    fn warn(&mut self, message: String) {
+        if !self.scene.warnings.contains(&message) {
+            self.scene.warnings.push(message);
+        }
+    }
+
+    /// Runs the directives of a token stream.
+    fn run(&mut self, tokens: &[Token]) -> io::Result<()> {
+        let mut start = 0;
+        while start < tokens.len() {
+            let Token::Word(directive) = &tokens[start] else {
+                return Err(invalid("expected a pbrt directive"));
+            };
+            let end = tokens[start + 1..]
+                .iter()
+                .position(Token::is_directive)
+                .map_or(tokens.len(), |i| start + 1 + i);
+            self.directive(directive, &tokens[start + 1..end])?;
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// Returns the numbers of the arguments of a directive, checking their count.
+    fn numbers(directive: &str, arguments: &[Token], count: usize) -> io::Result<Vec<f32>> {
+        let numbers: Vec<f32> = arguments
+            .iter()
+            .filter_map(|token| match token {
+                Token::Number(number) => Some(*number as f32),
+                _ => None,
+            })
+            .collect();
+        if numbers.len() != count {
+            return Err(invalid(&format!("{directive} expects {count} numbers")));
+        }
+        Ok(numbers)
+    }
+
+    /// Returns the leading strings of the arguments of a directive and its parameters.
+    fn strings(
+        directive: &str,
+        arguments: &[Token],
+        count: usize,
+    ) -> io::Result<(Vec<String>, Parameters)> {
+        let strings = arguments
+            .iter()
+            .take(count)
+            .map(|token| match token {
+                Token::Str(string) => Some(string.clone()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|strings| strings.len() == count)
+            .ok_or_else(|| invalid(&format!("{directive} expects {count} strings")))?;
+        Ok((strings, Parameters::parse(&arguments[count..])?))
+    }
+
+    /// Applies a transform after the current transform.
+    fn concat(&mut self, transform: &Matrix4) {
+        self.state.transform = &self.state.transform * transform;
+    }
+
+    fn directive(&mut self, directive: &str, arguments: &[Token]) -> io::Result<()> {
+        match directive {
+            "Identity" => self.state.transform = Matrix4::identity(),
+            "Translate" => {
+                let [x, y, z] = Self::numbers(directive, arguments, 3)?[..] else {
+                    unreachable!()
+                };
+                self.concat(&Matrix4::translation(&Vector3::new(x, y, z)));
+            }
+            "Scale" => {
+                let [x, y, z] = Self::numbers(directive, arguments, 3)?[..] else {
+                    unreachable!()
+                };
+                self.concat(&Matrix4::scaling(&Vector3::new(x, y, z)));
+            }
+            "Rotate" => {
+                let [angle, x, y, z] = Self::numbers(directive, arguments, 4)?[..] else {
+                    unreachable!()
+                };
+                let axis = Vector3::new(x, y, z).normolize();
+                let half_angle = deg_to_rad(angle) / 2.;
+                let sin = f32::sin(half_angle);
+                let quaternion = [
+                    axis.x * sin,
+                    axis.y * sin,
+                    axis.z * sin,
+                    f32::cos(half_angle),
+                ];
+                self.concat(&Matrix4::rotation(&quaternion));
+            }
+            "LookAt" => {
+                let v = Self::numbers(directive, arguments, 9)?;
+                let transform = look_at(
+                    &Point3::new(v[0], v[1], v[2]),
+                    &Point3::new(v[3], v[4], v[5]),
+                    &Vector3::new(v[6], v[7], v[8]),
+                )
+                .ok_or_else(|| invalid("LookAt up direction along the view direction"))?;
+                self.concat(&transform);
+            }
+            "Transform" | "ConcatTransform" => {
+                let numbers = Self::numbers(directive, arguments, 16)?;
+                let mut columns = [0.; 16];
+                columns.copy_from_slice(&numbers);
+                let transform = Matrix4::new_with_columns(&columns);
+                if directive == "Transform" {
+                    self.state.transform = transform;
+                } else {
+                    self.concat(&transform);
+                }
+            }
+            "CoordinateSystem" => {
+                let (names, _) = Self::strings(directive, arguments, 1)?;
+                self.coordinate_systems
+                    .insert(names[0].clone(), self.state.transform);
+            }
+            "CoordSysTransform" => {
+                let (names, _) = Self::strings(directive, arguments, 1)?;
+                match self.coordinate_systems.get(&names[0]) {
+                    Some(transform) => self.state.transform = *transform,
+                    None => self.warn(format!("unknown coordinate system {}", names[0])),
+                }
+            }
+            "ReverseOrientation" => {
+                self.state.reverse_orientation = !self.state.reverse_orientation;
+            }
+            "AttributeBegin" | "TransformBegin" => {
+                self.stack
+                    .push((self.state.clone(), directive == "TransformBegin"));
+            }
+            "AttributeEnd" | "TransformEnd" => {
+                let (state, transform_only) = self
+                    .stack
+                    .pop()
+                    .ok_or_else(|| invalid(&format!("unmatched {directive}")))?;
+                if transform_only {
+                    self.state.transform = state.transform;
+                } else {
+                    self.state = state;
+                }
+            }
+            "Camera" => {
+                let (kinds, parameters) = Self::strings(directive, arguments, 1)?;
+                if kinds[0] != "perspective" {
+                    self.warn(format!(
+                        "unsupported camera {}, using a perspective camera",
+                        kinds[0]
+                    ));
+                }
+                self.camera = Some(self.state.transform);
+                self.camera_parameters = parameters;
+                if let Some(camera_to_world) = self.state.transform.inverse() {
+                    self.coordinate_systems
+                        .insert("camera".to_owned(), camera_to_world);
+                }
+            }
+            "Film" => self.film = Self::strings(directive, arguments, 1)?.1,
+            "Sampler" => self.sampler = Self::strings(directive, arguments, 1)?.1,
+            "Integrator" => self.integrator = Self::strings(directive, arguments, 1)?.1,
+            "WorldBegin" => {
+                self.begin_world();
+                self.state.transform = Matrix4::identity();
+                self.coordinate_systems
+                    .insert("world".to_owned(), Matrix4::identity());
+            }
+            "WorldEnd" => {}
+            "Material" => {
+                let (kinds, parameters) = Self::strings(directive, arguments, 1)?;
+                self.state.material = self.material(&kinds[0], &parameters);
+            }
+            "MakeNamedMaterial" => {
+                let (names, parameters) = Self::strings(directive, arguments, 1)?;
+                let kind = parameters.string("type").unwrap_or_default().to_owned();
+                let material = self.material(&kind, &parameters);
+                self.materials.insert(names[0].clone(), material);
+            }
+            "NamedMaterial" => {
+                let (names, _) = Self::strings(directive, arguments, 1)?;
+                match self.materials.get(&names[0]) {
+                    Some(material) => self.state.material = Rc::clone(material),
+                    None => self.warn(format!("unknown named material {}", names[0])),
+                }
+            }
+            "Texture" => {
+                let (strings, parameters) = Self::strings(directive, arguments, 3)?;
+                let texture_type = match strings[1].as_str() {
+                    "spectrum" => TextureType::Spectrum,
+                    "float" => TextureType::Float,
+                    _ => {
+                        self.warn(format!("unsupported {} texture {}", strings[1], strings[0]));
+                        return Ok(());
+                    }
+                };
+                if let Some(texture) = self.texture(&strings[2], &parameters, texture_type)? {
+                    let textures = match texture_type {
+                        TextureType::Spectrum => &mut self.textures,
+                        TextureType::Float => &mut self.float_textures,
+                    };
+                    textures.insert(strings[0].clone(), texture);
+                }
+            }
+            "Shape" => {
+                let (kinds, parameters) = Self::strings(directive, arguments, 1)?;
+                self.shape(&kinds[0], &parameters)?;
+            }
+            "AreaLightSource" => {
+                let (kinds, parameters) = Self::strings(directive, arguments, 1)?;
+                if kinds[0] != "diffuse" {
+                    self.warn(format!("unsupported area light {}", kinds[0]));
+                    return Ok(());
+                }
+                let radiance = self
+                    .spectrum(&parameters, "L")
+                    .unwrap_or(Color3::one())
+                    .mul(parameters.float("scale", 1.));
+                self.state.area_light = Some((radiance, parameters.bool("twosided", false)));
+            }
+            "LightSource" => {
+                let (kinds, parameters) = Self::strings(directive, arguments, 1)?;
+                self.light(&kinds[0], &parameters)?;
+            }
+            "Include" | "Import" => {
+                let (names, _) = Self::strings(directive, arguments, 1)?;
+                if self.include_depth >= MAX_INCLUDE_DEPTH {
+                    return Err(invalid("pbrt includes nested too deeply"));
+                }
+                let text = fs::read_to_string(self.base_dir.join(&names[0]))?;
+                self.include_depth += 1;
+                self.run(&tokenize(&text)?)?;
+                self.include_depth -= 1;
+            }
+            "ObjectBegin" => {
+                self.warn("unsupported directive ObjectBegin, skipping object definitions".into());
+                self.stack.push((self.state.clone(), false));
+                self.in_object = true;
+            }
+            "ObjectEnd" => {
+                let (state, _) = self
+                    .stack
+                    .pop()
+                    .ok_or_else(|| invalid("unmatched ObjectEnd"))?;
+                self.state = state;
+                self.in_object = false;
+            }
+            _ => self.warn(format!("unsupported directive {directive}")),
+        }
+        Ok(())
+    }
+
+    /// Sets up the camera from the directives before `WorldBegin`, and the reflection bringing
+    /// the world into the crate.
+    fn begin_world(&mut self) {
+        let world_from_camera = self
+            .camera
+            .and_then(|camera| camera.inverse())
+            .unwrap_or_default();
+        let position = world_from_camera.transform_point(&Point3::zero());
+        let forward = world_from_camera.transform_vector(&Vector3::new(0., 0., 1.));
+        let up = world_from_camera.transform_vector(&Vector3::up());
+        let right = world_from_camera.transform_vector(&Vector3::new(1., 0., 0.));
+
+        // pbrt images show the camera space +x on the right, mirror the world when the camera of
+        // the crate would show it on the left.
+        let mirror = if up.cross(&forward.neg()).dot(&right) < 0. {
+            Matrix4::scaling(&Vector3::new(-1., 1., 1.))
+        } else {
+            Matrix4::identity()
+        };
+        let position = mirror.transform_point(&position);
+        let forward = mirror.transform_vector(&forward).normolize();
+        let up = mirror.transform_vector(&up).normolize();
+        self.mirror = Some(mirror);
+
+        // The field of view spans the shorter side of the image.
+        let width = self.film.float("xresolution", 1280.).max(1.);
+        let height = self.film.float("yresolution", 720.).max(1.);
+        let aspect = width / height;
+        let mut fov = deg_to_rad(self.camera_parameters.float("fov", 90.));
+        if aspect < 1. {
+            fov = 2. * f32::atan(f32::tan(fov / 2.) / aspect);
+        }
+
+        let camera = &mut self.scene.camera;
+        camera
+            .position(position)
+            .target(&position + &forward)
+            .up(up)
+            .fov(rad_to_deg(fov))
+            .aspect(aspect)
+            .width(width as u32)
+            .samples_per_pixel(self.sampler.float("pixelsamples", 16.) as u32)
+            .max_ray_depth(self.integrator.float("maxdepth", 5.).clamp(1., 255.) as u8)
+            .background(Color3::zero());
+        let lens_radius = self.camera_parameters.float("lensradius", 0.);
+        if lens_radius > 0. {
+            let focus_distance = self.camera_parameters.float("focaldistance", 1e6);
+            camera
+                .defocus_angle(rad_to_deg(2. * f32::atan(lens_radius / focus_distance)))
+                .focus_dist(focus_distance);
+        }
+        self.scene.filename = self.film.string("filename").map(str::to_owned);
+    }
+
+    /// Returns the transform from object space to the world of the crate.
+    fn world_transform(&self) -> Matrix4 {
+        &self.mirror.unwrap_or_default() * &self.state.transform
+    }
+
+    /// Returns a color parameter, `None` if it is missing.
+    fn spectrum(&mut self, parameters: &Parameters, name: &str) -> Option<Color3> {
+        let parameter = parameters.get(name)?;
+        let numbers = parameters.numbers(name).unwrap_or_default();
+        match (parameter.kind.as_str(), numbers.as_slice()) {
+            ("rgb" | "color", [r, g, b]) => Some(Color3::new(*r, *g, *b)),
+            ("float", [value]) => Some(Color3::new(*value, *value, *value)),
+            ("blackbody", [temperature, ..]) => {
+                // pbrt normalizes blackbody emitters to a peak of one.
+                let color = blackbody(*temperature);
+                let peak = f32::max(color.x, f32::max(color.y, color.z));
+                Some(if peak > 0. {
+                    color.mul(1. / peak)
+                } else {
+                    color
+                })
+            }
+            ("spectrum", []) => {
+                match parameter.values.first() {
+                    Some(ParameterValue::Str(named)) if named.starts_with("stdillum-") => {}
+                    Some(ParameterValue::Str(named)) => {
+                        let named = named.clone();
+                        self.warn(format!("unsupported named spectrum {named}, using white"));
+                    }
+                    _ => {}
+                }
+                Some(Color3::one())
+            }
+            ("spectrum", samples) => {
+                // Wavelength and value pairs, kept as their average value.
+                self.warn(format!(
+                    "sampled spectrum {name} approximated by its average"
+                ));
+                let values: Vec<f32> = samples.iter().skip(1).step_by(2).copied().collect();
+                let average = values.iter().sum::<f32>() / values.len().max(1) as f32;
+                Some(Color3::new(average, average, average))
+            }
+            _ => {
+                self.warn(format!("invalid {} parameter {name}", parameter.kind));
+                None
+            }
+        }
+    }
+
+    /// Returns a color or texture parameter as a texture, or a constant default if it is missing.
+    fn spectrum_texture(
+        &mut self,
+        parameters: &Parameters,
+        name: &str,
+        default: Color3,
+    ) -> Rc<dyn Texture> {
+        if parameters.get(name).is_some_and(|p| p.kind == "texture") {
+            let texture = parameters.string(name).unwrap_or_default().to_owned();
+            match self.textures.get(&texture) {
+                Some(texture) => return Rc::clone(texture),
+                None => self.warn(format!("unknown texture {texture}")),
+            }
+        }
+        let color = self.spectrum(parameters, name).unwrap_or(default);
+        Rc::new(SolidColorTexture::new_with_color(color))
+    }
+
+    /// Returns the normal incidence reflectance of a conductor, from its `eta` and `k` parameters.
+    fn conductor_reflectance(&mut self, parameters: &Parameters) -> Color3 {
+        let named = match parameters.get("eta").map(|p| p.values.first()) {
+            None => Some("metal-Cu-eta".to_owned()),
+            Some(Some(ParameterValue::Str(named))) => Some(named.clone()),
+            _ => None,
+        };
+        if let Some(named) = named {
+            let metal = named
+                .strip_prefix("metal-")
+                .and_then(|metal| metal.strip_suffix("-eta"));
+            return match METALS.iter().find(|(name, _)| Some(*name) == metal) {
+                Some((_, [r, g, b])) => Color3::new(*r, *g, *b),
+                None => {
+                    self.warn(format!(
+                        "unsupported conductor spectrum {named}, using copper"
+                    ));
+                    let [r, g, b] = METALS[3].1;
+                    Color3::new(r, g, b)
+                }
+            };
+        }
+
+        // The Fresnel reflectance of each channel at normal incidence.
+        let eta = self.spectrum(parameters, "eta").unwrap_or(Color3::one());
+        let k = self.spectrum(parameters, "k").unwrap_or(Color3::zero());
+        let reflectance =
+            |n: f32, k: f32| ((n - 1.) * (n - 1.) + k * k) / ((n + 1.) * (n + 1.) + k * k);
+        Color3::new(
+            reflectance(eta.x, k.x),
+            reflectance(eta.y, k.y),
+            reflectance(eta.z, k.z),
+        )
+    }
+
+    /// Returns the material of a `Material` or `MakeNamedMaterial` directive, bump mapped by its
+    /// displacement texture if it has one.
+    fn material(&mut self, kind: &str, parameters: &Parameters) -> Rc<dyn Material> {
+        let material = self.base_material(kind, parameters);
+        if parameters.get("displacement").is_none() {
+            return material;
+        }
+        let displacement = self.float_texture(parameters, "displacement", 0.);
+        Rc::new(NormalMappedMaterial::new_with_bump_map(
+            material,
+            displacement,
+            1.,
+        ))
+    }
+
+    /// Returns the material of a `Material` or `MakeNamedMaterial` directive, without its
+    /// displacement.
+    fn base_material(&mut self, kind: &str, parameters: &Parameters) -> Rc<dyn Material> {
+        match kind {
+            "diffuse" => {
+                let reflectance =
+                    self.spectrum_texture(parameters, "reflectance", Color3::new(0.5, 0.5, 0.5));
+                Rc::new(LambertianMaterial::new(reflectance))
+            }
+            "conductor" => {
+                let albedo = if parameters.get("reflectance").is_some() {
+                    self.spectrum_texture(parameters, "reflectance", Color3::one())
+                } else {
+                    let reflectance = self.conductor_reflectance(parameters);
+                    Rc::new(SolidColorTexture::new_with_color(reflectance))
+                };
+                if parameters
+                    .get("roughness")
+                    .is_some_and(|p| p.kind == "texture")
+                {
+                    let roughness = self.float_texture(parameters, "roughness", 0.);
+                    return Rc::new(MetalMaterial::new_with_roughness_texture(albedo, roughness));
+                }
+                // Anisotropic roughness is averaged, unmapped roughness is a microfacet alpha.
+                let roughness = match parameters.get("roughness") {
+                    Some(_) => parameters.float("roughness", 0.),
+                    None => {
+                        (parameters.float("uroughness", 0.) + parameters.float("vroughness", 0.))
+                            / 2.
+                    }
+                };
+                let roughness = if parameters.bool("remaproughness", true) {
+                    roughness
+                } else {
+                    roughness.sqrt()
+                };
+                Rc::new(MetalMaterial::new(albedo, roughness.clamp(0., 1.)))
+            }
+            "dielectric" | "thindielectric" => {
+                let eta = match parameters.get("eta").map(|p| p.values.first()) {
+                    Some(Some(ParameterValue::Number(eta))) => *eta as f32,
+                    Some(_) => {
+                        self.warn("named dielectric spectra approximated by an eta of 1.5".into());
+                        1.5
+                    }
+                    None => 1.5,
+                };
+                Rc::new(DielectricMaterial::new(eta))
+            }
+            _ => {
+                self.warn(format!(
+                    "unsupported material {kind}, using a diffuse material"
+                ));
+                let reflectance =
+                    self.spectrum_texture(parameters, "reflectance", Color3::new(0.5, 0.5, 0.5));
+                Rc::new(LambertianMaterial::new(reflectance))
+            }
+        }
+    }
+
+    /// Returns the texture of a `Texture` directive, `None` if its class is unsupported.
+    fn texture(
+        &mut self,
+        kind: &str,
+        parameters: &Parameters,
+        texture_type: TextureType,
+    ) -> io::Result<Option<Rc<dyn Texture>>> {
+        match kind {
+            "imagemap" => {
+                let filename = parameters
+                    .string("filename")
+                    .ok_or_else(|| invalid("imagemap texture without filename"))?;
+                let path = self.base_dir.join(filename);
+                let path = path.to_string_lossy().into_owned();
+                let mut texture = match parameters.string("encoding") {
+                    None => ImageTexture::new(path),
+                    Some(encoding) => {
+                        let encoding = match encoding {
+                            "sRGB" => ImageEncoding::SRGB,
+                            "linear" => ImageEncoding::Linear,
+                            _ => {
+                                self.warn(format!("unsupported imagemap encoding {encoding}"));
+                                ImageEncoding::SRGB
+                            }
+                        };
+                        ImageTexture::new_with_encoding(path, encoding)
+                    }
+                }
+                .map_err(|error| invalid(&format!("invalid image {filename}: {error}")))?;
+                texture.filter = match parameters.string("filter").unwrap_or("bilinear") {
+                    "point" => ImageFilter::Point,
+                    "bilinear" => ImageFilter::Bilinear,
+                    "trilinear" => ImageFilter::Trilinear,
+                    "ewa" | "EWA" => ImageFilter::EWA,
+                    filter => {
+                        self.warn(format!("unsupported imagemap filter {filter}"));
+                        ImageFilter::Bilinear
+                    }
+                };
+                texture.wrap = match parameters.string("wrap").unwrap_or("repeat") {
+                    "repeat" => WrapMode::Repeat,
+                    "clamp" => WrapMode::Clamp,
+                    wrap => {
+                        self.warn(format!("unsupported imagemap wrap mode {wrap}"));
+                        WrapMode::Repeat
+                    }
+                };
+                texture.max_anisotropy = parameters.float("maxanisotropy", 8.);
+                let mut texture: Rc<dyn Texture> = Rc::new(texture);
+                let transform = uv_transform(parameters);
+                if transform != UVTransform::new() {
+                    texture = Rc::new(UVTransformTexture::new(texture, transform));
+                }
+                if texture_type == TextureType::Float {
+                    texture = Rc::new(ChannelTexture::new(texture, TextureChannel::Average));
+                }
+                Ok(Some(texture))
+            }
+            "checkerboard" => {
+                let even = self.input_texture(parameters, "tex1", 1., texture_type);
+                let odd = self.input_texture(parameters, "tex2", 0., texture_type);
+                if parameters.float("dimension", 2.) == 3. {
+                    return Ok(Some(Rc::new(CheckerTexture::new(1., even, odd))));
+                }
+                let frequency = Vector2::new(
+                    parameters.float("uscale", 1.),
+                    parameters.float("vscale", 1.),
+                );
+                Ok(Some(Rc::new(CheckerTexture::new_uv(frequency, even, odd))))
+            }
+            _ => {
+                let texture = self.node_texture(kind, parameters, texture_type)?;
+                if texture.is_none() {
+                    self.warn(format!("unsupported texture {kind}"));
+                }
+                Ok(texture)
+            }
+        }
+    }
+
+    /// Returns the material of new shapes, the emission of the area light if there is one.
+    fn shape_material(&self) -> Rc<dyn Material> {
+        match self.state.area_light {
+            Some((radiance, false)) => Rc::new(EmissiveMaterial::new_with_color(radiance)),
+            Some((radiance, true)) => Rc::new(EmissiveMaterial::new_two_sided(Rc::new(
+                SolidColorTexture::new_with_color(radiance),
+            ))),
+            None => Rc::clone(&self.state.material),
+        }
+    }
+
+    /// Adds an object to the world, and to the lights if it emits light.
+    fn add(&mut self, object: Rc<dyn Hittable>) {
+        if self.state.area_light.is_some() {
+            self.scene.lights.add(Rc::clone(&object));
+        }
+        self.scene.world.add(object);
+    }
+
+    /// Adds a mesh given in object space, bringing it into the world.
+    fn add_mesh(&mut self, mut mesh: TriangleMesh) {
+        if mesh.triangle_count() == 0 {
+            return;
+        }
+        let transform = self.world_transform();
+        let normal_transform = transform.inverse().unwrap_or_default();
+        for position in &mut mesh.positions {
+            *position = transform.transform_point(position);
+        }
+        let sign = if self.state.reverse_orientation {
+            -1.
+        } else {
+            1.
+        };
+        if let Some(normals) = &mut mesh.normals {
+            for normal in normals {
+                *normal = normal_transform
+                    .transform_normal(normal)
+                    .normolize()
+                    .mul(sign);
+            }
+        }
+
+        // Faces point along the cross product of their edges, flipped like pbrt flips them.
+        if swaps_handedness(&transform) != self.state.reverse_orientation {
+            for triangle in &mut mesh.indices {
+                triangle.swap(1, 2);
+            }
+        }
+
+        let mut triangles = mesh.triangles(self.shape_material());
+        if self.state.area_light.is_some() {
+            let mut lights = HittableList::new();
+            for triangle in &triangles.objects {
+                lights.add(Rc::clone(triangle));
+            }
+            self.scene.lights.add(Rc::new(lights));
+        }
+        self.scene.world.add(Rc::new(BVHNode::new(&mut triangles)));
+    }
+
+    fn shape(&mut self, kind: &str, parameters: &Parameters) -> io::Result<()> {
+        if self.in_object {
+            return Ok(());
+        }
+        match kind {
+            "sphere" => {
+                for partial in ["zmin", "zmax", "phimax"] {
+                    if parameters.get(partial).is_some() {
+                        self.warn(format!("unsupported sphere parameter {partial}"));
+                    }
+                }
+                let radius = parameters.float("radius", 1.);
+                let material = self.shape_material();
+                let transform = self.world_transform();
+
+                // Transforms keeping the sphere round give a sphere, others an instance.
+                let axes = [
+                    Vector3::new(1., 0., 0.),
+                    Vector3::new(0., 1., 0.),
+                    Vector3::new(0., 0., 1.),
+                ]
+                .map(|axis| transform.transform_vector(&axis));
+                let scale = axes[0].length();
+                let tolerance = 1e-4 * scale * scale;
+                let round = (0..3).all(|i| {
+                    (axes[i].length_squared() - scale * scale).abs() < tolerance
+                        && axes[i].dot(&axes[(i + 1) % 3]).abs() < tolerance
+                });
+                let object: Rc<dyn Hittable> = if round {
+                    let center = transform.transform_point(&Point3::zero());
+                    Rc::new(Sphere::new(center, radius * scale, material))
+                } else if transform.inverse().is_none() {
+                    // Spheres scaled flat along some axis are hidden.
+                    return Ok(());
+                } else {
+                    let sphere = Rc::new(Sphere::new(Point3::zero(), radius, material));
+                    Rc::new(TransformInstance::new(sphere, transform))
+                };
+                self.add(object);
+            }
+            "trianglemesh" => {
+                let positions = parameters
+                    .points("P")
+                    .ok_or_else(|| invalid("trianglemesh without P"))?;
+                let indices = match parameters.indices("indices")? {
+                    Some(indices) => indices,
+                    None if positions.len() == 3 => vec![0, 1, 2],
+                    None => return Err(invalid("trianglemesh without indices")),
+                };
+                if indices.len() % 3 != 0 || indices.iter().any(|&i| i >= positions.len()) {
+                    return Err(invalid("invalid trianglemesh indices"));
+                }
+                let vertex_count = positions.len();
+                let mut mesh = TriangleMesh::new(
+                    positions,
+                    indices
+                        .chunks_exact(3)
+                        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+                        .collect(),
+                );
+                mesh.normals = parameters
+                    .points("N")
+                    .filter(|normals| normals.len() == vertex_count);
+                mesh.uvs = parameters
+                    .uvs("uv")
+                    .or_else(|| parameters.uvs("st"))
+                    .filter(|uvs| uvs.len() == vertex_count);
+                self.add_mesh(mesh);
+            }
+            "plymesh" => {
+                let filename = parameters
+                    .string("filename")
+                    .ok_or_else(|| invalid("plymesh without filename"))?;
+                let mesh = TriangleMesh::load_ply(self.base_dir.join(filename))?;
+                self.add_mesh(mesh);
+            }
+            "bilinearmesh" => {
+                let positions = parameters
+                    .points("P")
+                    .ok_or_else(|| invalid("bilinearmesh without P"))?;
+                let indices = match parameters.indices("indices")? {
+                    Some(indices) => indices,
+                    None if positions.len() == 4 => vec![0, 1, 2, 3],
+                    None => return Err(invalid("bilinearmesh without indices")),
+                };
+                if indices.len() % 4 != 0 || indices.iter().any(|&i| i >= positions.len()) {
+                    return Err(invalid("invalid bilinearmesh indices"));
+                }
+                let vertex_count = positions.len();
+                let normals = parameters
+                    .points("N")
+                    .filter(|normals| normals.len() == vertex_count);
+                let uvs = parameters.uvs("uv").filter(|uvs| uvs.len() == vertex_count);
+
+                // Each patch p00, p10, p01, p11 is split into two triangles. Without uv
+                // coordinates every patch spans [0, 1] in u and v, so vertices are not shared.
+                let patches = indices.chunks_exact(4);
+                let mesh = if uvs.is_some() {
+                    let triangles = patches
+                        .flat_map(|p| [[p[0], p[1], p[3]], [p[0], p[3], p[2]]])
+                        .collect();
+                    let mut mesh = TriangleMesh::new(positions, triangles);
+                    mesh.normals = normals;
+                    mesh.uvs = uvs;
+                    mesh
+                } else {
+                    let mut vertices = Vec::new();
+                    let mut patch_normals = Vec::new();
+                    let mut triangles = Vec::new();
+                    for p in patches {
+                        let base = vertices.len();
+                        vertices.extend(p.iter().map(|&i| positions[i]));
+                        if let Some(normals) = &normals {
+                            patch_normals.extend(p.iter().map(|&i| normals[i]));
+                        }
+                        triangles.push([base, base + 1, base + 3]);
+                        triangles.push([base, base + 3, base + 2]);
+                    }
+                    let patch_count = triangles.len() / 2;
+                    let mut mesh = TriangleMesh::new(vertices, triangles);
+                    mesh.normals = normals.map(|_| patch_normals);
+                    mesh.uvs = Some(
+                        (0..patch_count)
+                            .flat_map(|_| {
+                                [
+                                    Vector2::new(0., 0.),
+                                    Vector2::new(1., 0.),
+                                    Vector2::new(0., 1.),
+                                    Vector2::new(1., 1.),
+                                ]
+                            })
+                            .collect(),
+                    );
+                    mesh
+                };
+                self.add_mesh(mesh);
+            }
+            _ => self.warn(format!("unsupported shape {kind}")),
+        }
+        Ok(())
+    }
+
+    fn light(&mut self, kind: &str, parameters: &Parameters) -> io::Result<()> {
+        let transform = self.world_transform();
+        let scale = parameters.float("scale", 1.);
+        let point = |name: &str, default: Point3| {
+            parameters
+                .points(name)
+                .and_then(|points| points.first().copied())
+                .unwrap_or(default)
+        };
+        let from = point("from", Point3::zero());
+        let to = point("to", Point3::new(0., 0., 1.));
+
+        let light: Rc<dyn Light> = match kind {
+            "point" => {
+                let intensity = self.spectrum(parameters, "I").unwrap_or(Color3::one());
+                Rc::new(PointLight::new(
+                    transform.transform_point(&from),
+                    intensity.mul(scale),
+                ))
+            }
+            "spot" => {
+                let intensity = self.spectrum(parameters, "I").unwrap_or(Color3::one());
+                let cone_angle = parameters.float("coneangle", 30.);
+                let cone_delta = parameters.float("conedelta", 5.);
+                Rc::new(SpotLight::new(
+                    transform.transform_point(&from),
+                    transform.transform_point(&to),
+                    intensity.mul(scale),
+                    cone_angle,
+                    cone_angle - cone_delta,
+                ))
+            }
+            "distant" => {
+                let radiance = self.spectrum(parameters, "L").unwrap_or(Color3::one());
+                Rc::new(DirectionalLight::new(
+                    transform.transform_vector(&(&to - &from)),
+                    radiance.mul(scale),
+                ))
+            }
+            "infinite" => match parameters.string("filename") {
+                Some(filename) => {
+                    let filename = filename.to_owned();
+                    Rc::new(self.environment_map(&filename, &transform, scale)?)
+                }
+                None => {
+                    let radiance = self.spectrum(parameters, "L").unwrap_or(Color3::one());
+                    Rc::new(EnvironmentLight::new_with_pixels(
+                        vec![radiance],
+                        1,
+                        1,
+                        0.,
+                        scale,
+                    ))
+                }
+            },
+            _ => {
+                self.warn(format!("unsupported light {kind}"));
+                return Ok(());
+            }
+        };
+        self.light_sources.push(light);
+        Ok(())
+    }
+
+    /// Returns an environment light from a pbrt environment map, a square image in the
+    /// equal-area octahedral mapping, resampled to the equirectangular mapping of the crate.
+    fn environment_map(
+        &self,
+        filename: &str,
+        transform: &Matrix4,
+        scale: f32,
+    ) -> io::Result<EnvironmentLight> {
+        let image = image::open(self.base_dir.join(filename))
+            .map_err(|error| invalid(&format!("invalid image {filename}: {error}")))?;
+        let encoding = ImageEncoding::detect(&image);
+        let image = image.to_rgb32f();
+        let size = image.width() as usize;
+        if size == 0 || image.height() as usize != size {
+            return Err(invalid("pbrt environment maps must be square"));
+        }
+
+        let light_from_world = transform.inverse().unwrap_or_default();
+        let (width, height) = (2 * size, size);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let theta = PI * (y as f32 + 0.5) / height as f32;
+            for x in 0..width {
+                let phi = 2. * PI * (x as f32 + 0.5) / width as f32;
+                let direction = Vector3::new(
+                    f32::sin(theta) * f32::cos(phi),
+                    f32::cos(theta),
+                    f32::sin(theta) * f32::sin(phi),
+                );
+                let uv =
+                    equal_area_square(&light_from_world.transform_vector(&direction).normolize());
+                let i = ((uv.u() * size as f32) as usize).min(size - 1);
+                let j = ((uv.v() * size as f32) as usize).min(size - 1);
+                let pixel = image.get_pixel(i as u32, j as u32);
+                let [r, g, b] = pixel.0.map(|channel| encoding.decode(channel));
+                pixels.push(Color3::new(r, g, b));
+            }
+        }
+        Ok(EnvironmentLight::new_with_pixels(
+            pixels, width, height, 0., scale,
+        ))
+    }
+}
+
+/**
+pbrt Scene
+
+A scene imported from a file in a subset of the pbrt-v4 scene format, to render the test scenes of
+the rendering community and compare against their reference images. Supported are:
+
+- `LookAt`, `Translate`, `Scale`, `Rotate`, `Transform`, `ConcatTransform`, named coordinate
+  systems, `ReverseOrientation`, and `AttributeBegin`/`AttributeEnd` blocks.
+- `Camera "perspective"` with `Film`, `Sampler` and `Integrator` settings.
+- `Shape` `sphere`, `trianglemesh`, `plymesh` and `bilinearmesh`.
+- `Material` `diffuse`, `conductor` and `dielectric`, and named materials.
+- `Texture` `imagemap`, `checkerboard`, `constant`, `scale` and `mix`, spectrum and float, float
+  textures displacing materials or roughening conductors, and the texture nodes and procedural
+  textures of the crate as extra classes, see below.
+- `AreaLightSource "diffuse"`, and `LightSource` `infinite`, `point`, `spot` and `distant`.
+- `Include` and `Import`.
+
+Other directives, types and parameters are skipped or approximated with a warning. pbrt's world is
+left-handed, so the scene is mirrored when needed to render the same image as pbrt.
+
+Texture networks may also use texture classes of the crate, which pbrt itself doesn't read. Their
+texture parameters are named textures of the same type, or constants:
+
+- `add`, `subtract` and `multiply` of `tex1` and `tex2`, and `invert` and `clamp` (`min`, `max`)
+  of `tex`, see `MathTexture`.
+- `ramp`, coloring `tex` by a color ramp of `positions` and `colors`, and `curve`, mapping the
+  channels of `tex` through `points` given as x and y pairs, see `RemapTexture`.
+- `hsv`, shifting the `hue` of `tex` in turns and scaling its `saturation` and `value`.
+- `uvtransform` of `tex` by `uscale`, `vscale`, `rotate` in degrees, `udelta` and `vdelta`,
+  wrapping the uv coordinates into the unit square when `tile` is true. `imagemap` textures take
+  the same parameters.
+- `objectspace`, reading `tex` at hit points in the space of instanced or moving objects.
+- `triplanar` projections of `tex`, with `scale` and `sharpness`.
+- `warp` of `tex` by the scalar texture `warp`, up to `amount`.
+- `channel` of `tex`: `red`, `green`, `blue`, `alpha`, `average` or `luminance`.
+- `worley` (`scale`, `feature` `f1`, `f2` or `f2-f1`, `seed`), `fractal` (`type` `fbm` or
+  `ridged`, `scale`, `octaves`, `lacunarity`, `gain`), `wood` (`rings`, `distortion`,
+  `noisescale`) and `gradient` (`type` `linear` from `start` to `end`, or `radial` around
+  `center` up to `radius`), colored by a color ramp of `positions` and `colors`.
+- `brick` of `tex1` bricks and `tex2` mortar, with `width`, `height`, `mortar` and `offset`.
+
+```text
+Texture "cells" "float" "worley" "float scale" 8 "string feature" "f2-f1"
+Texture "stone" "spectrum" "ramp" "texture tex" "cells"
+    "float positions" [ 0 0.1 ] "rgb colors" [ 0.1 0.1 0.1  0.6 0.55 0.5 ]
+Texture "bump" "float" "scale" "texture tex" "cells" "float scale" 0.01
+Material "diffuse" "texture reflectance" "stone" "texture displacement" "bump"
+```
+
+# Examples
+
+```no_run
+use raytracing::{
+    core::{BVHNode, PBRTScene},
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+let mut scene = PBRTScene::load("scenes/cornell-box.pbrt").unwrap();
+for warning in &scene.warnings {
+    eprintln!("warning: {warning}");
+}
+let mut camera = scene.camera.build().unwrap();
+let lights: Rc<dyn Hittable> = Rc::new(scene.lights);
+camera
+    .render(
+        Rc::new(BVHNode::new(&mut scene.world)),
+        Some(lights),
+        "cornell-box.ppm".to_owned(),
+    )
+    .unwrap();
+```
+*/
+pub struct PBRTScene {
+    /// The shapes of the scene.
+    pub world: HittableList,
+
+    /// The shapes emitting light, to be sampled as lights when rendering.
+    pub lights: HittableList,
+
+    /// The camera with the film resolution, samples per pixel, ray depth and light sources set.
+    pub camera: CameraBuilder,
+
+    /// The file name of the film, if set.
+    pub filename: Option<String>,
+
+    /// The directives and parameters that were skipped or approximated.
+    pub warnings: Vec<String>,
+}
+
+impl PBRTScene {
+    /// Load a `PBRTScene` from a `.pbrt` file, included files, meshes and images are read
+    /// relative to the file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&fs::read_to_string(path)?, base_dir)
+    }
+
+    /// Parse a `PBRTScene` from the content of a `.pbrt` file, included files, meshes and images
+    /// are read relative to `base_dir`.
+    pub fn parse(text: &str, base_dir: &Path) -> io::Result<Self> {
+        let mut importer = Importer::new(base_dir);
+        importer.run(&tokenize(text)?)?;
+        if importer.mirror.is_none() {
+            importer.begin_world();
+        }
+        if !importer.stack.is_empty() {
+            importer.warn("unmatched AttributeBegin".into());
+        }
+
+        let mut scene = importer.scene;
+        scene.camera.light_sources(importer.light_sources);
+        Ok(scene)
+    }
+}
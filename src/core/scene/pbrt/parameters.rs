@@ -0,0 +1,241 @@
+use crate::core::{Point3, Vector2};
+use std::io;
+
+/// Returns an error for malformed data.
+pub(super) fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// A token of a pbrt scene file.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    /// A bare word, the name of a directive or a boolean value.
+    Word(String),
+
+    /// A quoted string.
+    Str(String),
+
+    /// A number.
+    Number(f64),
+
+    /// An opening bracket.
+    Open,
+
+    /// A closing bracket.
+    Close,
+}
+
+impl Token {
+    /// Returns true if the token starts a directive, bare booleans are values.
+    pub(super) fn is_directive(&self) -> bool {
+        matches!(self, Token::Word(word) if word != "true" && word != "false")
+    }
+}
+
+/// Returns the tokens of the text of a pbrt scene file, without its comments.
+pub(super) fn tokenize(text: &str) -> io::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '[' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ']' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' => {
+                chars.next();
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => string.push('\n'),
+                            Some('t') => string.push('\t'),
+                            Some(c) => string.push(c),
+                            None => return Err(invalid("unterminated string in pbrt file")),
+                        },
+                        Some(c) => string.push(c),
+                        None => return Err(invalid("unterminated string in pbrt file")),
+                    }
+                }
+                tokens.push(Token::Str(string));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(c) =
+                    chars.next_if(|&c| !c.is_whitespace() && !matches!(c, '"' | '[' | ']' | '#'))
+                {
+                    word.push(c);
+                }
+                let starts_number =
+                    word.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c));
+                tokens.push(if starts_number {
+                    Token::Number(
+                        word.parse()
+                            .map_err(|_| invalid(&format!("invalid number {word} in pbrt file")))?,
+                    )
+                } else {
+                    Token::Word(word)
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// A value of a parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ParameterValue {
+    Number(f64),
+    Str(String),
+    Bool(bool),
+}
+
+/// A parameter of a directive, such as `"float radius" [ 2 ]`.
+#[derive(Debug, Clone)]
+pub(super) struct Parameter {
+    /// The type of the parameter, such as `float`, `rgb` or `texture`.
+    pub(super) kind: String,
+
+    /// The name of the parameter.
+    pub(super) name: String,
+
+    /// The values of the parameter.
+    pub(super) values: Vec<ParameterValue>,
+}
+
+/// The parameter list of a directive.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Parameters(Vec<Parameter>);
+
+impl Parameters {
+    /// Parses the parameters following the leading arguments of a directive.
+    pub(super) fn parse(tokens: &[Token]) -> io::Result<Self> {
+        let mut parameters = Vec::new();
+        let mut tokens = tokens.iter();
+        while let Some(token) = tokens.next() {
+            let Token::Str(declaration) = token else {
+                return Err(invalid("expected a pbrt parameter declaration"));
+            };
+            let mut words = declaration.split_whitespace();
+            let (Some(kind), Some(name), None) = (words.next(), words.next(), words.next()) else {
+                return Err(invalid(&format!(
+                    "invalid pbrt parameter declaration {declaration}"
+                )));
+            };
+
+            let value = |token: &Token| match token {
+                Token::Number(number) => Ok(ParameterValue::Number(*number)),
+                Token::Str(string) => Ok(ParameterValue::Str(string.clone())),
+                Token::Word(word) => Ok(ParameterValue::Bool(word == "true")),
+                _ => Err(invalid("invalid pbrt parameter value")),
+            };
+            let values = match tokens.next() {
+                Some(Token::Open) => {
+                    let mut values = Vec::new();
+                    loop {
+                        match tokens.next() {
+                            Some(Token::Close) => break,
+                            Some(token) => values.push(value(token)?),
+                            None => return Err(invalid("unterminated pbrt parameter list")),
+                        }
+                    }
+                    values
+                }
+                Some(token) => vec![value(token)?],
+                None => return Err(invalid("pbrt parameter without value")),
+            };
+            parameters.push(Parameter {
+                kind: kind.to_owned(),
+                name: name.to_owned(),
+                values,
+            });
+        }
+        Ok(Self(parameters))
+    }
+
+    /// Returns the parameter of a name.
+    pub(super) fn get(&self, name: &str) -> Option<&Parameter> {
+        self.0.iter().find(|parameter| parameter.name == name)
+    }
+
+    /// Returns the numbers of a parameter.
+    pub(super) fn numbers(&self, name: &str) -> Option<Vec<f32>> {
+        self.get(name)?
+            .values
+            .iter()
+            .map(|value| match value {
+                ParameterValue::Number(number) => Some(*number as f32),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the first number of a parameter, or a default if it is missing.
+    pub(super) fn float(&self, name: &str, default: f32) -> f32 {
+        self.numbers(name)
+            .and_then(|numbers| numbers.first().copied())
+            .unwrap_or(default)
+    }
+
+    /// Returns the string of a parameter.
+    pub(super) fn string(&self, name: &str) -> Option<&str> {
+        match self.get(name)?.values.first()? {
+            ParameterValue::Str(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean of a parameter, or a default if it is missing.
+    pub(super) fn bool(&self, name: &str, default: bool) -> bool {
+        match self
+            .get(name)
+            .and_then(|parameter| parameter.values.first())
+        {
+            Some(ParameterValue::Bool(value)) => *value,
+            Some(ParameterValue::Str(value)) => value == "true",
+            _ => default,
+        }
+    }
+
+    /// Returns the points of a parameter holding triples of numbers.
+    pub(super) fn points(&self, name: &str) -> Option<Vec<Point3>> {
+        let numbers = self.numbers(name)?;
+        Some(
+            numbers
+                .chunks_exact(3)
+                .map(|p| Point3::new(p[0], p[1], p[2]))
+                .collect(),
+        )
+    }
+
+    /// Returns the uv coordinates of a parameter holding pairs of numbers.
+    pub(super) fn uvs(&self, name: &str) -> Option<Vec<Vector2>> {
+        let numbers = self.numbers(name)?;
+        Some(
+            numbers
+                .chunks_exact(2)
+                .map(|uv| Vector2::new(uv[0], uv[1]))
+                .collect(),
+        )
+    }
+
+    /// Returns the indices of a parameter, checking they are not negative.
+    pub(super) fn indices(&self, name: &str) -> io::Result<Option<Vec<usize>>> {
+        let Some(numbers) = self.numbers(name) else {
+            return Ok(None);
+        };
+        if numbers.iter().any(|&i| i < 0. || i.fract() != 0.) {
+            return Err(invalid(&format!("invalid pbrt {name} index")));
+        }
+        Ok(Some(numbers.iter().map(|&i| i as usize).collect()))
+    }
+}
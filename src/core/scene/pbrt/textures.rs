@@ -0,0 +1,263 @@
+use super::{
+    parameters::{invalid, Parameters},
+    Importer,
+};
+use crate::{
+    core::{
+        BrickTexture, ChannelTexture, Color3, ColorRamp, DomainWarpTexture, FractalKind,
+        FractalNoiseTexture, GradientTexture, HSVTexture, MathTexture, ObjectSpaceTexture,
+        RemapTexture, SolidColorTexture, TextureChannel, TriplanarTexture, UVTransform,
+        UVTransformTexture, Vector2, WoodTexture, WorleyFeature, WorleyNoise, WorleyTexture,
+    },
+    traits::Texture,
+};
+use std::{io, rc::Rc};
+
+/// The type of a texture, which its texture parameters share.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TextureType {
+    Spectrum,
+    Float,
+}
+
+/// Returns the uv transform of the `uscale`, `vscale`, `rotate`, `udelta`, `vdelta` and `tile`
+/// parameters, the uv mapping of pbrt's textures with the rotation and tiling of the crate.
+pub(super) fn uv_transform(parameters: &Parameters) -> UVTransform {
+    UVTransform {
+        scale: Vector2::new(
+            parameters.float("uscale", 1.),
+            parameters.float("vscale", 1.),
+        ),
+        rotation: parameters.float("rotate", 0.),
+        offset: Vector2::new(
+            parameters.float("udelta", 0.),
+            parameters.float("vdelta", 0.),
+        ),
+        tile: parameters.bool("tile", false),
+    }
+}
+
+impl Importer {
+    /// Returns a float or texture parameter as a gray texture, or a constant default if it is
+    /// missing.
+    pub(super) fn float_texture(
+        &mut self,
+        parameters: &Parameters,
+        name: &str,
+        default: f32,
+    ) -> Rc<dyn Texture> {
+        if parameters.get(name).is_some_and(|p| p.kind == "texture") {
+            let texture = parameters.string(name).unwrap_or_default().to_owned();
+            match self.float_textures.get(&texture) {
+                Some(texture) => return Rc::clone(texture),
+                None => self.warn(format!("unknown float texture {texture}")),
+            }
+        }
+        let value = parameters.float(name, default);
+        Rc::new(SolidColorTexture::new(value, value, value))
+    }
+
+    /// Returns a texture parameter of a texture of the given type, or a constant default if it
+    /// is missing.
+    pub(super) fn input_texture(
+        &mut self,
+        parameters: &Parameters,
+        name: &str,
+        default: f32,
+        texture_type: TextureType,
+    ) -> Rc<dyn Texture> {
+        match texture_type {
+            TextureType::Spectrum => {
+                self.spectrum_texture(parameters, name, Color3::new(default, default, default))
+            }
+            TextureType::Float => self.float_texture(parameters, name, default),
+        }
+    }
+
+    /// Returns the color ramp of the `positions` and `colors` parameters, black to white if they
+    /// are missing.
+    fn color_ramp(&self, parameters: &Parameters) -> io::Result<ColorRamp> {
+        let (Some(positions), Some(colors)) = (
+            parameters.numbers("positions"),
+            parameters.numbers("colors"),
+        ) else {
+            return Ok(ColorRamp::new_grayscale());
+        };
+        if positions.is_empty() || colors.len() != 3 * positions.len() {
+            return Err(invalid("color ramp positions and colors mismatch"));
+        }
+        let stops = positions
+            .iter()
+            .zip(colors.chunks_exact(3))
+            .map(|(position, color)| (*position, Color3::new(color[0], color[1], color[2])))
+            .collect();
+        Ok(ColorRamp::new_with_colors(stops))
+    }
+
+    /// Returns the texture of pbrt's `constant`, `scale` and `mix` classes and of the texture
+    /// classes of the crate, `None` if the class is unknown.
+    pub(super) fn node_texture(
+        &mut self,
+        kind: &str,
+        parameters: &Parameters,
+        texture_type: TextureType,
+    ) -> io::Result<Option<Rc<dyn Texture>>> {
+        let texture: Rc<dyn Texture> = match kind {
+            "constant" => self.input_texture(parameters, "value", 1., texture_type),
+            "scale" => Rc::new(MathTexture::new_multiply(
+                self.input_texture(parameters, "tex", 1., texture_type),
+                self.float_texture(parameters, "scale", 1.),
+            )),
+            "mix" => Rc::new(MathTexture::new_mix(
+                self.input_texture(parameters, "tex1", 0., texture_type),
+                self.input_texture(parameters, "tex2", 1., texture_type),
+                self.float_texture(parameters, "amount", 0.5),
+            )),
+            "add" | "subtract" | "multiply" => {
+                let first = self.input_texture(parameters, "tex1", 0., texture_type);
+                let second = self.input_texture(parameters, "tex2", 0., texture_type);
+                Rc::new(match kind {
+                    "add" => MathTexture::new_add(first, second),
+                    "subtract" => MathTexture::new_subtract(first, second),
+                    _ => MathTexture::new_multiply(first, second),
+                })
+            }
+            "invert" => {
+                let input = self.input_texture(parameters, "tex", 0., texture_type);
+                Rc::new(MathTexture::new_invert(input))
+            }
+            "clamp" => Rc::new(MathTexture::new_clamp(
+                self.input_texture(parameters, "tex", 0., texture_type),
+                parameters.float("min", 0.),
+                parameters.float("max", 1.),
+            )),
+            "ramp" => {
+                let ramp = self.color_ramp(parameters)?;
+                let input = self.float_texture(parameters, "tex", 0.);
+                Rc::new(RemapTexture::new_with_ramp(input, ramp))
+            }
+            "curve" => {
+                let points = parameters
+                    .uvs("points")
+                    .filter(|points| !points.is_empty())
+                    .ok_or_else(|| invalid("curve texture without points"))?;
+                let input = self.input_texture(parameters, "tex", 0., texture_type);
+                Rc::new(RemapTexture::new_with_curve(input, points))
+            }
+            "hsv" => {
+                let mut texture =
+                    HSVTexture::new(self.input_texture(parameters, "tex", 0., texture_type));
+                texture.hue_shift = parameters.float("hue", 0.);
+                texture.saturation = parameters.float("saturation", 1.);
+                texture.value = parameters.float("value", 1.);
+                Rc::new(texture)
+            }
+            "uvtransform" => {
+                let input = self.input_texture(parameters, "tex", 0., texture_type);
+                Rc::new(UVTransformTexture::new(input, uv_transform(parameters)))
+            }
+            "objectspace" => {
+                let input = self.input_texture(parameters, "tex", 0., texture_type);
+                Rc::new(ObjectSpaceTexture::new(input))
+            }
+            "triplanar" => {
+                let input = self.input_texture(parameters, "tex", 0., texture_type);
+                let mut texture = TriplanarTexture::new(input, parameters.float("scale", 1.));
+                texture.sharpness = parameters.float("sharpness", 4.);
+                Rc::new(texture)
+            }
+            "warp" => Rc::new(DomainWarpTexture::new(
+                self.input_texture(parameters, "tex", 0., texture_type),
+                self.float_texture(parameters, "warp", 0.5),
+                parameters.float("amount", 0.1),
+            )),
+            "channel" => {
+                let channel = match parameters.string("channel").unwrap_or("average") {
+                    "red" => TextureChannel::Red,
+                    "green" => TextureChannel::Green,
+                    "blue" => TextureChannel::Blue,
+                    "alpha" => TextureChannel::Alpha,
+                    "average" => TextureChannel::Average,
+                    "luminance" => TextureChannel::Luminance,
+                    channel => return Err(invalid(&format!("unknown texture channel {channel}"))),
+                };
+                let input = self.spectrum_texture(parameters, "tex", Color3::zero());
+                Rc::new(ChannelTexture::new(input, channel))
+            }
+            "worley" => {
+                let feature = match parameters.string("feature").unwrap_or("f1") {
+                    "f1" => WorleyFeature::F1,
+                    "f2" => WorleyFeature::F2,
+                    "f2-f1" => WorleyFeature::F2MinusF1,
+                    feature => return Err(invalid(&format!("unknown worley feature {feature}"))),
+                };
+                let ramp = self.color_ramp(parameters)?;
+                let mut texture = WorleyTexture::new(parameters.float("scale", 1.), feature, ramp);
+                if parameters.get("seed").is_some() {
+                    let seed = parameters.float("seed", 0.) as u32;
+                    texture.noise = WorleyNoise::new_with_seed(seed);
+                }
+                Rc::new(texture)
+            }
+            "fractal" => {
+                let mut texture = FractalNoiseTexture::new(
+                    parameters.float("scale", 1.),
+                    self.color_ramp(parameters)?,
+                );
+                texture.kind = match parameters.string("type").unwrap_or("fbm") {
+                    "fbm" => FractalKind::FBM,
+                    "ridged" => FractalKind::Ridged,
+                    kind => return Err(invalid(&format!("unknown fractal type {kind}"))),
+                };
+                texture.octaves = parameters.float("octaves", 6.).max(1.) as usize;
+                texture.lacunarity = parameters.float("lacunarity", 2.);
+                texture.gain = parameters.float("gain", 0.5);
+                Rc::new(texture)
+            }
+            "wood" => {
+                let mut texture =
+                    WoodTexture::new(parameters.float("rings", 8.), self.color_ramp(parameters)?);
+                texture.distortion = parameters.float("distortion", 0.5);
+                texture.noise_scale = parameters.float("noisescale", 2.);
+                Rc::new(texture)
+            }
+            "gradient" => {
+                let ramp = self.color_ramp(parameters)?;
+                let point = |name: &str, default: Vector2| {
+                    parameters
+                        .uvs(name)
+                        .and_then(|points| points.first().copied())
+                        .unwrap_or(default)
+                };
+                Rc::new(match parameters.string("type").unwrap_or("linear") {
+                    "linear" => GradientTexture::new_linear(
+                        point("start", Vector2::zero()),
+                        point("end", Vector2::new(1., 0.)),
+                        ramp,
+                    ),
+                    "radial" => GradientTexture::new_radial(
+                        point("center", Vector2::new(0.5, 0.5)),
+                        parameters.float("radius", 0.5),
+                        ramp,
+                    ),
+                    kind => return Err(invalid(&format!("unknown gradient type {kind}"))),
+                })
+            }
+            "brick" => {
+                let mut texture = BrickTexture::new(
+                    Vector2::new(
+                        parameters.float("width", 0.25),
+                        parameters.float("height", 0.1),
+                    ),
+                    parameters.float("mortar", 0.01),
+                    self.input_texture(parameters, "tex1", 1., texture_type),
+                    self.input_texture(parameters, "tex2", 0., texture_type),
+                );
+                texture.row_offset = parameters.float("offset", 0.5);
+                Rc::new(texture)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(texture))
+    }
+}
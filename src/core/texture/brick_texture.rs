@@ -0,0 +1,133 @@
+use super::SolidColorTexture;
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/**
+Brick Texture
+
+A Texture of bricks or tiles laid in rows along u, separated by mortar. Each row is shifted along
+u by a fraction of a brick, half a brick for running bond and none for tiles. The bricks and the
+mortar are textures of their own, so they may be noisy or image textured.
+
+# Examples
+
+```
+use raytracing::{
+    core::{BrickTexture, Color3, Point3, Vector2},
+    traits::Texture,
+};
+
+// Bricks of 0.25 x 0.1 in uv, with mortar 0.01 wide.
+let texture = BrickTexture::new_with_solid_color(
+    Vector2::new(0.25, 0.1),
+    0.01,
+    Color3::new(0.6, 0.2, 0.1),
+    Color3::new(0.8, 0.8, 0.8),
+);
+let brick = texture.value(&Vector2::new(0.1, 0.05), &Point3::zero());
+let mortar = texture.value(&Vector2::new(0.1, 0.1), &Point3::zero());
+# assert_eq!(brick, Color3::new(0.6, 0.2, 0.1));
+# assert_eq!(mortar, Color3::new(0.8, 0.8, 0.8));
+```
+*/
+#[derive(Debug)]
+pub struct BrickTexture {
+    /// The texture of the bricks.
+    pub brick: Rc<dyn Texture>,
+
+    /// The texture of the mortar.
+    pub mortar: Rc<dyn Texture>,
+
+    /// The size in uv of a brick and its share of the mortar around it.
+    pub size: Vector2,
+
+    /// The width in uv of the mortar between bricks.
+    pub mortar_width: f32,
+
+    /// The shift along u of each row, in bricks.
+    pub row_offset: f32,
+}
+
+impl BrickTexture {
+    /// Create a new `BrickTexture` in running bond with the given brick size, mortar width and
+    /// textures.
+    pub fn new(
+        size: Vector2,
+        mortar_width: f32,
+        brick: Rc<dyn Texture>,
+        mortar: Rc<dyn Texture>,
+    ) -> Self {
+        Self {
+            brick,
+            mortar,
+            size,
+            mortar_width,
+            row_offset: 0.5,
+        }
+    }
+
+    /// Create a new `BrickTexture` in running bond with the given brick size, mortar width and
+    /// colors.
+    pub fn new_with_solid_color(
+        size: Vector2,
+        mortar_width: f32,
+        brick_color: Color3,
+        mortar_color: Color3,
+    ) -> Self {
+        Self::new(
+            size,
+            mortar_width,
+            Rc::new(SolidColorTexture::new_with_color(brick_color)),
+            Rc::new(SolidColorTexture::new_with_color(mortar_color)),
+        )
+    }
+
+    /// Create a new `BrickTexture` of tiles in a grid with the given tile size, grout width and
+    /// textures.
+    pub fn new_tile(
+        size: Vector2,
+        mortar_width: f32,
+        tile: Rc<dyn Texture>,
+        mortar: Rc<dyn Texture>,
+    ) -> Self {
+        Self {
+            row_offset: 0.,
+            ..Self::new(size, mortar_width, tile, mortar)
+        }
+    }
+
+    /// Returns true if the uv coordinates are in the mortar.
+    pub fn is_mortar(&self, uv: &Vector2) -> bool {
+        let row = f32::floor(uv.v() / self.size.y);
+        let u = uv.u() / self.size.x + row * self.row_offset;
+        let x = (u - f32::floor(u)) * self.size.x;
+        let y = uv.v() - row * self.size.y;
+
+        // Each brick has half of the mortar around it.
+        let half = 0.5 * self.mortar_width;
+        x < half || x > self.size.x - half || y < half || y > self.size.y - half
+    }
+}
+
+impl Texture for BrickTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        if self.is_mortar(uv) {
+            self.mortar.value(uv, point)
+        } else {
+            self.brick.value(uv, point)
+        }
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let Some(uv) = hit_record.uv else {
+            return Color3::zero();
+        };
+        if self.is_mortar(&uv) {
+            self.mortar.value_at_hit(hit_record)
+        } else {
+            self.brick.value_at_hit(hit_record)
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/// The channel a `ChannelTexture` reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureChannel {
+    /// The red channel.
+    Red,
+
+    /// The green channel.
+    Green,
+
+    /// The blue channel.
+    Blue,
+
+    /// The opacity.
+    Alpha,
+
+    /// The average of the rgb channels, the scalar value of a texture.
+    Average,
+
+    /// The luminance of the rgb channels.
+    Luminance,
+}
+
+/**
+Channel Texture
+
+A scalar Texture reading a single channel of another texture as a gray color, for inputs such as
+roughness, masks or bump heights packed into the channels of a texture, like the roughness in the
+green channel of glTF metallic-roughness images.
+
+# Examples
+
+```
+use raytracing::{
+    core::{ChannelTexture, Color3, Point3, SolidColorTexture, TextureChannel, Vector2},
+    traits::Texture,
+};
+use std::rc::Rc;
+
+let packed = Rc::new(SolidColorTexture::new(0., 0.25, 1.));
+let roughness = ChannelTexture::new(packed, TextureChannel::Green);
+let value = roughness.value(&Vector2::zero(), &Point3::zero());
+# assert_eq!(value, Color3::new(0.25, 0.25, 0.25));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct ChannelTexture {
+    /// The texture to read.
+    pub texture: Rc<dyn Texture>,
+
+    /// The channel to read.
+    pub channel: TextureChannel,
+}
+
+impl ChannelTexture {
+    /// Create a new `ChannelTexture` reading a channel of a texture.
+    pub fn new(texture: Rc<dyn Texture>, channel: TextureChannel) -> Self {
+        Self { texture, channel }
+    }
+
+    /// Returns the gray color of the channel of a color, or of the opacity.
+    fn gray(&self, color: impl FnOnce() -> Color3, alpha: impl FnOnce() -> f32) -> Color3 {
+        let value = match self.channel {
+            TextureChannel::Red => color().x,
+            TextureChannel::Green => color().y,
+            TextureChannel::Blue => color().z,
+            TextureChannel::Alpha => alpha(),
+            TextureChannel::Average => color().average(),
+            TextureChannel::Luminance => color().luminance(),
+        };
+        Color3::new(value, value, value)
+    }
+}
+
+impl Texture for ChannelTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.gray(
+            || self.texture.value(uv, point),
+            || self.texture.alpha(uv, point),
+        )
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return Color3::zero();
+        };
+        self.gray(
+            || self.texture.value_at_hit(hit_record),
+            || self.texture.alpha(&uv, &point),
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use super::SolidColorTexture;
-use crate::core::{Color3, Point3};
+use crate::core::{Color3, HitRecord, Point3, Vector2};
 use std::rc::Rc;
 
 pub use crate::traits::Texture;
@@ -7,7 +7,28 @@ pub use crate::traits::Texture;
 /**
 Checker Texture
 
-A Texture that alternates between two textures.
+A Texture that alternates between two textures, in cubes of space or in squares of the uv
+coordinates. Checks in space stay put when objects move through them and cut across curved
+surfaces, checks in uv follow the surface.
+
+# Examples
+
+```
+use raytracing::{
+    core::{CheckerTexture, Color3, Point3, Vector2},
+    traits::Texture,
+};
+
+// 8 checks around a sphere and 4 from pole to pole.
+let texture = CheckerTexture::new_uv_with_solid_color(
+    Vector2::new(8., 4.),
+    Color3::one(),
+    Color3::zero(),
+);
+let color = texture.value(&Vector2::new(0.2, 0.1), &Point3::zero());
+# assert_eq!(color, Color3::zero());
+# assert_eq!(texture.value(&Vector2::new(0.2, 0.3), &Point3::zero()), Color3::one());
+```
 */
 #[derive(Debug)]
 pub struct CheckerTexture {
@@ -19,6 +40,10 @@ pub struct CheckerTexture {
 
     /// The inverted uv scale.
     invert_scale: f32,
+
+    /// The number of checks along u and v over the uv square, checking in uv instead of space
+    /// when set.
+    pub uv_frequency: Option<Vector2>,
 }
 
 impl CheckerTexture {
@@ -28,31 +53,75 @@ impl CheckerTexture {
             even,
             odd,
             invert_scale: 1. / scale,
+            uv_frequency: None,
         }
     }
 
     /// Create a new `CheckerTexture` with the given scale and two colors.
     pub fn new_with_solid_color(scale: f32, even_color: Color3, odd_color: Color3) -> Self {
+        Self::new(
+            scale,
+            Rc::new(SolidColorTexture::new_with_color(even_color)),
+            Rc::new(SolidColorTexture::new_with_color(odd_color)),
+        )
+    }
+
+    /// Create a new `CheckerTexture` checking in uv, with the given number of checks along u and
+    /// v and two textures.
+    pub fn new_uv(frequency: Vector2, even: Rc<dyn Texture>, odd: Rc<dyn Texture>) -> Self {
         Self {
-            even: Rc::new(SolidColorTexture::new_with_color(even_color)),
-            odd: Rc::new(SolidColorTexture::new_with_color(odd_color)),
-            invert_scale: 1. / scale,
+            uv_frequency: Some(frequency),
+            ..Self::new(1., even, odd)
         }
     }
-}
 
-impl Texture for CheckerTexture {
-    fn value(&self, uv: &crate::core::Vector2, point: &Point3) -> Color3 {
+    /// Create a new `CheckerTexture` checking in uv, with the given number of checks along u and
+    /// v and two colors.
+    pub fn new_uv_with_solid_color(
+        frequency: Vector2,
+        even_color: Color3,
+        odd_color: Color3,
+    ) -> Self {
+        Self::new_uv(
+            frequency,
+            Rc::new(SolidColorTexture::new_with_color(even_color)),
+            Rc::new(SolidColorTexture::new_with_color(odd_color)),
+        )
+    }
+
+    /// Returns true if the even texture is used at the uv coordinates and hit point.
+    pub fn is_even(&self, uv: &Vector2, point: &Point3) -> bool {
+        if let Some(frequency) = self.uv_frequency {
+            let u_factor = f32::floor(uv.u() * frequency.u()) as i32;
+            let v_factor = f32::floor(uv.v() * frequency.v()) as i32;
+            return (u_factor + v_factor).rem_euclid(2) == 0;
+        }
+
         let x_factor = f32::floor(self.invert_scale * point.x) as i32;
         let y_fatcor = f32::floor(self.invert_scale * point.y) as i32;
         let z_factor = f32::floor(self.invert_scale * point.z) as i32;
 
-        let is_even = (x_factor + y_fatcor + z_factor) % 2 == 0;
+        (x_factor + y_fatcor + z_factor) % 2 == 0
+    }
+}
 
-        if is_even {
+impl Texture for CheckerTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        if self.is_even(uv, point) {
             self.even.value(uv, point)
         } else {
             self.odd.value(uv, point)
         }
     }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return Color3::zero();
+        };
+        if self.is_even(&uv, &point) {
+            self.even.value_at_hit(hit_record)
+        } else {
+            self.odd.value_at_hit(hit_record)
+        }
+    }
 }
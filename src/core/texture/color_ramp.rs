@@ -0,0 +1,92 @@
+use super::SolidColorTexture;
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/**
+Color Ramp
+
+Maps values to colors by interpolating linearly between stops, each a position and a texture, so
+a procedural texture may blend other textures as well as colors. Values before the first stop or
+after the last one take its color.
+
+# Examples
+
+```
+use raytracing::core::{Color3, ColorRamp, Point3, Vector2};
+
+let ramp = ColorRamp::new_with_colors(vec![
+    (0., Color3::zero()),
+    (1., Color3::new(1., 0.5, 0.)),
+]);
+let color = ramp.value(0.5, &Vector2::zero(), &Point3::zero());
+# assert_eq!(color, Color3::new(0.5, 0.25, 0.));
+# assert_eq!(ramp.value(2., &Vector2::zero(), &Point3::zero()), Color3::new(1., 0.5, 0.));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct ColorRamp {
+    /// The stops, sorted by position.
+    stops: Vec<(f32, Rc<dyn Texture>)>,
+}
+
+impl ColorRamp {
+    /// Create a new `ColorRamp` from stops of textures, in any order.
+    pub fn new(mut stops: Vec<(f32, Rc<dyn Texture>)>) -> Self {
+        assert!(!stops.is_empty(), "a color ramp needs a stop");
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// Create a new `ColorRamp` from stops of colors, in any order.
+    pub fn new_with_colors(stops: Vec<(f32, Color3)>) -> Self {
+        Self::new(
+            stops
+                .into_iter()
+                .map(|(position, color)| {
+                    let texture: Rc<dyn Texture> =
+                        Rc::new(SolidColorTexture::new_with_color(color));
+                    (position, texture)
+                })
+                .collect(),
+        )
+    }
+
+    /// Create a new `ColorRamp` between two textures, at 0 and 1.
+    pub fn new_with_textures(low: Rc<dyn Texture>, high: Rc<dyn Texture>) -> Self {
+        Self::new(vec![(0., low), (1., high)])
+    }
+
+    /// Create a new `ColorRamp` from black at 0 to white at 1.
+    pub fn new_grayscale() -> Self {
+        Self::new_with_colors(vec![(0., Color3::zero()), (1., Color3::one())])
+    }
+
+    /// Returns the color of a value, evaluating the textures of the stops around it by uv
+    /// coordinates and hit point.
+    pub fn value(&self, t: f32, uv: &Vector2, point: &Point3) -> Color3 {
+        self.blend(t, |texture| texture.value(uv, point))
+    }
+
+    /// Returns the color of a value, evaluating the textures of the stops around it at a hit.
+    pub fn value_at_hit(&self, t: f32, hit_record: &HitRecord) -> Color3 {
+        self.blend(t, |texture| texture.value_at_hit(hit_record))
+    }
+
+    /// Returns the color of a value, evaluating the textures of the stops with `input`.
+    fn blend(&self, t: f32, input: impl Fn(&dyn Texture) -> Color3) -> Color3 {
+        let next = self.stops.partition_point(|(position, _)| *position <= t);
+        if next == 0 {
+            return input(self.stops[0].1.as_ref());
+        }
+        if next == self.stops.len() {
+            return input(self.stops[next - 1].1.as_ref());
+        }
+
+        let (start, low) = &self.stops[next - 1];
+        let (end, high) = &self.stops[next];
+        let weight = (t - start) / (end - start);
+        &(&input(low.as_ref()) * (1. - weight)) + &(&input(high.as_ref()) * weight)
+    }
+}
@@ -0,0 +1,98 @@
+use crate::core::{Color3, HitRecord, Point3, Vector2, Vector3};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/// Offsets at which the warp texture is read for each axis of the displacement, far apart so the
+/// axes vary independently.
+const AXIS_OFFSETS: [[f32; 3]; 3] = [[0., 0., 0.], [5.2, 1.3, 7.1], [1.7, 9.2, 3.4]];
+
+/**
+Domain Warp Texture
+
+A Texture that reads another texture at points displaced by a warp texture, to swirl or distort
+noise and patterns. The displacement along each axis is the scalar value of the warp texture, the
+average of its rgb channels, read at a different offset for each axis and mapped from [0, 1] to
+[-amount, amount]. The uv coordinates are displaced by the first two axes.
+
+# Examples
+
+```
+use raytracing::{
+    core::{
+        ColorRamp, DomainWarpTexture, FractalNoiseTexture, Point3, Vector2, WorleyFeature,
+        WorleyTexture,
+    },
+    traits::Texture,
+};
+use std::rc::Rc;
+
+// Cells swirled by noise.
+let cells = Rc::new(WorleyTexture::new(4., WorleyFeature::F1, ColorRamp::new_grayscale()));
+let noise = Rc::new(FractalNoiseTexture::new(2., ColorRamp::new_grayscale()));
+let texture = DomainWarpTexture::new(cells, noise, 0.3);
+let color = texture.value(&Vector2::zero(), &Point3::new(0.1, 0.2, 0.3));
+# assert!(color.x >= 0. && color.x <= 1.);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct DomainWarpTexture {
+    /// The warped texture.
+    pub texture: Rc<dyn Texture>,
+
+    /// The scalar texture displacing the points.
+    pub warp: Rc<dyn Texture>,
+
+    /// The largest displacement, in units of space and uv.
+    pub amount: f32,
+}
+
+impl DomainWarpTexture {
+    /// Create a new `DomainWarpTexture` displacing a texture by a warp texture.
+    pub fn new(texture: Rc<dyn Texture>, warp: Rc<dyn Texture>, amount: f32) -> Self {
+        Self {
+            texture,
+            warp,
+            amount,
+        }
+    }
+
+    /// Returns the displacement, reading the warp texture with `read` at the offset uv
+    /// coordinates and points of each axis.
+    fn displacement(&self, read: impl Fn(&Vector2, &Vector3) -> Color3) -> Vector3 {
+        let [x, y, z] = AXIS_OFFSETS.map(|[ox, oy, oz]| {
+            let value = read(&Vector2::new(ox, oy), &Vector3::new(ox, oy, oz)).average();
+            self.amount * (2. * value - 1.)
+        });
+        Vector3::new(x, y, z)
+    }
+}
+
+impl Texture for DomainWarpTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        let displacement = self.displacement(|uv_offset, offset| {
+            self.warp.value(&(uv + uv_offset), &(point + offset))
+        });
+        let uv = uv + &Vector2::new(displacement.x, displacement.y);
+        self.texture.value(&uv, &(point + &displacement))
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let (Some(uv), Some(point)) = (hit_record.uv, hit_record.point) else {
+            return Color3::zero();
+        };
+
+        let displaced = |uv_offset: &Vector2, offset: &Vector3| {
+            let mut displaced = hit_record.clone();
+            displaced.uv = Some(&uv + uv_offset);
+            displaced.point = Some(&point + offset);
+            displaced
+        };
+        let displacement = self.displacement(|uv_offset, offset| {
+            self.warp.value_at_hit(&displaced(uv_offset, offset))
+        });
+        let uv_displacement = Vector2::new(displacement.x, displacement.y);
+        self.texture
+            .value_at_hit(&displaced(&uv_displacement, &displacement))
+    }
+}
@@ -0,0 +1,111 @@
+use super::ColorRamp;
+use crate::core::{Color3, HitRecord, PerlinNoise, Point3, Vector2};
+
+pub use crate::traits::Texture;
+
+/// How the octaves of a `FractalNoiseTexture` add up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FractalKind {
+    /// Fractional Brownian motion, soft clouds mapped to [0, 1] around 0.5.
+    #[default]
+    FBM,
+
+    /// Ridged multifractal, sharp crests in [0, 1] like mountain ranges or veins.
+    Ridged,
+}
+
+/**
+Fractal Noise Texture
+
+A Texture that maps octaves of Perlin noise at the hit point in space through a color ramp. Each
+octave is `lacunarity` times finer and `gain` times weaker than the previous one. The uv
+coordinates are ignored.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, ColorRamp, FractalKind, FractalNoiseTexture, Point3, Vector2},
+    traits::Texture,
+};
+
+let ramp = ColorRamp::new_with_colors(vec![
+    (0.2, Color3::new(0.1, 0.1, 0.1)),
+    (0.8, Color3::new(0.9, 0.8, 0.7)),
+]);
+let mut texture = FractalNoiseTexture::new(4., ramp);
+texture.kind = FractalKind::Ridged;
+texture.octaves = 8;
+let color = texture.value(&Vector2::zero(), &Point3::new(0.1, 0.2, 0.3));
+# assert!(color.x >= 0.1 && color.x <= 0.9);
+```
+*/
+#[derive(Debug)]
+pub struct FractalNoiseTexture {
+    /// The noise.
+    noise: PerlinNoise,
+
+    /// How the octaves add up.
+    pub kind: FractalKind,
+
+    /// The frequency of the first octave, per unit of space.
+    pub scale: f32,
+
+    /// The number of octaves.
+    pub octaves: usize,
+
+    /// The frequency factor between octaves.
+    pub lacunarity: f32,
+
+    /// The amplitude factor between octaves.
+    pub gain: f32,
+
+    /// The colors of the noise values.
+    pub ramp: ColorRamp,
+}
+
+impl FractalNoiseTexture {
+    /// Create a new fBm `FractalNoiseTexture` with the given frequency and ramp, 6 octaves, a
+    /// lacunarity of 2 and a gain of 0.5.
+    pub fn new(scale: f32, ramp: ColorRamp) -> Self {
+        Self {
+            noise: PerlinNoise::new(),
+            kind: FractalKind::FBM,
+            scale,
+            octaves: 6,
+            lacunarity: 2.,
+            gain: 0.5,
+            ramp,
+        }
+    }
+
+    /// Returns the noise at a point, mostly between 0 and 1.
+    pub fn noise_value(&self, point: &Point3) -> f32 {
+        let scaled_point = point * self.scale;
+        match self.kind {
+            FractalKind::FBM => {
+                let fbm = self
+                    .noise
+                    .fbm(&scaled_point, self.octaves, self.lacunarity, self.gain);
+                0.5 + 0.5 * fbm
+            }
+            FractalKind::Ridged => {
+                self.noise
+                    .ridged(&scaled_point, self.octaves, self.lacunarity, self.gain)
+            }
+        }
+    }
+}
+
+impl Texture for FractalNoiseTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.ramp.value(self.noise_value(point), uv, point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let Some(point) = hit_record.point else {
+            return Color3::zero();
+        };
+        self.ramp.value_at_hit(self.noise_value(&point), hit_record)
+    }
+}
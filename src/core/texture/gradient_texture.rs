@@ -0,0 +1,90 @@
+use super::ColorRamp;
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+
+pub use crate::traits::Texture;
+
+/// The shape of a `GradientTexture`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GradientShape {
+    /// From 0 at `start` to 1 at `end`, constant across the line between them.
+    Linear { start: Vector2, end: Vector2 },
+
+    /// From 0 at `center` to 1 at `radius` away from it.
+    Radial { center: Vector2, radius: f32 },
+}
+
+/**
+Gradient Texture
+
+A Texture that maps a linear or radial gradient in uv through a color ramp, for fades, vignettes
+or masks driving other textures.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, ColorRamp, GradientTexture, Point3, Vector2},
+    traits::Texture,
+};
+
+// Black at the center, white from 0.5 away.
+let texture = GradientTexture::new_radial(Vector2::new(0.5, 0.5), 0.5, ColorRamp::new_grayscale());
+let color = texture.value(&Vector2::new(0.75, 0.5), &Point3::zero());
+# assert_eq!(color, Color3::new(0.5, 0.5, 0.5));
+```
+*/
+#[derive(Debug)]
+pub struct GradientTexture {
+    /// The shape of the gradient.
+    shape: GradientShape,
+
+    /// The colors along the gradient.
+    pub ramp: ColorRamp,
+}
+
+impl GradientTexture {
+    /// Create a new linear `GradientTexture` from `start` to `end` in uv.
+    pub fn new_linear(start: Vector2, end: Vector2, ramp: ColorRamp) -> Self {
+        Self {
+            shape: GradientShape::Linear { start, end },
+            ramp,
+        }
+    }
+
+    /// Create a new radial `GradientTexture` around `center` in uv.
+    pub fn new_radial(center: Vector2, radius: f32, ramp: ColorRamp) -> Self {
+        Self {
+            shape: GradientShape::Radial { center, radius },
+            ramp,
+        }
+    }
+
+    /// Returns the position along the gradient of uv coordinates, 0 at its start and 1 at its
+    /// end.
+    pub fn position(&self, uv: &Vector2) -> f32 {
+        match self.shape {
+            GradientShape::Linear { start, end } => {
+                let axis = &end - &start;
+                let offset = uv - &start;
+                (offset.x * axis.x + offset.y * axis.y) / (axis.x * axis.x + axis.y * axis.y)
+            }
+            GradientShape::Radial { center, radius } => {
+                let offset = uv - &center;
+                f32::sqrt(offset.x * offset.x + offset.y * offset.y) / radius
+            }
+        }
+    }
+}
+
+impl Texture for GradientTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.ramp.value(self.position(uv), uv, point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let Some(uv) = hit_record.uv else {
+            return Color3::zero();
+        };
+        self.ramp.value_at_hit(self.position(&uv), hit_record)
+    }
+}
@@ -0,0 +1,71 @@
+use crate::core::{blackbody, Color3, DensityGrid, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/// How the values of a `GridTexture` are turned into colors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GridMapping {
+    /// The first three channels as rgb, or the first channel as gray.
+    Color,
+
+    /// The first channel as a temperature in kelvin, mapped to black body radiance.
+    Blackbody,
+}
+
+/**
+Grid Texture
+
+A Texture that reads a `DensityGrid` at the hit point in space, e.g. the emission or temperature of
+a fire simulation driving the emission of a medium. The uv coordinates are ignored.
+*/
+#[derive(Debug)]
+pub struct GridTexture {
+    /// The grid of values.
+    grid: Rc<DensityGrid>,
+
+    /// How the values are turned into colors.
+    mapping: GridMapping,
+
+    /// Factor applied to the colors.
+    pub scale: f32,
+}
+
+impl GridTexture {
+    /// Create a new `GridTexture` reading rgb values, or gray values from a single channel grid.
+    pub fn new(grid: Rc<DensityGrid>, scale: f32) -> Self {
+        Self {
+            grid,
+            mapping: GridMapping::Color,
+            scale,
+        }
+    }
+
+    /// Create a new `GridTexture` reading temperatures in kelvin, giving the black body radiance
+    /// in W/(sr·m²·nm) times `scale`.
+    pub fn new_blackbody(grid: Rc<DensityGrid>, scale: f32) -> Self {
+        Self {
+            grid,
+            mapping: GridMapping::Blackbody,
+            scale,
+        }
+    }
+}
+
+impl Texture for GridTexture {
+    fn value(&self, _uv: &Vector2, point: &Point3) -> Color3 {
+        let color = match self.mapping {
+            GridMapping::Color if self.grid.channels() >= 3 => Color3::new(
+                self.grid.value(point, 0),
+                self.grid.value(point, 1),
+                self.grid.value(point, 2),
+            ),
+            GridMapping::Color => {
+                let value = self.grid.value(point, 0);
+                Color3::new(value, value, value)
+            }
+            GridMapping::Blackbody => blackbody(self.grid.value(point, 0)),
+        };
+        &color * self.scale
+    }
+}
@@ -0,0 +1,74 @@
+use crate::core::{hsv_to_rgb, rgb_to_hsv, Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/**
+HSV Texture
+
+A Texture that adjusts the hue, saturation and value of another texture, to vary or grade a
+texture without repainting it.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, HSVTexture, Point3, SolidColorTexture, Vector2},
+    traits::Texture,
+};
+use std::rc::Rc;
+
+// Red turned green, at half saturation.
+let mut texture = HSVTexture::new(Rc::new(SolidColorTexture::new(1., 0., 0.)));
+texture.hue_shift = 1. / 3.;
+texture.saturation = 0.5;
+let color = texture.value(&Vector2::zero(), &Point3::zero());
+# assert!((&color - &Color3::new(0.5, 1., 0.5)).length() < 1e-5);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct HSVTexture {
+    /// The texture to adjust.
+    pub texture: Rc<dyn Texture>,
+
+    /// The shift of the hue, in turns.
+    pub hue_shift: f32,
+
+    /// The factor of the saturation, clamped to one.
+    pub saturation: f32,
+
+    /// The factor of the value.
+    pub value: f32,
+}
+
+impl HSVTexture {
+    /// Create a new `HSVTexture` leaving a texture unchanged until adjusted.
+    pub fn new(texture: Rc<dyn Texture>) -> Self {
+        Self {
+            texture,
+            hue_shift: 0.,
+            saturation: 1.,
+            value: 1.,
+        }
+    }
+
+    /// Returns a color of the texture adjusted.
+    fn adjust(&self, color: &Color3) -> Color3 {
+        let hsv = rgb_to_hsv(color);
+        hsv_to_rgb(&Color3::new(
+            hsv.x + self.hue_shift,
+            f32::clamp(hsv.y * self.saturation, 0., 1.),
+            hsv.z * self.value,
+        ))
+    }
+}
+
+impl Texture for HSVTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.adjust(&self.texture.value(uv, point))
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        self.adjust(&self.texture.value_at_hit(hit_record))
+    }
+}
@@ -1,47 +1,393 @@
-use crate::core::{Color3, Interval, Point3, Vector2};
-use image::{ImageResult, RgbImage};
+use crate::core::{Color3, ColorSpace, HitRecord, ImageEncoding, Point3, Vector2};
+use image::{DynamicImage, ImageResult};
 
 pub use crate::traits::Texture;
 
+/// The filter an `ImageTexture` reads its texels with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFilter {
+    /// The nearest texel, in the mip level matching the footprint of the ray.
+    Point,
+
+    /// Bilinear interpolation of the texels, in the mip level matching the footprint of the ray.
+    #[default]
+    Bilinear,
+
+    /// Bilinear interpolation in the two mip levels around the footprint of the ray, blended.
+    Trilinear,
+
+    /// Elliptically weighted average over the footprint of the ray, sharp along the longer axis
+    /// of footprints stretched by grazing angles.
+    EWA,
+}
+
+/// How an `ImageTexture` reads uv coordinates outside of [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WrapMode {
+    /// The image repeats.
+    Repeat,
+
+    /// The edge texels extend outwards.
+    #[default]
+    Clamp,
+
+    /// The image repeats, mirrored every other time.
+    Mirror,
+}
+
+impl WrapMode {
+    /// Internal method, returns the texel index of an index outside of an image of a size.
+    fn wrap(&self, index: i64, size: usize) -> usize {
+        let size = size as i64;
+        let index = match self {
+            WrapMode::Repeat => index.rem_euclid(size),
+            WrapMode::Clamp => index.clamp(0, size - 1),
+            WrapMode::Mirror => {
+                let index = index.rem_euclid(2 * size);
+                if index < size {
+                    index
+                } else {
+                    2 * size - 1 - index
+                }
+            }
+        };
+        index as usize
+    }
+}
+
+/// A level of the mip pyramid of an `ImageTexture`, rgba texels row by row from the top.
+#[derive(Debug)]
+struct MipLevel {
+    width: usize,
+    height: usize,
+    texels: Vec<[f32; 4]>,
+}
+
+impl MipLevel {
+    /// Returns the level of half the resolution, averaging blocks of 2x2 texels.
+    fn downsample(&self) -> Self {
+        let width = self.width.div_ceil(2);
+        let height = self.height.div_ceil(2);
+        let mut texels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut texel = [0.; 4];
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let i = usize::min(2 * x + dx, self.width - 1);
+                    let j = usize::min(2 * y + dy, self.height - 1);
+                    let source = self.texels[j * self.width + i];
+                    for c in 0..4 {
+                        texel[c] += source[c] / 4.;
+                    }
+                }
+                texels.push(texel);
+            }
+        }
+        Self {
+            width,
+            height,
+            texels,
+        }
+    }
+
+    /// Returns the texel at integer coordinates, wrapped into the image.
+    fn texel(&self, x: i64, y: i64, wrap: WrapMode) -> [f32; 4] {
+        let i = wrap.wrap(x, self.width);
+        let j = wrap.wrap(y, self.height);
+        self.texels[j * self.width + i]
+    }
+
+    /// Returns the texel containing image coordinates in [0, 1], from the top left.
+    fn nearest(&self, s: f32, t: f32, wrap: WrapMode) -> [f32; 4] {
+        let x = f32::floor(s * self.width as f32) as i64;
+        let y = f32::floor(t * self.height as f32) as i64;
+        self.texel(x, y, wrap)
+    }
+
+    /// Returns the bilinear interpolation of the four texels around image coordinates.
+    fn bilinear(&self, s: f32, t: f32, wrap: WrapMode) -> [f32; 4] {
+        let x = s * self.width as f32 - 0.5;
+        let y = t * self.height as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (dx, dy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+        let mut result = [0.; 4];
+        for (i, j, weight) in [
+            (x0, y0, (1. - dx) * (1. - dy)),
+            (x0 + 1, y0, dx * (1. - dy)),
+            (x0, y0 + 1, (1. - dx) * dy),
+            (x0 + 1, y0 + 1, dx * dy),
+        ] {
+            let texel = self.texel(i, j, wrap);
+            for c in 0..4 {
+                result[c] += texel[c] * weight;
+            }
+        }
+        result
+    }
+
+    /// Returns the average of the texels inside the ellipse spanned by two axes around image
+    /// coordinates, weighted by a gaussian falling to zero at its edge.
+    fn ewa(&self, s: f32, t: f32, axis0: [f32; 2], axis1: [f32; 2], wrap: WrapMode) -> [f32; 4] {
+        let (width, height) = (self.width as f32, self.height as f32);
+        let (s, t) = (s * width - 0.5, t * height - 0.5);
+        let axis0 = [axis0[0] * width, axis0[1] * height];
+        let axis1 = [axis1[0] * width, axis1[1] * height];
+
+        // The implicit equation a * s^2 + b * s * t + c * t^2 < 1 of the ellipse, widened by a
+        // texel so that thin ellipses still cover texel centers.
+        let mut a = axis0[1] * axis0[1] + axis1[1] * axis1[1] + 1.;
+        let mut b = -2. * (axis0[0] * axis0[1] + axis1[0] * axis1[1]);
+        let mut c = axis0[0] * axis0[0] + axis1[0] * axis1[0] + 1.;
+        let inverse_f = 1. / (a * c - b * b / 4.);
+        a *= inverse_f;
+        b *= inverse_f;
+        c *= inverse_f;
+
+        // The bounding box of the ellipse.
+        let determinant = -b * b + 4. * a * c;
+        let inverse_determinant = 1. / determinant;
+        let s_extent = 2. * inverse_determinant * f32::sqrt(determinant * c);
+        let t_extent = 2. * inverse_determinant * f32::sqrt(determinant * a);
+        let (s0, s1) = ((s - s_extent).ceil() as i64, (s + s_extent).floor() as i64);
+        let (t0, t1) = ((t - t_extent).ceil() as i64, (t + t_extent).floor() as i64);
+
+        let mut sum = [0.; 4];
+        let mut weight_sum = 0.;
+        for y in t0..=t1 {
+            let dt = y as f32 - t;
+            for x in s0..=s1 {
+                let ds = x as f32 - s;
+                let r2 = a * ds * ds + b * ds * dt + c * dt * dt;
+                if r2 < 1. {
+                    let weight = f32::exp(-2. * r2) - f32::exp(-2.);
+                    let texel = self.texel(x, y, wrap);
+                    for c in 0..4 {
+                        sum[c] += texel[c] * weight;
+                    }
+                    weight_sum += weight;
+                }
+            }
+        }
+        if weight_sum <= 0. {
+            return self.bilinear((s + 0.5) / width, (t + 0.5) / height, wrap);
+        }
+        sum.map(|value| value / weight_sum)
+    }
+}
+
 /**
 Image Texture
 
-A Texture that reads image data from a file.
+A Texture that reads image data from a file. Integer images are decoded from sRGB and float
+images are read as linear, unless the encoding is given. The image is kept with a pyramid of mip
+levels, each half the resolution of the previous one. Lookups at hits of camera rays, whose ray
+differentials give the footprint of a pixel in uv space, read the level matching the footprint,
+so that minified textures don't alias. Lookups by uv coordinates alone, through `value` and
+`alpha`, have no footprint and read the image itself.
+
+# Examples
+
+```no_run
+use raytracing::core::{ImageFilter, ImageTexture, WrapMode};
+
+let mut texture = ImageTexture::new("assets/earthmap.jpg".to_owned()).unwrap();
+texture.filter = ImageFilter::EWA;
+texture.wrap = WrapMode::Repeat;
+```
 */
 #[derive(Debug)]
 pub struct ImageTexture {
-    /// Image data.
-    image: RgbImage,
+    /// Image data, then the mip levels down to a single texel, in linear values.
+    levels: Vec<MipLevel>,
+
+    /// The encoding of the values of the image.
+    encoding: ImageEncoding,
+
+    /// The filter of texture lookups, bilinear by default.
+    pub filter: ImageFilter,
+
+    /// How uv coordinates outside of [0, 1] are read, clamping by default.
+    pub wrap: WrapMode,
+
+    /// The largest ratio of the axes of the footprints EWA filtering averages over, longer
+    /// footprints are widened to bound the texels read.
+    pub max_anisotropy: f32,
 }
 
 impl ImageTexture {
-    /// Create a new `ImageTexture` from the given path.
+    /// Create a new `ImageTexture` from the given path, decoding integer images as sRGB and
+    /// reading float images as linear.
     pub fn new(path: String) -> ImageResult<Self> {
-        let image = image::io::Reader::open(path)?.decode()?.to_rgb8();
-        ImageResult::Ok(Self { image })
+        let image = image::io::Reader::open(path)?.decode()?;
+        ImageResult::Ok(Self::new_with_image(&image, ImageEncoding::detect(&image)))
+    }
+
+    /// Create a new `ImageTexture` from the given path with the encoding of its values, such as
+    /// `ImageEncoding::Raw` for normal maps.
+    pub fn new_with_encoding(path: String, encoding: ImageEncoding) -> ImageResult<Self> {
+        let image = image::io::Reader::open(path)?.decode()?;
+        ImageResult::Ok(Self::new_with_image(&image, encoding))
+    }
+
+    /// Create a new `ImageTexture` from the content of an image file, such as an image embedded
+    /// in a scene file.
+    pub fn new_with_bytes(bytes: &[u8]) -> ImageResult<Self> {
+        let image = image::load_from_memory(bytes)?;
+        ImageResult::Ok(Self::new_with_image(&image, ImageEncoding::detect(&image)))
+    }
+
+    /// Create a new `ImageTexture` from decoded image data of any bit depth, decoded to linear
+    /// values before the mip levels are filtered.
+    pub fn new_with_image(image: &DynamicImage, encoding: ImageEncoding) -> Self {
+        let image = image.to_rgba32f();
+        let mut levels = Vec::new();
+        if image.width() > 0 && image.height() > 0 {
+            let mut level = MipLevel {
+                width: image.width() as usize,
+                height: image.height() as usize,
+                texels: image
+                    .pixels()
+                    .map(|pixel| {
+                        let [r, g, b, a] = pixel.0;
+                        [
+                            encoding.decode(r),
+                            encoding.decode(g),
+                            encoding.decode(b),
+                            a,
+                        ]
+                    })
+                    .collect(),
+            };
+            while level.width > 1 || level.height > 1 {
+                let next = level.downsample();
+                levels.push(level);
+                level = next;
+            }
+            levels.push(level);
+        }
+        Self {
+            levels,
+            encoding,
+            filter: ImageFilter::default(),
+            wrap: WrapMode::default(),
+            max_anisotropy: 8.,
+        }
+    }
+
+    /// Returns the encoding the values of the image were decoded from.
+    pub fn encoding(&self) -> ImageEncoding {
+        self.encoding
+    }
+
+    /// Converts the colors of the image from the color space of the image to another, such as
+    /// the working space of the camera. Raw data is left as it is.
+    pub fn convert_color_space(&mut self, from: ColorSpace, to: ColorSpace) {
+        if !self.encoding.is_color() || from == to {
+            return;
+        }
+        let conversion = from.conversion(to);
+        for level in &mut self.levels {
+            for texel in &mut level.texels {
+                let color = conversion.transform_vector(&Color3::new(texel[0], texel[1], texel[2]));
+                texel[..3].copy_from_slice(&[color.x, color.y, color.z]);
+            }
+        }
+    }
+
+    /// Returns the number of mip levels, the image included.
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the filtered rgba color at uv coordinates, over a footprint given by the change of
+    /// the uv coordinates from one pixel to the next along x and y.
+    pub fn lookup(&self, uv: &Vector2, duvdx: &Vector2, duvdy: &Vector2) -> [f32; 4] {
+        // If we have no texture data, then return a solid cyan color as a debugging aid.
+        if self.levels.is_empty() {
+            return [0., 1., 1., 1.];
+        }
+
+        // Images are stored from the top, v runs from the bottom.
+        let (s, t) = (uv.u(), 1. - uv.v());
+        let axis0 = [duvdx.u(), -duvdx.v()];
+        let axis1 = [duvdy.u(), -duvdy.v()];
+        let last = self.levels.len() - 1;
+        let resolution = usize::max(self.levels[0].width, self.levels[0].height) as f32;
+
+        if self.filter != ImageFilter::EWA {
+            // The level where the footprint spans about a texel.
+            let width = 2.
+                * [axis0[0], axis0[1], axis1[0], axis1[1]]
+                    .iter()
+                    .fold(0., |max: f32, value| max.max(value.abs()));
+            let level = f32::log2(f32::max(width * resolution, 1e-8));
+            if level >= last as f32 {
+                return self.levels[last].texel(0, 0, self.wrap);
+            }
+            let i = level.max(0.).floor() as usize;
+            return match self.filter {
+                ImageFilter::Point => self.levels[i].nearest(s, t, self.wrap),
+                ImageFilter::Bilinear => self.levels[i].bilinear(s, t, self.wrap),
+                _ if level <= 0. => self.levels[0].bilinear(s, t, self.wrap),
+                _ => {
+                    let blend = level - i as f32;
+                    let lower = self.levels[i].bilinear(s, t, self.wrap);
+                    let upper = self.levels[i + 1].bilinear(s, t, self.wrap);
+                    std::array::from_fn(|c| lower[c] * (1. - blend) + upper[c] * blend)
+                }
+            };
+        }
+
+        // Footprints too anisotropic are widened along their shorter axis.
+        let length = |axis: &[f32; 2]| f32::sqrt(axis[0] * axis[0] + axis[1] * axis[1]);
+        let (major, mut minor) = if length(&axis0) >= length(&axis1) {
+            (axis0, axis1)
+        } else {
+            (axis1, axis0)
+        };
+        let major_length = length(&major);
+        let mut minor_length = length(&minor);
+        if minor_length * self.max_anisotropy < major_length && minor_length > 0. {
+            let scale = major_length / (minor_length * self.max_anisotropy);
+            minor = minor.map(|value| value * scale);
+            minor_length *= scale;
+        }
+        if minor_length == 0. {
+            return self.levels[0].bilinear(s, t, self.wrap);
+        }
+
+        // The level where the shorter axis spans about a texel.
+        let level = f32::max(0., f32::log2(minor_length * resolution));
+        let i = level.floor() as usize;
+        if i >= last {
+            return self.levels[last].texel(0, 0, self.wrap);
+        }
+        let blend = level - i as f32;
+        let lower = self.levels[i].ewa(s, t, major, minor, self.wrap);
+        let upper = self.levels[i + 1].ewa(s, t, major, minor, self.wrap);
+        std::array::from_fn(|c| lower[c] * (1. - blend) + upper[c] * blend)
     }
 }
 
 impl Texture for ImageTexture {
     fn value(&self, uv: &Vector2, _point: &Point3) -> Color3 {
-        // If we have no texture data, then return a solid cyan color as a debugging aid.
-        if self.image.height() <= 0 {
-            return Color3::new(0., 1., 1.);
-        }
-
-        // Format the uv coordinates to [0,1], and flip v.
-        let u = Interval::new(0., 1.).clamp(uv.u());
-        let v = 1.0 - Interval::new(0., 1.).clamp(uv.v());
-
-        // Get pixel color from image.
-        let color_scale = 1.0 / 255.0;
-        let i = u * self.image.width() as f32;
-        let j = v * self.image.height() as f32;
-        let pixel = self.image.get_pixel(i as u32, j as u32);
-        Color3::new(
-            color_scale * pixel[0] as f32,
-            color_scale * pixel[1] as f32,
-            color_scale * pixel[2] as f32,
-        )
+        let [r, g, b, _] = self.lookup(uv, &Vector2::zero(), &Vector2::zero());
+        Color3::new(r, g, b)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let (Some(uv), Some(_)) = (hit_record.uv, hit_record.point) else {
+            return Color3::zero();
+        };
+
+        let duvdx = hit_record.duvdx.unwrap_or_default();
+        let duvdy = hit_record.duvdy.unwrap_or_default();
+        let [r, g, b, _] = self.lookup(&uv, &duvdx, &duvdy);
+        Color3::new(r, g, b)
+    }
+
+    fn alpha(&self, uv: &Vector2, _point: &Point3) -> f32 {
+        self.lookup(uv, &Vector2::zero(), &Vector2::zero())[3]
     }
 }
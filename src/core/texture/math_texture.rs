@@ -0,0 +1,167 @@
+use super::SolidColorTexture;
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/// The operation of a `MathTexture` on its input textures.
+#[derive(Debug, Clone)]
+pub enum TextureOperation {
+    /// The sum of two textures.
+    Add(Rc<dyn Texture>, Rc<dyn Texture>),
+
+    /// The difference of two textures.
+    Subtract(Rc<dyn Texture>, Rc<dyn Texture>),
+
+    /// The product of two textures, channel by channel.
+    Multiply(Rc<dyn Texture>, Rc<dyn Texture>),
+
+    /// The linear interpolation of two textures by the average of the rgb channels of a mask,
+    /// `0` giving the first texture and `1` the second one.
+    Mix {
+        first: Rc<dyn Texture>,
+        second: Rc<dyn Texture>,
+        mask: Rc<dyn Texture>,
+    },
+
+    /// One minus a texture.
+    Invert(Rc<dyn Texture>),
+
+    /// A texture clamped to a range.
+    Clamp {
+        texture: Rc<dyn Texture>,
+        min: f32,
+        max: f32,
+    },
+}
+
+/**
+Math Texture
+
+A Texture that combines other textures, the nodes of texture networks. Its inputs may be any
+texture, including other math textures, and are evaluated at the same hit, so image textures
+below it are still filtered by the footprint of the hit.
+
+Scalar textures, such as masks, roughness or bump heights, are gray textures, the average of the
+rgb channels of a texture being its scalar value.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, MathTexture, Point3, SolidColorTexture, Vector2},
+    traits::Texture,
+};
+use std::rc::Rc;
+
+let red = Rc::new(SolidColorTexture::new(1., 0., 0.));
+let blue = Rc::new(SolidColorTexture::new(0., 0., 1.));
+let purple = MathTexture::new_mix_with_weight(red, blue, 0.25);
+let color = purple.value(&Vector2::zero(), &Point3::zero());
+# assert_eq!(color, Color3::new(0.75, 0., 0.25));
+let inverted = MathTexture::new_invert(Rc::new(purple));
+# assert_eq!(inverted.value(&Vector2::zero(), &Point3::zero()), Color3::new(0.25, 1., 0.75));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct MathTexture {
+    /// The operation and its inputs.
+    pub operation: TextureOperation,
+}
+
+impl MathTexture {
+    /// Create a new `MathTexture` adding two textures.
+    pub fn new_add(first: Rc<dyn Texture>, second: Rc<dyn Texture>) -> Self {
+        Self {
+            operation: TextureOperation::Add(first, second),
+        }
+    }
+
+    /// Create a new `MathTexture` subtracting the second texture from the first one.
+    pub fn new_subtract(first: Rc<dyn Texture>, second: Rc<dyn Texture>) -> Self {
+        Self {
+            operation: TextureOperation::Subtract(first, second),
+        }
+    }
+
+    /// Create a new `MathTexture` multiplying two textures.
+    pub fn new_multiply(first: Rc<dyn Texture>, second: Rc<dyn Texture>) -> Self {
+        Self {
+            operation: TextureOperation::Multiply(first, second),
+        }
+    }
+
+    /// Create a new `MathTexture` interpolating two textures by a mask texture.
+    pub fn new_mix(first: Rc<dyn Texture>, second: Rc<dyn Texture>, mask: Rc<dyn Texture>) -> Self {
+        Self {
+            operation: TextureOperation::Mix {
+                first,
+                second,
+                mask,
+            },
+        }
+    }
+
+    /// Create a new `MathTexture` interpolating two textures by a constant weight.
+    pub fn new_mix_with_weight(
+        first: Rc<dyn Texture>,
+        second: Rc<dyn Texture>,
+        weight: f32,
+    ) -> Self {
+        let mask = Rc::new(SolidColorTexture::new(weight, weight, weight));
+        Self::new_mix(first, second, mask)
+    }
+
+    /// Create a new `MathTexture` inverting a texture.
+    pub fn new_invert(texture: Rc<dyn Texture>) -> Self {
+        Self {
+            operation: TextureOperation::Invert(texture),
+        }
+    }
+
+    /// Create a new `MathTexture` clamping a texture to `[min, max]`.
+    pub fn new_clamp(texture: Rc<dyn Texture>, min: f32, max: f32) -> Self {
+        Self {
+            operation: TextureOperation::Clamp { texture, min, max },
+        }
+    }
+
+    /// Returns the result of the operation, evaluating the inputs with `input`.
+    fn evaluate(&self, input: impl Fn(&dyn Texture) -> Color3) -> Color3 {
+        match &self.operation {
+            TextureOperation::Add(first, second) => {
+                &input(first.as_ref()) + &input(second.as_ref())
+            }
+            TextureOperation::Subtract(first, second) => {
+                &input(first.as_ref()) - &input(second.as_ref())
+            }
+            TextureOperation::Multiply(first, second) => {
+                &input(first.as_ref()) * &input(second.as_ref())
+            }
+            TextureOperation::Mix {
+                first,
+                second,
+                mask,
+            } => {
+                let weight = input(mask.as_ref()).average();
+                &(&input(first.as_ref()) * (1. - weight)) + &(&input(second.as_ref()) * weight)
+            }
+            TextureOperation::Invert(texture) => &Color3::one() - &input(texture.as_ref()),
+            TextureOperation::Clamp { texture, min, max } => {
+                let color = input(texture.as_ref());
+                let clamp = |value: f32| value.max(*min).min(*max);
+                Color3::new(clamp(color.x), clamp(color.y), clamp(color.z))
+            }
+        }
+    }
+}
+
+impl Texture for MathTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.evaluate(|texture| texture.value(uv, point))
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        self.evaluate(|texture| texture.value_at_hit(hit_record))
+    }
+}
@@ -1,9 +1,41 @@
+mod brick_texture;
+mod channel_texture;
 mod checker_texture;
+mod color_ramp;
+mod domain_warp_texture;
+mod fractal_noise_texture;
+mod gradient_texture;
+mod grid_texture;
+mod hsv_texture;
 mod image_texture;
+mod math_texture;
 mod noise_texture;
+mod object_space_texture;
+mod remap_texture;
 mod solid_texture;
+mod triplanar_texture;
+mod uv_transform_texture;
+mod vertex_color_texture;
+mod wood_texture;
+mod worley_texture;
 
+pub use brick_texture::*;
+pub use channel_texture::*;
 pub use checker_texture::*;
+pub use color_ramp::*;
+pub use domain_warp_texture::*;
+pub use fractal_noise_texture::*;
+pub use gradient_texture::*;
+pub use grid_texture::*;
+pub use hsv_texture::*;
 pub use image_texture::*;
+pub use math_texture::*;
 pub use noise_texture::*;
+pub use object_space_texture::*;
+pub use remap_texture::*;
 pub use solid_texture::*;
+pub use triplanar_texture::*;
+pub use uv_transform_texture::*;
+pub use vertex_color_texture::*;
+pub use wood_texture::*;
+pub use worley_texture::*;
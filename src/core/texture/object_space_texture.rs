@@ -0,0 +1,65 @@
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/**
+Object Space Texture
+
+A Texture that reads another texture at the hit point in the space of the object, before
+instancing and motion, so 3D procedural textures such as noise, checkers in space or wood stick to
+instanced and moving objects instead of sliding through world space. Hits on objects that are
+neither instanced nor moving read the world space point.
+
+# Examples
+
+```
+use raytracing::{
+    core::{CheckerTexture, Color3, HitRecord, ObjectSpaceTexture, Point3, Vector2},
+    traits::Texture,
+};
+use std::rc::Rc;
+
+let checker = Rc::new(CheckerTexture::new_with_solid_color(1., Color3::one(), Color3::zero()));
+let texture = ObjectSpaceTexture::new(checker);
+
+// A hit on an object moved by one check along x.
+let mut hit_record = HitRecord::new();
+hit_record.uv = Some(Vector2::zero());
+hit_record.point = Some(Point3::new(1.5, 0.5, 0.5));
+hit_record.object_point = Some(Point3::new(0.5, 0.5, 0.5));
+let color = texture.value_at_hit(&hit_record);
+# assert_eq!(color, Color3::one());
+```
+*/
+#[derive(Debug, Clone)]
+pub struct ObjectSpaceTexture {
+    /// The texture read in object space.
+    pub texture: Rc<dyn Texture>,
+}
+
+impl ObjectSpaceTexture {
+    /// Create a new `ObjectSpaceTexture` reading a texture in object space.
+    pub fn new(texture: Rc<dyn Texture>) -> Self {
+        Self { texture }
+    }
+}
+
+impl Texture for ObjectSpaceTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.texture.value(uv, point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let Some(object_point) = hit_record.object_point else {
+            return self.texture.value_at_hit(hit_record);
+        };
+        let mut object_record = hit_record.clone();
+        object_record.point = Some(object_point);
+        self.texture.value_at_hit(&object_record)
+    }
+
+    fn alpha(&self, uv: &Vector2, point: &Point3) -> f32 {
+        self.texture.alpha(uv, point)
+    }
+}
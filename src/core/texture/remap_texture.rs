@@ -0,0 +1,106 @@
+use super::ColorRamp;
+use crate::core::{Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/// How a `RemapTexture` maps the values of its input.
+#[derive(Debug, Clone)]
+pub enum Remapping {
+    /// The average of the rgb channels through a color ramp, to color a scalar texture.
+    Ramp(ColorRamp),
+
+    /// Each channel through a curve, linear between its points sorted by x, and flat before the
+    /// first point and after the last one.
+    Curve(Vec<Vector2>),
+}
+
+/**
+Remap Texture
+
+A Texture that remaps the values of another texture, coloring a scalar texture with a color ramp
+or adjusting the contrast of a texture with a curve.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, Point3, RemapTexture, SolidColorTexture, Vector2},
+    traits::Texture,
+};
+use std::rc::Rc;
+
+// Raises the contrast around 0.5.
+let gray = Rc::new(SolidColorTexture::new(0.4, 0.5, 0.6));
+let curve = vec![Vector2::new(0.25, 0.), Vector2::new(0.75, 1.)];
+let texture = RemapTexture::new_with_curve(gray, curve);
+let color = texture.value(&Vector2::zero(), &Point3::zero());
+# assert!((&color - &Color3::new(0.3, 0.5, 0.7)).length() < 1e-6);
+```
+*/
+#[derive(Debug, Clone)]
+pub struct RemapTexture {
+    /// The texture to remap.
+    pub texture: Rc<dyn Texture>,
+
+    /// How its values are mapped.
+    pub remapping: Remapping,
+}
+
+impl RemapTexture {
+    /// Create a new `RemapTexture` coloring a scalar texture with a color ramp.
+    pub fn new_with_ramp(texture: Rc<dyn Texture>, ramp: ColorRamp) -> Self {
+        Self {
+            texture,
+            remapping: Remapping::Ramp(ramp),
+        }
+    }
+
+    /// Create a new `RemapTexture` mapping each channel of a texture through a curve, given by
+    /// its points in any order.
+    pub fn new_with_curve(texture: Rc<dyn Texture>, mut points: Vec<Vector2>) -> Self {
+        assert!(!points.is_empty(), "a curve needs a point");
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self {
+            texture,
+            remapping: Remapping::Curve(points),
+        }
+    }
+
+    /// Returns a value mapped through a curve.
+    fn curve(points: &[Vector2], x: f32) -> f32 {
+        let next = points.partition_point(|point| point.x <= x);
+        if next == 0 {
+            return points[0].y;
+        }
+        if next == points.len() {
+            return points[next - 1].y;
+        }
+        let (start, end) = (points[next - 1], points[next]);
+        start.y + (x - start.x) / (end.x - start.x) * (end.y - start.y)
+    }
+
+    /// Returns a color of the texture remapped, by the ramp evaluated with `ramp` for ramps.
+    fn remap(&self, color: Color3, ramp: impl Fn(&ColorRamp, f32) -> Color3) -> Color3 {
+        match &self.remapping {
+            Remapping::Ramp(color_ramp) => ramp(color_ramp, color.average()),
+            Remapping::Curve(points) => Color3::new(
+                Self::curve(points, color.x),
+                Self::curve(points, color.y),
+                Self::curve(points, color.z),
+            ),
+        }
+    }
+}
+
+impl Texture for RemapTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        let color = self.texture.value(uv, point);
+        self.remap(color, |ramp, t| ramp.value(t, uv, point))
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let color = self.texture.value_at_hit(hit_record);
+        self.remap(color, |ramp, t| ramp.value_at_hit(t, hit_record))
+    }
+}
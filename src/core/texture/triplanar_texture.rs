@@ -0,0 +1,103 @@
+use crate::core::{Color3, HitRecord, Point3, Vector2, Vector3};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/// Returns the uv coordinates of a point or a vector projected along an axis.
+fn project(vector: &Vector3, axis: usize, scale: f32) -> Vector2 {
+    match axis {
+        0 => Vector2::new(vector.z * scale, vector.y * scale),
+        1 => Vector2::new(vector.x * scale, vector.z * scale),
+        _ => Vector2::new(vector.x * scale, vector.y * scale),
+    }
+}
+
+/**
+Triplanar Texture
+
+A Texture that projects another texture along the x, y and z axes of space and blends the three
+projections by the normal of the hit, to texture surfaces without uv coordinates or with stretched
+ones. Without a normal, only the projection along z is read.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, ColorRamp, GradientTexture, Point3, TriplanarTexture, Vector2},
+    traits::Texture,
+};
+use std::rc::Rc;
+
+let stripes = GradientTexture::new_linear(
+    Vector2::zero(),
+    Vector2::new(1., 0.),
+    ColorRamp::new_grayscale(),
+);
+let mut texture = TriplanarTexture::new(Rc::new(stripes), 0.5);
+texture.sharpness = 8.;
+
+// Points without a normal read the projection along z.
+let color = texture.value(&Vector2::zero(), &Point3::new(0.5, 0., 0.));
+# assert_eq!(color, Color3::new(0.25, 0.25, 0.25));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct TriplanarTexture {
+    /// The projected texture.
+    pub texture: Rc<dyn Texture>,
+
+    /// The number of texture repeats per unit of space.
+    pub scale: f32,
+
+    /// The exponent of the blend weights, higher values give narrower transitions between the
+    /// projections.
+    pub sharpness: f32,
+}
+
+impl TriplanarTexture {
+    /// Create a new `TriplanarTexture` with the given scale and a sharpness of 4.
+    pub fn new(texture: Rc<dyn Texture>, scale: f32) -> Self {
+        Self {
+            texture,
+            scale,
+            sharpness: 4.,
+        }
+    }
+
+    /// Returns the blend weights of the projections for a normal, adding up to one.
+    pub fn weights(&self, normal: &Vector3) -> [f32; 3] {
+        let weights = [normal.x, normal.y, normal.z].map(|n| f32::abs(n).powf(self.sharpness));
+        let total = weights[0] + weights[1] + weights[2];
+        if total > 0. {
+            weights.map(|weight| weight / total)
+        } else {
+            [0., 0., 1.]
+        }
+    }
+}
+
+impl Texture for TriplanarTexture {
+    fn value(&self, _uv: &Vector2, point: &Point3) -> Color3 {
+        self.texture.value(&project(point, 2, self.scale), point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let (Some(point), Some(normal)) = (hit_record.point, hit_record.normal) else {
+            return Color3::zero();
+        };
+
+        let mut color = Color3::zero();
+        for (axis, weight) in self.weights(&normal).into_iter().enumerate() {
+            if weight <= 0. {
+                continue;
+            }
+            // Each projection has its own uv coordinates, and footprint from the one in space.
+            let mut projected = hit_record.clone();
+            projected.uv = Some(project(&point, axis, self.scale));
+            projected.duvdx = hit_record.dpdx.map(|dpdx| project(&dpdx, axis, self.scale));
+            projected.duvdy = hit_record.dpdy.map(|dpdy| project(&dpdy, axis, self.scale));
+            color += &(&self.texture.value_at_hit(&projected) * weight);
+        }
+        color
+    }
+}
@@ -0,0 +1,152 @@
+use crate::core::{deg_to_rad, Color3, HitRecord, Point3, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/**
+UV Transform
+
+An affine transform of uv coordinates: a scale, then a counterclockwise rotation around the
+origin, then an offset. Tiling transforms then wrap the coordinates into [0, 1), repeating
+textures that don't repeat by themselves, such as clamped images or gradients.
+
+# Examples
+
+```
+use raytracing::core::{UVTransform, Vector2};
+
+let mut transform = UVTransform::new_with_scale(2., 4.);
+transform.rotation = 90.;
+let uv = transform.apply(&Vector2::new(0.5, 0.25));
+# assert!((uv.u() + 1.).abs() < 1e-6 && (uv.v() - 1.).abs() < 1e-6);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UVTransform {
+    /// The scale along u and v, the number of times a texture repeats over the uv square.
+    pub scale: Vector2,
+
+    /// The rotation in degrees.
+    pub rotation: f32,
+
+    /// The offset added after the rotation.
+    pub offset: Vector2,
+
+    /// Whether the coordinates are wrapped into [0, 1) last.
+    pub tile: bool,
+}
+
+impl UVTransform {
+    /// Create a new `UVTransform` leaving uv coordinates unchanged.
+    pub fn new() -> Self {
+        Self {
+            scale: Vector2::new(1., 1.),
+            rotation: 0.,
+            offset: Vector2::zero(),
+            tile: false,
+        }
+    }
+
+    /// Create a new `UVTransform` scaling uv coordinates.
+    pub fn new_with_scale(u_scale: f32, v_scale: f32) -> Self {
+        Self {
+            scale: Vector2::new(u_scale, v_scale),
+            ..Self::new()
+        }
+    }
+
+    /// Returns transformed uv coordinates.
+    pub fn apply(&self, uv: &Vector2) -> Vector2 {
+        let uv = &self.apply_vector(uv) + &self.offset;
+        if self.tile {
+            Vector2::new(uv.x - f32::floor(uv.x), uv.y - f32::floor(uv.y))
+        } else {
+            uv
+        }
+    }
+
+    /// Returns a transformed difference of uv coordinates, such as a uv derivative, which the
+    /// offset and the tiling leave unchanged.
+    pub fn apply_vector(&self, duv: &Vector2) -> Vector2 {
+        let scaled = duv * &self.scale;
+        let (sin, cos) = f32::sin_cos(deg_to_rad(self.rotation));
+        Vector2::new(
+            cos * scaled.x - sin * scaled.y,
+            sin * scaled.x + cos * scaled.y,
+        )
+    }
+
+    /// Returns a copy of a hit record with its uv coordinates and their derivatives transformed.
+    pub fn apply_to_hit(&self, hit_record: &HitRecord) -> HitRecord {
+        let mut transformed = hit_record.clone();
+        transformed.uv = hit_record.uv.map(|uv| self.apply(&uv));
+        transformed.duvdx = hit_record.duvdx.map(|duv| self.apply_vector(&duv));
+        transformed.duvdy = hit_record.duvdy.map(|duv| self.apply_vector(&duv));
+        transformed
+    }
+}
+
+impl Default for UVTransform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/**
+UV Transform Texture
+
+A Texture that reads another texture at transformed uv coordinates, to tile, move or rotate it
+on a surface. The uv derivatives of hits are transformed too, so image textures stay filtered.
+
+# Examples
+
+```
+use raytracing::{
+    core::{
+        Color3, ColorRamp, GradientTexture, Point3, UVTransform, UVTransformTexture, Vector2,
+    },
+    traits::Texture,
+};
+use std::rc::Rc;
+
+// A gradient along u, stretched twice as long.
+let gradient = GradientTexture::new_linear(
+    Vector2::zero(),
+    Vector2::new(1., 0.),
+    ColorRamp::new_grayscale(),
+);
+let texture = UVTransformTexture::new(Rc::new(gradient), UVTransform::new_with_scale(0.5, 1.));
+let color = texture.value(&Vector2::new(1., 0.), &Point3::zero());
+# assert_eq!(color, Color3::new(0.5, 0.5, 0.5));
+```
+*/
+#[derive(Debug, Clone)]
+pub struct UVTransformTexture {
+    /// The transformed texture.
+    pub texture: Rc<dyn Texture>,
+
+    /// The transform of the uv coordinates.
+    pub transform: UVTransform,
+}
+
+impl UVTransformTexture {
+    /// Create a new `UVTransformTexture` reading a texture at transformed uv coordinates.
+    pub fn new(texture: Rc<dyn Texture>, transform: UVTransform) -> Self {
+        Self { texture, transform }
+    }
+}
+
+impl Texture for UVTransformTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.texture.value(&self.transform.apply(uv), point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        self.texture
+            .value_at_hit(&self.transform.apply_to_hit(hit_record))
+    }
+
+    fn alpha(&self, uv: &Vector2, point: &Point3) -> f32 {
+        self.texture.alpha(&self.transform.apply(uv), point)
+    }
+}
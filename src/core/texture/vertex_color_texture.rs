@@ -0,0 +1,63 @@
+use crate::core::{Color3, HitRecord, Point3, SolidColorTexture, Vector2};
+use std::rc::Rc;
+
+pub use crate::traits::Texture;
+
+/**
+Vertex Color Texture
+
+A Texture that reads the colors of the vertices of a `TriangleMesh`, interpolated at the hit.
+Where there are no vertex colors, such as on other primitives, it returns the fallback texture.
+
+# Examples
+```
+use raytracing::{
+    core::{Color3, HitRecord, Point3, Vector2, VertexColorTexture},
+    traits::Texture,
+};
+
+let texture = VertexColorTexture::new();
+let mut record = HitRecord::new();
+record.uv = Some(Vector2::new(0., 0.));
+record.point = Some(Point3::zero());
+# assert_eq!(texture.value_at_hit(&record), Color3::one());
+record.vertex_color = Some(Color3::new(1., 0., 0.));
+# assert_eq!(texture.value_at_hit(&record), Color3::new(1., 0., 0.));
+```
+*/
+#[derive(Debug)]
+pub struct VertexColorTexture {
+    /// Texture used where there are no vertex colors.
+    fallback: Rc<dyn Texture>,
+}
+
+impl VertexColorTexture {
+    /// Create a new `VertexColorTexture` falling back to white.
+    pub fn new() -> Self {
+        Self::new_with_fallback(Rc::new(SolidColorTexture::new_with_color(Color3::one())))
+    }
+
+    /// Create a new `VertexColorTexture` falling back to the given texture.
+    pub fn new_with_fallback(fallback: Rc<dyn Texture>) -> Self {
+        Self { fallback }
+    }
+}
+
+impl Default for VertexColorTexture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Texture for VertexColorTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.fallback.value(uv, point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        match hit_record.vertex_color {
+            Some(color) => color,
+            None => self.fallback.value_at_hit(hit_record),
+        }
+    }
+}
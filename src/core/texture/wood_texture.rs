@@ -0,0 +1,91 @@
+use super::ColorRamp;
+use crate::core::{Color3, HitRecord, PerlinNoise, Point3, Vector2};
+
+pub use crate::traits::Texture;
+
+/**
+Wood Texture
+
+A Texture of growth rings around the y axis of the hit point in space. The position across each
+ring, from 0 to 1, is mapped through a color ramp, and the rings are distorted by noise stretched
+along the trunk. The uv coordinates are ignored.
+
+# Examples
+
+```
+use raytracing::{
+    core::{Color3, ColorRamp, Point3, Vector2, WoodTexture},
+    traits::Texture,
+};
+
+let ramp = ColorRamp::new_with_colors(vec![
+    (0., Color3::new(0.8, 0.6, 0.4)),
+    (0.7, Color3::new(0.7, 0.5, 0.3)),
+    (1., Color3::new(0.4, 0.25, 0.1)),
+]);
+let mut texture = WoodTexture::new(8., ramp);
+texture.distortion = 0.;
+
+// Without distortion, the rings are an eighth of a unit apart.
+let early = texture.value(&Vector2::zero(), &Point3::new(0., 1., 0.));
+let late = texture.value(&Vector2::zero(), &Point3::new(0.25 - 0.01, 1., 0.));
+# assert!((&early - &Color3::new(0.8, 0.6, 0.4)).length() < 1e-5);
+# assert!(late.x < 0.5);
+```
+*/
+#[derive(Debug)]
+pub struct WoodTexture {
+    /// The noise.
+    noise: PerlinNoise,
+
+    /// The number of rings per unit of radius.
+    pub rings: f32,
+
+    /// How far the noise shifts the rings, in rings.
+    pub distortion: f32,
+
+    /// The frequency of the noise, per unit of space across the trunk.
+    pub noise_scale: f32,
+
+    /// The colors across a ring.
+    pub ramp: ColorRamp,
+}
+
+impl WoodTexture {
+    /// Create a new `WoodTexture` with the given ring frequency and ramp, distorted by half a
+    /// ring.
+    pub fn new(rings: f32, ramp: ColorRamp) -> Self {
+        Self {
+            noise: PerlinNoise::new(),
+            rings,
+            distortion: 0.5,
+            noise_scale: 2.,
+            ramp,
+        }
+    }
+
+    /// Returns the position of a point across its ring, 0 at its inner edge and 1 at its outer one.
+    pub fn ring(&self, point: &Point3) -> f32 {
+        let radius = f32::sqrt(point.x * point.x + point.z * point.z);
+        let mut ring = radius * self.rings;
+        if self.distortion != 0. {
+            // The grain runs along the trunk, so the noise varies four times slower along it.
+            let noise_point = &Point3::new(point.x, 0.25 * point.y, point.z) * self.noise_scale;
+            ring += self.distortion * self.noise.fbm(&noise_point, 4, 2., 0.5);
+        }
+        ring - f32::floor(ring)
+    }
+}
+
+impl Texture for WoodTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.ramp.value(self.ring(point), uv, point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let Some(point) = hit_record.point else {
+            return Color3::zero();
+        };
+        self.ramp.value_at_hit(self.ring(&point), hit_record)
+    }
+}
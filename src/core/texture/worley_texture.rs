@@ -0,0 +1,91 @@
+use super::ColorRamp;
+use crate::core::{Color3, HitRecord, Point3, Vector2, WorleyNoise};
+
+pub use crate::traits::Texture;
+
+/// Which distances of a `WorleyTexture` are mapped to colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorleyFeature {
+    /// The distance to the nearest feature point, dark at the points and bright between them.
+    #[default]
+    F1,
+
+    /// The distance to the second nearest feature point.
+    F2,
+
+    /// The difference of the two, dark along the borders of the cells.
+    F2MinusF1,
+}
+
+/**
+Worley Texture
+
+A Texture that maps cellular noise at the hit point in space through a color ramp, for cells,
+scales, cracks or stone. Distances are in units of the cell size, F1 is below about 0.9 and F2
+below about 1.2. The uv coordinates are ignored.
+
+# Examples
+
+```
+use raytracing::{
+    core::{ColorRamp, Point3, Vector2, WorleyFeature, WorleyNoise, WorleyTexture},
+    traits::Texture,
+};
+
+// Dark cracks between bright cells, ten cells per unit.
+let mut texture = WorleyTexture::new(10., WorleyFeature::F2MinusF1, ColorRamp::new_grayscale());
+texture.noise = WorleyNoise::new_with_seed(3);
+let color = texture.value(&Vector2::zero(), &Point3::new(0.1, 0.2, 0.3));
+# assert!(color.x >= 0. && color.x <= 1.);
+```
+*/
+#[derive(Debug)]
+pub struct WorleyTexture {
+    /// The noise, with a random seed unless set.
+    pub noise: WorleyNoise,
+
+    /// The number of cells per unit of space.
+    pub scale: f32,
+
+    /// The distances mapped to colors.
+    pub feature: WorleyFeature,
+
+    /// The colors of the distances.
+    pub ramp: ColorRamp,
+}
+
+impl WorleyTexture {
+    /// Create a new `WorleyTexture` with the given cell frequency, feature and ramp.
+    pub fn new(scale: f32, feature: WorleyFeature, ramp: ColorRamp) -> Self {
+        Self {
+            noise: WorleyNoise::new(),
+            scale,
+            feature,
+            ramp,
+        }
+    }
+
+    /// Returns the feature of the distances to the nearest feature points of a point.
+    pub fn feature_value(&self, point: &Point3) -> f32 {
+        let (f1, f2) = self.noise.distances(&(point * self.scale));
+        match self.feature {
+            WorleyFeature::F1 => f1,
+            WorleyFeature::F2 => f2,
+            WorleyFeature::F2MinusF1 => f2 - f1,
+        }
+    }
+}
+
+impl Texture for WorleyTexture {
+    fn value(&self, uv: &Vector2, point: &Point3) -> Color3 {
+        self.ramp.value(self.feature_value(point), uv, point)
+    }
+
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        let Some(point) = hit_record.point else {
+            return Color3::zero();
+        };
+        self.ramp
+            .value_at_hit(self.feature_value(&point), hit_record)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{AxisAlignedBoundingBox, HitRecord, Interval, Point3, Ray, Vector3};
+use crate::core::{AxisAlignedBoundingBox, HitRecord, Interval, LightBounds, Point3, Ray, Vector3};
 use std::{cmp::Ordering, fmt};
 
 /// Interface for all hittable objects.
@@ -9,6 +9,27 @@ pub trait Hittable: fmt::Debug {
     /// Returns the bounding box of this object.
     fn bounding_box(&self) -> &AxisAlignedBoundingBox;
 
+    /// Returns every hit of the ray with this object within the interval, nearest first. The
+    /// default steps from hit to hit, objects that know all of their hits at once may override it.
+    fn hit_all(&self, ray: &Ray, ray_interval: &Interval) -> Vec<HitRecord> {
+        /// Most hits returned, guarding against surfaces hit again at the same point.
+        const MAX_HITS: usize = 64;
+
+        // Step past each hit by a small distance along the ray.
+        let step = 1e-4 / ray.direction.length();
+        let mut hits = Vec::new();
+        let mut t_min = ray_interval.min;
+        while hits.len() < MAX_HITS {
+            let mut record = HitRecord::new();
+            if !self.hit(ray, &Interval::new(t_min, ray_interval.max), &mut record) {
+                break;
+            }
+            t_min = record.t + step;
+            hits.push(record);
+        }
+        hits
+    }
+
     fn pdf_value(&self, _origin: &Point3, _direction: &Vector3) -> f32 {
         0.0
     }
@@ -16,6 +37,28 @@ pub trait Hittable: fmt::Debug {
     fn random(&self, _origin: &Point3) -> Vector3 {
         Vector3::new(1., 0., 0.)
     }
+
+    /// Returns the surface area of this object, zero if it is unknown.
+    fn area(&self) -> f32 {
+        0.
+    }
+
+    /// Returns the power emitted by this object in luminance units, zero if it doesn't emit light.
+    fn power(&self) -> f32 {
+        0.
+    }
+
+    /// Returns the bounds of the light emitted by this object, or `None` if it doesn't emit light.
+    fn light_bounds(&self) -> Option<LightBounds> {
+        let power = self.power();
+        if power <= 0. {
+            return None;
+        }
+        Some(LightBounds::new_omnidirectional(
+            self.bounding_box().clone(),
+            power,
+        ))
+    }
 }
 
 /// Returns the order of the two hittable objects.
@@ -0,0 +1,24 @@
+use crate::core::{Color3, LightRecord, Point3, Vector3};
+use std::fmt;
+
+/// Interface for light sources that are not part of the scene geometry.
+pub trait Light: fmt::Debug {
+    /// Sample the light arriving at the given point, returns `None` if no light arrives.
+    fn sample(&self, point: &Point3) -> Option<LightRecord>;
+
+    /// Returns true if the light is infinitesimal, so it can only be reached by sampling it.
+    /// Other lights surround the scene and are seen by rays that escape it.
+    fn is_delta(&self) -> bool {
+        true
+    }
+
+    /// Returns the probability density of `sample` returning the direction from the origin.
+    fn pdf_value(&self, _origin: &Point3, _direction: &Vector3) -> f32 {
+        0.
+    }
+
+    /// Returns the radiance arriving along a ray that escapes the scene in the given direction.
+    fn emitted(&self, _direction: &Vector3) -> Color3 {
+        Color3::zero()
+    }
+}
@@ -1,5 +1,12 @@
-use crate::core::{Color3, HitRecord, Ray, ScatterRecord, Vector2, Vector3};
-use std::fmt;
+use crate::{
+    core::{Color3, HitRecord, Ray, ScatterRecord, Vector2, Vector3},
+    traits::Medium,
+};
+use std::{
+    fmt,
+    ops::{Add, Neg},
+    rc::Rc,
+};
 
 /// Material trait.
 pub trait Material: fmt::Debug {
@@ -22,7 +29,48 @@ pub trait Material: fmt::Debug {
         Color3::zero()
     }
 
+    /// Returns the luminance of the light emitted at the point, normal, uv and face of the hit
+    /// record, viewed head-on. Primitives use it to estimate the power of emissive surfaces.
+    fn emitted_luminance(&self, hit_record: &HitRecord) -> f32 {
+        let (Some(uv), Some(point), Some(normal)) =
+            (hit_record.uv, hit_record.point, hit_record.normal)
+        else {
+            return 0.;
+        };
+        let ray_in = Ray::new(point.add(&normal), normal.neg());
+        self.emitted(&ray_in, hit_record, &uv, &point).luminance()
+    }
+
+    /// Returns the medium filling the inside of the surface at the hit record, for materials that
+    /// scatter light below their surface. Rays refracted into the surface travel through it.
+    fn interior_medium(&self, _hit_record: &HitRecord) -> Option<Rc<dyn Medium>> {
+        None
+    }
+
+    /// Returns the probability that `scatter` samples a lobe that is not specular, described by
+    /// `scattering_pdf` and `scattering_color`. Materials that are specular, that don't scatter, or
+    /// that pick between specular and other lobes at random override it.
+    fn diffuse_probability(&self, _ray_in: &Ray, _hit_record: &HitRecord) -> f32 {
+        1.
+    }
+
     fn scattering_pdf(&self, _ray_in: &Ray, _hit_record: &HitRecord, _ray_scattered: &Ray) -> f32 {
         return 0.;
     }
+
+    /// Returns the color the material scatters from `ray_in` into `ray_scattered`, that is the
+    /// attenuation times the scattering pdf. Materials whose attenuation depends on the scattered
+    /// direction, such as layered materials, override it.
+    fn scattering_color(
+        &self,
+        ray_in: &Ray,
+        hit_record: &HitRecord,
+        ray_scattered: &Ray,
+    ) -> Color3 {
+        let mut scatter_record = ScatterRecord::new();
+        if !self.scatter(ray_in, hit_record, &mut scatter_record) {
+            return Color3::zero();
+        }
+        &scatter_record.attenuation * self.scattering_pdf(ray_in, hit_record, ray_scattered)
+    }
 }
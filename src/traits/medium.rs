@@ -0,0 +1,16 @@
+use crate::core::{Color3, Interval, MediumRecord, Ray};
+use crate::traits::Material;
+use std::{fmt, rc::Rc};
+
+/// Interface for participating media that rays travel through between surfaces.
+pub trait Medium: fmt::Debug {
+    /// Sample where the ray scatters within the interval, if it does, and the throughput weight of
+    /// the sample.
+    fn sample(&self, ray: &Ray, ray_interval: &Interval) -> MediumRecord;
+
+    /// Returns the fraction of light passing through the medium along the ray within the interval.
+    fn transmittance(&self, ray: &Ray, ray_interval: &Interval) -> Color3;
+
+    /// Returns the phase function scattering light at the points sampled by `sample`.
+    fn phase_function(&self) -> Rc<dyn Material>;
+}
@@ -1,9 +1,15 @@
 mod hittable;
+mod light;
 mod material;
+mod medium;
 mod pdf;
+mod planar_shape;
 mod texture;
 
 pub use hittable::*;
+pub use light::*;
 pub use material::*;
+pub use medium::*;
 pub use pdf::*;
+pub use planar_shape::*;
 pub use texture::*;
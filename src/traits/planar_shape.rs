@@ -0,0 +1,20 @@
+use crate::core::Vector2;
+use std::fmt;
+
+/// Interface for the shapes of planar primitives, given in the planar coordinates `(α, β)` of a
+/// point `origin + α u + β v` on the plane.
+pub trait PlanarShape: fmt::Debug {
+    /// Returns true if the planar coordinates lie inside the shape.
+    fn contains(&self, alpha: f32, beta: f32) -> bool;
+
+    /// Returns the lower and upper corners of the planar coordinates covered by the shape, which
+    /// are mapped to uv coordinates in `[0, 1]²`.
+    fn bounds(&self) -> (Vector2, Vector2);
+
+    /// Returns the area of the shape in planar coordinates, one for the unit square.
+    fn area(&self) -> f32;
+
+    /// Returns planar coordinates distributed uniformly by area within the shape, given two
+    /// uniform random numbers in `[0, 1)`.
+    fn sample(&self, r1: f32, r2: f32) -> Vector2;
+}
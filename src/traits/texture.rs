@@ -1,8 +1,22 @@
-use crate::core::{Color3, Point3, Vector2};
+use crate::core::{Color3, HitRecord, Point3, Vector2};
 use std::fmt;
 
 /// Texture trait.
 pub trait Texture: fmt::Debug {
     /// Return the color of the texture by uv coordinates and hit point.
     fn value(&self, uv: &Vector2, point: &Point3) -> Color3;
+
+    /// Return the color of the texture at a hit, by its uv coordinates and hit point by default.
+    /// Textures reading data the primitive attaches to the hit, like vertex colors, override it.
+    fn value_at_hit(&self, hit_record: &HitRecord) -> Color3 {
+        match (hit_record.uv, hit_record.point) {
+            (Some(uv), Some(point)) => self.value(&uv, &point),
+            _ => Color3::zero(),
+        }
+    }
+
+    /// Return the opacity of the texture by uv coordinates and hit point, opaque by default.
+    fn alpha(&self, _uv: &Vector2, _point: &Point3) -> f32 {
+        1.
+    }
 }
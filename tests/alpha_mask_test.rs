@@ -0,0 +1,104 @@
+extern crate raytracing;
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use raytracing::{
+    core::{
+        AlphaMask, AlphaSource, Color3, HitRecord, ImageEncoding, ImageTexture, Interval,
+        LambertianMaterial, Point3, Quad, Ray, SolidColorTexture, Vector2, Vector3,
+    },
+    traits::{Hittable, Texture},
+};
+use std::rc::Rc;
+
+fn unit_quad(z: f32) -> Rc<Quad> {
+    Rc::new(Quad::new(
+        Point3::new(0., 0., z),
+        Vector3::new(1., 0., 0.),
+        Vector3::new(0., 1., 0.),
+        Rc::new(LambertianMaterial::new_with_color(Color3::one())),
+    ))
+}
+
+#[test]
+fn alpha_mask_threshold_test() {
+    let ray = Ray::new(Point3::new(0.5, 0.5, -1.), Vector3::new(0., 0., 1.));
+    let interval = Interval::new(0.001, f32::INFINITY);
+
+    let transparent = AlphaMask::new_with_threshold(
+        unit_quad(0.),
+        Rc::new(SolidColorTexture::new(0.2, 0.2, 0.2)),
+        AlphaSource::Color,
+        0.5,
+    );
+    assert!(!transparent.hit(&ray, &interval, &mut HitRecord::new()));
+
+    let opaque = AlphaMask::new_with_threshold(
+        unit_quad(0.),
+        Rc::new(SolidColorTexture::new(0.8, 0.8, 0.8)),
+        AlphaSource::Color,
+        0.5,
+    );
+    assert!(opaque.hit(&ray, &interval, &mut HitRecord::new()));
+
+    // Textures without an alpha channel are fully opaque.
+    let solid = AlphaMask::new(unit_quad(0.), Rc::new(SolidColorTexture::new(0., 0., 0.)));
+    assert!(solid.hit(&ray, &interval, &mut HitRecord::new()));
+}
+
+#[test]
+fn alpha_mask_stochastic_test() {
+    let interval = Interval::new(0.001, f32::INFINITY);
+    let mask = AlphaMask::new_stochastic(
+        unit_quad(0.),
+        Rc::new(SolidColorTexture::new(0.3, 0.3, 0.3)),
+        AlphaSource::Color,
+    );
+
+    // Rays through a grid of points hit in proportion to the opacity.
+    let n = 100;
+    let mut hits = 0;
+    for i in 0..n {
+        for j in 0..n {
+            let x = (i as f32 + 0.5) / n as f32;
+            let y = (j as f32 + 0.5) / n as f32;
+            let ray = Ray::new(Point3::new(x, y, -1.), Vector3::new(0., 0., 1.));
+            if mask.hit(&ray, &interval, &mut HitRecord::new()) {
+                hits += 1;
+            }
+        }
+    }
+    let rate = hits as f32 / (n * n) as f32;
+    assert!((rate - 0.3).abs() < 0.03, "{rate}");
+
+    // The same ray always gets the same answer.
+    let ray = Ray::new(Point3::new(0.4, 0.6, -1.), Vector3::new(0., 0., 1.));
+    let first = mask.hit(&ray, &interval, &mut HitRecord::new());
+    assert!((0..8).all(|_| mask.hit(&ray, &interval, &mut HitRecord::new()) == first));
+}
+
+#[test]
+fn image_texture_alpha_test() {
+    // Opaque on the left half, transparent on the right half.
+    let image = RgbaImage::from_fn(4, 1, |x, _| {
+        let alpha = if x < 2 { 255 } else { 0 };
+        Rgba([255, 255, 255, alpha])
+    });
+    let texture = Rc::new(ImageTexture::new_with_image(
+        &DynamicImage::ImageRgba8(image),
+        ImageEncoding::Raw,
+    ));
+    assert_eq!(
+        texture.alpha(&Vector2::new(0.125, 0.5), &Point3::zero()),
+        1.
+    );
+    assert_eq!(
+        texture.alpha(&Vector2::new(0.875, 0.5), &Point3::zero()),
+        0.
+    );
+
+    let mask = AlphaMask::new(unit_quad(0.), texture);
+    let interval = Interval::new(0.001, f32::INFINITY);
+    let ray = |x: f32| Ray::new(Point3::new(x, 0.5, -1.), Vector3::new(0., 0., 1.));
+    assert!(mask.hit(&ray(0.1), &interval, &mut HitRecord::new()));
+    assert!(!mask.hit(&ray(0.9), &interval, &mut HitRecord::new()));
+}
@@ -0,0 +1,103 @@
+extern crate raytracing;
+
+use image::{DynamicImage, ImageBuffer, Rgb, Rgba};
+use raytracing::{
+    core::{
+        linear_to_srgb, srgb_to_linear, Color3, ColorSpace, ImageEncoding, ImageTexture, Point3,
+        Vector2,
+    },
+    traits::Texture,
+};
+
+const SPACES: [ColorSpace; 4] = [
+    ColorSpace::SRGB,
+    ColorSpace::Rec2020,
+    ColorSpace::ACEScg,
+    ColorSpace::DisplayP3,
+];
+
+#[test]
+fn srgb_transfer_test() {
+    assert_eq!(srgb_to_linear(0.), 0.);
+    assert!((srgb_to_linear(1.) - 1.).abs() < 1e-6);
+    assert!((srgb_to_linear(0.02) - 0.02 / 12.92).abs() < 1e-7);
+    for i in 0..=100 {
+        let value = i as f32 / 100.;
+        assert!((linear_to_srgb(srgb_to_linear(value)) - value).abs() < 1e-5);
+        for space in SPACES {
+            assert!((space.decode(space.encode(value)) - value).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn color_space_conversion_test() {
+    // The luminance row of sRGB.
+    let to_xyz = ColorSpace::SRGB.to_xyz();
+    let luminance = to_xyz.m[1];
+    assert!((luminance[0] - 0.2126).abs() < 1e-3);
+    assert!((luminance[1] - 0.7152).abs() < 1e-3);
+    assert!((luminance[2] - 0.0722).abs() < 1e-3);
+
+    for from in SPACES {
+        for to in SPACES {
+            // White stays white, its white point adapted to the other one.
+            let white = from.convert(&Color3::one(), to);
+            assert!((&white - &Color3::one()).length() < 1e-3, "{from:?} {to:?}");
+
+            // Conversions are inverse of each other.
+            let color = Color3::new(0.8, 0.3, 0.1);
+            let back = to.convert(&from.convert(&color, to), from);
+            assert!((&back - &color).length() < 1e-4, "{from:?} {to:?}");
+        }
+    }
+
+    // The red of sRGB in ACEScg.
+    let red = ColorSpace::SRGB.convert(&Color3::new(1., 0., 0.), ColorSpace::ACEScg);
+    assert!(
+        (&red - &Color3::new(0.6131, 0.0701, 0.0206)).length() < 2e-3,
+        "{red:?}"
+    );
+}
+
+/// Returns the color of a texture of a single pixel.
+fn value(texture: &ImageTexture) -> Color3 {
+    texture.value(&Vector2::new(0.5, 0.5), &Point3::zero())
+}
+
+#[test]
+fn image_encoding_test() {
+    // 8-bit images are sRGB unless they hold data.
+    let image = DynamicImage::ImageRgba8(ImageBuffer::from_pixel(1, 1, Rgba([128, 128, 128, 255])));
+    assert_eq!(ImageEncoding::detect(&image), ImageEncoding::SRGB);
+    let texture = ImageTexture::new_with_image(&image, ImageEncoding::SRGB);
+    assert!((value(&texture).x - srgb_to_linear(128. / 255.)).abs() < 1e-6);
+    let texture = ImageTexture::new_with_image(&image, ImageEncoding::Raw);
+    assert!((value(&texture).x - 128. / 255.).abs() < 1e-6);
+
+    // So are 16-bit images, with their precision.
+    let image = DynamicImage::ImageRgb16(ImageBuffer::from_pixel(1, 1, Rgb([1000, 1000, 1000])));
+    assert_eq!(ImageEncoding::detect(&image), ImageEncoding::SRGB);
+    let texture = ImageTexture::new_with_image(&image, ImageEncoding::detect(&image));
+    assert!((value(&texture).x - srgb_to_linear(1000. / 65535.)).abs() < 1e-7);
+
+    // Float images are linear, and may exceed one.
+    let image = DynamicImage::ImageRgb32F(ImageBuffer::from_pixel(1, 1, Rgb([0.5, 2., 4.])));
+    assert_eq!(ImageEncoding::detect(&image), ImageEncoding::Linear);
+    let texture = ImageTexture::new_with_image(&image, ImageEncoding::detect(&image));
+    assert_eq!(value(&texture), Color3::new(0.5, 2., 4.));
+}
+
+#[test]
+fn image_texture_color_space_test() {
+    let image = DynamicImage::ImageRgb32F(ImageBuffer::from_pixel(1, 1, Rgb([1., 0., 0.])));
+    let mut texture = ImageTexture::new_with_image(&image, ImageEncoding::Linear);
+    texture.convert_color_space(ColorSpace::SRGB, ColorSpace::Rec2020);
+    let expected = ColorSpace::SRGB.convert(&Color3::new(1., 0., 0.), ColorSpace::Rec2020);
+    assert!((&value(&texture) - &expected).length() < 1e-6);
+
+    // Raw data isn't converted.
+    let mut texture = ImageTexture::new_with_image(&image, ImageEncoding::Raw);
+    texture.convert_color_space(ColorSpace::SRGB, ColorSpace::Rec2020);
+    assert_eq!(value(&texture), Color3::new(1., 0., 0.));
+}
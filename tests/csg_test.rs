@@ -0,0 +1,99 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        get_cube_box, CSGNode, Color3, Cylinder, HitRecord, Interval, LambertianMaterial,
+        MetalMaterial, Point3, Ray, SolidColorTexture, Sphere, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::rc::Rc;
+
+fn materials() -> (Rc<dyn Material>, Rc<dyn Material>) {
+    (
+        Rc::new(LambertianMaterial::new_with_color(Color3::one())),
+        Rc::new(MetalMaterial::new(
+            Rc::new(SolidColorTexture::new_with_color(Color3::one())),
+            0.,
+        )),
+    )
+}
+
+#[test]
+fn csg_difference_test() {
+    // A plate with a hole drilled through its middle.
+    let (plate_material, drill_material) = materials();
+    let plate = get_cube_box(
+        Point3::new(-2., -0.5, -2.),
+        Point3::new(2., 0.5, 2.),
+        plate_material,
+    );
+    let drill = Rc::new(Cylinder::new_capped(
+        Point3::new(0., -1., 0.),
+        Point3::new(0., 1., 0.),
+        1.,
+        drill_material,
+    ));
+    let drilled_plate = CSGNode::new_difference(plate, drill);
+    let interval = Interval::new(0.001, f32::INFINITY);
+
+    // Straight through the hole.
+    let ray = Ray::new(Point3::new(0., 3., 0.), Vector3::new(0., -1., 0.));
+    assert!(drilled_plate.hit_all(&ray, &interval).is_empty());
+
+    // Across the hole, the walls of the hole face into it.
+    let ray = Ray::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+    let hits = drilled_plate.hit_all(&ray, &interval);
+    let t: Vec<f32> = hits.iter().map(|record| record.t).collect();
+    assert_eq!(hits.len(), 4, "{:?}", t);
+    for (record, expected_t) in hits.iter().zip([1., 2., 4., 5.]) {
+        assert!((record.t - expected_t).abs() < 1e-4, "{:?}", t);
+    }
+    assert!(hits[0].front_face && !hits[1].front_face);
+    assert!(hits[2].front_face && !hits[3].front_face);
+    assert_eq!(hits[1].normal.unwrap(), Vector3::new(-1., 0., 0.));
+    assert!(format!("{:?}", hits[1].material).contains("Metal"));
+    assert!(format!("{:?}", hits[0].material).contains("Lambertian"));
+
+    // From inside of the plate, the first surface is where the ray leaves it.
+    let ray = Ray::new(Point3::new(-1.5, 0., 0.), Vector3::new(1., 0., 0.));
+    let mut record = HitRecord::new();
+    assert!(drilled_plate.hit(&ray, &interval, &mut record));
+    assert!((record.t - 0.5).abs() < 1e-4);
+    assert!(!record.front_face);
+}
+
+#[test]
+fn csg_union_intersection_test() {
+    let (material, _) = materials();
+    let left = Rc::new(Sphere::new(Point3::new(-0.5, 0., 0.), 1., material.clone()));
+    let right = Rc::new(Sphere::new(Point3::new(0.5, 0., 0.), 1., material.clone()));
+    let interval = Interval::new(0.001, f32::INFINITY);
+    let ray = Ray::new(Point3::new(-3., 0., 0.), Vector3::new(1., 0., 0.));
+
+    // The surfaces inside of the union are gone.
+    let union = CSGNode::new_union(left.clone(), right.clone());
+    let t: Vec<f32> = union.hit_all(&ray, &interval).iter().map(|r| r.t).collect();
+    assert_eq!(t.len(), 2);
+    assert!((t[0] - 1.5).abs() < 1e-4 && (t[1] - 4.5).abs() < 1e-4);
+
+    // A lens, only the overlap remains.
+    let lens = Rc::new(CSGNode::new_intersection(left, right));
+    let t: Vec<f32> = lens.hit_all(&ray, &interval).iter().map(|r| r.t).collect();
+    assert_eq!(t.len(), 2);
+    assert!((t[0] - 2.5).abs() < 1e-4 && (t[1] - 3.5).abs() < 1e-4);
+    let bbox = lens.bounding_box();
+    assert!((bbox.x.min + 0.5).abs() < 1e-4 && (bbox.x.max - 0.5).abs() < 1e-4);
+
+    // Nested nodes, a lens with a hole through its center.
+    let hole = Rc::new(Cylinder::new_capped(
+        Point3::new(-2., 0., 0.),
+        Point3::new(2., 0., 0.),
+        0.2,
+        material,
+    ));
+    let pierced_lens = CSGNode::new_difference(lens, hole);
+    assert!(pierced_lens.hit_all(&ray, &interval).is_empty());
+    let ray = Ray::new(Point3::new(-3., 0.5, 0.), Vector3::new(1., 0., 0.));
+    assert_eq!(pierced_lens.hit_all(&ray, &interval).len(), 2);
+}
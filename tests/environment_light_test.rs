@@ -0,0 +1,72 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{Color3, EnvironmentLight, Point3, SkyLight, Vector3},
+    traits::Light,
+};
+
+fn random_environment(rotation: f32) -> EnvironmentLight {
+    let mut rng = rand::thread_rng();
+    let (width, height) = (16, 8);
+    let mut pixels: Vec<Color3> = (0..width * height)
+        .map(|_| Vector3::random(0., 1., &mut rng))
+        .collect();
+    // A small and bright sun.
+    pixels[2 * width + 5] = Color3::new(500., 450., 400.);
+    EnvironmentLight::new_with_pixels(pixels, width, height, rotation, 1.)
+}
+
+#[test]
+fn environment_light_pdf_test() {
+    let light = random_environment(30.);
+    let origin = Point3::zero();
+
+    // The pdf over the sphere of directions should integrate to one. The directions are spread
+    // evenly over the sphere, random ones make the bright sun a noisy estimate.
+    let (rows, columns) = (400, 500);
+    let mut integral = 0.;
+    for row in 0..rows {
+        let z = 1. - 2. * (row as f32 + 0.5) / rows as f32;
+        let radius = f32::sqrt(1. - z * z);
+        for column in 0..columns {
+            let phi = 2. * std::f32::consts::PI * (column as f32 + 0.5) / columns as f32;
+            let direction = Vector3::new(radius * phi.cos(), radius * phi.sin(), z);
+            integral += light.pdf_value(&origin, &direction) * 4. * std::f32::consts::PI;
+        }
+    }
+    integral /= (rows * columns) as f32;
+    assert!((integral - 1.).abs() < 0.05, "pdf integral is {}", integral);
+}
+
+#[test]
+fn environment_light_sample_test() {
+    let light = random_environment(-75.);
+    let origin = Point3::zero();
+
+    for _ in 0..100 {
+        let record = light.sample(&origin).unwrap();
+        let pdf = light.pdf_value(&origin, &record.direction);
+        assert!((record.pdf - pdf).abs() <= 1e-2 * pdf.max(1.));
+        assert_eq!(record.radiance, light.emitted(&record.direction));
+        assert!((record.direction.length() - 1.).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn sky_light_test() {
+    let sky = SkyLight::new(30., 0., 3., 0.3, 0.1);
+    let origin = Point3::zero();
+
+    // Near the sun the sky is brighter than on the opposite side.
+    let towards_sun = Vector3::new(1., 0.6, 0.);
+    let away_from_sun = Vector3::new(-1., 0.6, 0.);
+    assert!(sky.emitted(&towards_sun).luminance() > sky.emitted(&away_from_sun).luminance());
+
+    // The sun is reddened by the atmosphere and covers its own solid angle.
+    let sun = sky.sun();
+    assert!(sun.radiance.x > sun.radiance.z);
+    let record = sun.sample(&origin).unwrap();
+    assert_eq!(record.pdf, 1. / sun.solid_angle());
+    assert_eq!(sun.pdf_value(&origin, &record.direction), record.pdf);
+    assert_eq!(sun.emitted(&Vector3::new(0., 1., 0.)), Color3::zero());
+}
@@ -0,0 +1,301 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        Color3, GLTFScene, HitRecord, Interval, LambertianMaterial, Matrix4, Point3, Ray, Sphere,
+        TransformInstance, Vector3,
+    },
+    traits::Hittable,
+};
+use std::{ops::Mul, path::Path, rc::Rc};
+
+/// The document of a quad scaled by a child node under a translated parent, with a camera and a
+/// spot light pointing down. `BUFFER` stands for the buffer, a URI or nothing for a `.glb` file.
+const DOCUMENT: &str = r#"{
+    "asset": { "version": "2.0" },
+    "extensionsUsed": ["KHR_lights_punctual"],
+    "extensions": {
+        "KHR_lights_punctual": {
+            "lights": [{
+                "type": "spot",
+                "color": [1, 0.5, 0.25],
+                "intensity": 8,
+                "spot": { "innerConeAngle": 0.2, "outerConeAngle": 0.4 }
+            }]
+        }
+    },
+    "scene": 0,
+    "scenes": [{ "nodes": [0, 2, 3] }],
+    "nodes": [
+        { "translation": [0, 0, -5], "children": [1] },
+        { "mesh": 0, "scale": [2, 1, 1] },
+        { "camera": 0, "translation": [0, 1, 5] },
+        {
+            "translation": [0, 4, 0],
+            "rotation": [-0.70710678, 0, 0, 0.70710678],
+            "extensions": { "KHR_lights_punctual": { "light": 0 } }
+        }
+    ],
+    "cameras": [{
+        "type": "perspective",
+        "perspective": { "yfov": 0.5, "aspectRatio": 1.5, "znear": 0.1 }
+    }],
+    "meshes": [{
+        "primitives": [{
+            "attributes": { "POSITION": 0, "TEXCOORD_0": 1 },
+            "indices": 2,
+            "material": 0
+        }]
+    }],
+    "materials": [{
+        "pbrMetallicRoughness": { "baseColorFactor": [1, 0, 0, 1], "metallicFactor": 0 }
+    }],
+    "accessors": [
+        { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+          "min": [-1, -1, 0], "max": [1, 1, 0] },
+        { "bufferView": 1, "componentType": 5126, "count": 4, "type": "VEC2" },
+        { "bufferView": 2, "componentType": 5123, "count": 6, "type": "SCALAR" }
+    ],
+    "bufferViews": [
+        { "buffer": 0, "byteOffset": 0, "byteLength": 48 },
+        { "buffer": 0, "byteOffset": 48, "byteLength": 32 },
+        { "buffer": 0, "byteOffset": 80, "byteLength": 12 }
+    ],
+    "buffers": [BUFFER]
+}"#;
+
+/// Returns the buffer of `DOCUMENT`, a quad with uv coordinates starting at its top left corner.
+fn buffer() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let positions = [-1., -1., 0., 1., -1., 0., 1., 1., 0., -1., 1., 0.];
+    let uvs = [0., 1., 1., 1., 1., 0., 0., 0.];
+    for value in positions.iter().chain(&uvs) {
+        bytes.extend_from_slice(&f32::to_le_bytes(*value));
+    }
+    for index in [0u16, 1, 2, 0, 2, 3] {
+        bytes.extend_from_slice(&index.to_le_bytes());
+    }
+    bytes
+}
+
+/// Returns the base64 encoding of some bytes.
+fn encode_base64(bytes: &[u8]) -> String {
+    let alphabet = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+    for chunk in bytes.chunks(3) {
+        let word = chunk.iter().enumerate().fold(0u32, |word, (i, &byte)| {
+            word | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(alphabet[(word >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+    text
+}
+
+/// Returns `DOCUMENT` as a `.gltf` file with its buffer embedded.
+fn gltf() -> Vec<u8> {
+    let buffer = format!(
+        r#"{{ "byteLength": 92, "uri": "data:application/octet-stream;base64,{}" }}"#,
+        encode_base64(&buffer())
+    );
+    DOCUMENT.replace("BUFFER", &buffer).into_bytes()
+}
+
+/// Returns `DOCUMENT` as a `.glb` file.
+fn glb() -> Vec<u8> {
+    let mut json = DOCUMENT
+        .replace("BUFFER", r#"{ "byteLength": 92 }"#)
+        .into_bytes();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    let bin = buffer();
+
+    let mut bytes = b"glTF".to_vec();
+    bytes.extend_from_slice(&2u32.to_le_bytes());
+    bytes.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+    bytes.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"JSON");
+    bytes.extend_from_slice(&json);
+    bytes.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"BIN\0");
+    bytes.extend_from_slice(&bin);
+    bytes
+}
+
+/// Checks the quad of `DOCUMENT` is placed by the node hierarchy.
+fn assert_quad(scene: &GLTFScene) {
+    assert_eq!(scene.world.objects.len(), 1);
+    let quad = &scene.world.objects[0];
+
+    // The quad spans [-2, 2] along x after the scale of its node, 5 units away.
+    let ray = Ray::new(Point3::new(1.5, 0.5, 0.), Vector3::new(0., 0., -1.));
+    let mut record = HitRecord::new();
+    assert!(quad.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!((record.t - 5.).abs() < 1e-5);
+    assert!((record.normal.unwrap().z - 1.).abs() < 1e-5);
+
+    // glTF uv coordinates start at the top, the crate reads v from the bottom.
+    let uv = record.uv.unwrap();
+    assert!((uv.u() - 0.875).abs() < 1e-5);
+    assert!((uv.v() - 0.75).abs() < 1e-5);
+
+    let ray = Ray::new(Point3::new(2.5, 0.5, 0.), Vector3::new(0., 0., -1.));
+    assert!(!quad.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+}
+
+#[test]
+fn gltf_embedded_buffer_test() {
+    let scene = GLTFScene::parse(&gltf(), Path::new("")).unwrap();
+    assert_quad(&scene);
+}
+
+#[test]
+fn glb_test() {
+    let scene = GLTFScene::parse(&glb(), Path::new("")).unwrap();
+    assert_quad(&scene);
+}
+
+#[test]
+fn gltf_camera_test() {
+    let scene = GLTFScene::parse(&gltf(), Path::new("")).unwrap();
+    assert_eq!(scene.cameras.len(), 1);
+    let camera = scene.cameras[0].build().unwrap();
+    assert_eq!(camera.position, Point3::new(0., 1., 5.));
+    assert_eq!(camera.target, Point3::new(0., 1., 4.));
+    assert!((camera.fov - 0.5f32.to_degrees()).abs() < 1e-4);
+    assert_eq!(camera.aspect, 1.5);
+}
+
+#[test]
+fn gltf_spot_light_test() {
+    let scene = GLTFScene::parse(&gltf(), Path::new("")).unwrap();
+    assert_eq!(scene.light_sources.len(), 1);
+    let light = &scene.light_sources[0];
+
+    // The light points down from 4 units up, lighting the point below it only.
+    let record = light.sample(&Point3::zero()).unwrap();
+    assert!((record.direction.y - 1.).abs() < 1e-5);
+    assert!((record.distance - 4.).abs() < 1e-5);
+    let expected = Color3::new(8., 4., 2.).mul(1. / 16.);
+    assert!((&record.radiance - &expected).length() < 1e-4);
+    assert!(light.sample(&Point3::new(4., 0., 0.)).is_none());
+}
+
+#[test]
+fn gltf_invalid_test() {
+    // Required extensions the importer doesn't understand are rejected.
+    let document = String::from_utf8(gltf()).unwrap().replace(
+        r#""extensionsUsed""#,
+        r#""extensionsRequired": ["KHR_draco_mesh_compression"], "extensionsUsed""#,
+    );
+    assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+
+    // Accessors reading past their buffer view are rejected.
+    let document = String::from_utf8(gltf())
+        .unwrap()
+        .replace(r#""count": 6"#, r#""count": 7"#);
+    assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+
+    // Offsets and counts overflowing the arithmetic don't wrap around into range.
+    let huge = u64::MAX.to_string();
+    let document = String::from_utf8(gltf())
+        .unwrap()
+        .replace(r#""byteOffset": 48"#, &format!(r#""byteOffset": {huge}"#));
+    assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+    let document = String::from_utf8(gltf()).unwrap().replace(
+        r#""count": 6"#,
+        &format!(r#""count": 6, "byteOffset": {huge}"#),
+    );
+    assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+
+    // Accessors without a buffer view can't ask for unbounded zeros.
+    let document = String::from_utf8(gltf()).unwrap().replace(
+        r#""bufferView": 1, "componentType": 5126, "count": 4"#,
+        r#""componentType": 5126, "count": 1000000000000"#,
+    );
+    assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+
+    // Vertex attributes of the wrong type are rejected, even with enough values for every vertex.
+    for (attribute, count, kind) in [("COLOR_0", 4, "SCALAR"), ("NORMAL", 12, "SCALAR")] {
+        let document = String::from_utf8(gltf())
+            .unwrap()
+            .replace(
+                r#""TEXCOORD_0": 1 }"#,
+                &format!(r#""TEXCOORD_0": 1, "{attribute}": 3 }}"#),
+            )
+            .replace(
+                r#""count": 6, "type": "SCALAR" }"#,
+                &format!(
+                    r#""count": 6, "type": "SCALAR" }},
+        {{ "componentType": 5126, "count": {count}, "type": "{kind}" }}"#
+                ),
+            );
+        assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+    }
+
+    // So are files of glTF 1.0.
+    let document = String::from_utf8(gltf())
+        .unwrap()
+        .replace(r#""version": "2.0""#, r#""version": "1.0""#);
+    assert!(GLTFScene::parse(document.as_bytes(), Path::new("")).is_err());
+}
+
+#[test]
+fn transform_instance_test() {
+    let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let sphere = Rc::new(Sphere::new(Point3::zero(), 1., material));
+    let transform = &Matrix4::translation(&Vector3::new(0., 0., -5.))
+        * &Matrix4::scaling(&Vector3::new(1., 1., 2.));
+    let ellipsoid = TransformInstance::new(sphere, transform);
+
+    // The ellipsoid reaches 2 units towards the ray.
+    let ray = Ray::new(Point3::zero(), Vector3::new(0., 0., -1.));
+    let mut record = HitRecord::new();
+    assert!(ellipsoid.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!((record.t - 3.).abs() < 1e-4);
+
+    // Normals stay perpendicular to the stretched surface.
+    let ray = Ray::new(Point3::new(0., 0.8, 0.), Vector3::new(0., 0., -1.));
+    assert!(ellipsoid.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    let normal = record.normal.unwrap();
+    let tangent = Vector3::new(0., -0.6, 1.6);
+    assert!(normal.dot(&tangent).abs() < 1e-4);
+    assert!((normal.length() - 1.).abs() < 1e-5);
+}
+
+#[test]
+fn transform_instance_pdf_test() {
+    let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let sphere = Rc::new(Sphere::new(Point3::zero(), 1., material));
+    let transform = &Matrix4::translation(&Vector3::new(0., 0., -4.))
+        * &Matrix4::scaling(&Vector3::new(2., 1., 0.5));
+    let ellipsoid = TransformInstance::new(sphere, transform);
+
+    // The density integrates to one over the directions towards the ellipsoid.
+    let origin = Point3::zero();
+    let (rows, columns) = (400, 800);
+    let mut integral = 0.;
+    for i in 0..rows {
+        for j in 0..columns {
+            let z = 1. - 2. * (i as f32 + 0.5) / rows as f32;
+            let phi = 2. * std::f32::consts::PI * (j as f32 + 0.5) / columns as f32;
+            let r = f32::sqrt(1. - z * z);
+            let direction = Vector3::new(r * phi.cos(), r * phi.sin(), z);
+            integral += ellipsoid.pdf_value(&origin, &direction);
+        }
+    }
+    integral *= 4. * std::f32::consts::PI / (rows * columns) as f32;
+    assert!((integral - 1.).abs() < 0.05, "integral {integral}");
+
+    // Sampled directions point at the ellipsoid.
+    for _ in 0..100 {
+        let direction = ellipsoid.random(&origin);
+        assert!(ellipsoid.pdf_value(&origin, &direction) > 0.);
+    }
+}
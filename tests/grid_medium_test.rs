@@ -0,0 +1,91 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        AxisAlignedBoundingBox, Color3, DensityGrid, GridMedium, HenyeyGreensteinPDF, HitRecord,
+        Interval, Point3, Ray, Vector3,
+    },
+    traits::{Hittable, ProbabilityDensityFunction},
+};
+use std::{io, rc::Rc};
+
+/// Encode a single channel grid over the unit cube as a `.vol` file.
+fn vol_file(nx: i32, ny: i32, nz: i32, data: &[f32]) -> Vec<u8> {
+    let mut bytes = b"VOL".to_vec();
+    bytes.push(3);
+    for value in [1, nx, ny, nz, 1] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in [0f32, 0., 0., 1., 1., 1.].iter().chain(data) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes
+}
+
+#[test]
+fn density_grid_parse_test() {
+    let grid = DensityGrid::parse(&vol_file(2, 2, 1, &[0., 1., 2., 3.])).unwrap();
+    assert_eq!(grid.resolution(), [2, 2, 1]);
+    assert_eq!(grid.value(&Point3::new(0.25, 0.25, 0.5), 0), 0.);
+    assert_eq!(grid.value(&Point3::new(0.75, 0.75, 0.5), 0), 3.);
+    assert_eq!(grid.value(&Point3::new(0.5, 0.5, 0.5), 0), 1.5);
+
+    assert!(DensityGrid::parse(b"VOX").is_err());
+    assert!(DensityGrid::parse(&vol_file(2, 2, 2, &[0., 1., 2., 3.])).is_err());
+
+    // Sizes overflowing the voxel count or the file length are errors too.
+    for (nx, ny, nz) in [(i32::MAX, i32::MAX, i32::MAX), (1 << 30, 1 << 30, 4)] {
+        let error = DensityGrid::parse(&vol_file(nx, ny, nz, &[0.])).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn grid_medium_tracking_test() {
+    // A half full grid, the ray crosses one unit of density 1 and one unit of density 0.
+    let bbox =
+        AxisAlignedBoundingBox::new_with_two_points(&Point3::zero(), &Point3::new(2., 1., 1.));
+    let grid = Rc::new(DensityGrid::new(4, 1, 1, 1, vec![1., 1., 0., 0.], bbox));
+    let medium = GridMedium::new(grid, 0.5, 0.5, Color3::one(), 0.);
+
+    let ray = Ray::new(Point3::new(-1., 0.5, 0.5), Vector3::new(1., 0., 0.));
+    let interval = Interval::new(0.001, f32::INFINITY);
+    let count = 20000;
+
+    // Trilinear interpolation ramps the density down over the middle voxel half widths.
+    let expected = f32::exp(-1.);
+    let mut transmittance = 0.;
+    let mut misses = 0;
+    for _ in 0..count {
+        transmittance += medium.transmittance(&ray, &interval);
+        let mut record = HitRecord::new();
+        if !medium.hit(&ray, &interval, &mut record) {
+            misses += 1;
+        } else {
+            assert!(record.point.unwrap().x < 1.5);
+        }
+    }
+    transmittance /= count as f32;
+    let miss_rate = misses as f32 / count as f32;
+    assert!((transmittance - expected).abs() < 0.02, "{}", transmittance);
+    assert!((miss_rate - expected).abs() < 0.02, "{}", miss_rate);
+}
+
+#[test]
+fn henyey_greenstein_test() {
+    let g = 0.6;
+    let pdf = HenyeyGreensteinPDF::new(Vector3::new(0., 0., 1.), g);
+
+    // The mean cosine of the scattered directions is the anisotropy, and the pdf integrates to one.
+    let count = 100000;
+    let mut mean_cosine = 0.;
+    let mut integral = 0.;
+    for _ in 0..count {
+        mean_cosine += pdf.generate().z;
+        integral += pdf.value(&Vector3::random_unit_vector()) * 4. * std::f32::consts::PI;
+    }
+    mean_cosine /= count as f32;
+    integral /= count as f32;
+    assert!((mean_cosine - g).abs() < 0.01, "{}", mean_cosine);
+    assert!((integral - 1.).abs() < 0.05, "{}", integral);
+}
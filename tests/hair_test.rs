@@ -0,0 +1,168 @@
+extern crate raytracing;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use raytracing::{
+    core::{
+        Color3, Curve, CurveType, HairMaterial, HitRecord, Interval, LambertianMaterial, Point3,
+        Ray, Vector2, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::{f32::consts::PI, rc::Rc};
+
+/// Returns a hit record on a fiber along x at the offset `h` across it, seen along `-direction`.
+fn fiber_hit(direction: &Vector3, h: f32) -> HitRecord {
+    let mut record = HitRecord::new();
+    record.point = Some(Point3::zero());
+    record.normal = Some(Vector3::new(0., 0., 1.));
+    record.uv = Some(Vector2::new(0.5, (h + 1.) / 2.));
+    record.dpdu = Some(Vector3::new(1., 0., 0.));
+    record.dpdv = Some(direction.cross(&Vector3::new(1., 0., 0.)));
+    record
+}
+
+/// Returns a random unit vector drawn from the given generator.
+fn random_unit_vector(rng: &mut StdRng) -> Vector3 {
+    let z: f32 = rng.gen_range(-1. ..1.);
+    let phi = 2. * PI * rng.gen::<f32>();
+    let radius = f32::sqrt(1. - z * z);
+    Vector3::new(radius * f32::cos(phi), radius * f32::sin(phi), z)
+}
+
+#[test]
+fn curve_test() {
+    let material: Rc<dyn Material> = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let points = [
+        Point3::new(-1., 0., 0.),
+        Point3::new(-0.3, 0., 0.),
+        Point3::new(0.4, 0., 0.),
+        Point3::new(1., 0., 0.),
+    ];
+    let curve = Curve::new(
+        points,
+        [0.2, 0.2, 0.2, 0.2],
+        CurveType::Cylinder,
+        material.clone(),
+    );
+
+    let interval = Interval::new(0.001, f32::INFINITY);
+    let mut rng = rand::thread_rng();
+    for _ in 0..200 {
+        let offset = rng.gen_range(-0.15..0.15);
+        let ray = Ray::new(Point3::new(0.2, offset, 3.), Vector3::new(0., 0., -1.));
+        let mut record = HitRecord::new();
+        let hit = curve.hit(&ray, &interval, &mut record);
+        if f32::abs(offset) < 0.099 {
+            assert!(hit);
+            assert!((record.t - 3.).abs() < 1e-4);
+            // The normal bends around the fiber with the offset across it.
+            let normal = record.normal.unwrap();
+            assert!((normal.y - offset / 0.1).abs() < 1e-3);
+            let v = record.uv.unwrap().v();
+            assert!((f32::abs(2. * v - 1.) - f32::abs(offset) / 0.1).abs() < 1e-3);
+            assert!(record.dpdu.unwrap().x > 0.);
+        } else if f32::abs(offset) > 0.101 {
+            assert!(!hit);
+        }
+    }
+
+    // The curves of a spline share their ends and split the u coordinates.
+    let spline = Curve::new_spline(
+        &[
+            Point3::new(0., 0., 0.),
+            Point3::new(1., 0., 0.),
+            Point3::new(2., 0., 0.),
+            Point3::new(3., 0., 0.),
+            Point3::new(4., 0., 0.),
+            Point3::new(5., 0., 0.),
+            Point3::new(6., 0., 0.),
+        ],
+        &[0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1],
+        CurveType::Flat,
+        material,
+    );
+    assert_eq!(spline.len(), 2);
+    let ray = Ray::new(Point3::new(4.5, 0., 1.), Vector3::new(0., 0., -1.));
+    let mut record = HitRecord::new();
+    assert!(!spline[0].hit(&ray, &interval, &mut record));
+    assert!(spline[1].hit(&ray, &interval, &mut record));
+    assert!((record.uv.unwrap().u() - 0.75).abs() < 1e-3);
+}
+
+#[test]
+fn hair_furnace_test() {
+    // Without absorption the fiber scatters all of the light arriving at it.
+    let mut rng = StdRng::seed_from_u64(7);
+    for (beta_m, beta_n) in [(0.2, 0.3), (0.5, 0.5), (0.8, 0.9)] {
+        let mut hair = HairMaterial::new(Color3::zero());
+        hair.beta_m = beta_m;
+        hair.beta_n = beta_n;
+
+        let sample_count = 200000;
+        let mut integral = 0.;
+        for _ in 0..sample_count {
+            let direction = random_unit_vector(&mut rng);
+            let record = fiber_hit(&direction, rng.gen_range(-1. ..1.));
+            let ray_in = Ray::new(Point3::zero(), direction);
+            let ray_scattered = Ray::new(Point3::zero(), random_unit_vector(&mut rng));
+            let color = hair.scattering_color(&ray_in, &record, &ray_scattered);
+            integral += color.luminance() * 4. * PI;
+        }
+        integral /= sample_count as f32;
+        assert!(
+            (integral - 1.).abs() < 0.05,
+            "furnace integral is {}",
+            integral
+        );
+    }
+
+    // Grazing hits at the edges of the fiber reflect everything and scatter no higher orders.
+    let hair = HairMaterial::new(Color3::zero());
+    let direction = Vector3::new(-0.025, 0.753, -0.658).normolize();
+    for h in [-1., 1.] {
+        let record = fiber_hit(&direction, h);
+        let ray_in = Ray::new(Point3::zero(), direction);
+        for _ in 0..100 {
+            let ray_scattered = Ray::new(Point3::zero(), random_unit_vector(&mut rng));
+            let color = hair.scattering_color(&ray_in, &record, &ray_scattered);
+            assert!(color.x.is_finite() && color.x >= 0., "{color:?}");
+        }
+    }
+}
+
+#[test]
+fn hair_sampling_test() {
+    // Without absorption the sampling density is proportional to the scattered light, and the
+    // sampled directions follow it.
+    let mut rng = StdRng::seed_from_u64(11);
+    let hair = HairMaterial::new(Color3::zero());
+    let sample_count = 20000;
+    let mut estimate = 0.;
+    for _ in 0..sample_count {
+        let direction = random_unit_vector(&mut rng);
+        let record = fiber_hit(&direction, rng.gen_range(-1. ..1.));
+        let ray_in = Ray::new(Point3::zero(), direction);
+
+        let mut scatter_record = raytracing::core::ScatterRecord::new();
+        assert!(hair.scatter(&ray_in, &record, &mut scatter_record));
+        let pdf = scatter_record.pdf.unwrap();
+        let sampled = Ray::new(Point3::zero(), pdf.generate());
+        let pdf_value = pdf.value(&sampled.direction);
+        assert!((hair.scattering_pdf(&ray_in, &record, &sampled) - pdf_value).abs() < 1e-4);
+        if pdf_value > 0. {
+            let weight = hair
+                .scattering_color(&ray_in, &record, &sampled)
+                .luminance()
+                / pdf_value;
+            assert!((weight - 1.).abs() < 1e-2, "sample weight is {}", weight);
+        }
+
+        // One-sample balance estimate of the integral of the density, with a uniform direction.
+        let uniform = 1. / (4. * PI);
+        let other = random_unit_vector(&mut rng);
+        estimate +=
+            pdf_value / (pdf_value + uniform) + pdf.value(&other) / (pdf.value(&other) + uniform);
+    }
+    estimate /= sample_count as f32;
+    assert!((estimate - 1.).abs() < 0.02, "pdf integral is {}", estimate);
+}
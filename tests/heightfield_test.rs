@@ -0,0 +1,100 @@
+extern crate raytracing;
+
+use rand::Rng;
+use raytracing::{
+    core::{
+        AxisAlignedBoundingBox, Color3, Heightfield, HitRecord, HittableList, Interval,
+        LambertianMaterial, Point3, Ray, Triangle, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::rc::Rc;
+
+#[test]
+fn heightfield_test() {
+    // Walking the grid finds the same hits as intersecting all of its triangles.
+    let mut rng = rand::thread_rng();
+    let material: Rc<dyn Material> = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let (width, depth) = (9, 7);
+    let heights: Vec<f32> = (0..width * depth).map(|_| rng.gen_range(0. ..1.)).collect();
+    let bbox = AxisAlignedBoundingBox::new_with_two_points(
+        &Point3::new(-2., -0.5, -1.),
+        &Point3::new(2., 0.5, 2.),
+    );
+    let heightfield = Heightfield::new(heights, width, depth, bbox, material.clone());
+
+    let mut triangles = HittableList::new();
+    for j in 0..depth - 1 {
+        for i in 0..width - 1 {
+            let point = |i: usize, j: usize| {
+                Point3::new(
+                    -2. + 4. * i as f32 / (width - 1) as f32,
+                    heightfield.height(i, j),
+                    -1. + 3. * j as f32 / (depth - 1) as f32,
+                )
+            };
+            let corners = [
+                point(i, j),
+                point(i + 1, j),
+                point(i + 1, j + 1),
+                point(i, j + 1),
+            ];
+            for [a, b, c] in [[0, 1, 2], [0, 2, 3]] {
+                triangles.add(Rc::new(Triangle::new_with_vertices(
+                    corners[a],
+                    corners[b],
+                    corners[c],
+                    material.clone(),
+                )));
+            }
+        }
+    }
+
+    let interval = Interval::new(0.001, f32::INFINITY);
+    let mut mismatches = 0;
+    for _ in 0..2000 {
+        let origin = Point3::new(
+            rng.gen_range(-3. ..3.),
+            rng.gen_range(-1. ..2.),
+            rng.gen_range(-2. ..3.),
+        );
+        let ray = Ray::new(origin, Vector3::random_unit_vector());
+        let mut expected = HitRecord::new();
+        let mut record = HitRecord::new();
+        let hit = triangles.hit(&ray, &interval, &mut expected);
+        if heightfield.hit(&ray, &interval, &mut record) != hit {
+            // Rays grazing an edge between two triangles may slip through either one.
+            mismatches += 1;
+            continue;
+        }
+        if hit {
+            assert!(
+                (record.t - expected.t).abs() < 1e-4,
+                "{} != {}",
+                record.t,
+                expected.t
+            );
+            let uv = record.uv.unwrap();
+            assert!((0. ..=1.).contains(&uv.u()) && (0. ..=1.).contains(&uv.v()));
+            let normal = record.normal.unwrap();
+            assert!((normal.length() - 1.).abs() < 1e-4);
+            assert!(normal.dot(&ray.direction) < 0.);
+        }
+    }
+    assert!(mismatches < 10, "{} rays disagree", mismatches);
+}
+
+#[test]
+fn heightfield_image_size_test() {
+    // Images a single pixel wide or tall don't make a surface.
+    let material: Rc<dyn Material> = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let bbox =
+        AxisAlignedBoundingBox::new_with_two_points(&Point3::zero(), &Point3::new(1., 1., 1.));
+    for (width, height) in [(1, 3), (3, 1)] {
+        let path = std::env::temp_dir().join(format!("heightfield_{width}x{height}.png"));
+        image::GrayImage::new(width, height).save(&path).unwrap();
+        let heightfield =
+            Heightfield::new_from_image(path.display().to_string(), bbox.clone(), material.clone());
+        assert!(heightfield.is_err());
+    }
+}
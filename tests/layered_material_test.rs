@@ -0,0 +1,157 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        CoatedMaterial, Color3, DielectricMaterial, HitRecord, LambertianMaterial, MetalMaterial,
+        MixMaterial, Point3, Ray, ScatterRecord, SolidColorTexture, Vector2, Vector3,
+        VertexColorTexture,
+    },
+    traits::Material,
+};
+use std::rc::Rc;
+
+/// Returns a hit of the z = 0 plane at the origin, facing up.
+fn hit_record() -> HitRecord {
+    let mut hit_record = HitRecord::new();
+    hit_record.point = Some(Point3::zero());
+    hit_record.uv = Some(Vector2::new(0.5, 0.5));
+    hit_record.normal = Some(Vector3::new(0., 0., 1.));
+    hit_record.front_face = true;
+    hit_record
+}
+
+/// Returns a ray arriving at the origin at 60 degrees from the normal.
+fn ray_in() -> Ray {
+    let direction = Vector3::new(f32::sin(1.0472), 0., -f32::cos(1.0472));
+    Ray::new(&Point3::zero() - &direction, direction)
+}
+
+/// Estimates the albedo of a material, the mean throughput of a bounce as the integrator
+/// computes it from `scatter`, the scattering pdf and `scattering_color`.
+fn mean_albedo(material: &dyn Material, samples: usize) -> Color3 {
+    let (ray_in, hit_record) = (ray_in(), hit_record());
+    let mut total = Color3::zero();
+    for _ in 0..samples {
+        let mut scatter_record = ScatterRecord::new();
+        if !material.scatter(&ray_in, &hit_record, &mut scatter_record) {
+            continue;
+        }
+        if scatter_record.skip_pdf {
+            total += &scatter_record.attenuation;
+            continue;
+        }
+        let pdf = scatter_record.pdf.unwrap();
+        let direction = pdf.generate();
+        let pdf_value = pdf.value(&direction);
+        if pdf_value > 0. {
+            let ray_scattered = Ray::new(Point3::zero(), direction);
+            let color = material.scattering_color(&ray_in, &hit_record, &ray_scattered);
+            total += &(&color / pdf_value);
+        }
+    }
+    &total / samples as f32
+}
+
+/// Returns the albedo of a clear coat of the given index of refraction over a white diffuse base,
+/// the coat reflection plus the light reaching the base and leaving through the coat.
+fn coated_albedo(ior: f32) -> f32 {
+    let fresnel = |cosine: f32| DielectricMaterial::reflectance(cosine, 1. / ior);
+    let n = 10000;
+    let transmitted: f32 = (0..n)
+        .map(|i| {
+            let theta = (i as f32 + 0.5) / n as f32 * std::f32::consts::FRAC_PI_2;
+            let (sin, cos) = f32::sin_cos(theta);
+            2. * cos * sin * (1. - fresnel(cos)) * std::f32::consts::FRAC_PI_2 / n as f32
+        })
+        .sum();
+    let fresnel_in = fresnel(f32::cos(1.0472));
+    fresnel_in + (1. - fresnel_in) * transmitted
+}
+
+fn white() -> Rc<LambertianMaterial> {
+    Rc::new(LambertianMaterial::new_with_color(Color3::one()))
+}
+
+#[test]
+fn coated_material_energy_test() {
+    let coated = CoatedMaterial::new(white(), 1.5);
+    let expected = coated_albedo(1.5);
+    assert!(expected < 1.);
+    let albedo = mean_albedo(&coated, 40000);
+    assert!((albedo.x - expected).abs() < 0.02, "{albedo:?} {expected}");
+    assert!(albedo.x == albedo.y && albedo.y == albedo.z);
+
+    // Only the base lobe is not specular.
+    let fresnel_in = DielectricMaterial::reflectance(f32::cos(1.0472), 1. / 1.5);
+    let probability = coated.diffuse_probability(&ray_in(), &hit_record());
+    assert!((probability - (1. - fresnel_in)).abs() < 1e-5);
+}
+
+#[test]
+fn coated_material_tint_test() {
+    // The tint is read at the hit, so vertex colors absorb in the coat.
+    let tint = Rc::new(VertexColorTexture::new());
+    let coated = CoatedMaterial::new_with_tint(white(), 1.5, tint);
+    let ray_scattered = Ray::new(Point3::zero(), Vector3::new(0., 0.6, 0.8));
+    let mut hit_record = hit_record();
+    let clear = coated.scattering_color(&ray_in(), &hit_record, &ray_scattered);
+    hit_record.vertex_color = Some(Color3::new(1., 0., 1.));
+    let color = coated.scattering_color(&ray_in(), &hit_record, &ray_scattered);
+    assert!(clear.y > 0. && color.y == 0.);
+    assert!((color.x - clear.x).abs() < 1e-6);
+}
+
+#[test]
+fn mix_material_furnace_test() {
+    // Mixes of lossless materials lose no energy, specular or not.
+    let glass = Rc::new(DielectricMaterial::new(1.5));
+    let mix = MixMaterial::new_with_weight(white(), glass, 0.3);
+    let albedo = mean_albedo(&mix, 40000);
+    assert!((albedo.x - 1.).abs() < 0.02, "{albedo:?}");
+
+    // Mixing with a coat, which picks its specular lobe at random, blends the albedos.
+    let coated = Rc::new(CoatedMaterial::new(white(), 1.5));
+    let mix = MixMaterial::new_with_weight(white(), coated, 0.6);
+    let expected = 0.4 + 0.6 * coated_albedo(1.5);
+    let albedo = mean_albedo(&mix, 40000);
+    assert!((albedo.x - expected).abs() < 0.02, "{albedo:?} {expected}");
+}
+
+#[test]
+fn mix_material_weights_test() {
+    let (ray_in, hit_record) = (ray_in(), hit_record());
+    let ray_scattered = Ray::new(Point3::zero(), Vector3::new(0., 0.6, 0.8));
+    let red = Rc::new(LambertianMaterial::new_with_color(Color3::new(1., 0., 0.)));
+    let blue = Rc::new(LambertianMaterial::new_with_color(Color3::new(0., 0., 1.)));
+    let cosine = 0.8 / std::f32::consts::PI;
+
+    let mix = MixMaterial::new(
+        red.clone(),
+        blue,
+        Rc::new(SolidColorTexture::new(0.25, 0.25, 0.25)),
+    );
+    let color = mix.scattering_color(&ray_in, &hit_record, &ray_scattered);
+    assert!((&color - &Color3::new(0.75 * cosine, 0., 0.25 * cosine)).length() < 1e-6);
+    let pdf = mix.scattering_pdf(&ray_in, &hit_record, &ray_scattered);
+    assert!((pdf - cosine).abs() < 1e-6);
+
+    // A mirror takes its share of the scatters, the diffuse lobe the rest at full strength.
+    let mirror = Rc::new(MetalMaterial::new(
+        Rc::new(SolidColorTexture::new(1., 1., 1.)),
+        0.,
+    ));
+    let mix = MixMaterial::new_with_weight(red, mirror, 0.25);
+    assert_eq!(mix.diffuse_probability(&ray_in, &hit_record), 0.75);
+    let color = mix.scattering_color(&ray_in, &hit_record, &ray_scattered);
+    assert!((&color - &Color3::new(cosine, 0., 0.)).length() < 1e-6);
+
+    let n = 10000;
+    let specular = (0..n)
+        .filter(|_| {
+            let mut scatter_record = ScatterRecord::new();
+            mix.scatter(&ray_in, &hit_record, &mut scatter_record) && scatter_record.skip_pdf
+        })
+        .count();
+    let rate = specular as f32 / n as f32;
+    assert!((rate - 0.25).abs() < 0.03, "{rate}");
+}
@@ -0,0 +1,220 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        AreaLight, BVHNode, Color3, EmissiveMaterial, HitRecord, HittableList, Interval,
+        LambertianMaterial, LightBVH, LightEmission, Matrix4, MeshTriangle, Point3, Quad, Ray,
+        RotateYInstance, Sphere, TransformInstance, TranslateInstance, TriangleMesh, Vector3,
+    },
+    traits::Hittable,
+};
+use std::rc::Rc;
+
+/// A unit square light facing down at the given position.
+fn ceiling_light(x: f32, z: f32, radiance: f32) -> Rc<dyn Hittable> {
+    Rc::new(Quad::new(
+        Point3::new(x, 2., z),
+        Vector3::new(1., 0., 0.),
+        Vector3::new(0., 0., 1.),
+        Rc::new(EmissiveMaterial::new_with_color(Color3::new(
+            radiance, radiance, radiance,
+        ))),
+    ))
+}
+
+#[test]
+fn light_power_selection_test() {
+    let mut lights = HittableList::new();
+    lights.add(ceiling_light(-2., 0., 1.));
+    lights.add(ceiling_light(2., 0., 3.));
+    assert!((lights.power() - 4. * std::f32::consts::PI).abs() < 1e-4);
+
+    // The brighter light gets three times the share of the samples.
+    let origin = Point3::new(0., 0., 0.5);
+    let dim = &Vector3::new(-1.5, 2., 0.5) - &origin;
+    let bright = &Vector3::new(2.5, 2., 0.5) - &origin;
+    let dim_pdf = lights.pdf_value(&origin, &dim) / lights.objects[0].pdf_value(&origin, &dim);
+    let bright_pdf =
+        lights.pdf_value(&origin, &bright) / lights.objects[1].pdf_value(&origin, &bright);
+    assert!((dim_pdf - 0.25).abs() < 1e-4);
+    assert!((bright_pdf - 0.75).abs() < 1e-4);
+
+    // Objects pushed directly are picked too, and a target without power makes the pick uniform.
+    let glass = Rc::new(Sphere::new(
+        Point3::new(0., 1., 3.),
+        0.5,
+        Rc::new(LambertianMaterial::new_with_color(Color3::one())),
+    ));
+    lights.objects.push(glass.clone());
+    let dim_pdf = lights.pdf_value(&origin, &dim) / lights.objects[0].pdf_value(&origin, &dim);
+    assert!((dim_pdf - 1. / 3.).abs() < 1e-4);
+    let toward = &Point3::new(0., 1., 3.) - &origin;
+    let glass_pdf = lights.pdf_value(&origin, &toward) / glass.pdf_value(&origin, &toward);
+    assert!((glass_pdf - 1. / 3.).abs() < 1e-4);
+    for _ in 0..100 {
+        lights.random(&origin);
+    }
+}
+
+#[test]
+fn light_bvh_test() {
+    let mut lights = HittableList::new();
+    for i in 0..8 {
+        lights.add(ceiling_light(i as f32 * 3., 0., 1. + i as f32));
+    }
+    let bvh = LightBVH::new(&lights);
+    let origin = Point3::new(1.5, 0., 0.5);
+
+    // Every light is reachable and the probabilities of picking them add up to one.
+    let mut probabilities = vec![0.; lights.objects.len()];
+    for i in 0..10000 {
+        let (light, probability) = bvh.sample(&origin, i as f32 / 10000.).unwrap();
+        let index = lights
+            .objects
+            .iter()
+            .position(|object| Rc::ptr_eq(object, &light))
+            .unwrap();
+        probabilities[index] = probability;
+    }
+    assert!(probabilities.iter().all(|&probability| probability > 0.));
+    assert!((probabilities.iter().sum::<f32>() - 1.).abs() < 1e-4);
+
+    // The light right above the point is the most likely one.
+    assert!(probabilities[0] > probabilities[7]);
+
+    // The pdf of a direction towards a light is the light pdf weighted by its probability.
+    for (i, light) in lights.objects.iter().enumerate() {
+        let direction = &Vector3::new(i as f32 * 3. + 0.5, 2., 0.5) - &origin;
+        let expected = probabilities[i] * light.pdf_value(&origin, &direction);
+        let pdf = bvh.pdf_value(&origin, &direction);
+        assert!(
+            (pdf - expected).abs() <= 1e-4 * expected,
+            "{} != {}",
+            pdf,
+            expected
+        );
+    }
+}
+
+#[test]
+fn instanced_light_bvh_test() {
+    // A translated, a rotated and a stretched copy of a light, and a triangle of a mesh.
+    let translated = TranslateInstance::new(ceiling_light(0., 0., 1.), Vector3::new(4., 0., 0.));
+    let rotated = RotateYInstance::new(ceiling_light(0., 0., 1.), 90.);
+    let transform = &Matrix4::translation(&Vector3::new(-6., 0., 0.))
+        * &Matrix4::scaling(&Vector3::new(2., 1., 1.));
+    let stretched = TransformInstance::new(ceiling_light(0., 0., 1.), transform);
+    let mesh = Rc::new(TriangleMesh::new(
+        vec![
+            Point3::new(0., 2., 4.),
+            Point3::new(1., 2., 4.),
+            Point3::new(0., 2., 5.),
+        ],
+        vec![[0, 1, 2]],
+    ));
+    let triangle = MeshTriangle::new(
+        mesh,
+        0,
+        Rc::new(EmissiveMaterial::new_with_color(Color3::one())),
+    );
+
+    let pi = std::f32::consts::PI;
+    assert!((translated.power() - pi).abs() < 1e-4);
+    assert!((rotated.power() - pi).abs() < 1e-4);
+    assert!((stretched.power() - 2. * pi).abs() < 1e-4);
+    assert!((triangle.power() - 0.5 * pi).abs() < 1e-4);
+
+    // The bounds follow the instances and keep facing down.
+    let bounds = translated.light_bounds().unwrap();
+    assert!(bounds.bbox.x.min > 3.9 && bounds.bbox.x.max < 5.1);
+    let bounds = rotated.light_bounds().unwrap();
+    assert!((bounds.w.y + 1.).abs() < 1e-5 && bounds.cos_theta_o == 1.);
+    let bounds = stretched.light_bounds().unwrap();
+    assert!((bounds.power - 2. * pi).abs() < 1e-4 && bounds.bbox.x.max < -3.9);
+    assert!((bounds.w.y + 1.).abs() < 1e-5 && bounds.cos_theta_o == 1.);
+    let bounds = triangle.light_bounds().unwrap();
+    assert!((bounds.w.y + 1.).abs() < 1e-5 && bounds.cos_theta_o == 1.);
+
+    let mut lights = HittableList::new();
+    lights.add(Rc::new(translated));
+    lights.add(Rc::new(rotated));
+    lights.add(Rc::new(stretched));
+    lights.add(Rc::new(triangle));
+    assert!((lights.power() - 4.5 * pi).abs() < 1e-3);
+
+    // Every light is picked, and sampled directions towards it have a density.
+    let bvh = LightBVH::new(&lights);
+    let origin = Point3::new(0., 0., 0.);
+    let mut probabilities = vec![0.; lights.objects.len()];
+    for i in 0..10000 {
+        let (light, probability) = bvh.sample(&origin, i as f32 / 10000.).unwrap();
+        let index = lights
+            .objects
+            .iter()
+            .position(|object| Rc::ptr_eq(object, &light))
+            .unwrap();
+        probabilities[index] = probability;
+
+        let direction = light.random(&origin);
+        assert!(light.pdf_value(&origin, &direction) > 0., "{index}");
+    }
+    assert!(probabilities.iter().all(|&probability| probability > 0.));
+    assert!((probabilities.iter().sum::<f32>() - 1.).abs() < 1e-4);
+}
+
+#[test]
+fn area_light_test() {
+    let quad = |size: f32| -> Rc<dyn Hittable> {
+        Rc::new(Quad::new(
+            Point3::zero(),
+            Vector3::new(size, 0., 0.),
+            Vector3::new(0., 0., size),
+            Rc::new(EmissiveMaterial::new_with_color(Color3::zero())),
+        ))
+    };
+
+    // Resizing a light specified by power keeps its power and changes its radiance.
+    let small = AreaLight::new(quad(1.), Color3::one(), LightEmission::Lumens(683.), false);
+    let large = AreaLight::new(quad(2.), Color3::one(), LightEmission::Lumens(683.), false);
+    assert!((small.power() - 1.).abs() < 1e-4);
+    assert!((large.power() - 1.).abs() < 1e-4);
+    assert!((small.radiance().luminance() - 4. * large.radiance().luminance()).abs() < 1e-4);
+
+    // Resizing a light specified by luminance keeps its radiance.
+    let small = AreaLight::new(quad(1.), Color3::one(), LightEmission::Nits(683.), true);
+    let large = AreaLight::new(quad(2.), Color3::one(), LightEmission::Nits(683.), true);
+    assert_eq!(small.radiance(), large.radiance());
+    assert!((large.power() - 8. * std::f32::consts::PI).abs() < 1e-3);
+
+    // A two-sided light is seen from behind.
+    let mut record = HitRecord::new();
+    let ray = Ray::new(Point3::new(0.5, 1., 0.5), Vector3::new(0., -1., 0.));
+    assert!(small.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!(!record.front_face);
+    let material = record.material.clone().unwrap();
+    let emitted = material.emitted(&ray, &record, &record.uv.unwrap(), &record.point.unwrap());
+    assert_eq!(emitted, small.radiance());
+
+    // Transformed shapes spread the power over their transformed area.
+    let transform = Matrix4::scaling(&Vector3::new(2., 1., 3.));
+    let stretched = Rc::new(TransformInstance::new(quad(1.), transform));
+    assert!((stretched.area() - 6.).abs() < 1e-4);
+    let light = AreaLight::new(stretched, Color3::one(), LightEmission::Lumens(683.), false);
+    assert!((light.power() - 1.).abs() < 1e-4);
+    assert!((light.radiance().luminance() - 1. / (6. * std::f32::consts::PI)).abs() < 1e-4);
+}
+
+#[test]
+#[should_panic(expected = "needs a shape of known area")]
+fn area_light_without_area_test() {
+    // Hierarchies don't know their area, so power can't be spread over them.
+    let mut shapes = HittableList::new();
+    shapes.add(ceiling_light(0., 0., 1.));
+    let shapes = BVHNode::new(&mut shapes);
+    AreaLight::new(
+        Rc::new(shapes),
+        Color3::one(),
+        LightEmission::Watts(100.),
+        false,
+    );
+}
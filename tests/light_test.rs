@@ -0,0 +1,64 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{Color3, DirectionalLight, IesProfile, Point3, SpotLight, Vector3},
+    traits::Light,
+};
+use std::io;
+
+#[test]
+fn spot_light_cone_test() {
+    let light = SpotLight::new(
+        Point3::new(0., 1., 0.),
+        Point3::zero(),
+        Color3::one(),
+        30.,
+        20.,
+    );
+
+    // Inside the falloff angle the full intensity arrives.
+    let record = light.sample(&Point3::zero()).unwrap();
+    assert_eq!(record.direction, Vector3::new(0., 1., 0.));
+    assert_eq!(record.radiance, Color3::one());
+
+    // Between the falloff and cone angles the intensity fades out.
+    let record = light.sample(&Point3::new(f32::tan(0.45), 0., 0.)).unwrap();
+    assert!(record.radiance.x > 0. && record.radiance.x < 1.);
+
+    // Outside the cone no light arrives.
+    assert!(light.sample(&Point3::new(1., 0., 0.)).is_none());
+}
+
+#[test]
+fn directional_light_test() {
+    let light = DirectionalLight::new(Vector3::new(0., -2., 0.), Color3::new(3., 3., 3.));
+    let record = light.sample(&Point3::new(5., 0., 5.)).unwrap();
+    assert_eq!(record.direction, Vector3::new(0., 1., 0.));
+    assert_eq!(record.distance, f32::INFINITY);
+    assert_eq!(record.radiance, Color3::new(3., 3., 3.));
+    assert!(light.is_delta());
+}
+
+#[test]
+fn ies_profile_counts_test() {
+    let profile = |counts: &str| {
+        IesProfile::parse(&format!(
+            "TILT=NONE\n1 1000 1 {counts} 1 1 2 0 0 0\n1 1 100\n0 45 90\n0\n100 50 0"
+        ))
+    };
+    assert!(profile("3 1").is_ok());
+
+    // Counts that are huge, negative, fractional or not numbers are errors, not allocations.
+    for counts in [
+        "1e30 1",
+        "3 4000000000",
+        "-3 1",
+        "2.5 1",
+        "inf 1",
+        "NaN 1",
+        "4 1",
+    ] {
+        let error = profile(counts).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{counts}");
+    }
+}
@@ -0,0 +1,122 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        get_cube_box, Color3, DielectricMaterial, HitRecord, HomogeneousMedium, Interval,
+        LambertianMaterial, MediumBoundary, Point3, Ray, Sphere, SubsurfaceMaterial, Vector3,
+    },
+    traits::{Hittable, Material, Medium},
+};
+use std::{ops::Neg, rc::Rc};
+
+#[test]
+fn homogeneous_medium_sample_test() {
+    let medium = HomogeneousMedium::new(Color3::new(0.5, 1., 2.), Color3::new(0.5, 0., 1.), 0.);
+    let ray = Ray::new(Point3::zero(), Vector3::new(1., 0., 0.));
+    let interval = Interval::new(0., 1.);
+    let transmittance = medium.transmittance(&ray, &interval);
+    assert!((transmittance.x - f32::exp(-1.)).abs() < 1e-6);
+    assert!((transmittance.z - f32::exp(-3.)).abs() < 1e-6);
+
+    // Weighted passes through the medium estimate the transmittance of each channel.
+    let samples = 200000;
+    let mut passed = Color3::zero();
+    for _ in 0..samples {
+        let record = medium.sample(&ray, &interval);
+        match record.t {
+            Some(t) => assert!(interval.contains(t)),
+            None => passed += &record.weight,
+        }
+    }
+    let passed = &passed / samples as f32;
+    assert!((passed.x - transmittance.x).abs() < 0.01);
+    assert!((passed.y - transmittance.y).abs() < 0.01);
+    assert!((passed.z - transmittance.z).abs() < 0.01);
+}
+
+#[test]
+fn medium_boundary_test() {
+    let fog: Rc<dyn Medium> = Rc::new(HomogeneousMedium::new_with_albedo(
+        Color3::one(),
+        Color3::one(),
+        0.,
+    ));
+    let glass = Rc::new(Sphere::new(
+        Point3::zero(),
+        1.,
+        Rc::new(DielectricMaterial::new(1.5)),
+    ));
+    let boundary = MediumBoundary::new(glass.clone(), Some(fog.clone()), None);
+
+    // Entering the sphere from outside leads into the fog.
+    let ray = Ray::new(Point3::new(-2., 0., 0.), Vector3::new(1., 0., 0.));
+    let mut record = HitRecord::new();
+    assert!(boundary.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!(record.material.is_some());
+    let interface = record.medium_interface.clone().unwrap();
+    assert!(interface.medium_along(&record, &ray.direction).is_some());
+    assert!(interface
+        .medium_along(&record, &ray.direction.neg())
+        .is_none());
+
+    // Leaving it from inside leads back into vacuum.
+    let ray = Ray::new(Point3::zero(), Vector3::new(1., 0., 0.));
+    let mut record = HitRecord::new();
+    assert!(boundary.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    let interface = record.medium_interface.clone().unwrap();
+    assert!(interface.medium_along(&record, &ray.direction).is_none());
+
+    // Invisible boundaries have no material.
+    let boundary = MediumBoundary::new_invisible(glass, Some(fog), None);
+    let mut record = HitRecord::new();
+    assert!(boundary.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!(record.material.is_none());
+}
+
+#[test]
+fn cube_box_faces_test() {
+    let material = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let cube_box = get_cube_box(Point3::zero(), Point3::one(), material);
+
+    // Every face is hit from the outside.
+    let center = Point3::new(0.5, 0.5, 0.5);
+    for axis in 0..3 {
+        for side in [-1., 1.] {
+            let mut origin = center;
+            origin[axis] += 2. * side;
+            let ray = Ray::new(origin, &center - &origin);
+            let mut record = HitRecord::new();
+            assert!(cube_box.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+            assert!((record.t - 0.75).abs() < 1e-5);
+            assert!(record.front_face);
+        }
+    }
+}
+
+#[test]
+fn subsurface_material_test() {
+    let skin = Rc::new(SubsurfaceMaterial::new_with_color(
+        Color3::new(0.8, 0.5, 0.4),
+        Color3::new(1., 0.5, 0.25),
+        1.4,
+    ));
+    let sphere = Sphere::new(Point3::zero(), 1., skin.clone());
+
+    let ray = Ray::new(Point3::new(-2., 0., 0.), Vector3::new(1., 0., 0.));
+    let mut record = HitRecord::new();
+    assert!(sphere.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+
+    // The medium inside is as dense as the mean free path of each channel.
+    let medium = skin.interior_medium(&record).unwrap();
+    let transmittance = medium.transmittance(&ray, &Interval::new(0., 1.));
+    assert!((transmittance.x - f32::exp(-1.)).abs() < 1e-5);
+    assert!((transmittance.y - f32::exp(-2.)).abs() < 1e-5);
+    assert!((transmittance.z - f32::exp(-4.)).abs() < 1e-5);
+
+    // Brighter surfaces scatter more of the light inside.
+    assert!(
+        SubsurfaceMaterial::single_scattering_albedo(0.8)
+            > SubsurfaceMaterial::single_scattering_albedo(0.4)
+    );
+    assert!(SubsurfaceMaterial::single_scattering_albedo(0.8) > 0.8);
+}
@@ -0,0 +1,176 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        BVHNode, Color3, HitRecord, Interval, LambertianMaterial, Point3, Ray, TriangleMesh,
+        Vector3, VertexColorTexture,
+    },
+    traits::{Hittable, Material, Texture},
+};
+use std::rc::Rc;
+
+const ASCII_PLY: &str = "ply
+format ascii 1.0
+comment a unit square split into two triangles
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+property float u
+property float v
+property uchar red
+property uchar green
+property uchar blue
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 1 0 0 255 0 0
+1 0 0 0 0 1 1 0 0 255 0
+1 1 0 0 0 1 1 1 0 0 255
+0 1 0 0 0 1 0 1 255 255 255
+4 0 1 2 3
+";
+
+/// Returns the data of `ASCII_PLY` as a binary PLY file.
+fn binary_ply(big_endian: bool) -> Vec<u8> {
+    let format = if big_endian {
+        "binary_big_endian"
+    } else {
+        "binary_little_endian"
+    };
+    let mut bytes = ASCII_PLY
+        .replace("format ascii", &format!("format {}", format))
+        .split("end_header\n")
+        .next()
+        .unwrap()
+        .as_bytes()
+        .to_vec();
+    bytes.extend_from_slice(b"end_header\n");
+
+    let floats = |bytes: &mut Vec<u8>, values: &[f32]| {
+        for value in values {
+            if big_endian {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    };
+    let vertices: [([f32; 8], [u8; 3]); 4] = [
+        ([0., 0., 0., 0., 0., 1., 0., 0.], [255, 0, 0]),
+        ([1., 0., 0., 0., 0., 1., 1., 0.], [0, 255, 0]),
+        ([1., 1., 0., 0., 0., 1., 1., 1.], [0, 0, 255]),
+        ([0., 1., 0., 0., 0., 1., 0., 1.], [255, 255, 255]),
+    ];
+    for (values, color) in vertices {
+        floats(&mut bytes, &values);
+        bytes.extend_from_slice(&color);
+    }
+    bytes.push(4);
+    for index in [0i32, 1, 2, 3] {
+        if big_endian {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        } else {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+#[test]
+fn ply_test() {
+    let ascii = TriangleMesh::parse_ply(ASCII_PLY.as_bytes()).unwrap();
+    assert_eq!(ascii.triangle_count(), 2);
+    assert_eq!(ascii.indices, vec![[0, 1, 2], [0, 2, 3]]);
+    assert_eq!(ascii.positions[2], Point3::new(1., 1., 0.));
+    assert_eq!(ascii.normals.as_ref().unwrap()[1], Vector3::new(0., 0., 1.));
+    assert_eq!(ascii.uvs.as_ref().unwrap()[3].v(), 1.);
+    assert_eq!(ascii.colors.as_ref().unwrap()[1], Color3::new(0., 1., 0.));
+
+    for big_endian in [false, true] {
+        let binary = TriangleMesh::parse_ply(&binary_ply(big_endian)).unwrap();
+        assert_eq!(binary.indices, ascii.indices);
+        assert_eq!(binary.positions, ascii.positions);
+        assert_eq!(binary.colors, ascii.colors);
+    }
+
+    // Malformed files are reported, not panicked on.
+    assert!(TriangleMesh::parse_ply(b"not a ply").is_err());
+    assert!(TriangleMesh::parse_ply(&ASCII_PLY.as_bytes()[..ASCII_PLY.len() - 6]).is_err());
+    assert!(TriangleMesh::parse_ply(ASCII_PLY.replace("4 0 1 2 3", "3 0 1 7").as_bytes()).is_err());
+    assert!(TriangleMesh::parse_ply(&binary_ply(false)[..300]).is_err());
+}
+
+#[test]
+fn stl_test() {
+    let ascii = "solid square
+facet normal 0 0 1
+  outer loop
+    vertex 0 0 0
+    vertex 1 0 0
+    vertex 1 1 0
+  endloop
+endfacet
+facet normal 0 0 1
+  outer loop
+    vertex 0 0 0
+    vertex 1 1 0
+    vertex 0 1 0
+  endloop
+endfacet
+endsolid square
+";
+    let mesh = TriangleMesh::parse_stl(ascii.as_bytes()).unwrap();
+    assert_eq!(mesh.triangle_count(), 2);
+    assert_eq!(mesh.positions[5], Point3::new(0., 1., 0.));
+
+    // A binary file whose header starts with `solid` like an ASCII one.
+    let mut binary = b"solid binary".to_vec();
+    binary.resize(80, 0);
+    binary.extend_from_slice(&2u32.to_le_bytes());
+    for triangle in mesh.positions.chunks(3) {
+        binary.extend_from_slice(&[0u8; 12]);
+        for vertex in triangle {
+            for value in [vertex.x, vertex.y, vertex.z] {
+                binary.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        binary.extend_from_slice(&[0u8; 2]);
+    }
+    let binary_mesh = TriangleMesh::parse_stl(&binary).unwrap();
+    assert_eq!(binary_mesh.positions, mesh.positions);
+    assert_eq!(binary_mesh.indices, mesh.indices);
+
+    assert!(TriangleMesh::parse_stl(b"short").is_err());
+    assert!(TriangleMesh::parse_stl(&binary[..binary.len() - 10]).is_err());
+    assert!(
+        TriangleMesh::parse_stl(ascii.replace("vertex 0 1 0", "vertex 0 1").as_bytes()).is_err()
+    );
+}
+
+#[test]
+fn mesh_triangle_test() {
+    let mesh = TriangleMesh::parse_ply(ASCII_PLY.as_bytes()).unwrap();
+    let material: Rc<dyn Material> =
+        Rc::new(LambertianMaterial::new(Rc::new(VertexColorTexture::new())));
+    let square = BVHNode::new(&mut mesh.triangles(material));
+
+    // Colors, uv coordinates and normals are interpolated between the vertices.
+    let ray = Ray::new(Point3::new(0.75, 0.25, 1.), Vector3::new(0., 0., -1.));
+    let mut record = HitRecord::new();
+    assert!(square.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!((record.t - 1.).abs() < 1e-6);
+    let uv = record.uv.unwrap();
+    assert!((uv.u() - 0.75).abs() < 1e-6 && (uv.v() - 0.25).abs() < 1e-6);
+    assert!(record.normal.unwrap().equals(&Vector3::new(0., 0., 1.)));
+    assert!(record.dpdu.unwrap().equals(&Vector3::new(1., 0., 0.)));
+    assert!(record.dpdv.unwrap().equals(&Vector3::new(0., 1., 0.)));
+    let color = VertexColorTexture::new().value_at_hit(&record);
+    assert!(color.equals(&Color3::new(0.25, 0.5, 0.25)));
+
+    let ray = Ray::new(Point3::new(1.5, 0.5, 1.), Vector3::new(0., 0., -1.));
+    assert!(!square.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+}
@@ -0,0 +1,82 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        ColorRamp, GradientTexture, HitRecord, NormalMappedMaterial, Point3, Ray, ScatterRecord,
+        SolidColorTexture, Vector2, Vector3,
+    },
+    traits::{Material, Texture},
+};
+use std::rc::Rc;
+
+/// A material scattering the shading normal it is given as its attenuation.
+#[derive(Debug)]
+struct NormalProbe;
+
+impl Material for NormalProbe {
+    fn scatter(
+        &self,
+        _ray_in: &Ray,
+        hit_record: &HitRecord,
+        scatter_record: &mut ScatterRecord,
+    ) -> bool {
+        scatter_record.attenuation = hit_record.normal.unwrap();
+        true
+    }
+}
+
+/// Returns the shading normal of a material on a hit of the z = 0 plane, facing up with its
+/// tangents along x and y.
+fn shading_normal(material: &dyn Material) -> Vector3 {
+    let mut hit_record = HitRecord::new();
+    hit_record.point = Some(Point3::new(0.3, 0.4, 0.));
+    hit_record.uv = Some(Vector2::new(0.3, 0.4));
+    hit_record.normal = Some(Vector3::new(0., 0., 1.));
+    hit_record.dpdu = Some(Vector3::new(1., 0., 0.));
+    hit_record.dpdv = Some(Vector3::new(0., 1., 0.));
+    hit_record.front_face = true;
+
+    let ray = Ray::new(Point3::new(0.3, 0.4, 1.), Vector3::new(0., 0., -1.));
+    let mut scatter_record = ScatterRecord::new();
+    assert!(material.scatter(&ray, &hit_record, &mut scatter_record));
+    scatter_record.attenuation
+}
+
+fn assert_close(a: Vector3, b: Vector3) {
+    assert!((&a - &b).length() < 1e-3, "{a:?} {b:?}");
+}
+
+#[test]
+fn flat_normal_map_test() {
+    let flat = Rc::new(SolidColorTexture::new(0.5, 0.5, 1.));
+    let material = NormalMappedMaterial::new_with_normal_map(Rc::new(NormalProbe), flat);
+    assert_close(shading_normal(&material), Vector3::new(0., 0., 1.));
+}
+
+#[test]
+fn tilted_normal_map_test() {
+    // Half along the tangent and half along the normal.
+    let tilted = Rc::new(SolidColorTexture::new(0.75, 0.5, 0.75));
+    let material = NormalMappedMaterial::new_with_normal_map(Rc::new(NormalProbe), tilted);
+    let expected = Vector3::new(1., 0., 1.).normolize();
+    assert_close(shading_normal(&material), expected);
+}
+
+#[test]
+fn linear_bump_map_test() {
+    // Heights rising by `scale` along u tilt the normal away from dpdu, to (-scale, 0, 1).
+    let ramp: Rc<dyn Texture> = Rc::new(GradientTexture::new_linear(
+        Vector2::zero(),
+        Vector2::new(1., 0.),
+        ColorRamp::new_grayscale(),
+    ));
+    let scale = 0.5;
+    let material = NormalMappedMaterial::new_with_bump_map(Rc::new(NormalProbe), ramp, scale);
+    let expected = Vector3::new(-scale, 0., 1.).normolize();
+    assert_close(shading_normal(&material), expected);
+
+    // A constant height leaves the normal unchanged.
+    let constant = Rc::new(SolidColorTexture::new(0.7, 0.7, 0.7));
+    let material = NormalMappedMaterial::new_with_bump_map(Rc::new(NormalProbe), constant, 2.);
+    assert_close(shading_normal(&material), Vector3::new(0., 0., 1.));
+}
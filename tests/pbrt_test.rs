@@ -0,0 +1,222 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{Color3, HitRecord, Interval, PBRTScene, Point3, Ray, ScatterRecord, Vector3},
+    traits::Hittable,
+};
+use std::path::Path;
+
+/// A camera looking down +z at a sphere on the right of the image, a red quad light facing the
+/// camera behind it and a checkered floor.
+const SCENE: &str = r#"
+# The camera, 5 units before the origin.
+LookAt 0 0 -5  0 0 0  0 1 0
+Camera "perspective" "float fov" [ 45 ]
+Film "rgb" "integer xresolution" [ 400 ] "integer yresolution" [ 200 ]
+    "string filename" "scene.exr"
+Sampler "halton" "integer pixelsamples" 32
+Integrator "volpath" "integer maxdepth" [ 8 ]
+Option "bool disablepixeljitter" true
+
+WorldBegin
+
+LightSource "point" "rgb I" [ 2 2 2 ] "point3 from" [ 0 4 0 ]
+
+Texture "checks" "spectrum" "checkerboard" "float uscale" 4 "float vscale" 4
+    "rgb tex1" [ 1 1 1 ] "rgb tex2" [ 0 0 0 ]
+MakeNamedMaterial "floor" "string type" "diffuse" "texture reflectance" "checks"
+
+AttributeBegin
+    Translate 2 0 0
+    Material "dielectric" "float eta" 1.33
+    Shape "sphere" "float radius" 0.5
+AttributeEnd
+
+AttributeBegin
+    AreaLightSource "diffuse" "rgb L" [ 4 0 0 ] "float scale" 2
+    Translate 0 0 3
+    Shape "trianglemesh" "point3 P" [ -1 -1 0  1 -1 0  1 1 0  -1 1 0 ]
+        "integer indices" [ 0 2 1  0 3 2 ]
+AttributeEnd
+
+AttributeBegin
+    NamedMaterial "floor"
+    Shape "bilinearmesh" "point3 P" [ -4 -1 -4  4 -1 -4  -4 -1 4  4 -1 4 ]
+AttributeEnd
+
+MakeNamedMedium "fog" "string type" "homogeneous"
+Shape "cylinder" "float radius" 1
+"#;
+
+fn hit(object: &dyn Hittable, origin: Point3, direction: Vector3) -> Option<HitRecord> {
+    let ray = Ray::new(origin, direction);
+    let mut record = HitRecord::new();
+    object
+        .hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record)
+        .then_some(record)
+}
+
+#[test]
+fn pbrt_camera_test() {
+    let scene = PBRTScene::parse(SCENE, Path::new("")).unwrap();
+    let camera = scene.camera.build().unwrap();
+    assert_eq!(camera.position, Point3::new(0., 0., -5.));
+    assert_eq!(camera.target, Point3::new(0., 0., -4.));
+    assert!((camera.fov - 45.).abs() < 1e-4);
+    assert_eq!(camera.aspect, 2.);
+    assert_eq!(camera.width, 400);
+    assert_eq!(camera.samples_per_pixel, 32);
+    assert_eq!(camera.max_ray_depth, 8);
+    assert_eq!(camera.background, Color3::zero());
+    assert_eq!(camera.light_sources.len(), 1);
+    assert_eq!(scene.filename.as_deref(), Some("scene.exr"));
+
+    // The field of view of portrait images spans their width.
+    let portrait = SCENE.replace("yresolution\" [ 200 ]", "yresolution\" [ 800 ]");
+    let camera = PBRTScene::parse(&portrait, Path::new(""))
+        .unwrap()
+        .camera
+        .build()
+        .unwrap();
+    let horizontal = 2. * f32::atan(f32::tan(camera.fov.to_radians() / 2.) * camera.aspect);
+    assert!((horizontal.to_degrees() - 45.).abs() < 1e-3);
+}
+
+#[test]
+fn pbrt_handedness_test() {
+    let scene = PBRTScene::parse(SCENE, Path::new("")).unwrap();
+    assert_eq!(scene.world.objects.len(), 3);
+
+    // pbrt shows +x on the right of this camera, the crate shows it on the left, so the sphere
+    // is mirrored to stay on the right of the image.
+    let direction = Vector3::new(0., 0., 1.);
+    let record = hit(&scene.world, Point3::new(-2., 0., -5.), direction).unwrap();
+    assert!((record.t - 4.5).abs() < 1e-4);
+    assert!(hit(&scene.world, Point3::new(2., 0., -5.), direction).is_none());
+
+    // The quad light faces the camera like in pbrt, where faces wound counterclockwise seen from
+    // the camera face away from it.
+    let record = hit(&scene.world, Point3::new(0.5, 0.5, -5.), direction).unwrap();
+    assert!((record.t - 8.).abs() < 1e-4);
+    assert!(record.front_face);
+    let ray = Ray::new(Point3::new(0.5, 0.5, -5.), direction);
+    let emitted = record.material.clone().unwrap().emitted(
+        &ray,
+        &record,
+        &record.uv.unwrap(),
+        &record.point.unwrap(),
+    );
+    assert_eq!(emitted, Color3::new(8., 0., 0.));
+}
+
+#[test]
+fn pbrt_lights_test() {
+    let scene = PBRTScene::parse(SCENE, Path::new("")).unwrap();
+    assert_eq!(scene.lights.objects.len(), 1);
+
+    let camera = scene.camera.build().unwrap();
+    let record = camera.light_sources[0].sample(&Point3::zero()).unwrap();
+    assert!((record.direction.y - 1.).abs() < 1e-5);
+    assert!((record.distance - 4.).abs() < 1e-5);
+}
+
+#[test]
+fn pbrt_named_material_test() {
+    let scene = PBRTScene::parse(SCENE, Path::new("")).unwrap();
+
+    // The floor patch spans [0, 1] in u and v from its corner at pbrt's x = -4, z = -4, and is
+    // checkered 4 times along each.
+    let albedo = |x: f32, z: f32| {
+        let origin = Point3::new(x, 0., z);
+        let record = hit(&scene.world, origin, Vector3::new(0., -1., 0.)).unwrap();
+        let uv = record.uv.unwrap();
+        assert!((uv.u() - (4. - x) / 8.).abs() < 1e-4);
+        assert!((uv.v() - (z + 4.) / 8.).abs() < 1e-4);
+        let ray = Ray::new(origin, Vector3::new(0., -1., 0.));
+        let mut scatter_record = ScatterRecord::new();
+        let material = record.material.clone().unwrap();
+        assert!(material.scatter(&ray, &record, &mut scatter_record));
+        scatter_record.attenuation
+    };
+    assert_eq!(albedo(3.5, -3.5), Color3::one());
+    assert_eq!(albedo(1.5, -3.5), Color3::zero());
+    assert_eq!(albedo(1.5, -1.5), Color3::one());
+}
+
+#[test]
+fn pbrt_warnings_test() {
+    let scene = PBRTScene::parse(SCENE, Path::new("")).unwrap();
+    assert_eq!(
+        scene.warnings,
+        vec![
+            "unsupported directive Option".to_owned(),
+            "unsupported directive MakeNamedMedium".to_owned(),
+            "unsupported shape cylinder".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn pbrt_invalid_test() {
+    assert!(PBRTScene::parse("AttributeEnd", Path::new("")).is_err());
+    assert!(PBRTScene::parse("Translate 1 2", Path::new("")).is_err());
+    assert!(PBRTScene::parse("Shape \"sphere\" \"float radius\" [ 1", Path::new("")).is_err());
+    assert!(PBRTScene::parse(
+        "Shape \"trianglemesh\" \"point3 P\" [ 0 0 0 1 0 0 0 1 0 ] \"integer indices\" [ 0 1 3 ]",
+        Path::new("")
+    )
+    .is_err());
+}
+
+#[test]
+fn pbrt_singular_transform_test() {
+    // Spheres scaled to nothing along some axis are dropped rather than instanced.
+    for scale in ["1 1 0", "0 0 0"] {
+        let text = format!("WorldBegin\nScale {scale}\nShape \"sphere\"\n");
+        let scene = PBRTScene::parse(&text, Path::new("")).unwrap();
+        assert!(scene.world.objects.is_empty());
+    }
+}
+
+/// A sphere whose reflectance and bump come from texture networks.
+const NETWORK: &str = r#"
+WorldBegin
+Texture "quarter" "float" "constant" "float value" 0.25
+Texture "red" "spectrum" "constant" "rgb value" [ 1 0 0 ]
+Texture "mixed" "spectrum" "mix" "texture tex1" "red" "rgb tex2" [ 0 0 1 ]
+    "texture amount" "quarter"
+Texture "graded" "spectrum" "hsv" "texture tex" "mixed" "float value" 2
+Texture "cells" "float" "worley" "float scale" 8 "string feature" "f2-f1" "integer seed" 3
+Texture "stuck" "float" "objectspace" "texture tex" "cells"
+Texture "tiled" "float" "uvtransform" "texture tex" "stuck" "float uscale" 2 "bool tile" true
+Texture "bump" "float" "scale" "texture tex" "tiled" "float scale" 0.01
+Material "diffuse" "texture reflectance" "graded" "texture displacement" "bump"
+Shape "sphere"
+"#;
+
+#[test]
+fn pbrt_texture_network_test() {
+    let scene = PBRTScene::parse(NETWORK, Path::new("")).unwrap();
+    assert!(scene.warnings.is_empty(), "{:?}", scene.warnings);
+
+    let origin = Point3::new(0., 0., -5.);
+    let direction = Vector3::new(0., 0., 1.);
+    let record = hit(&scene.world, origin, direction).unwrap();
+    let mut scatter_record = ScatterRecord::new();
+    let material = record.material.clone().unwrap();
+    assert!(material.scatter(&Ray::new(origin, direction), &record, &mut scatter_record));
+    let expected = Color3::new(1.5, 0., 0.5);
+    assert!((&scatter_record.attenuation - &expected).length() < 1e-5);
+
+    // Unknown classes are skipped, malformed nodes are errors.
+    let scene = PBRTScene::parse("Texture \"t\" \"spectrum\" \"directionmix\"", Path::new(""));
+    assert_eq!(
+        scene.unwrap().warnings,
+        vec!["unsupported texture directionmix".to_owned()]
+    );
+    assert!(PBRTScene::parse(
+        "Texture \"t\" \"spectrum\" \"ramp\" \"float positions\" [ 0 1 ] \"rgb colors\" [ 1 1 1 ]",
+        Path::new("")
+    )
+    .is_err());
+}
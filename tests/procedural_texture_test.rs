@@ -0,0 +1,188 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        BrickTexture, Color3, ColorRamp, FractalKind, FractalNoiseTexture, GradientTexture,
+        HitRecord, PerlinNoise, Point3, SolidColorTexture, Vector2, VertexColorTexture,
+        WoodTexture, WorleyFeature, WorleyNoise, WorleyTexture,
+    },
+    traits::Texture,
+};
+use std::rc::Rc;
+
+/// Returns points spread over a few units of space.
+fn points() -> impl Iterator<Item = Point3> {
+    (0..500).map(|i| {
+        let i = i as f32;
+        Point3::new(
+            f32::sin(i * 1.3) * 3.,
+            f32::cos(i * 0.7) * 3.,
+            i * 0.013 - 2.,
+        )
+    })
+}
+
+#[test]
+fn worley_noise_test() {
+    let noise = WorleyNoise::new_with_seed(42);
+    for point in points() {
+        let (f1, f2) = noise.distances(&point);
+        assert!(0. <= f1 && f1 <= f2, "{f1} {f2}");
+        assert!(f1 < f32::sqrt(3.) && f2 < 2.);
+
+        // The distances change no faster than the point moves.
+        let moved = &point + &Point3::new(1e-3, 0., 0.);
+        let (g1, g2) = noise.distances(&moved);
+        assert!((g1 - f1).abs() <= 1e-3 + 1e-5 && (g2 - f2).abs() <= 1e-3 + 1e-5);
+    }
+
+    // The seed decides the feature points.
+    let other = WorleyNoise::new_with_seed(43);
+    assert!(points().any(|point| noise.distances(&point) != other.distances(&point)));
+    assert!(points().all(|point| {
+        noise.distances(&point) == WorleyNoise::new_with_seed(42).distances(&point)
+    }));
+}
+
+#[test]
+fn worley_texture_test() {
+    let mut texture = WorleyTexture::new(1., WorleyFeature::F1, ColorRamp::new_grayscale());
+    texture.noise = WorleyNoise::new_with_seed(1);
+    let noise = WorleyNoise::new_with_seed(1);
+    for point in points() {
+        let (f1, f2) = noise.distances(&point);
+        texture.feature = WorleyFeature::F1;
+        let color = texture.value(&Vector2::zero(), &point);
+        assert!((color.x - f1.min(1.)).abs() < 1e-6);
+        texture.feature = WorleyFeature::F2MinusF1;
+        let color = texture.value(&Vector2::zero(), &point);
+        assert!((color.x - (f2 - f1).min(1.)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn fractal_noise_test() {
+    let noise = PerlinNoise::new();
+    for point in points() {
+        let fbm = noise.fbm(&point, 6, 2., 0.5);
+        assert!((-1. ..=1.).contains(&fbm), "{fbm}");
+        let ridged = noise.ridged(&point, 6, 2., 0.5);
+        assert!((0. ..=1.).contains(&ridged), "{ridged}");
+    }
+
+    // One octave is the noise itself.
+    let point = Point3::new(0.3, 0.6, 0.9);
+    assert_eq!(noise.fbm(&point, 1, 2., 0.5), noise.noise(&point));
+
+    // Ridged noise is brightest along the zero crossings of the noise.
+    let ramp = ColorRamp::new_grayscale();
+    let mut texture = FractalNoiseTexture::new(1., ramp);
+    texture.kind = FractalKind::Ridged;
+    texture.octaves = 1;
+    let color = texture.value(&Vector2::zero(), &Point3::zero());
+    assert!((color.x - 1.).abs() < 1e-6);
+}
+
+#[test]
+fn color_ramp_test() {
+    // Stops blend textures, and may be given in any order.
+    let gradient: Rc<dyn Texture> = Rc::new(GradientTexture::new_linear(
+        Vector2::zero(),
+        Vector2::new(1., 0.),
+        ColorRamp::new_grayscale(),
+    ));
+    let red: Rc<dyn Texture> = Rc::new(SolidColorTexture::new(1., 0., 0.));
+    let ramp = ColorRamp::new(vec![(1., red), (0., gradient)]);
+    let uv = Vector2::new(0.5, 0.);
+    assert_eq!(
+        ramp.value(0., &uv, &Point3::zero()),
+        Color3::new(0.5, 0.5, 0.5)
+    );
+    assert_eq!(
+        ramp.value(0.5, &uv, &Point3::zero()),
+        Color3::new(0.75, 0.25, 0.25)
+    );
+    assert_eq!(
+        ramp.value(-1., &uv, &Point3::zero()),
+        Color3::new(0.5, 0.5, 0.5)
+    );
+    assert_eq!(
+        ramp.value(3., &uv, &Point3::zero()),
+        Color3::new(1., 0., 0.)
+    );
+}
+
+#[test]
+fn gradient_texture_test() {
+    let texture = GradientTexture::new_linear(
+        Vector2::new(0., 0.),
+        Vector2::new(0., 2.),
+        ColorRamp::new_grayscale(),
+    );
+    assert_eq!(texture.position(&Vector2::new(5., 1.)), 0.5);
+    assert_eq!(texture.position(&Vector2::new(0., -2.)), -1.);
+    let color = texture.value(&Vector2::new(0., 0.5), &Point3::zero());
+    assert_eq!(color, Color3::new(0.25, 0.25, 0.25));
+
+    let texture = GradientTexture::new_radial(Vector2::zero(), 2., ColorRamp::new_grayscale());
+    assert_eq!(texture.position(&Vector2::new(0.6, 0.8)), 0.5);
+}
+
+#[test]
+fn brick_texture_test() {
+    let size = Vector2::new(0.2, 0.1);
+    let brick = Rc::new(SolidColorTexture::new(1., 0., 0.));
+    let mortar = Rc::new(SolidColorTexture::new(1., 1., 1.));
+    let mut texture = BrickTexture::new(size, 0.02, brick, mortar);
+
+    // Joints between the bricks of the first row, shifted by half a brick in the second.
+    assert!(texture.is_mortar(&Vector2::new(0.2, 0.05)));
+    assert!(!texture.is_mortar(&Vector2::new(0.15, 0.05)));
+    assert!(texture.is_mortar(&Vector2::new(0.1, 0.15)));
+    assert!(!texture.is_mortar(&Vector2::new(0.2, 0.15)));
+
+    // Bed joints between the rows, and bricks repeating past the uv square.
+    assert!(texture.is_mortar(&Vector2::new(0.05, 0.1)));
+    assert!(texture.is_mortar(&Vector2::new(-0.1, -0.05)));
+    assert_eq!(
+        texture.value(&Vector2::new(1.15, 1.05), &Point3::zero()),
+        Color3::new(1., 0., 0.)
+    );
+
+    // Tiles line up.
+    texture.row_offset = 0.;
+    assert!(texture.is_mortar(&Vector2::new(0.2, 0.15)));
+    assert!(!texture.is_mortar(&Vector2::new(0.1, 0.15)));
+}
+
+#[test]
+fn procedural_texture_at_hit_test() {
+    // Inputs reading the hit, like vertex colors, see it through the procedural textures.
+    let mut hit_record = HitRecord::new();
+    hit_record.uv = Some(Vector2::new(0.15, 0.05));
+    hit_record.point = Some(Point3::new(0.3, 0.2, 0.1));
+    hit_record.vertex_color = Some(Color3::new(0.2, 0.4, 0.6));
+    let vertex_color = Rc::new(VertexColorTexture::new());
+    let ramp = || ColorRamp::new_with_textures(vertex_color.clone(), vertex_color.clone());
+
+    let white = Rc::new(SolidColorTexture::new(1., 1., 1.));
+    let brick = BrickTexture::new(Vector2::new(0.2, 0.1), 0.02, vertex_color.clone(), white);
+    let textures: [Box<dyn Texture>; 5] = [
+        Box::new(brick),
+        Box::new(GradientTexture::new_linear(
+            Vector2::zero(),
+            Vector2::new(1., 0.),
+            ramp(),
+        )),
+        Box::new(WoodTexture::new(4., ramp())),
+        Box::new(WorleyTexture::new(4., WorleyFeature::F1, ramp())),
+        Box::new(FractalNoiseTexture::new(4., ramp())),
+    ];
+    for texture in textures {
+        let color = texture.value_at_hit(&hit_record);
+        assert!(
+            (&color - &Color3::new(0.2, 0.4, 0.6)).length() < 1e-5,
+            "{color:?}"
+        );
+    }
+}
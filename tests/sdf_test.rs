@@ -0,0 +1,131 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        AxisAlignedBoundingBox, Color3, HitRecord, Interval, LambertianMaterial, Point3, Ray,
+        SDFHittable, SDFNode, Sphere, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::rc::Rc;
+
+fn cube(size: f32) -> AxisAlignedBoundingBox {
+    AxisAlignedBoundingBox::new_with_two_points(
+        &Point3::new(-size, -size, -size),
+        &Point3::new(size, size, size),
+    )
+}
+
+#[test]
+fn sdf_sphere_test() {
+    // Sphere tracing a sphere finds the same hits as the analytic one.
+    let material: Rc<dyn Material> = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let center = Vector3::new(0.2, -0.1, 0.3);
+    let sdf = SDFHittable::new(
+        SDFNode::sphere(1.).translate(center),
+        cube(2.),
+        material.clone(),
+    );
+    let sphere = Sphere::new(center, 1., material);
+
+    let interval = Interval::new(0.001, f32::INFINITY);
+    for i in 0..1000 {
+        // Rays from outside, and from inside for some of them.
+        let origin = if i % 4 == 0 {
+            &center + &(&Vector3::random_in_unit_sphere() * 0.9)
+        } else {
+            &Vector3::random_unit_vector() * 3.
+        };
+        let ray = Ray::new(origin, Vector3::random_unit_vector());
+
+        let mut expected = HitRecord::new();
+        let mut record = HitRecord::new();
+        let expected_hit = sphere.hit(&ray, &interval, &mut expected);
+        let hit = sdf.hit(&ray, &interval, &mut record);
+        if expected_hit != hit {
+            // Only rays grazing the sphere may disagree.
+            let offset = &ray.origin - &center;
+            let closest = &offset - &(&ray.direction * offset.dot(&ray.direction));
+            assert!((closest.length() - 1.).abs() < 1e-2, "{:?}", ray);
+            continue;
+        }
+        if !hit {
+            continue;
+        }
+        // Hits stop short of the surface by the tolerance, more so at grazing angles.
+        let point = record.point.unwrap();
+        assert!(((&point - &center).length() - 1.).abs() < 1e-3);
+        assert!((record.t - expected.t).abs() < 1e-2);
+        assert_eq!(record.front_face, expected.front_face);
+        assert!(record.normal.unwrap().dot(&expected.normal.unwrap()) > 0.999);
+
+        // A ray leaving the surface doesn't hit it again at the same point.
+        let leaving = Ray::new(point, ray.direction);
+        let mut next = HitRecord::new();
+        if sdf.hit(&leaving, &interval, &mut next) {
+            assert!(next.t > 1e-2);
+        }
+    }
+}
+
+#[test]
+fn sdf_node_test() {
+    let point = Point3::new(0.5, 0.25, 0.);
+
+    let cube = SDFNode::cube_box(Vector3::new(1., 1., 1.));
+    assert!((cube.distance(&Point3::new(2., 0., 0.)) - 1.).abs() < 1e-6);
+    assert!((cube.distance(&Point3::new(2., 2., 1.)) - f32::sqrt(2.)).abs() < 1e-6);
+    assert!((cube.distance(&point) + 0.5).abs() < 1e-6);
+
+    let torus = SDFNode::torus(1., 0.25);
+    assert!(torus.distance(&Point3::new(1., 0., 0.)) < 0.);
+    assert!((torus.distance(&Point3::zero()) - 0.75).abs() < 1e-6);
+
+    // Smooth blends stay below the sharp ones near the seam.
+    let a = SDFNode::sphere(1.).translate(Vector3::new(-0.8, 0., 0.));
+    let b = SDFNode::sphere(1.).translate(Vector3::new(0.8, 0., 0.));
+    let seam = Point3::new(0., 0.65, 0.);
+    assert!(
+        a.clone().smooth_union(b.clone(), 0.3).distance(&seam)
+            < a.clone().union(b.clone()).distance(&seam)
+    );
+    assert!(
+        a.clone()
+            .smooth_subtraction(b.clone(), 0.3)
+            .distance(&Point3::new(-0.9, 0., 0.))
+            < 0.
+    );
+
+    // Repetition tiles the space, twisting keeps the axis in place.
+    let spheres = SDFNode::sphere(0.25).repeat(Vector3::new(1., 0., 1.));
+    assert!((spheres.distance(&Point3::new(3., 0., -2.)) + 0.25).abs() < 1e-5);
+    let twisted = SDFNode::cube_box(Vector3::new(0.5, 2., 0.1)).twist(1.);
+    assert!(twisted.distance(&Point3::new(0., 1.5, 0.)) < 0.);
+
+    let closure = SDFNode::function(|point: &Point3| point.y);
+    assert_eq!(closure.distance(&point), 0.25);
+}
+
+#[test]
+fn sdf_twist_test() {
+    // A twisted box is hit with a Lipschitz bound above one.
+    let material: Rc<dyn Material> = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let mut twisted = SDFHittable::new(
+        SDFNode::cube_box(Vector3::new(0.5, 1., 0.2)).twist(1.5),
+        cube(1.2),
+        material,
+    );
+    twisted.lipschitz = 2.;
+
+    let ray = Ray::new(Point3::new(0., 0., -3.), Vector3::new(0., 0., 1.));
+    let mut record = HitRecord::new();
+    assert!(twisted.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!((record.t - 2.8).abs() < 1e-3);
+
+    // Above the box, the top face is rotated by 1.5 radians.
+    let (sin, cos) = f32::sin_cos(1.5);
+    let corner = Point3::new(0.45 * cos, 2., -0.45 * sin);
+    let ray = Ray::new(corner, Vector3::new(0., -1., 0.));
+    assert!(twisted.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    assert!((record.t - 1.).abs() < 1e-2);
+}
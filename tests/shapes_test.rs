@@ -0,0 +1,140 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        Annulus, Color3, Cone, Cylinder, Disk, Ellipse, HitRecord, Interval, LambertianMaterial,
+        Point3, Quad, Ray, Torus, Triangle, Vector3,
+    },
+    traits::{Hittable, Material},
+};
+use std::rc::Rc;
+
+fn shapes() -> Vec<Rc<dyn Hittable>> {
+    let material: Rc<dyn Material> = Rc::new(LambertianMaterial::new_with_color(Color3::one()));
+    let axis = Vector3::new(1., 2., 0.5);
+    vec![
+        Rc::new(Quad::new(
+            Point3::new(-0.5, 0., -0.5),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(-0.8, 0.3, 1.),
+            material.clone(),
+        )),
+        Rc::new(Triangle::new_with_vertices(
+            Point3::new(-0.5, 0., 0.),
+            Point3::new(0.6, 0.2, -0.3),
+            Point3::new(0., 0.4, 0.8),
+            material.clone(),
+        )),
+        Rc::new(Ellipse::new(
+            Point3::zero(),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(0., 0.3, 0.5),
+            material.clone(),
+        )),
+        Rc::new(Ellipse::new_disk(
+            Point3::zero(),
+            axis,
+            1.,
+            material.clone(),
+        )),
+        Rc::new(Disk::new_annulus(
+            Point3::zero(),
+            axis,
+            0.4,
+            1.,
+            material.clone(),
+        )),
+        Rc::new(Disk::new(Point3::zero(), axis, 1., material.clone())),
+        Rc::new(Annulus::new_elliptical(
+            Point3::zero(),
+            Vector3::new(1., 0., 0.),
+            Vector3::new(0., 0.3, 0.5),
+            0.5,
+            material.clone(),
+        )),
+        Rc::new(Cylinder::new(
+            Point3::new(0., -0.5, 0.),
+            Point3::new(0.2, 0.5, 0.1),
+            0.5,
+            material.clone(),
+        )),
+        Rc::new(Cylinder::new_capped(
+            Point3::new(0., -0.5, 0.),
+            Point3::new(0.2, 0.5, 0.1),
+            0.5,
+            material.clone(),
+        )),
+        Rc::new(Cone::new_capped(
+            Point3::new(0., -0.5, 0.),
+            Point3::new(0.1, 0.7, -0.2),
+            0.6,
+            material.clone(),
+        )),
+        Rc::new(Torus::new(Point3::zero(), axis, 0.8, 0.3, material)),
+    ]
+}
+
+#[test]
+fn shape_pdf_test() {
+    // Sampling the surface by area covers every direction toward it exactly once, so the density
+    // integrates to one. Combining uniform directions with the samples of the shape by the balance
+    // heuristic keeps every term of the estimate bounded.
+    let origin = Point3::new(0.6, 2.4, -1.8);
+    let uniform = 1. / (4. * std::f32::consts::PI);
+    let samples = 100000;
+    for shape in shapes() {
+        let mut integral = 0.;
+        for _ in 0..samples {
+            let direction = Vector3::random_unit_vector();
+            let pdf = shape.pdf_value(&origin, &direction);
+            integral += pdf / (uniform + pdf);
+
+            let direction = shape.random(&origin);
+            let pdf = shape.pdf_value(&origin, &direction);
+            integral += pdf / (uniform + pdf);
+        }
+        integral /= samples as f32;
+        assert!((integral - 1.).abs() < 0.02, "{:?}: {}", shape, integral);
+    }
+}
+
+#[test]
+fn shape_hit_test() {
+    for shape in shapes() {
+        let bbox = shape.bounding_box().clone();
+        let mut hits = 0;
+        for _ in 0..1000 {
+            let origin = &Vector3::random_unit_vector() * 4.;
+            let target = &shape.random(&origin) + &origin;
+            let ray = Ray::new(origin, &target - &origin);
+            let mut record = HitRecord::new();
+            if !shape.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record) {
+                continue;
+            }
+            hits += 1;
+
+            // Hits lie within the bounds, no farther than the sampled point.
+            let point = record.point.unwrap();
+            assert!(record.t <= 1. + 1e-3);
+            for axis in 0..3 {
+                assert!(bbox.axis(axis).min - 1e-3 <= point[axis]);
+                assert!(point[axis] <= bbox.axis(axis).max + 1e-3);
+            }
+
+            // The normal faces the ray, and the tangents follow the outward normal.
+            let normal = record.normal.unwrap();
+            assert!((normal.length() - 1.).abs() < 1e-3);
+            assert!(normal.dot(&ray.direction) <= 1e-4);
+            let uv = record.uv.unwrap();
+            assert!((0. ..=1.).contains(&uv.u()) && (0. ..=1.).contains(&uv.v()));
+            let tangent_normal = record.dpdu.unwrap().cross(&record.dpdv.unwrap());
+            let outward_sign = if record.front_face { 1. } else { -1. };
+            assert!(
+                tangent_normal.dot(&normal) * outward_sign >= 0.,
+                "{:?}",
+                shape
+            );
+        }
+        assert!(hits > 500, "{:?}: {}", shape, hits);
+    }
+}
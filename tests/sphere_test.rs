@@ -110,3 +110,19 @@ fn sphere_bounding_box_test() {
         assert_eq!(moving_sphere.bounding_box().axis(n).max, 2.);
     }
 }
+
+#[test]
+fn sphere_tangents_test() {
+    for point in [
+        Vector3::new(1., 0., 0.),
+        Vector3::new(0., 0., -1.),
+        Vector3::new(1., 1., 1.).normolize(),
+        Vector3::new(-0.3, -0.8, 0.4).normolize(),
+    ] {
+        let (dpdu, dpdv) = Sphere::compute_tangents(&point, 2.);
+        assert!(f32::abs(dpdu.dot(&point)) < 1e-4);
+        assert!(f32::abs(dpdv.dot(&point)) < 1e-4);
+        // The tangents should form a frame around the outward normal.
+        assert!(dpdu.cross(&dpdv).dot(&point) > 0.);
+    }
+}
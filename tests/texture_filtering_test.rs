@@ -0,0 +1,148 @@
+extern crate raytracing;
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use raytracing::core::{
+    HitRecord, ImageEncoding, ImageFilter, ImageTexture, Point3, Ray, RayDifferential, Vector2,
+    Vector3, WrapMode,
+};
+
+/// Returns a texture of vertical stripes, white in even columns and black in odd ones.
+fn stripes(size: u32) -> ImageTexture {
+    let image = RgbaImage::from_fn(size, size, |x, _| {
+        let value = if x % 2 == 0 { 255 } else { 0 };
+        Rgba([value, value, value, 255])
+    });
+    ImageTexture::new_with_image(&DynamicImage::ImageRgba8(image), ImageEncoding::Raw)
+}
+
+#[test]
+fn image_texture_wrap_test() {
+    let image = RgbaImage::from_fn(2, 1, |x, _| {
+        Rgba([255 * (1 - x as u8), 255 * x as u8, 0, 255])
+    });
+    let mut texture =
+        ImageTexture::new_with_image(&DynamicImage::ImageRgba8(image), ImageEncoding::Raw);
+    texture.filter = ImageFilter::Point;
+    let red = |uv: Vector2, texture: &ImageTexture| {
+        texture.lookup(&uv, &Vector2::zero(), &Vector2::zero())[0]
+    };
+
+    assert_eq!(red(Vector2::new(0.25, 0.5), &texture), 1.);
+    assert_eq!(red(Vector2::new(0.75, 0.5), &texture), 0.);
+
+    // Past the right edge, the image extends its last column, repeats or mirrors.
+    assert_eq!(texture.wrap, WrapMode::Clamp);
+    assert_eq!(red(Vector2::new(1.25, 0.5), &texture), 0.);
+    texture.wrap = WrapMode::Repeat;
+    assert_eq!(red(Vector2::new(1.25, 0.5), &texture), 1.);
+    texture.wrap = WrapMode::Mirror;
+    assert_eq!(red(Vector2::new(1.25, 0.5), &texture), 0.);
+    assert_eq!(red(Vector2::new(-0.25, 0.5), &texture), 1.);
+}
+
+#[test]
+fn image_texture_mip_test() {
+    let texture = stripes(64);
+    assert_eq!(texture.level_count(), 7);
+
+    // Texel centers read the texels, footprints spanning many texels read their average.
+    let center = Vector2::new(0.5 / 64., 0.5);
+    assert_eq!(
+        texture.lookup(&center, &Vector2::zero(), &Vector2::zero())[0],
+        1.
+    );
+    let footprint = Vector2::new(0.25, 0.);
+    let value = texture.lookup(&center, &footprint, &Vector2::new(0., 0.25))[0];
+    assert!((value - 0.5).abs() < 1e-5, "value {value}");
+}
+
+#[test]
+fn image_texture_ewa_test() {
+    let mut texture = stripes(64);
+    let center = Vector2::new(0.5 / 64., 0.5);
+
+    // A footprint along the stripes, a quarter texel wide across them.
+    let duvdx = Vector2::new(0.25 / 64., 0.);
+    let duvdy = Vector2::new(0., 0.05);
+
+    // Isotropic filters blur the stripes to the size of the longer axis.
+    texture.filter = ImageFilter::Trilinear;
+    let trilinear = texture.lookup(&center, &duvdx, &duvdy)[0];
+    assert!((trilinear - 0.5).abs() < 0.1, "trilinear {trilinear}");
+
+    // EWA keeps them.
+    texture.filter = ImageFilter::EWA;
+    let ewa = texture.lookup(&center, &duvdx, &duvdy)[0];
+    assert!(ewa > 0.8, "ewa {ewa}");
+}
+
+/// Returns a ray towards the origin along -z, with a differential offset along x and y.
+fn ray(offset: f32) -> Ray {
+    let origin = Point3::new(0., 0., 1.);
+    let direction = Vector3::new(0., 0., -1.);
+    let differential = RayDifferential {
+        rx_origin: origin,
+        rx_direction: Vector3::new(offset, 0., -1.),
+        ry_origin: origin,
+        ry_direction: Vector3::new(0., offset, -1.),
+    };
+    Ray::new_with_differential(origin, direction, 0., differential)
+}
+
+/// Returns the hit of `ray` on the plane z = 0, whose uv coordinates span 2 units.
+fn hit_record() -> HitRecord {
+    let mut hit_record = HitRecord::new();
+    hit_record.point = Some(Point3::zero());
+    hit_record.normal = Some(Vector3::new(0., 0., 1.));
+    hit_record.uv = Some(Vector2::new(0.5, 0.5));
+    hit_record.dpdu = Some(Vector3::new(2., 0., 0.));
+    hit_record.dpdv = Some(Vector3::new(0., 2., 0.));
+    hit_record.front_face = true;
+    hit_record
+}
+
+#[test]
+fn compute_differentials_test() {
+    let mut hit_record = hit_record();
+    hit_record.compute_differentials(&ray(0.01));
+    assert!((&hit_record.dpdx.unwrap() - &Vector3::new(0.01, 0., 0.)).length() < 1e-6);
+    assert!((&hit_record.dpdy.unwrap() - &Vector3::new(0., 0.01, 0.)).length() < 1e-6);
+    let (duvdx, duvdy) = (hit_record.duvdx.unwrap(), hit_record.duvdy.unwrap());
+    assert!((duvdx.u() - 0.005).abs() < 1e-6 && duvdx.v().abs() < 1e-6);
+    assert!(duvdy.u().abs() < 1e-6 && (duvdy.v() - 0.005).abs() < 1e-6);
+
+    // Rays without differentials leave no footprint.
+    let mut hit_record = self::hit_record();
+    hit_record.compute_differentials(&Ray::new(
+        Point3::new(0., 0., 1.),
+        Vector3::new(0., 0., -1.),
+    ));
+    assert!(hit_record.duvdx.is_none());
+}
+
+#[test]
+fn scattered_differential_test() {
+    let ray = ray(0.01);
+    let mut hit_record = hit_record();
+    hit_record.compute_differentials(&ray);
+    let normal = hit_record.normal.unwrap();
+    let offset_direction = ray.differential.unwrap().rx_direction.normolize();
+
+    // The offset rays reflect like the ray.
+    let reflected = ray.direction.normolize().reflect(&normal);
+    let differential = ray
+        .scattered_differential(&hit_record, &reflected, None)
+        .unwrap();
+    let expected = offset_direction.reflect(&normal);
+    assert!((&differential.rx_direction - &expected).length() < 1e-4);
+    assert!((&differential.rx_origin - &Point3::new(0.01, 0., 0.)).length() < 1e-6);
+
+    // And refract like it, to first order.
+    let eta = 1. / 1.5;
+    let refracted = ray.direction.normolize().refract(&normal, eta);
+    let differential = ray
+        .scattered_differential(&hit_record, &refracted, Some(eta))
+        .unwrap();
+    let expected = offset_direction.refract(&normal, eta);
+    assert!((&differential.rx_direction - &expected).length() < 1e-4);
+}
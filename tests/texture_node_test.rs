@@ -0,0 +1,189 @@
+extern crate raytracing;
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use raytracing::{
+    core::{
+        hsv_to_rgb, rgb_to_hsv, ChannelTexture, Color3, ColorRamp, DomainWarpTexture,
+        GradientTexture, HSVTexture, HitRecord, ImageEncoding, ImageTexture, MathTexture, Point3,
+        RemapTexture, SolidColorTexture, TextureChannel, TriplanarTexture, UVTransform,
+        UVTransformTexture, Vector2, Vector3,
+    },
+    traits::Texture,
+};
+use std::rc::Rc;
+
+fn solid(r: f32, g: f32, b: f32) -> Rc<dyn Texture> {
+    Rc::new(SolidColorTexture::new(r, g, b))
+}
+
+/// Returns a gradient along u, black at 0 and white at 1.
+fn gradient() -> Rc<dyn Texture> {
+    Rc::new(GradientTexture::new_linear(
+        Vector2::zero(),
+        Vector2::new(1., 0.),
+        ColorRamp::new_grayscale(),
+    ))
+}
+
+fn value(texture: &dyn Texture, uv: Vector2) -> Color3 {
+    texture.value(&uv, &Point3::zero())
+}
+
+#[test]
+fn math_texture_test() {
+    let a = solid(0.2, 0.4, 0.6);
+    let b = solid(0.5, 0.5, 2.);
+    let uv = Vector2::zero();
+    let add = MathTexture::new_add(a.clone(), b.clone());
+    assert!((&value(&add, uv) - &Color3::new(0.7, 0.9, 2.6)).length() < 1e-6);
+    let subtract = MathTexture::new_subtract(a.clone(), b.clone());
+    assert!((&value(&subtract, uv) - &Color3::new(-0.3, -0.1, -1.4)).length() < 1e-6);
+    let multiply = MathTexture::new_multiply(a.clone(), b.clone());
+    assert!((&value(&multiply, uv) - &Color3::new(0.1, 0.2, 1.2)).length() < 1e-6);
+    let clamp = MathTexture::new_clamp(Rc::new(subtract), 0., 1.);
+    assert_eq!(value(&clamp, uv), Color3::zero());
+
+    // Mixing by a mask texture, here a gradient along u.
+    let mix = MathTexture::new_mix(a.clone(), b.clone(), gradient());
+    assert_eq!(value(&mix, Vector2::new(0., 0.)), value(a.as_ref(), uv));
+    assert_eq!(value(&mix, Vector2::new(1., 0.)), value(b.as_ref(), uv));
+    let half = value(&mix, Vector2::new(0.5, 0.));
+    assert!((&half - &Color3::new(0.35, 0.45, 1.3)).length() < 1e-6);
+}
+
+#[test]
+fn remap_texture_test() {
+    let curve = vec![
+        Vector2::new(1., 0.),
+        Vector2::new(0., 1.),
+        Vector2::new(0.5, 0.),
+    ];
+    let texture = RemapTexture::new_with_curve(gradient(), curve);
+    let at = |u: f32| value(&texture, Vector2::new(u, 0.)).x;
+    assert_eq!(at(0.), 1.);
+    assert_eq!(at(0.25), 0.5);
+    assert_eq!(at(0.75), 0.);
+    assert_eq!(at(2.), 0.);
+
+    // Ramps color the average of the input.
+    let ramp = ColorRamp::new_with_colors(vec![
+        (0., Color3::new(1., 0., 0.)),
+        (1., Color3::new(0., 0., 1.)),
+    ]);
+    let texture = RemapTexture::new_with_ramp(solid(0., 0.75, 0.75), ramp);
+    assert_eq!(value(&texture, Vector2::zero()), Color3::new(0.5, 0., 0.5));
+}
+
+#[test]
+fn hsv_texture_test() {
+    for color in [
+        Color3::new(0.2, 0.4, 0.6),
+        Color3::new(0.9, 0.1, 0.3),
+        Color3::new(0.5, 0.5, 0.5),
+        Color3::new(0.3, 0.8, 0.1),
+    ] {
+        let back = hsv_to_rgb(&rgb_to_hsv(&color));
+        assert!((&back - &color).length() < 1e-5, "{color:?} {back:?}");
+    }
+
+    // A full turn of hue changes nothing, desaturating gives grays.
+    let mut texture = HSVTexture::new(solid(0.9, 0.1, 0.3));
+    texture.hue_shift = 1.;
+    let color = value(&texture, Vector2::zero());
+    assert!((&color - &Color3::new(0.9, 0.1, 0.3)).length() < 1e-5);
+    texture.saturation = 0.;
+    texture.value = 0.5;
+    assert_eq!(
+        value(&texture, Vector2::zero()),
+        Color3::new(0.45, 0.45, 0.45)
+    );
+}
+
+/// Returns a texture of vertical stripes, white in even columns and black in odd ones.
+fn stripes() -> Rc<ImageTexture> {
+    let image = RgbaImage::from_fn(64, 64, |x, _| {
+        let value = if x % 2 == 0 { 255 } else { 0 };
+        Rgba([value, value, value, 255])
+    });
+    Rc::new(ImageTexture::new_with_image(
+        &DynamicImage::ImageRgba8(image),
+        ImageEncoding::Raw,
+    ))
+}
+
+#[test]
+fn uv_transform_texture_test() {
+    let mut transform = UVTransform::new_with_scale(2., 3.);
+    transform.offset = Vector2::new(0.5, 0.);
+    transform.rotation = 90.;
+    let uv = transform.apply(&Vector2::new(1., 1.));
+    assert!((uv.u() - -2.5).abs() < 1e-6 && (uv.v() - 2.).abs() < 1e-6);
+    let duv = transform.apply_vector(&Vector2::new(1., 0.));
+    assert!(duv.u().abs() < 1e-6 && (duv.v() - 2.).abs() < 1e-6);
+
+    // Footprints are scaled with the uv coordinates, so tiled images blur as they shrink.
+    let image = stripes();
+    let texture = UVTransformTexture::new(image.clone(), UVTransform::new_with_scale(4., 4.));
+    let mut hit_record = HitRecord::new();
+    hit_record.point = Some(Point3::zero());
+    hit_record.uv = Some(Vector2::new(0.5 / 256., 0.5));
+    hit_record.duvdx = Some(Vector2::new(1. / 256., 0.));
+    hit_record.duvdy = Some(Vector2::new(0., 1. / 256.));
+    let color = texture.value_at_hit(&hit_record);
+    let expected = image.lookup(
+        &Vector2::new(0.5 / 64., 2.),
+        &Vector2::new(1. / 64., 0.),
+        &Vector2::new(0., 1. / 64.),
+    );
+    assert!((color.x - expected[0]).abs() < 1e-6);
+    assert!(color.x < 0.9, "{color:?}");
+}
+
+#[test]
+fn triplanar_texture_test() {
+    let mut texture = TriplanarTexture::new(gradient(), 0.5);
+    let weights = texture.weights(&Vector3::new(0., -1., 0.));
+    assert_eq!(weights, [0., 1., 0.]);
+    let weights = texture.weights(&Vector3::new(1., 1., 0.).normolize());
+    assert!((weights[0] - 0.5).abs() < 1e-6 && (weights[1] - 0.5).abs() < 1e-6);
+    texture.sharpness = 1.;
+    let weights = texture.weights(&Vector3::new(0.6, 0., 0.8));
+    assert!((weights[0] - 0.6 / 1.4).abs() < 1e-6);
+
+    // Hits on a floor read the projection along y, whose u is x.
+    let mut hit_record = HitRecord::new();
+    hit_record.point = Some(Point3::new(0.8, 3., 5.));
+    hit_record.normal = Some(Vector3::new(0., 1., 0.));
+    hit_record.uv = Some(Vector2::zero());
+    let color = texture.value_at_hit(&hit_record);
+    assert!((color.x - 0.4).abs() < 1e-6);
+}
+
+#[test]
+fn domain_warp_texture_test() {
+    // A constant warp shifts the texture along every axis.
+    let warp = solid(0.75, 0.75, 0.75);
+    let texture = DomainWarpTexture::new(gradient(), warp, 0.2);
+    let color = value(&texture, Vector2::new(0.2, 0.));
+    assert!((color.x - 0.3).abs() < 1e-6);
+
+    let mut hit_record = HitRecord::new();
+    hit_record.point = Some(Point3::zero());
+    hit_record.uv = Some(Vector2::new(0.5, 0.));
+    assert!((texture.value_at_hit(&hit_record).x - 0.6).abs() < 1e-6);
+}
+
+#[test]
+fn channel_texture_test() {
+    let packed = solid(0.1, 0.2, 0.6);
+    let channel = |channel| {
+        value(
+            &ChannelTexture::new(packed.clone(), channel),
+            Vector2::zero(),
+        )
+    };
+    assert_eq!(channel(TextureChannel::Red), Color3::new(0.1, 0.1, 0.1));
+    assert_eq!(channel(TextureChannel::Blue), Color3::new(0.6, 0.6, 0.6));
+    assert_eq!(channel(TextureChannel::Alpha), Color3::one());
+    assert!((channel(TextureChannel::Average).x - 0.3).abs() < 1e-6);
+}
@@ -0,0 +1,130 @@
+extern crate raytracing;
+
+use raytracing::{
+    core::{
+        CheckerTexture, Color3, HitRecord, Interval, LambertianMaterial, Matrix4,
+        ObjectSpaceTexture, Point3, Ray, Sphere, TransformInstance, TranslateInstance, UVTransform,
+        UVTransformTexture, Vector2, Vector3,
+    },
+    traits::{Hittable, Texture},
+};
+use std::rc::Rc;
+
+fn material() -> Rc<LambertianMaterial> {
+    Rc::new(LambertianMaterial::new_with_color(Color3::one()))
+}
+
+/// Returns the record of the hit of a ray at the given time, panicking on a miss.
+fn hit(object: &dyn Hittable, origin: Point3, direction: Vector3, time: f32) -> HitRecord {
+    let mut record = HitRecord::new();
+    let ray = Ray::new_with_time(origin, direction, time);
+    assert!(object.hit(&ray, &Interval::new(0.001, f32::INFINITY), &mut record));
+    record
+}
+
+fn assert_close(a: Point3, b: Point3) {
+    assert!((&a - &b).length() < 1e-4, "{a:?} {b:?}");
+}
+
+#[test]
+fn uv_checker_texture_test() {
+    let checker = CheckerTexture::new_uv_with_solid_color(
+        Vector2::new(4., 2.),
+        Color3::one(),
+        Color3::zero(),
+    );
+    let at = |u: f32, v: f32| checker.value(&Vector2::new(u, v), &Point3::new(0.1, 0.7, 0.3));
+    assert_eq!(at(0.1, 0.1), Color3::one());
+    assert_eq!(at(0.3, 0.1), Color3::zero());
+    assert_eq!(at(0.3, 0.6), Color3::one());
+    // Checks continue beyond the unit square.
+    assert_eq!(at(-0.1, 0.1), Color3::zero());
+
+    // Checks in space ignore the uv coordinates.
+    let checker = CheckerTexture::new_with_solid_color(1., Color3::one(), Color3::zero());
+    assert!(checker.is_even(&Vector2::new(0.3, 0.1), &Point3::new(0.5, 0.5, 0.5)));
+    assert!(!checker.is_even(&Vector2::new(0.1, 0.1), &Point3::new(1.5, 0.5, 0.5)));
+}
+
+#[test]
+fn uv_transform_tile_test() {
+    let mut transform = UVTransform::new_with_scale(3., 1.);
+    transform.offset = Vector2::new(0., -0.25);
+    transform.tile = true;
+    let uv = transform.apply(&Vector2::new(0.5, 0.1));
+    assert!((uv.u() - 0.5).abs() < 1e-6 && (uv.v() - 0.85).abs() < 1e-6);
+
+    // Tiling a uv checker of a single check repeats it.
+    let checker = Rc::new(CheckerTexture::new_uv_with_solid_color(
+        Vector2::new(2., 1.),
+        Color3::one(),
+        Color3::zero(),
+    ));
+    let mut transform = UVTransform::new_with_scale(2., 2.);
+    transform.tile = true;
+    let texture = UVTransformTexture::new(checker, transform);
+    let at = |u: f32| texture.value(&Vector2::new(u, 0.1), &Point3::zero());
+    assert_eq!(at(0.1), Color3::one());
+    assert_eq!(at(0.3), Color3::zero());
+    assert_eq!(at(0.6), Color3::one());
+}
+
+#[test]
+fn object_point_test() {
+    // Hits on plain objects have no object point.
+    let sphere = Rc::new(Sphere::new(Point3::zero(), 1., material()));
+    let record = hit(
+        sphere.as_ref(),
+        Point3::new(0., 0., 5.),
+        Vector3::new(0., 0., -1.),
+        0.,
+    );
+    assert_eq!(record.object_point, None);
+
+    // Instances keep the point of the innermost object.
+    let transform = &Matrix4::translation(&Vector3::new(3., 0., 0.))
+        * &Matrix4::scaling(&Vector3::new(2., 2., 2.));
+    let instance = TransformInstance::new(sphere.clone(), transform);
+    let instance = TranslateInstance::new(Rc::new(instance), Vector3::new(0., 1., 0.));
+    let record = hit(
+        &instance,
+        Point3::new(3., 1., 5.),
+        Vector3::new(0., 0., -1.),
+        0.,
+    );
+    assert_close(record.point.unwrap(), Point3::new(3., 1., 2.));
+    assert_close(record.object_point.unwrap(), Point3::new(0., 0., 1.));
+
+    // Moving spheres keep the point at the start of their motion.
+    let moving = Sphere::new_moving_sphere(Point3::zero(), Point3::new(4., 0., 0.), 1., material());
+    let record = hit(
+        &moving,
+        Point3::new(2., 0., 5.),
+        Vector3::new(0., 0., -1.),
+        0.5,
+    );
+    assert_close(record.point.unwrap(), Point3::new(2., 0., 1.));
+    assert_close(record.object_point.unwrap(), Point3::new(0., 0., 1.));
+}
+
+#[test]
+fn object_space_texture_test() {
+    let checker = Rc::new(CheckerTexture::new_with_solid_color(
+        1.,
+        Color3::one(),
+        Color3::zero(),
+    ));
+    let texture = ObjectSpaceTexture::new(checker.clone());
+    let sphere = Rc::new(Sphere::new(Point3::new(0.5, 0.5, 0.), 0.25, material()));
+    let origin = Point3::new(0.5, 0.5, 5.);
+    let direction = Vector3::new(0., 0., -1.);
+    let record = hit(sphere.as_ref(), origin, direction, 0.);
+    let color = texture.value_at_hit(&record);
+    assert_eq!(color, checker.value_at_hit(&record));
+
+    // Moved by one check, the world space checker flips while the object space one stays.
+    let moved = TranslateInstance::new(sphere, Vector3::new(1., 0., 0.));
+    let record = hit(&moved, &origin + &Vector3::new(1., 0., 0.), direction, 0.);
+    assert_ne!(checker.value_at_hit(&record), color);
+    assert_eq!(texture.value_at_hit(&record), color);
+}